@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS job_queue;
+DROP FUNCTION IF EXISTS update_updated_at_column();
@@ -41,8 +41,8 @@ ADD COLUMN retry_at TIMESTAMPTZ,
 ADD COLUMN lock_expires_at TIMESTAMPTZ,
 ADD COLUMN worker_id VARCHAR(100);
 
-CREATE INDEX idx_jobs_status_scheduled
-ON jobs(status, scheduled_at);
+CREATE INDEX idx_job_queue_status_scheduled
+ON job_queue(status, scheduled_at);
 
 ALTER TABLE job_queue 
 ADD COLUMN queue_name VARCHAR(50) NOT NULL DEFAULT 'default';
@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS idx_users_two_factor_enabled;
+ALTER TABLE users
+DROP COLUMN IF EXISTS two_factor_secret,
+DROP COLUMN IF EXISTS two_factor_enabled,
+DROP COLUMN IF EXISTS two_factor_confirmed_at,
+DROP COLUMN IF EXISTS two_factor_recovery_codes;
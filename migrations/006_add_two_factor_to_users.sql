@@ -0,0 +1,9 @@
+-- Add TOTP two-factor authentication columns to users table
+ALTER TABLE users
+ADD COLUMN two_factor_secret VARCHAR(64),
+ADD COLUMN two_factor_enabled BOOLEAN NOT NULL DEFAULT FALSE,
+ADD COLUMN two_factor_confirmed_at TIMESTAMPTZ,
+ADD COLUMN two_factor_recovery_codes TEXT[] NOT NULL DEFAULT '{}';
+
+-- Partial index to quickly list enrolled accounts
+CREATE INDEX idx_users_two_factor_enabled ON users(two_factor_enabled) WHERE two_factor_enabled = TRUE;
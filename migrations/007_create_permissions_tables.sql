@@ -0,0 +1,37 @@
+-- Permission catalogue
+CREATE TABLE IF NOT EXISTS permissions (
+    name VARCHAR(100) PRIMARY KEY,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Role -> permission grants (role names match users.role)
+CREATE TABLE IF NOT EXISTS role_permissions (
+    role VARCHAR(50) NOT NULL,
+    permission VARCHAR(100) NOT NULL REFERENCES permissions(name) ON DELETE CASCADE,
+    granted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (role, permission)
+);
+
+CREATE INDEX idx_role_permissions_role ON role_permissions(role);
+
+-- Default catalogue. "*" and "prefix.*" act as wildcards.
+INSERT INTO permissions (name, description) VALUES
+    ('*', 'Every permission'),
+    ('users.view', 'View any user account'),
+    ('users.manage', 'Create, update, activate and delete user accounts'),
+    ('roles.manage', 'Manage role permissions'),
+    ('test_items.*', 'Full access to test items'),
+    ('queue.view', 'View queue statistics and jobs'),
+    ('queue.manage', 'Retry and cancel queued jobs'),
+    ('system.view', 'View host and application telemetry')
+ON CONFLICT (name) DO NOTHING;
+
+-- Default grants mirroring the previous hard-coded role checks
+INSERT INTO role_permissions (role, permission) VALUES
+    ('admin', '*'),
+    ('moderator', 'users.view'),
+    ('moderator', 'test_items.*'),
+    ('moderator', 'queue.view'),
+    ('premium', 'test_items.*')
+ON CONFLICT (role, permission) DO NOTHING;
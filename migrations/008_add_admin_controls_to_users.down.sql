@@ -0,0 +1,4 @@
+-- idx_users_role belongs to 002
+DELETE FROM permissions WHERE name = 'users.impersonate';
+DROP INDEX IF EXISTS idx_users_created_at;
+ALTER TABLE users DROP COLUMN IF EXISTS password_reset_required;
@@ -0,0 +1,11 @@
+-- Admin-forced password change on next login
+ALTER TABLE users
+ADD COLUMN password_reset_required BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Indexes used by the admin user search
+CREATE INDEX IF NOT EXISTS idx_users_role ON users(role);
+CREATE INDEX IF NOT EXISTS idx_users_created_at ON users(created_at);
+
+INSERT INTO permissions (name, description) VALUES
+    ('users.impersonate', 'Sign in as another (non-admin) user')
+ON CONFLICT (name) DO NOTHING;
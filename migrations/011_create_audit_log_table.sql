@@ -0,0 +1,36 @@
+-- Append-only record of security-relevant actions
+CREATE TABLE IF NOT EXISTS audit_log (
+    id VARCHAR(36) PRIMARY KEY,
+    -- NULL for anonymous actions (e.g. a failed login)
+    actor_id VARCHAR(36),
+    -- Set when the actor was being impersonated
+    impersonator_id VARCHAR(36),
+    action VARCHAR(100) NOT NULL,
+    target_type VARCHAR(50),
+    target_id VARCHAR(255),
+    ip_address VARCHAR(45),
+    user_agent TEXT,
+    -- Structured before/after or request details
+    changes JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_audit_log_created_at ON audit_log(created_at);
+CREATE INDEX idx_audit_log_actor_id ON audit_log(actor_id);
+CREATE INDEX idx_audit_log_action ON audit_log(action);
+CREATE INDEX idx_audit_log_target ON audit_log(target_type, target_id);
+
+-- Entries are never edited. Deletes stay possible for the retention purge.
+CREATE OR REPLACE FUNCTION audit_log_reject_update() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_log_no_update
+BEFORE UPDATE ON audit_log
+FOR EACH ROW EXECUTE FUNCTION audit_log_reject_update();
+
+INSERT INTO permissions (name, description) VALUES
+    ('audit.view', 'Read the audit log')
+ON CONFLICT (name) DO NOTHING;
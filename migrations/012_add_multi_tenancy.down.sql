@@ -0,0 +1,27 @@
+-- Back to a single tenant. Restoring the global email / username / identity
+-- constraints fails if two tenants share a value; resolve those rows first.
+DROP INDEX IF EXISTS idx_audit_log_tenant_id;
+ALTER TABLE audit_log DROP COLUMN IF EXISTS tenant_id;
+
+ALTER TABLE oauth_states DROP COLUMN IF EXISTS tenant_id;
+
+ALTER TABLE user_identities DROP CONSTRAINT IF EXISTS user_identities_tenant_provider_subject_key;
+ALTER TABLE user_identities DROP COLUMN IF EXISTS tenant_id;
+ALTER TABLE user_identities ADD CONSTRAINT user_identities_provider_subject_key UNIQUE (provider, subject);
+
+ALTER TABLE api_keys DROP COLUMN IF EXISTS tenant_id;
+
+DROP INDEX IF EXISTS idx_job_queue_tenant_id;
+ALTER TABLE job_queue DROP COLUMN IF EXISTS tenant_id;
+ALTER TABLE dead_letter_queue DROP COLUMN IF EXISTS tenant_id;
+
+DROP INDEX IF EXISTS idx_test_items_tenant_id;
+ALTER TABLE test_items DROP COLUMN IF EXISTS tenant_id;
+
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_tenant_email_key;
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_tenant_username_key;
+ALTER TABLE users DROP COLUMN IF EXISTS tenant_id;
+ALTER TABLE users ADD CONSTRAINT users_email_key UNIQUE (email);
+ALTER TABLE users ADD CONSTRAINT users_username_key UNIQUE (username);
+
+DROP TABLE IF EXISTS tenants;
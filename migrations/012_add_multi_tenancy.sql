@@ -0,0 +1,49 @@
+-- Tenants hosted on this deployment. Existing data moves to the "default" tenant.
+CREATE TABLE IF NOT EXISTS tenants (
+    id VARCHAR(36) PRIMARY KEY,
+    -- Subdomain / X-Tenant-ID value
+    slug VARCHAR(63) NOT NULL UNIQUE,
+    name VARCHAR(255) NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO tenants (id, slug, name) VALUES ('default', 'default', 'Default')
+ON CONFLICT (id) DO NOTHING;
+
+-- Users: email and username are unique per tenant
+ALTER TABLE users ADD COLUMN tenant_id VARCHAR(36) NOT NULL DEFAULT 'default' REFERENCES tenants(id);
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_email_key;
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_username_key;
+ALTER TABLE users ADD CONSTRAINT users_tenant_email_key UNIQUE (tenant_id, email);
+ALTER TABLE users ADD CONSTRAINT users_tenant_username_key UNIQUE (tenant_id, username);
+
+ALTER TABLE test_items ADD COLUMN tenant_id VARCHAR(36) NOT NULL DEFAULT 'default' REFERENCES tenants(id);
+CREATE INDEX idx_test_items_tenant_id ON test_items(tenant_id, created_at DESC);
+
+-- Jobs run in the tenant that enqueued them
+ALTER TABLE job_queue ADD COLUMN tenant_id VARCHAR(36) NOT NULL DEFAULT 'default';
+ALTER TABLE dead_letter_queue ADD COLUMN tenant_id VARCHAR(36) NOT NULL DEFAULT 'default';
+CREATE INDEX idx_job_queue_tenant_id ON job_queue(tenant_id);
+
+ALTER TABLE api_keys ADD COLUMN tenant_id VARCHAR(36) NOT NULL DEFAULT 'default' REFERENCES tenants(id);
+
+-- The same provider account may be linked once per tenant
+ALTER TABLE user_identities ADD COLUMN tenant_id VARCHAR(36) NOT NULL DEFAULT 'default' REFERENCES tenants(id);
+ALTER TABLE user_identities DROP CONSTRAINT IF EXISTS user_identities_provider_subject_key;
+ALTER TABLE user_identities ADD CONSTRAINT user_identities_tenant_provider_subject_key UNIQUE (tenant_id, provider, subject);
+
+ALTER TABLE oauth_states ADD COLUMN tenant_id VARCHAR(36) NOT NULL DEFAULT 'default';
+
+-- NULL for entries that could not be attributed to a tenant
+ALTER TABLE audit_log ADD COLUMN tenant_id VARCHAR(36);
+CREATE INDEX idx_audit_log_tenant_id ON audit_log(tenant_id, created_at);
+
+-- New rows must always name their tenant
+ALTER TABLE users ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE test_items ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE job_queue ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE dead_letter_queue ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE api_keys ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE user_identities ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE oauth_states ALTER COLUMN tenant_id DROP DEFAULT;
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_test_items_deleted_at;
+DROP INDEX IF EXISTS idx_users_deleted_at;
+ALTER TABLE test_items DROP COLUMN IF EXISTS deleted_at;
+ALTER TABLE users DROP COLUMN IF EXISTS deleted_at;
@@ -0,0 +1,7 @@
+-- Soft deletes: trashed rows keep their data (and unique email/username)
+-- until the purge job removes them after SOFT_DELETE_RETENTION_DAYS
+ALTER TABLE users ADD COLUMN deleted_at TIMESTAMPTZ;
+ALTER TABLE test_items ADD COLUMN deleted_at TIMESTAMPTZ;
+
+CREATE INDEX idx_users_deleted_at ON users(deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX idx_test_items_deleted_at ON test_items(deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,2 @@
+ALTER TABLE test_items DROP COLUMN IF EXISTS version;
+ALTER TABLE users DROP COLUMN IF EXISTS version;
@@ -0,0 +1,4 @@
+-- Optimistic concurrency: every update must name the version it read and bumps it.
+-- Exposed over HTTP as the ETag of the resource.
+ALTER TABLE users ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE test_items ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_test_items_search_vector;
+ALTER TABLE test_items DROP COLUMN IF EXISTS search_vector;
+DROP INDEX IF EXISTS idx_users_search_vector;
+ALTER TABLE users DROP COLUMN IF EXISTS search_vector;
@@ -0,0 +1,17 @@
+-- Full-text search: generated tsvector columns kept in sync by Postgres, with GIN indexes.
+-- Emails and usernames are also indexed split on their punctuation so `doe` finds `john.doe@example.com`.
+ALTER TABLE users ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector('simple', username), 'A') ||
+    setweight(to_tsvector('simple', translate(username, '._-', '   ')), 'A') ||
+    setweight(to_tsvector('simple', email), 'B') ||
+    setweight(to_tsvector('simple', translate(email, '@.+_-', '     ')), 'B')
+) STORED;
+
+CREATE INDEX idx_users_search_vector ON users USING GIN (search_vector);
+
+ALTER TABLE test_items ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', subject), 'A') ||
+    setweight(to_tsvector('english', coalesce(optional_field, '')), 'B')
+) STORED;
+
+CREATE INDEX idx_test_items_search_vector ON test_items USING GIN (search_vector);
@@ -0,0 +1,25 @@
+-- Uploaded CSV / NDJSON files applied by a queued job, with progress and an error report
+CREATE TABLE IF NOT EXISTS data_imports (
+    id VARCHAR(36) PRIMARY KEY,
+    tenant_id VARCHAR(36) NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    -- `test_items` or `users`
+    entity VARCHAR(50) NOT NULL,
+    -- `csv` or `ndjson`
+    format VARCHAR(10) NOT NULL,
+    -- pending | running | completed | failed
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    file_path TEXT NOT NULL,
+    -- Rows read so far, and how many of them were rejected
+    processed_rows INTEGER NOT NULL DEFAULT 0,
+    failed_rows INTEGER NOT NULL DEFAULT 0,
+    -- CSV of rejected rows (row, id, error), once the import is done
+    error_report_path TEXT,
+    -- Why the whole import failed (unreadable file, database error)
+    error_message TEXT,
+    created_by VARCHAR(36),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_data_imports_tenant ON data_imports(tenant_id, created_at);
@@ -0,0 +1,3 @@
+DELETE FROM permissions WHERE name = 'maintenance.manage';
+DROP TABLE IF EXISTS maintenance_state;
+DROP FUNCTION IF EXISTS notify_maintenance_changed();
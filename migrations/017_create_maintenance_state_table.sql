@@ -0,0 +1,23 @@
+-- Maintenance mode shared by every instance (MAINTENANCE_STORE=database): one row while the application is down
+CREATE TABLE IF NOT EXISTS maintenance_state (
+    id SMALLINT PRIMARY KEY DEFAULT 1 CHECK (id = 1),
+    -- message, retry, secret, render, norender, redirect, created_at
+    state JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Instances LISTEN on this channel and reload their cached state
+CREATE OR REPLACE FUNCTION notify_maintenance_changed() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_notify('maintenance_changed', '');
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER maintenance_state_changed
+AFTER INSERT OR UPDATE OR DELETE ON maintenance_state
+FOR EACH STATEMENT EXECUTE FUNCTION notify_maintenance_changed();
+
+INSERT INTO permissions (name, description) VALUES
+    ('maintenance.manage', 'Put the application into and out of maintenance mode')
+ON CONFLICT (name) DO NOTHING;
@@ -0,0 +1,4 @@
+ALTER TABLE users
+DROP COLUMN IF EXISTS two_factor_last_step,
+DROP COLUMN IF EXISTS two_factor_failed_attempts,
+DROP COLUMN IF EXISTS two_factor_locked_until;
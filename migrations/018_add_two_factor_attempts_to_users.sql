@@ -0,0 +1,7 @@
+-- TOTP replay protection and lockout of the second login step
+ALTER TABLE users
+-- Time step of the last accepted code; that step and earlier ones are refused
+ADD COLUMN two_factor_last_step BIGINT,
+-- Consecutive wrong codes; reset on success or when the lockout starts
+ADD COLUMN two_factor_failed_attempts INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN two_factor_locked_until TIMESTAMPTZ;
@@ -0,0 +1,4 @@
+-- Revocation of issued JWTs: tokens carry the version they were issued with
+-- and are refused once it no longer matches (role change, deactivation, password reset)
+ALTER TABLE users
+ADD COLUMN token_version INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,4 @@
+-- API keys only count as second-factor verified when minted from a 2FA session.
+-- Existing keys were created before this was recorded, so they start unverified.
+ALTER TABLE api_keys
+ADD COLUMN created_with_mfa BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,6 @@
+-- Role grants are shared by every tenant, so managing them is a platform operation:
+-- it needs a default-tenant user whose role holds `roles.manage` by name (the `*` of
+-- tenant admins doesn't count). Keep the default tenant's admins able to do it.
+INSERT INTO role_permissions (role, permission) VALUES
+    ('admin', 'roles.manage')
+ON CONFLICT (role, permission) DO NOTHING;
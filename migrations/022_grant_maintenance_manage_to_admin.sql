@@ -0,0 +1,6 @@
+-- Maintenance state and bypass secrets are shared by every instance and tenant, so
+-- managing them needs a default-tenant user whose role holds `maintenance.manage`
+-- by name (the `*` of tenant admins doesn't count).
+INSERT INTO role_permissions (role, permission) VALUES
+    ('admin', 'maintenance.manage')
+ON CONFLICT (role, permission) DO NOTHING;
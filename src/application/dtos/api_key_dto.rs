@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use validator::Validate;
+
+/// DTO to create an API key
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct CreateApiKeyRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: String,
+
+    /// Permissions the key may use, e.g. `["queue.view"]`
+    #[serde(default)]
+    pub scopes: Vec<String>,
+
+    /// Days until the key expires; omit for a key that never expires
+    #[validate(range(min = 1, max = 365, message = "Expiry must be between 1 and 365 days"))]
+    pub expires_in_days: Option<i64>,
+}
+
+/// DTO describing an API key (without secret material)
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ApiKeyResponse {
+    pub id: String,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Vec<String>,
+    pub created_with_mfa: bool,
+    pub expires_at: Option<String>,
+    pub last_used_at: Option<String>,
+    pub revoked_at: Option<String>,
+    pub created_at: String,
+}
+
+/// DTO returned once on creation; `key` cannot be retrieved again
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CreatedApiKeyResponse {
+    #[serde(flatten)]
+    pub api_key: ApiKeyResponse,
+    pub key: String,
+}
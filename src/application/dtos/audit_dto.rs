@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use serde_json::Value;
+
+/// Query params for the audit log (`?actor_id=&action=user.*&from=2024-01-01T00:00:00Z&...`)
+#[derive(Debug, Deserialize, Default, JsonSchema)]
+pub struct AuditLogQuery {
+    pub actor_id: Option<String>,
+    /// Exact action or a `prefix.*` wildcard
+    pub action: Option<String>,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub ip_address: Option<String>,
+    /// Inclusive lower bound (RFC 3339)
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound (RFC 3339)
+    pub to: Option<DateTime<Utc>>,
+    pub page: Option<i32>,
+    pub per_page: Option<i32>,
+    /// `next_cursor` of the previous page (empty for the first page); replaces `page`
+    pub cursor: Option<String>,
+}
+
+/// DTO for an audit log entry
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AuditEntryResponse {
+    pub id: String,
+    pub tenant_id: Option<String>,
+    pub actor_id: Option<String>,
+    pub impersonator_id: Option<String>,
+    pub action: String,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub changes: Option<Value>,
+    pub created_at: String,
+}
@@ -1,9 +1,10 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use crate::domain::value_objects::Role;
 use validator::Validate;  
 
 /// // DTO to register a new user
-#[derive(Debug, Deserialize, Serialize, Validate)]  
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]  
 pub struct RegisterUserRequest {
     #[validate(email(message = "Invalid email format"))]
     pub email: String,
@@ -16,7 +17,7 @@ pub struct RegisterUserRequest {
 }
 
 /// DTO for user login
-#[derive(Debug, Deserialize, Serialize, Validate)]  
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]  
 pub struct LoginRequest {
     #[validate(email(message = "Invalid email format"))]
     pub email: String,
@@ -25,8 +26,21 @@ pub struct LoginRequest {
     pub password: String,
 }
 
+/// DTO to change a password that an admin marked for reset
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct ChangePasswordRequest {
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+
+    #[validate(length(min = 1, message = "Current password is required"))]
+    pub current_password: String,
+
+    #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
+    pub new_password: String,
+}
+
 /// DTO to update user profile
-#[derive(Debug, Deserialize, Serialize, Validate)]  
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]  
 pub struct UpdateProfileRequest {
     #[validate(length(min = 3, max = 50, message = "Username must be between 3 and 50 characters"))]
     pub username: Option<String>,
@@ -36,10 +50,13 @@ pub struct UpdateProfileRequest {
     
     #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
     pub password: Option<String>,
+
+    /// Required together with `password`
+    pub current_password: Option<String>,
 }
 
 /// DTO to update user role (admin only)
-#[derive(Debug, Deserialize, Serialize, Validate)]  
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]  
 pub struct UpdateRoleRequest {
     #[validate(length(min = 1, message = "Role is required"))]
     pub role: String,
@@ -47,43 +64,27 @@ pub struct UpdateRoleRequest {
 
 
 /// DTO for user response (without sensitive data)
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct UserResponse {
     pub id: String,
     pub email: String,
     pub username: String,
     pub role: String,
     pub is_active: bool,
+    pub password_reset_required: bool,
+    pub two_factor_enabled: bool,
     pub created_at: String,
     pub updated_at: String,
+    /// Only present for trashed users
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<String>,
+    /// Current version, also sent as the `ETag`
+    pub version: i32,
 }
 
 /// DTO for authentication response
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AuthResponse {
     pub user: UserResponse,
     pub token: String,
 }
-
-/// DTO for paginated response
-#[derive(Debug, Serialize, Deserialize)]
-pub struct PaginatedResponse<T> {
-    pub data: Vec<T>,
-    pub total: i32,
-    pub page: i32,
-    pub per_page: i32,
-    pub total_pages: i32,
-}
-
-impl<T> PaginatedResponse<T> {
-    pub fn new(data: Vec<T>, total: i32, page: i32, per_page: i32) -> Self {
-        let total_pages = (total + per_page - 1) / per_page;
-        Self {
-            data,
-            total,
-            page,
-            per_page,
-            total_pages,
-        }
-    }
-}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+use crate::domain::entities::{DataFormat, ImportStatus, TestItem, User};
+
+/// Query of the export endpoints: `format` is `csv` (default) or `ndjson`,
+/// `trashed` is `with` or `only` to include deleted records
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ExportQuery {
+    pub format: Option<String>,
+    pub trashed: Option<String>,
+}
+
+/// Query of the import endpoints: `format` of the uploaded body, `csv` (default) or `ndjson`
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ImportQuery {
+    pub format: Option<String>,
+}
+
+/// DTO for the state of an import
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DataImportResponse {
+    pub id: String,
+    pub entity: String,
+    pub format: DataFormat,
+    pub status: ImportStatus,
+    pub processed_rows: i32,
+    pub failed_rows: i32,
+    /// The rejected rows can be downloaded from `.../imports/{id}/errors`
+    pub has_error_report: bool,
+    pub error_message: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub finished_at: Option<String>,
+}
+
+/// A test item as a row of an export or import file.
+///
+/// On import, a row without `id` creates an item and a row with one updates it
+/// (checked against `version` when given). Timestamps are ignored on import, and an
+/// empty `optional_field` leaves the stored value unchanged.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct TestItemRecord {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub subject: Option<String>,
+    #[serde(default)]
+    pub optional_field: Option<String>,
+    #[serde(default)]
+    pub version: Option<i32>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+impl From<TestItem> for TestItemRecord {
+    fn from(item: TestItem) -> Self {
+        Self {
+            id: Some(item.id),
+            subject: Some(item.subject),
+            optional_field: item.optional_field,
+            version: Some(item.version),
+            created_at: Some(item.created_at.to_rfc3339()),
+            updated_at: Some(item.updated_at.to_rfc3339()),
+        }
+    }
+}
+
+/// A user as a row of an export or import file.
+///
+/// Imports only update the profile (`email`, `username`) of existing users; role and
+/// activation are exported for reference but changed through the audited admin endpoints.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct UserRecord {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub role: Option<String>,
+    #[serde(default)]
+    pub is_active: Option<bool>,
+    #[serde(default)]
+    pub version: Option<i32>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+impl From<User> for UserRecord {
+    fn from(user: User) -> Self {
+        Self {
+            id: Some(user.id),
+            email: Some(user.email.as_str().to_string()),
+            username: Some(user.username.as_str().to_string()),
+            role: Some(user.role.to_string()),
+            is_active: Some(user.is_active),
+            version: Some(user.version),
+            created_at: Some(user.created_at.to_rfc3339()),
+            updated_at: Some(user.updated_at.to_rfc3339()),
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use validator::Validate;
+
+use crate::domain::entities::{MaintenancePhase, MaintenanceState, DEFAULT_BYPASS_NAME};
+use crate::errors::DomainError;
+
+/// DTO to put the application into maintenance mode (the options of `ironclad down`)
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct MaintenanceRequest {
+    #[validate(length(min = 1, max = 500, message = "Message must be between 1 and 500 characters"))]
+    pub message: Option<String>,
+
+    /// Retry-After, in seconds, for a window without end (default: 60)
+    #[serde(default = "default_retry")]
+    #[validate(range(min = 1, max = 86400, message = "Retry must be between 1 and 86400 seconds"))]
+    pub retry: u32,
+
+    /// Secret of the `default` bypass: visiting `/{secret}` grants a signed bypass cookie
+    pub secret: Option<String>,
+
+    /// More named bypasses; those without a secret get a random one, returned once
+    #[serde(default)]
+    #[validate(length(max = 20, message = "At most 20 bypasses"))]
+    pub bypass: Vec<BypassRequest>,
+
+    /// Template under templates/render/down, e.g. `emergency` or `emergency::low`
+    pub render: Option<String>,
+
+    /// Answer browsers with JSON too
+    #[serde(default)]
+    pub norender: bool,
+
+    /// Redirect every request to this path or URL
+    pub redirect: Option<String>,
+
+    /// Start of a scheduled window, announced until then; omit to start at once
+    pub starts_at: Option<DateTime<Utc>>,
+
+    /// End of the window, after which the application is up again by itself
+    pub ends_at: Option<DateTime<Utc>>,
+
+    /// Path prefixes under maintenance, e.g. `["/api/test-items"]`; omit for the whole application
+    #[serde(default)]
+    #[validate(length(max = 50, message = "At most 50 path prefixes"))]
+    pub paths: Vec<String>,
+
+    /// IP addresses or CIDR blocks served as usual, e.g. `["10.0.0.0/8"]`
+    #[serde(default)]
+    #[validate(length(max = 100, message = "At most 100 allowed ranges"))]
+    pub allow: Vec<String>,
+}
+
+fn default_retry() -> u32 {
+    60
+}
+
+/// DTO to add (or rotate) a named bypass secret
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct BypassRequest {
+    /// Who the secret is for, e.g. `office` (letters, digits, `-` or `_`)
+    #[validate(length(min = 1, max = 50, message = "Name must be between 1 and 50 characters"))]
+    pub name: String,
+
+    /// 8 to 128 URL-safe characters; omit for a random secret
+    pub secret: Option<String>,
+}
+
+/// DTO returned once when a bypass is added; `secret` cannot be retrieved again
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BypassSecretResponse {
+    pub name: String,
+    pub secret: String,
+    /// Path to visit to get the bypass cookie
+    pub url: String,
+}
+
+impl BypassSecretResponse {
+    pub fn new(name: String, secret: String) -> Self {
+        let url = format!("/{}", secret);
+        Self { name, secret, url }
+    }
+}
+
+impl MaintenanceRequest {
+    /// Requested bypasses, `secret` first as the `default` one
+    pub fn bypass_requests(&self) -> Vec<BypassRequest> {
+        let default = self.secret.clone().map(|secret| BypassRequest {
+            name: DEFAULT_BYPASS_NAME.to_string(),
+            secret: Some(secret),
+        });
+        default.into_iter().chain(self.bypass.iter().cloned()).collect()
+    }
+
+    /// State without bypasses (their secrets are hashed by `MaintenanceService`)
+    pub fn into_state(self) -> Result<MaintenanceState, DomainError> {
+        Ok(MaintenanceState {
+            render: self.render,
+            norender: self.norender,
+            redirect: self.redirect,
+            starts_at: self.starts_at,
+            ends_at: self.ends_at,
+            paths: self.paths,
+            allow: self.allow.iter().map(|range| range.parse()).collect::<Result<_, _>>()?,
+            ..MaintenanceState::new(self.message, self.retry)
+        })
+    }
+}
+
+/// `up`, `scheduled` (announced) or `down`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MaintenanceStatus {
+    Up,
+    Scheduled,
+    Down,
+}
+
+/// DTO describing the maintenance window to clients
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MaintenanceWindowResponse {
+    pub status: MaintenanceStatus,
+    pub message: Option<String>,
+    pub starts_at: Option<String>,
+    pub ends_at: Option<String>,
+    /// Path prefixes concerned; empty for the whole application
+    pub paths: Vec<String>,
+}
+
+impl MaintenanceWindowResponse {
+    pub fn from_state(state: Option<&MaintenanceState>, now: DateTime<Utc>) -> Self {
+        let status = match state.map(|s| s.phase(now)) {
+            Some(MaintenancePhase::Active) => MaintenanceStatus::Down,
+            Some(MaintenancePhase::Scheduled) => MaintenanceStatus::Scheduled,
+            Some(MaintenancePhase::Ended) | None => MaintenanceStatus::Up,
+        };
+        // Nothing to tell about a window that is over
+        let state = state.filter(|_| status != MaintenanceStatus::Up);
+
+        Self {
+            status,
+            message: state.map(|s| s.message.clone()),
+            starts_at: state.map(|s| s.starts_at.unwrap_or(s.created_at).to_rfc3339()),
+            ends_at: state.and_then(|s| s.ends_at).map(|t| t.to_rfc3339()),
+            paths: state.map(|s| s.paths.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+/// DTO describing maintenance mode to admins (stored secrets are never returned)
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MaintenanceResponse {
+    #[serde(flatten)]
+    pub window: MaintenanceWindowResponse,
+    pub retry: Option<u32>,
+    /// Names of the bypass secrets
+    pub bypass: Vec<String>,
+    /// Secrets added by this request, only returned here
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secrets: Vec<BypassSecretResponse>,
+    pub render: Option<String>,
+    pub norender: bool,
+    pub redirect: Option<String>,
+    pub allow: Vec<String>,
+}
+
+impl MaintenanceResponse {
+    pub fn from_state(state: Option<&MaintenanceState>, now: DateTime<Utc>) -> Self {
+        let window = MaintenanceWindowResponse::from_state(state, now);
+        let state = state.filter(|_| window.status != MaintenanceStatus::Up);
+
+        Self {
+            window,
+            retry: state.map(|s| s.retry),
+            bypass: state.map(|s| s.bypass.iter().map(|b| b.name.clone()).collect()).unwrap_or_default(),
+            secrets: Vec::new(),
+            render: state.and_then(|s| s.render.clone()),
+            norender: state.is_some_and(|s| s.norender),
+            redirect: state.and_then(|s| s.redirect.clone()),
+            allow: state.map(|s| s.allow.iter().map(|range| range.to_string()).collect()).unwrap_or_default(),
+        }
+    }
+}
@@ -1,5 +1,27 @@
 pub mod auth_dto;
 pub mod test_item_dto;  // 🆕
+pub mod two_factor_dto;
+pub mod permission_dto;
+pub mod user_admin_dto;
+pub mod api_key_dto;
+pub mod oauth_dto;
+pub mod audit_dto;
+pub mod pagination_dto;
+pub mod search_dto;
+pub mod data_transfer_dto;
+pub mod route_dto;
+pub mod maintenance_dto;
 
 pub use auth_dto::*;
-pub use test_item_dto::*;  // 🆕
\ No newline at end of file
+pub use test_item_dto::*;  // 🆕
+pub use two_factor_dto::*;
+pub use permission_dto::*;
+pub use user_admin_dto::*;
+pub use api_key_dto::*;
+pub use oauth_dto::*;
+pub use audit_dto::*;
+pub use pagination_dto::*;
+pub use search_dto::*;
+pub use data_transfer_dto::*;
+pub use route_dto::*;
+pub use maintenance_dto::*;
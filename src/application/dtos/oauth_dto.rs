@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+/// Query params sent by the provider to the callback
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct OAuthCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    /// Set instead of `code` when the user denied access
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+/// DTO describing an enabled provider
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct OAuthProviderResponse {
+    pub name: String,
+    pub authorize_url: String,
+}
+
+/// DTO describing a linked external identity
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct UserIdentityResponse {
+    pub id: String,
+    pub provider: String,
+    pub email: Option<String>,
+    pub created_at: String,
+    pub last_login_at: Option<String>,
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+use crate::interfaces::repositories::{Paged, Pagination};
+
+/// DTO for paginated response.
+///
+/// Offset pages carry `total`, `page` and `total_pages`; cursor pages carry
+/// `next_cursor` instead (absent on the last page).
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "Paginated{T}")]
+pub struct PaginatedResponse<T> {
+    pub data: Vec<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<i32>,
+    pub per_page: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_pages: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl<T> PaginatedResponse<T> {
+    pub fn from_paged<U>(paged: Paged<U>, pagination: &Pagination, f: impl FnMut(U) -> T) -> Self {
+        let per_page = pagination.per_page();
+        let page = match pagination {
+            Pagination::Offset { page, .. } => Some(*page),
+            Pagination::Cursor { .. } => None,
+        };
+
+        Self {
+            data: paged.items.into_iter().map(f).collect(),
+            total: paged.total,
+            page,
+            per_page,
+            total_pages: paged.total.map(|total| (total + per_page as i64 - 1) / per_page as i64),
+            next_cursor: paged.next_cursor.map(|cursor| cursor.encode()),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use validator::Validate;
+
+/// DTO to add a permission to the catalogue
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct CreatePermissionRequest {
+    #[validate(length(min = 1, max = 100, message = "Permission name must be between 1 and 100 characters"))]
+    pub name: String,
+
+    #[validate(length(max = 255, message = "Description must be at most 255 characters"))]
+    pub description: Option<String>,
+}
+
+/// DTO to grant a permission to a role
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct GrantPermissionRequest {
+    #[validate(length(min = 1, max = 100, message = "Permission name must be between 1 and 100 characters"))]
+    pub permission: String,
+}
+
+/// DTO listing the permissions granted to a role
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RolePermissionsResponse {
+    pub role: String,
+    pub permissions: Vec<String>,
+}
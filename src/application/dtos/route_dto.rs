@@ -0,0 +1,9 @@
+use serde::Deserialize;
+use schemars::JsonSchema;
+
+/// Query of the route list: `method` (`GET`, `post`...) and a fragment of the `path`
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RouteListQuery {
+    pub method: Option<String>,
+    pub path: Option<String>,
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+use crate::interfaces::repositories::Ranked;
+
+/// DTO for a full-text search result: the resource plus its relevance
+/// and the matched text with `<mark>` around the hits (not HTML-escaped)
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SearchHit<T> {
+    #[serde(flatten)]
+    pub item: T,
+    pub rank: f32,
+    pub headline: String,
+}
+
+impl<T> SearchHit<T> {
+    pub fn from_ranked<U>(ranked: Ranked<U>, f: impl FnOnce(U) -> T) -> Self {
+        Self {
+            item: f(ranked.item),
+            rank: ranked.rank,
+            headline: ranked.headline,
+        }
+    }
+}
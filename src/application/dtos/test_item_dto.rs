@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use validator::Validate;
 
 /// DTO for creating a test item
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct CreateTestItemRequest {
     #[validate(length(min = 1, max = 255, message = "Subject must be between 1 and 255 characters"))]
     pub subject: String,
@@ -12,7 +13,7 @@ pub struct CreateTestItemRequest {
 }
 
 /// DTO for updating a test item
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct UpdateTestItemRequest {
     #[validate(length(min = 1, max = 255, message = "Subject must be between 1 and 255 characters"))]
     pub subject: Option<String>,
@@ -22,34 +23,96 @@ pub struct UpdateTestItemRequest {
 }
 
 /// DTO for test item response
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct TestItemResponse {
     pub id: String,
     pub subject: String,
     pub optional_field: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    /// Only present for trashed items
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<String>,
+    /// Current version, also sent as the `ETag`
+    pub version: i32,
 }
 
-/// DTO for paginated response
-#[derive(Debug, Serialize, Deserialize)]
-pub struct PaginatedTestItemsResponse {
-    pub data: Vec<TestItemResponse>,
-    pub total: i32,
-    pub page: i32,
-    pub per_page: i32,
-    pub total_pages: i32,
+/// DTO for `POST /api/test-items/bulk`
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct BulkCreateTestItemsRequest {
+    #[validate(length(min = 1, message = "At least one item is required"))]
+    pub items: Vec<CreateTestItemRequest>,
 }
 
-impl PaginatedTestItemsResponse {
-    pub fn new(data: Vec<TestItemResponse>, total: i32, page: i32, per_page: i32) -> Self {
-        let total_pages = (total + per_page - 1) / per_page;
+/// One element of `PATCH /api/test-items/bulk`
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct BulkUpdateTestItem {
+    pub id: String,
+    /// Version the change is based on (like `If-Match`); the current one if absent
+    pub version: Option<i32>,
+    #[serde(flatten)]
+    pub changes: UpdateTestItemRequest,
+}
+
+/// DTO for `PATCH /api/test-items/bulk`
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct BulkUpdateTestItemsRequest {
+    #[validate(length(min = 1, message = "At least one item is required"))]
+    pub items: Vec<BulkUpdateTestItem>,
+}
+
+/// DTO for `DELETE /api/test-items/bulk`
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct BulkDeleteTestItemsRequest {
+    #[validate(length(min = 1, message = "At least one id is required"))]
+    pub ids: Vec<String>,
+}
+
+/// Outcome of one element of a bulk request, with the status the single-item endpoint would return
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BulkItemResult {
+    /// Position of the element in the request
+    pub index: usize,
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item: Option<TestItemResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BulkItemResult {
+    pub fn ok(index: usize, status: u16, id: String, item: Option<TestItemResponse>) -> Self {
+        Self { index, status, id: Some(id), item, error: None }
+    }
+
+    pub fn failed(index: usize, status: u16, id: Option<String>, error: impl Into<String>) -> Self {
+        Self { index, status, id, item: None, error: Some(error.into()) }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// DTO for bulk responses: one result per element, in request order
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BulkResponse {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BulkItemResult>,
+}
+
+impl BulkResponse {
+    pub fn new(mut results: Vec<BulkItemResult>) -> Self {
+        results.sort_by_key(|r| r.index);
+        let succeeded = results.iter().filter(|r| r.is_success()).count();
+
         Self {
-            data,
-            total,
-            page,
-            per_page,
-            total_pages,
+            succeeded,
+            failed: results.len() - succeeded,
+            results,
         }
     }
-}
\ No newline at end of file
+}
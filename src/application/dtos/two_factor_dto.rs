@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use validator::Validate;
+
+use super::AuthResponse;
+
+/// DTO returned when starting TOTP enrollment
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TwoFactorSetupResponse {
+    /// Base32 secret for manual entry
+    pub secret: String,
+    /// `otpauth://` URI to render as a QR code
+    pub otpauth_uri: String,
+}
+
+/// DTO carrying a 6-digit TOTP code
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct TwoFactorCodeRequest {
+    #[validate(length(min = 6, max = 6, message = "Code must be 6 digits"))]
+    pub code: String,
+}
+
+/// DTO to disable two-factor authentication
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct DisableTwoFactorRequest {
+    #[validate(length(min = 1, message = "Password is required"))]
+    pub password: String,
+
+    #[validate(length(min = 6, max = 6, message = "Code must be 6 digits"))]
+    pub code: String,
+}
+
+/// DTO for the second login step (TOTP code or recovery code)
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct TwoFactorLoginRequest {
+    #[validate(length(min = 1, message = "MFA token is required"))]
+    pub mfa_token: String,
+
+    #[validate(length(min = 6, max = 6, message = "Code must be 6 digits"))]
+    pub code: Option<String>,
+
+    #[validate(length(min = 1, max = 32, message = "Invalid recovery code"))]
+    pub recovery_code: Option<String>,
+}
+
+/// DTO listing freshly generated recovery codes (shown only once)
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+/// DTO returned by the password step when a TOTP code is still required
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TwoFactorChallengeResponse {
+    pub two_factor_required: bool,
+    pub mfa_token: String,
+    pub expires_in: i64,
+}
+
+/// Result of the password step of the login
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum LoginResponse {
+    Authenticated(AuthResponse),
+    TwoFactorRequired(TwoFactorChallengeResponse),
+}
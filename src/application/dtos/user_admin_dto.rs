@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+use super::UserResponse;
+
+/// DTO returned when an admin starts impersonating a user
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ImpersonationResponse {
+    pub user: UserResponse,
+    pub token: String,
+    pub expires_in: i64,
+    pub impersonator_id: String,
+}
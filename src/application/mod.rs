@@ -1,3 +1,4 @@
 pub mod dtos;
 pub mod services;
-pub use services::{AuthService, UserService, TestItemService};
+pub mod policies;
+pub use services::{AuthService, UserService, TestItemService, TwoFactorService, PermissionService, ApiKeyService, OAuthService, AuditLogger, TenantService, DataTransferService, MaintenanceService};
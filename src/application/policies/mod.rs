@@ -0,0 +1,97 @@
+//! Resource-level authorization policies.
+//!
+//! A policy answers "may this actor view/update/delete this resource?" without
+//! touching HTTP, so it can be unit tested with plain values. Controllers load
+//! the resource, ask the policy and turn the decision into an `ApiError` with
+//! [`PolicyDecision::authorize`].
+
+pub mod test_item_policy;
+pub mod user_policy;
+
+pub use test_item_policy::TestItemPolicy;
+pub use user_policy::UserPolicy;
+
+use std::sync::Arc;
+use crate::domain::entities::user::Claims;
+use crate::domain::value_objects::Permission;
+use crate::errors::ApiError;
+
+/// Authenticated caller with the permissions granted to their role
+#[derive(Debug, Clone)]
+pub struct Actor {
+    pub claims: Claims,
+    pub permissions: Arc<Vec<Permission>>,
+}
+
+impl Actor {
+    pub fn new(claims: Claims, permissions: Arc<Vec<Permission>>) -> Self {
+        Self { claims, permissions }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.claims.sub
+    }
+
+    pub fn tenant_id(&self) -> &str {
+        &self.claims.tenant_id
+    }
+
+    /// Whether the actor's role holds the permission (wildcards included),
+    /// limited to the API key's scopes when authenticated with a key
+    pub fn can(&self, permission: &str) -> bool {
+        self.claims.scope_allows(permission)
+            && self.permissions.iter().any(|granted| granted.grants(permission))
+    }
+}
+
+/// Outcome of a policy check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyDecision {
+    /// Access granted
+    Allow,
+    /// The actor can see the resource but not perform the action (403)
+    Deny,
+    /// The actor must not learn the resource exists (404)
+    Hide,
+}
+
+impl PolicyDecision {
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, PolicyDecision::Allow)
+    }
+
+    /// Map the decision to the API's 403/404 responses
+    pub fn authorize(self, resource: &str) -> Result<(), ApiError> {
+        match self {
+            PolicyDecision::Allow => Ok(()),
+            PolicyDecision::Deny => Err(ApiError::Forbidden(format!(
+                "You don't have permission to perform this action on this {}",
+                resource
+            ))),
+            PolicyDecision::Hide => Err(ApiError::NotFound(format!("{} not found", capitalize(resource)))),
+        }
+    }
+}
+
+/// Per-entity authorization rules. Every action is denied unless overridden.
+pub trait Policy<R> {
+    fn view(&self, _actor: &Actor, _resource: &R) -> PolicyDecision {
+        PolicyDecision::Hide
+    }
+
+    fn update(&self, _actor: &Actor, _resource: &R) -> PolicyDecision {
+        PolicyDecision::Deny
+    }
+
+    fn delete(&self, _actor: &Actor, _resource: &R) -> PolicyDecision {
+        PolicyDecision::Deny
+    }
+}
+
+fn capitalize(value: &str) -> String {
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
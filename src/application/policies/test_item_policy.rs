@@ -0,0 +1,97 @@
+use crate::domain::entities::TestItem;
+use super::{Actor, Policy, PolicyDecision};
+
+/// Test items are shared by everyone in their tenant; changing them takes
+/// `test_items.update` / `test_items.delete`. Items of other tenants are always hidden.
+pub struct TestItemPolicy;
+
+impl TestItemPolicy {
+    fn same_tenant(actor: &Actor, item: &TestItem) -> bool {
+        actor.tenant_id() == item.tenant_id
+    }
+
+    fn require(actor: &Actor, item: &TestItem, permission: &str) -> PolicyDecision {
+        if !Self::same_tenant(actor, item) {
+            PolicyDecision::Hide
+        } else if actor.can(permission) {
+            PolicyDecision::Allow
+        } else {
+            PolicyDecision::Deny
+        }
+    }
+
+    /// Taking an item out of the trash counts as deleting it
+    pub fn restore(&self, actor: &Actor, item: &TestItem) -> PolicyDecision {
+        self.delete(actor, item)
+    }
+}
+
+impl Policy<TestItem> for TestItemPolicy {
+    fn view(&self, actor: &Actor, item: &TestItem) -> PolicyDecision {
+        if Self::same_tenant(actor, item) {
+            PolicyDecision::Allow
+        } else {
+            PolicyDecision::Hide
+        }
+    }
+
+    fn update(&self, actor: &Actor, item: &TestItem) -> PolicyDecision {
+        Self::require(actor, item, "test_items.update")
+    }
+
+    fn delete(&self, actor: &Actor, item: &TestItem) -> PolicyDecision {
+        Self::require(actor, item, "test_items.delete")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::domain::entities::user::Claims;
+    use crate::domain::value_objects::Permission;
+
+    fn actor(tenant_id: &str, permissions: &[&str]) -> Actor {
+        let claims = Claims::new(
+            "actor".to_string(),
+            "actor@test.com".to_string(),
+            "user".to_string(),
+            tenant_id.to_string(),
+            1000,
+        );
+        let permissions = permissions.iter().map(|p| Permission::from_trusted(p.to_string())).collect();
+        Actor::new(claims, Arc::new(permissions))
+    }
+
+    fn item() -> TestItem {
+        TestItem::new("default".to_string(), "subject".to_string(), None)
+    }
+
+    #[test]
+    fn test_tenant_members_can_view_but_not_change() {
+        let member = actor("default", &[]);
+        assert_eq!(TestItemPolicy.view(&member, &item()), PolicyDecision::Allow);
+        assert_eq!(TestItemPolicy.update(&member, &item()), PolicyDecision::Deny);
+        assert_eq!(TestItemPolicy.delete(&member, &item()), PolicyDecision::Deny);
+    }
+
+    #[test]
+    fn test_permissions_allow_changes() {
+        let editor = actor("default", &["test_items.*"]);
+        assert_eq!(TestItemPolicy.update(&editor, &item()), PolicyDecision::Allow);
+        assert_eq!(TestItemPolicy.delete(&editor, &item()), PolicyDecision::Allow);
+        assert_eq!(TestItemPolicy.restore(&editor, &item()), PolicyDecision::Allow);
+
+        let updater = actor("default", &["test_items.update"]);
+        assert_eq!(TestItemPolicy.update(&updater, &item()), PolicyDecision::Allow);
+        assert_eq!(TestItemPolicy.delete(&updater, &item()), PolicyDecision::Deny);
+    }
+
+    #[test]
+    fn test_other_tenant_is_hidden_even_from_admins() {
+        let foreign_admin = actor("acme", &["*"]);
+        assert_eq!(TestItemPolicy.view(&foreign_admin, &item()), PolicyDecision::Hide);
+        assert_eq!(TestItemPolicy.update(&foreign_admin, &item()), PolicyDecision::Hide);
+        assert_eq!(TestItemPolicy.delete(&foreign_admin, &item()), PolicyDecision::Hide);
+    }
+}
@@ -0,0 +1,152 @@
+use crate::domain::entities::User;
+use super::{Actor, Policy, PolicyDecision};
+
+/// Users can manage their own account; `users.view` / `users.manage` cover everyone else
+/// in the same tenant. Users of other tenants are always hidden.
+pub struct UserPolicy;
+
+impl UserPolicy {
+    fn same_tenant(actor: &Actor, user: &User) -> bool {
+        actor.tenant_id() == user.tenant_id
+    }
+
+    fn is_self(actor: &Actor, user: &User) -> bool {
+        actor.id() == user.id
+    }
+
+    /// The owner of an account acts on it without a role permission, but an API key
+    /// still needs a scope covering the action (`users.*` or `*` for both)
+    fn owner_decision(actor: &Actor, permission: &str) -> PolicyDecision {
+        if actor.claims.scope_allows(permission) {
+            PolicyDecision::Allow
+        } else {
+            PolicyDecision::Deny
+        }
+    }
+
+    /// Mutations on other accounts: hidden if the actor can't even view them
+    fn manage(&self, actor: &Actor, user: &User) -> PolicyDecision {
+        if !Self::same_tenant(actor, user) {
+            PolicyDecision::Hide
+        } else if Self::is_self(actor, user) {
+            Self::owner_decision(actor, "users.manage")
+        } else if actor.can("users.manage") {
+            PolicyDecision::Allow
+        } else if self.view(actor, user).is_allowed() {
+            PolicyDecision::Deny
+        } else {
+            PolicyDecision::Hide
+        }
+    }
+}
+
+impl Policy<User> for UserPolicy {
+    fn view(&self, actor: &Actor, user: &User) -> PolicyDecision {
+        if !Self::same_tenant(actor, user) {
+            PolicyDecision::Hide
+        } else if Self::is_self(actor, user) {
+            Self::owner_decision(actor, "users.view")
+        } else if actor.can("users.view") || actor.can("users.manage") {
+            PolicyDecision::Allow
+        } else {
+            PolicyDecision::Hide
+        }
+    }
+
+    fn update(&self, actor: &Actor, user: &User) -> PolicyDecision {
+        self.manage(actor, user)
+    }
+
+    fn delete(&self, actor: &Actor, user: &User) -> PolicyDecision {
+        self.manage(actor, user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::domain::entities::user::Claims;
+    use crate::domain::value_objects::{EmailAddress, Permission, Username};
+
+    fn actor(id: &str, permissions: &[&str]) -> Actor {
+        tenant_actor(id, "default", permissions)
+    }
+
+    fn tenant_actor(id: &str, tenant_id: &str, permissions: &[&str]) -> Actor {
+        let claims = Claims::new(
+            id.to_string(),
+            format!("{}@test.com", id),
+            "user".to_string(),
+            tenant_id.to_string(),
+            1000,
+        );
+        let permissions = permissions.iter().map(|p| Permission::from_trusted(p.to_string())).collect();
+        Actor::new(claims, Arc::new(permissions))
+    }
+
+    fn user() -> User {
+        let mut user = User::new(
+            "default".to_string(),
+            EmailAddress::new("target@test.com".to_string()).unwrap(),
+            Username::new("target".to_string()).unwrap(),
+            "hash".to_string(),
+        )
+        .unwrap();
+        user.id = "target".to_string();
+        user
+    }
+
+    #[test]
+    fn test_owner_can_manage_own_account() {
+        let owner = actor("target", &[]);
+        assert_eq!(UserPolicy.view(&owner, &user()), PolicyDecision::Allow);
+        assert_eq!(UserPolicy.update(&owner, &user()), PolicyDecision::Allow);
+        assert_eq!(UserPolicy.delete(&owner, &user()), PolicyDecision::Allow);
+    }
+
+    #[test]
+    fn test_stranger_gets_not_found() {
+        let stranger = actor("other", &[]);
+        assert_eq!(UserPolicy.view(&stranger, &user()), PolicyDecision::Hide);
+        assert_eq!(UserPolicy.delete(&stranger, &user()), PolicyDecision::Hide);
+    }
+
+    #[test]
+    fn test_viewer_is_forbidden_from_mutations() {
+        let viewer = actor("other", &["users.view"]);
+        assert_eq!(UserPolicy.view(&viewer, &user()), PolicyDecision::Allow);
+        assert_eq!(UserPolicy.update(&viewer, &user()), PolicyDecision::Deny);
+
+        let admin = actor("other", &["*"]);
+        assert_eq!(UserPolicy.delete(&admin, &user()), PolicyDecision::Allow);
+    }
+
+    #[test]
+    fn test_other_tenant_is_hidden_even_from_admins() {
+        let foreign_admin = tenant_actor("other", "acme", &["*"]);
+        assert_eq!(UserPolicy.view(&foreign_admin, &user()), PolicyDecision::Hide);
+        assert_eq!(UserPolicy.update(&foreign_admin, &user()), PolicyDecision::Hide);
+        assert_eq!(UserPolicy.delete(&foreign_admin, &user()), PolicyDecision::Hide);
+
+        // Same user ID in another tenant is not "self" either
+        let namesake = tenant_actor("target", "acme", &[]);
+        assert_eq!(UserPolicy.view(&namesake, &user()), PolicyDecision::Hide);
+    }
+
+    #[test]
+    fn test_owner_api_key_needs_a_user_scope() {
+        let mut key = actor("target", &["*"]);
+        key.claims.scopes = Some(vec!["test_items.export".to_string()]);
+        assert_eq!(UserPolicy.view(&key, &user()), PolicyDecision::Deny);
+        assert_eq!(UserPolicy.update(&key, &user()), PolicyDecision::Deny);
+        assert_eq!(UserPolicy.delete(&key, &user()), PolicyDecision::Deny);
+
+        key.claims.scopes = Some(vec!["users.view".to_string()]);
+        assert_eq!(UserPolicy.view(&key, &user()), PolicyDecision::Allow);
+        assert_eq!(UserPolicy.update(&key, &user()), PolicyDecision::Deny);
+
+        key.claims.scopes = Some(vec!["users.*".to_string()]);
+        assert_eq!(UserPolicy.update(&key, &user()), PolicyDecision::Allow);
+    }
+}
@@ -0,0 +1,141 @@
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use crate::application::dtos::{ApiKeyResponse, CreateApiKeyRequest, CreatedApiKeyResponse};
+use crate::config::AppConfig;
+use crate::domain::entities::ApiKey;
+use crate::domain::entities::user::Claims;
+use crate::domain::value_objects::Permission;
+use crate::errors::ApiError;
+use crate::interfaces::{ApiKeyRepository, UserRepository};
+use crate::utils::api_key;
+
+/// `last_used_at` is only rewritten when older than this, to avoid a write per request
+const LAST_USED_RESOLUTION_SECONDS: i64 = 60;
+
+/// Personal access tokens for machine clients
+pub struct ApiKeyService {
+    api_key_repository: Arc<dyn ApiKeyRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    config: Arc<AppConfig>,
+}
+
+impl ApiKeyService {
+    pub fn new(
+        api_key_repository: Arc<dyn ApiKeyRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        config: Arc<AppConfig>,
+    ) -> Self {
+        Self {
+            api_key_repository,
+            user_repository,
+            config,
+        }
+    }
+
+    /// Create a key for the session's user. The plain key is only returned here.
+    pub async fn create(
+        &self,
+        claims: &Claims,
+        request: CreateApiKeyRequest,
+    ) -> Result<CreatedApiKeyResponse, ApiError> {
+        // A key carries the second factor of the session that created it, so roles that
+        // require 2FA must mint keys from a 2FA session
+        if self.config.two_factor.is_required_for(&claims.role) && !claims.mfa {
+            return Err(ApiError::Forbidden(
+                "Two-factor authentication is required for this account".to_string(),
+            ));
+        }
+
+        let scopes = request
+            .scopes
+            .into_iter()
+            .map(Permission::new)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let expires_at = request.expires_in_days.map(|days| Utc::now() + Duration::days(days));
+        let generated = api_key::generate();
+
+        let mut key = ApiKey::new(
+            claims.tenant_id.clone(),
+            claims.sub.clone(),
+            request.name.trim().to_string(),
+            generated.prefix,
+            api_key::hash(&generated.key),
+            scopes,
+            expires_at,
+        );
+        key.created_with_mfa = claims.mfa;
+        let created = self.api_key_repository.create(&key).await?;
+
+        tracing::info!(user_id = %created.user_id, api_key_id = %created.id, "API key created");
+
+        Ok(CreatedApiKeyResponse {
+            api_key: created.to_response(),
+            key: generated.key,
+        })
+    }
+
+    /// List the user's keys
+    pub async fn list(&self, claims: &Claims) -> Result<Vec<ApiKeyResponse>, ApiError> {
+        let keys = self.api_key_repository.get_by_user(&claims.tenant_id, &claims.sub).await?;
+        Ok(keys.iter().map(|k| k.to_response()).collect())
+    }
+
+    /// Revoke one of the user's keys
+    pub async fn revoke(&self, claims: &Claims, api_key_id: &str) -> Result<(), ApiError> {
+        if !self.api_key_repository.revoke(&claims.tenant_id, api_key_id, &claims.sub).await? {
+            return Err(ApiError::NotFound("API key not found".to_string()));
+        }
+
+        tracing::info!(user_id = %claims.sub, api_key_id = %api_key_id, "API key revoked");
+        Ok(())
+    }
+
+    /// Resolve a presented key to request claims
+    pub async fn authenticate(&self, key: &str) -> Result<Claims, ApiError> {
+        let prefix = api_key::parse_prefix(key).ok_or(ApiError::Unauthorized)?;
+
+        let api_key = self
+            .api_key_repository
+            .get_by_prefix(prefix)
+            .await?
+            .ok_or(ApiError::Unauthorized)?;
+
+        let now = Utc::now();
+        if !api_key::verify(key, &api_key.key_hash) || !api_key.is_usable(now) {
+            return Err(ApiError::Unauthorized);
+        }
+
+        let user = self
+            .user_repository
+            .get_by_id(&api_key.tenant_id, &api_key.user_id)
+            .await?
+            .ok_or(ApiError::Unauthorized)?;
+
+        if !user.is_active() {
+            return Err(ApiError::Forbidden("Account is disabled".to_string()));
+        }
+
+        let stale = api_key
+            .last_used_at
+            .is_none_or(|t| (now - t).num_seconds() >= LAST_USED_RESOLUTION_SECONDS);
+        if stale {
+            if let Err(e) = self.api_key_repository.touch_last_used(&api_key.id).await {
+                tracing::warn!(api_key_id = %api_key.id, "Failed to record API key use: {}", e);
+            }
+        }
+
+        let mut claims = Claims::new(
+            user.id,
+            user.email.as_str().to_string(),
+            user.role.as_str().to_string(),
+            user.tenant_id,
+            api_key.expires_at.map_or(i64::MAX, |t| t.timestamp()),
+        );
+        claims.mfa = api_key.created_with_mfa;
+        claims.api_key_id = Some(api_key.id);
+        claims.scopes = Some(api_key.scopes.iter().map(|s| s.as_str().to_string()).collect());
+
+        Ok(claims)
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::Utc;
+use crate::application::dtos::{AuditEntryResponse, AuditLogQuery, PaginatedResponse};
+use crate::config::AppConfig;
+use crate::domain::entities::AuditEntry;
+use crate::errors::ApiError;
+use crate::interfaces::repositories::{AuditFilter, AuditLogRepository, Pagination};
+
+/// How often the retention purge runs
+const RETENTION_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Append-only audit trail of security-relevant actions
+pub struct AuditLogger {
+    audit_log_repository: Arc<dyn AuditLogRepository>,
+    config: Arc<AppConfig>,
+}
+
+impl AuditLogger {
+    pub fn new(audit_log_repository: Arc<dyn AuditLogRepository>, config: Arc<AppConfig>) -> Self {
+        Self {
+            audit_log_repository,
+            config,
+        }
+    }
+
+    /// Persist an entry. Never fails: a storage error is logged and the request carries on.
+    pub async fn record(&self, entry: AuditEntry) {
+        tracing::info!(
+            target: "audit",
+            action = %entry.action,
+            actor_id = entry.actor_id.as_deref().unwrap_or("-"),
+            target_id = entry.target_id.as_deref().unwrap_or("-"),
+            "{}",
+            entry.action
+        );
+
+        if let Err(e) = self.audit_log_repository.append(&entry).await {
+            tracing::error!(action = %entry.action, audit_id = %entry.id, "Failed to write audit entry: {}", e);
+        }
+    }
+
+    /// Filtered, paginated entries of one tenant (newest first)
+    pub async fn search(&self, tenant_id: &str, query: AuditLogQuery) -> Result<PaginatedResponse<AuditEntryResponse>, ApiError> {
+        let pagination = Pagination::new(query.page, query.per_page, query.cursor.as_deref(), 50, 200)?;
+
+        let filter = AuditFilter {
+            tenant_id: Some(tenant_id.to_string()),
+            actor_id: query.actor_id,
+            action: query.action,
+            target_type: query.target_type,
+            target_id: query.target_id,
+            ip_address: query.ip_address,
+            from: query.from,
+            to: query.to,
+        };
+
+        let entries = self.audit_log_repository.search(&filter, &pagination).await?;
+
+        Ok(PaginatedResponse::from_paged(entries, &pagination, |e| e.to_response()))
+    }
+
+    /// Delete entries past the retention window
+    pub async fn purge_expired(&self) -> Result<u64, ApiError> {
+        let days = self.config.audit.retention_days;
+        if days == 0 {
+            return Ok(0);
+        }
+
+        let cutoff = Utc::now() - chrono::Duration::days(days as i64);
+        self.audit_log_repository.purge_before(cutoff).await
+    }
+
+    /// Run `purge_expired` in the background once a day
+    pub fn start_retention(self: Arc<Self>) {
+        if self.config.audit.retention_days == 0 {
+            tracing::info!("Audit log retention disabled (AUDIT_RETENTION_DAYS=0)");
+            return;
+        }
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(RETENTION_INTERVAL);
+            loop {
+                interval.tick().await;
+                match self.purge_expired().await {
+                    Ok(0) => {}
+                    Ok(purged) => tracing::info!(purged, "Expired audit entries purged"),
+                    Err(e) => tracing::warn!("Audit log retention failed: {}", e),
+                }
+            }
+        });
+    }
+}
@@ -1,36 +1,51 @@
 use std::sync::Arc;
-use crate::application::dtos::{AuthResponse, LoginRequest, RegisterUserRequest};
+use chrono::Utc;
+use crate::application::dtos::{
+    AuthResponse, ChangePasswordRequest, LoginRequest, LoginResponse, RegisterUserRequest, TwoFactorChallengeResponse,
+    TwoFactorLoginRequest,
+};
 use crate::config::AppConfig;
-use crate::domain::entities::User;
+use crate::application::services::AuditLogger;
+use crate::domain::entities::{AuditEntry, RequestOrigin, User};
+use crate::domain::entities::user::TokenType;
 use crate::domain::value_objects::{EmailAddress, Username};
 use crate::errors::ApiError;
 use crate::interfaces::UserRepository;
-use crate::utils::auth::hash_password;
-use crate::utils::jwt::create_token;
+use crate::utils::jwt::{create_mfa_pending_token, create_mfa_verified_token, create_token, verify_token};
+use crate::utils::password::{PasswordHasher, PasswordVerification};
+use crate::utils::totp;
 use crate::shared::validator::validate_strong_password;
 
 pub struct AuthService {
     user_repository: Arc<dyn UserRepository>,
+    audit_logger: Arc<AuditLogger>,
     config: Arc<AppConfig>,
+    password_hasher: PasswordHasher,
 }
 
 impl AuthService {
-    pub fn new(user_repository: Arc<dyn UserRepository>, config: Arc<AppConfig>) -> Self {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        audit_logger: Arc<AuditLogger>,
+        config: Arc<AppConfig>,
+    ) -> Self {
         Self {
             user_repository,
+            audit_logger,
+            password_hasher: PasswordHasher::new(&config),
             config,
         }
     }
 
     /// Registrar nuevo usuario
-    pub async fn register(&self, request: RegisterUserRequest) -> Result<AuthResponse, ApiError> {
+    pub async fn register(&self, tenant_id: &str, request: RegisterUserRequest) -> Result<AuthResponse, ApiError> {
         // 1. Validar reglas del Application Service (Ej: Password fuerte en texto plano)
         if validate_strong_password(&request.password).is_err() {
             return Err(ApiError::ValidationError("Password does not meet security requirements".to_string()));
         }
 
         // 2. Validate asynchronous business logic rules (I/O)
-        if self.user_repository.exists_by_email(&request.email).await? {
+        if self.user_repository.exists_by_email(tenant_id, &request.email).await? {
             return Err(ApiError::Conflict("User already exists".to_string()));
         }
         
@@ -38,21 +53,16 @@ impl AuthService {
         let email_vo = EmailAddress::new(request.email)?;
         let username_vo = Username::new(request.username)?;
         
-        let password_hash = hash_password(&request.password, &self.config)?;
+        let password_hash = self.password_hasher.hash(&request.password).await?;
         
         // 4. Crear entidad de forma segura
-        let user = User::new(email_vo, username_vo, password_hash)?;
+        let user = User::new(tenant_id.to_string(), email_vo, username_vo, password_hash)?;
         
         // 5. Persistir
         let created_user = self.user_repository.create(&user).await?;
         
         // 6. Generar token
-        let token = create_token(
-            &created_user.id,
-            created_user.email.as_str(),
-            &created_user.role.to_string(),
-            &self.config,
-        )?;
+        let token = create_token(&created_user, &self.config)?;
         
         Ok(AuthResponse {
             user: created_user.to_response(),
@@ -61,10 +71,63 @@ impl AuthService {
     }
 
     /// Login de usuario
-    pub async fn login(&self, request: LoginRequest) -> Result<AuthResponse, ApiError> {
-        let user = self
+    ///
+    /// First step of the login. Accounts with 2FA enabled receive a short-lived
+    /// "MFA pending" token that must be exchanged via `verify_two_factor_login`.
+    pub async fn login(
+        &self,
+        tenant_id: &str,
+        request: LoginRequest,
+        origin: &RequestOrigin,
+    ) -> Result<LoginResponse, ApiError> {
+        let result = self.check_credentials(tenant_id, &request).await.and_then(|user| {
+            let response = self.complete_login(&user)?;
+            Ok((user, response))
+        });
+
+        match result {
+            Ok((user, response)) => {
+                self.audit_logger
+                    .record(
+                        AuditEntry::new("auth.login")
+                            .tenant(tenant_id)
+                            .actor_id(&user.id)
+                            .origin(origin)
+                            .target("user", &user.id)
+                            .changes(serde_json::json!({
+                                "method": "password",
+                                "two_factor_pending": user.has_two_factor()
+                            })),
+                    )
+                    .await;
+
+                Ok(response)
+            }
+            Err(e @ (ApiError::Unauthorized | ApiError::Forbidden(_))) => {
+                let reason = match &e {
+                    ApiError::Forbidden(message) => message.as_str(),
+                    _ => "invalid credentials",
+                };
+                self.audit_logger
+                    .record(
+                        AuditEntry::new("auth.login_failed")
+                            .tenant(tenant_id)
+                            .origin(origin)
+                            .changes(serde_json::json!({ "email": request.email, "reason": reason })),
+                    )
+                    .await;
+
+                Err(e)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Email + password check (upgrading outdated hashes on success)
+    async fn check_credentials(&self, tenant_id: &str, request: &LoginRequest) -> Result<User, ApiError> {
+        let mut user = self
             .user_repository
-            .get_by_email(&request.email)
+            .get_by_email(tenant_id, &request.email)
             .await?
             .ok_or(ApiError::Unauthorized)?;
 
@@ -72,20 +135,252 @@ impl AuthService {
             return Err(ApiError::Forbidden("Account is disabled".to_string()));
         }
 
-        if !crate::utils::auth::verify_password(&request.password, &user.password_hash)? {
+        match self.password_hasher.verify(&request.password, &user.password_hash).await? {
+            PasswordVerification::Mismatch => return Err(ApiError::Unauthorized),
+            PasswordVerification::Match { needs_rehash: true } => {
+                self.upgrade_password_hash(&mut user, &request.password).await;
+            }
+            PasswordVerification::Match { needs_rehash: false } => {}
+        }
+
+        Ok(user)
+    }
+
+    /// Issue the session for an authenticated user, or a 2FA challenge when enrolled.
+    /// Accounts flagged for a password reset get neither, whatever the login method.
+    pub fn complete_login(&self, user: &User) -> Result<LoginResponse, ApiError> {
+        if user.password_reset_required {
+            return Err(ApiError::Forbidden(
+                "Password reset required. Use /api/auth/change-password".to_string(),
+            ));
+        }
+
+        if user.has_two_factor() {
+            let mfa_token = create_mfa_pending_token(user, &self.config)?;
+
+            return Ok(LoginResponse::TwoFactorRequired(TwoFactorChallengeResponse {
+                two_factor_required: true,
+                mfa_token,
+                expires_in: self.config.two_factor.pending_token_ttl,
+            }));
+        }
+
+        let token = create_token(user, &self.config)?;
+
+        Ok(LoginResponse::Authenticated(AuthResponse {
+            user: user.to_response(),
+            token,
+        }))
+    }
+
+    /// Change the password of an account flagged for reset, then log in with the new one
+    pub async fn change_password(
+        &self,
+        tenant_id: &str,
+        request: ChangePasswordRequest,
+        origin: &RequestOrigin,
+    ) -> Result<LoginResponse, ApiError> {
+        let mut user = self
+            .user_repository
+            .get_by_email(tenant_id, &request.email)
+            .await?
+            .ok_or(ApiError::Unauthorized)?;
+
+        if !user.is_active() {
+            return Err(ApiError::Forbidden("Account is disabled".to_string()));
+        }
+
+        if !self
+            .password_hasher
+            .verify(&request.current_password, &user.password_hash)
+            .await?
+            .is_match()
+        {
             return Err(ApiError::Unauthorized);
         }
 
-        let token = create_token(
-            &user.id,
-            user.email.as_str(),
-            &user.role.to_string(),
-            &self.config,
-        )?;
+        if request.new_password == request.current_password {
+            return Err(ApiError::ValidationError(
+                "New password must be different from the current one".to_string(),
+            ));
+        }
+
+        if validate_strong_password(&request.new_password).is_err() {
+            return Err(ApiError::ValidationError("Password does not meet security requirements".to_string()));
+        }
+
+        user.update_password_hash(self.password_hasher.hash(&request.new_password).await?);
+        self.user_repository.update(&user).await?;
+
+        self.audit_logger
+            .record(
+                AuditEntry::new("auth.password_changed")
+                    .tenant(tenant_id)
+                    .actor_id(&user.id)
+                    .origin(origin)
+                    .target("user", &user.id),
+            )
+            .await;
+
+        self.login(
+            tenant_id,
+            LoginRequest {
+                email: request.email,
+                password: request.new_password,
+            },
+            origin,
+        )
+        .await
+    }
+
+    /// Second login step: exchange the MFA pending token and a TOTP (or recovery) code
+    pub async fn verify_two_factor_login(
+        &self,
+        request: TwoFactorLoginRequest,
+        origin: &RequestOrigin,
+    ) -> Result<AuthResponse, ApiError> {
+        let claims = verify_token(&request.mfa_token, &self.config)?;
+        if claims.token_type != TokenType::MfaPending {
+            return Err(ApiError::Unauthorized);
+        }
+
+        let mut user = self
+            .user_repository
+            .get_by_id(&claims.tenant_id, &claims.sub)
+            .await?
+            .ok_or(ApiError::Unauthorized)?;
+
+        if !user.is_active() {
+            return Err(ApiError::Forbidden("Account is disabled".to_string()));
+        }
+
+        let secret = match (&user.two_factor_secret, user.two_factor_enabled) {
+            (Some(secret), true) => secret.clone(),
+            _ => return Err(ApiError::Unauthorized),
+        };
+
+        let now = Utc::now();
+        if user.is_two_factor_locked(now) {
+            return Err(ApiError::TooManyRequests(
+                "Too many invalid two-factor codes, try again later".to_string(),
+            ));
+        }
+
+        let method = if request.code.is_some() { "totp" } else { "recovery_code" };
+        let verified = match (&request.code, &request.recovery_code) {
+            // A code is only accepted once: its step must be later than the last accepted one
+            (Some(code), _) => match totp::verify_code(&secret, code, now.timestamp()) {
+                Some(step) => self.user_repository.accept_two_factor_step(&user.tenant_id, &user.id, step).await?,
+                None => false,
+            },
+            (None, Some(recovery_code)) => {
+                let consumed = user.consume_recovery_code(&totp::hash_recovery_code(recovery_code));
+                if consumed {
+                    // Fails with a conflict if the same code is being redeemed concurrently
+                    user = self.user_repository.update(&user).await?;
+                    self.user_repository.clear_two_factor_failures(&user.tenant_id, &user.id).await?;
+                    tracing::warn!(user_id = %user.id, "Recovery code used to sign in");
+                }
+                consumed
+            }
+            (None, None) => {
+                return Err(ApiError::ValidationError(
+                    "Either code or recovery_code is required".to_string(),
+                ))
+            }
+        };
+
+        if !verified {
+            let two_factor = &self.config.two_factor;
+            self.user_repository
+                .record_two_factor_failure(
+                    &user.tenant_id,
+                    &user.id,
+                    two_factor.max_failed_attempts,
+                    now + chrono::Duration::seconds(two_factor.lockout_seconds),
+                )
+                .await?;
+        }
+
+        self.audit_logger
+            .record(
+                AuditEntry::new(if verified { "auth.two_factor_verified" } else { "auth.two_factor_failed" })
+                    .tenant(&user.tenant_id)
+                    .actor_id(&user.id)
+                    .origin(origin)
+                    .target("user", &user.id)
+                    .changes(serde_json::json!({ "method": method })),
+            )
+            .await;
+
+        if !verified {
+            return Err(ApiError::Unauthorized);
+        }
+
+        let token = create_mfa_verified_token(&user, &self.config)?;
 
         Ok(AuthResponse {
             user: user.to_response(),
             token,
         })
     }
-}
\ No newline at end of file
+
+    /// Re-hash with the current algorithm/parameters. Failures are logged, never fatal to the login.
+    async fn upgrade_password_hash(&self, user: &mut User, password: &str) {
+        let result = match self.password_hasher.hash(password).await {
+            Ok(password_hash) => {
+                user.upgrade_password_hash(password_hash);
+                self.user_repository.update(user).await.map(|updated| *user = updated)
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(()) => tracing::info!(user_id = %user.id, "Password hash upgraded"),
+            Err(e) => tracing::warn!(user_id = %user.id, "Failed to upgrade password hash: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::postgres::PgPoolOptions;
+    use crate::config::Sources;
+    use crate::infrastructure::persistence::postgres::{PostgresAuditLogRepository, PostgresUserRepository};
+
+    /// `complete_login` never touches the database, so a lazy pool is enough
+    fn service() -> AuthService {
+        let sources = Sources::new([
+            ("DATABASE_URL", "postgres://localhost/test"),
+            ("JWT_SECRET", "auth-test-signing-key"),
+        ]);
+        let config = Arc::new(AppConfig::from_sources(sources).unwrap());
+        let pool = PgPoolOptions::new().connect_lazy("postgres://localhost/test").unwrap();
+        let audit_logger = Arc::new(AuditLogger::new(
+            Arc::new(PostgresAuditLogRepository::new(pool.clone())),
+            config.clone(),
+        ));
+        AuthService::new(Arc::new(PostgresUserRepository::new(pool)), audit_logger, config)
+    }
+
+    fn user() -> User {
+        User::new(
+            "default".to_string(),
+            EmailAddress::new("user@test.com".to_string()).unwrap(),
+            Username::new("login_user".to_string()).unwrap(),
+            "hash".to_string(),
+        )
+        .unwrap()
+    }
+
+    #[actix_web::test]
+    async fn test_complete_login_refuses_accounts_flagged_for_reset() {
+        let service = service();
+        let mut user = user();
+        assert!(matches!(service.complete_login(&user), Ok(LoginResponse::Authenticated(_))));
+
+        user.require_password_reset();
+        assert!(matches!(service.complete_login(&user), Err(ApiError::Forbidden(_))));
+    }
+}
@@ -0,0 +1,523 @@
+use std::fs::File;
+use std::future::Future;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use actix_web::web::Bytes;
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use validator::Validate;
+
+use crate::application::dtos::{
+    BulkResponse,
+    BulkUpdateTestItem,
+    CreateTestItemRequest,
+    DataImportResponse,
+    TestItemRecord,
+    UpdateProfileRequest,
+    UpdateTestItemRequest,
+    UserRecord,
+};
+use crate::application::{TestItemService, UserService};
+use crate::config::ImportConfig;
+use crate::domain::entities::{DataFormat, DataImport, ImportStatus};
+use crate::errors::ApiError;
+use crate::interfaces::repositories::{DataImportRepository, TestItemRepository, Trashed, UserRepository};
+use crate::shared::validation_message;
+
+/// Rows read from an import file at a time; progress is saved after each batch
+const IMPORT_BATCH: usize = 500;
+
+/// Rows encoded into one chunk of an export response
+const EXPORT_CHUNK: usize = 200;
+
+/// Parsed rows of an import file with their 1-based row number (header excluded)
+type Batch<R> = Vec<(usize, Result<R, String>)>;
+
+/// A row that could not be parsed or applied, as written to the error report
+#[derive(Debug, Serialize)]
+struct Rejected {
+    row: usize,
+    id: Option<String>,
+    error: String,
+}
+
+/// CSV/NDJSON exports and imports of test items and users.
+///
+/// Exports stream from the database to the client. Imports are stored under the import
+/// directory and applied by a queued job in batches, through the same services as the API,
+/// so every row goes through the usual validation and version checks.
+pub struct DataTransferService {
+    test_item_repository: Arc<dyn TestItemRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    import_repository: Arc<dyn DataImportRepository>,
+    test_item_service: Arc<TestItemService>,
+    user_service: Arc<UserService>,
+    config: ImportConfig,
+}
+
+impl DataTransferService {
+    pub fn new(
+        test_item_repository: Arc<dyn TestItemRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        import_repository: Arc<dyn DataImportRepository>,
+        test_item_service: Arc<TestItemService>,
+        user_service: Arc<UserService>,
+        config: ImportConfig,
+    ) -> Self {
+        Self {
+            test_item_repository,
+            user_repository,
+            import_repository,
+            test_item_service,
+            user_service,
+            config,
+        }
+    }
+
+    /// Every test item of the tenant, encoded as it is read
+    pub fn export_test_items(&self, tenant_id: &str, format: DataFormat, trashed: Trashed) -> BoxStream<'static, Result<Bytes, ApiError>> {
+        let rows = self.test_item_repository
+            .stream_all(tenant_id, trashed)
+            .map(|row| row.map(TestItemRecord::from));
+        encode(rows, format)
+    }
+
+    /// Every user of the tenant, encoded as it is read (no credentials or 2FA data)
+    pub fn export_users(&self, tenant_id: &str, format: DataFormat, trashed: Trashed) -> BoxStream<'static, Result<Bytes, ApiError>> {
+        let rows = self.user_repository
+            .stream_all(tenant_id, trashed)
+            .map(|row| row.map(UserRecord::from));
+        encode(rows, format)
+    }
+
+    /// Store an uploaded file for `entity` and record a pending import.
+    /// Uploads larger than the configured limit are rejected.
+    pub async fn store_upload<S, E>(
+        &self,
+        tenant_id: &str,
+        entity: &str,
+        format: DataFormat,
+        created_by: Option<String>,
+        body: S,
+    ) -> Result<DataImportResponse, ApiError>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Unpin,
+        E: std::fmt::Display,
+    {
+        tokio::fs::create_dir_all(&self.config.directory)
+            .await
+            .map_err(|e| ApiError::InternalServerError(format!("Failed to create import directory: {}", e)))?;
+
+        let id = DataImport::new_id();
+        let path = Path::new(&self.config.directory).join(format!("{}.{}", id, format.as_str()));
+
+        let size = match self.write_upload(&path, body).await {
+            Ok(size) => size,
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&path).await;
+                return Err(e);
+            }
+        };
+        if size == 0 {
+            let _ = tokio::fs::remove_file(&path).await;
+            return Err(ApiError::ValidationError("The import file is empty".to_string()));
+        }
+
+        let import = DataImport::new(
+            id,
+            tenant_id.to_string(),
+            entity,
+            format,
+            path.to_string_lossy().into_owned(),
+            created_by,
+        );
+        let import = self.import_repository.create(&import).await?;
+
+        Ok(import.to_response())
+    }
+
+    async fn write_upload<S, E>(&self, path: &Path, mut body: S) -> Result<usize, ApiError>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Unpin,
+        E: std::fmt::Display,
+    {
+        let mut file = tokio::fs::File::create(path)
+            .await
+            .map_err(|e| ApiError::InternalServerError(format!("Failed to store import file: {}", e)))?;
+        let mut size = 0;
+
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk.map_err(|e| ApiError::ValidationError(format!("Failed to read upload: {}", e)))?;
+            size += chunk.len();
+            if size > self.config.max_bytes {
+                return Err(ApiError::ValidationError(format!(
+                    "The import file exceeds {} bytes",
+                    self.config.max_bytes
+                )));
+            }
+            file.write_all(&chunk)
+                .await
+                .map_err(|e| ApiError::InternalServerError(format!("Failed to store import file: {}", e)))?;
+        }
+
+        file.flush()
+            .await
+            .map_err(|e| ApiError::InternalServerError(format!("Failed to store import file: {}", e)))?;
+        Ok(size)
+    }
+
+    /// State of an import of `entity` within the tenant
+    pub async fn get_import(&self, tenant_id: &str, entity: &str, id: &str) -> Result<DataImportResponse, ApiError> {
+        Ok(self.find_import(tenant_id, entity, id).await?.to_response())
+    }
+
+    /// Path of the error report of an import, if some rows were rejected
+    pub async fn error_report(&self, tenant_id: &str, entity: &str, id: &str) -> Result<PathBuf, ApiError> {
+        self.find_import(tenant_id, entity, id)
+            .await?
+            .error_report_path
+            .map(PathBuf::from)
+            .ok_or_else(|| ApiError::NotFound("Import has no error report".to_string()))
+    }
+
+    async fn find_import(&self, tenant_id: &str, entity: &str, id: &str) -> Result<DataImport, ApiError> {
+        self.import_repository
+            .get_by_id(tenant_id, entity, id)
+            .await?
+            .ok_or_else(|| ApiError::NotFound("Import not found".to_string()))
+    }
+
+    /// Apply a stored import (run by the `RunImport` job).
+    ///
+    /// Only a pending import is applied, so a job that runs twice does not create its
+    /// rows twice. A file that cannot be applied marks the import `failed` instead of
+    /// failing the job: retrying would repeat the rows written before the failure.
+    pub async fn run_import(&self, import_id: &str) -> Result<(), ApiError> {
+        let Some(import) = self.import_repository.claim(import_id).await? else {
+            tracing::warn!(import_id, "Import missing or already started, skipping");
+            return Ok(());
+        };
+
+        let tenant_id = import.tenant_id.as_str();
+        let result = match import.entity.as_str() {
+            DataImport::TEST_ITEMS => {
+                self.apply_file(&import, move |rows| self.import_test_items(tenant_id, rows)).await
+            }
+            DataImport::USERS => {
+                self.apply_file(&import, move |rows| self.import_users(tenant_id, rows)).await
+            }
+            other => Err(ApiError::InternalServerError(format!("Unknown import entity: {}", other))),
+        };
+
+        match result {
+            Ok(report) => {
+                self.import_repository.complete(import_id, report.as_deref()).await?;
+                tracing::info!(import_id, tenant_id, entity = %import.entity, "📥 Import completed");
+            }
+            Err(e) => {
+                tracing::error!(import_id, tenant_id, "Import failed: {:?}", e);
+                self.import_repository
+                    .set_status(import_id, ImportStatus::Failed, Some(&e.to_string()))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read the file batch by batch, apply each batch and record the progress.
+    /// Returns the path of the error report if rows were rejected.
+    async fn apply_file<R, F, Fut>(&self, import: &DataImport, apply: F) -> Result<Option<String>, ApiError>
+    where
+        R: DeserializeOwned + Send + 'static,
+        F: Fn(Vec<(usize, R)>) -> Fut,
+        Fut: Future<Output = Result<Vec<Rejected>, ApiError>>,
+    {
+        let mut batches = read_rows::<R>(PathBuf::from(&import.file_path), import.format);
+        let report_path = Path::new(&self.config.directory).join(format!("{}-errors.csv", import.id));
+        let mut processed = 0;
+        let mut failed = 0;
+
+        while let Some(batch) = batches.recv().await {
+            let batch = batch?;
+            processed += batch.len();
+
+            let mut rows = Vec::with_capacity(batch.len());
+            let mut rejected = Vec::new();
+            for (row, record) in batch {
+                match record {
+                    Ok(record) => rows.push((row, record)),
+                    Err(error) => rejected.push(Rejected { row, id: None, error }),
+                }
+            }
+            if !rows.is_empty() {
+                rejected.extend(apply(rows).await?);
+            }
+
+            if !rejected.is_empty() {
+                append_report(&report_path, &rejected, failed == 0).await?;
+                failed += rejected.len();
+            }
+
+            self.import_repository
+                .update_progress(&import.id, processed as i32, failed as i32)
+                .await?;
+        }
+
+        Ok((failed > 0).then(|| report_path.to_string_lossy().into_owned()))
+    }
+
+    /// Rows with an `id` update that item, the others create one
+    async fn import_test_items(&self, tenant_id: &str, rows: Vec<(usize, TestItemRecord)>) -> Result<Vec<Rejected>, ApiError> {
+        let mut rejected = Vec::new();
+        let mut creates = (Vec::new(), Vec::new());
+        let mut updates = (Vec::new(), Vec::new());
+
+        for (row, record) in rows {
+            match (record.id, record.subject) {
+                (Some(id), subject) => {
+                    updates.0.push(row);
+                    updates.1.push(BulkUpdateTestItem {
+                        id,
+                        version: record.version,
+                        changes: UpdateTestItemRequest { subject, optional_field: record.optional_field },
+                    });
+                }
+                (None, Some(subject)) => {
+                    creates.0.push(row);
+                    creates.1.push(CreateTestItemRequest { subject, optional_field: record.optional_field });
+                }
+                (None, None) => rejected.push(Rejected { row, id: None, error: "Subject is required".to_string() }),
+            }
+        }
+
+        if !creates.1.is_empty() {
+            let response = self.test_item_service.bulk_create(tenant_id, creates.1).await?;
+            rejected.extend(rejected_rows(&creates.0, response));
+        }
+        if !updates.1.is_empty() {
+            let response = self.test_item_service.bulk_update(tenant_id, updates.1).await?;
+            rejected.extend(rejected_rows(&updates.0, response));
+        }
+
+        Ok(rejected)
+    }
+
+    /// Each row updates the profile of an existing user; a database error stops the import
+    async fn import_users(&self, tenant_id: &str, rows: Vec<(usize, UserRecord)>) -> Result<Vec<Rejected>, ApiError> {
+        let mut rejected = Vec::new();
+
+        for (row, record) in rows {
+            let Some(id) = record.id else {
+                rejected.push(Rejected { row, id: None, error: "Id is required".to_string() });
+                continue;
+            };
+
+            let request = UpdateProfileRequest {
+                username: record.username,
+                email: record.email,
+                password: None,
+                current_password: None,
+            };
+            let result = match request.validate() {
+                Ok(_) => self.user_service
+                    .update_profile(tenant_id, &id, request, record.version)
+                    .await
+                    .map(|_| ()),
+                Err(errors) => Err(ApiError::ValidationError(validation_message(&errors))),
+            };
+
+            match result {
+                Ok(()) => {}
+                Err(ApiError::DatabaseError(e)) => return Err(ApiError::DatabaseError(e)),
+                Err(e) => rejected.push(Rejected { row, id: Some(id), error: row_error(e) }),
+            }
+        }
+
+        Ok(rejected)
+    }
+}
+
+/// Encode rows as CSV (with a header line) or NDJSON, a chunk of rows at a time
+fn encode<R, S>(rows: S, format: DataFormat) -> BoxStream<'static, Result<Bytes, ApiError>>
+where
+    R: Serialize + Send + 'static,
+    S: Stream<Item = Result<R, ApiError>> + Send + 'static,
+{
+    let mut first = true;
+
+    rows.ready_chunks(EXPORT_CHUNK)
+        .map(move |chunk| {
+            let rows = chunk.into_iter().collect::<Result<Vec<R>, ApiError>>()?;
+            let bytes = match format {
+                DataFormat::Csv => encode_csv(&rows, first)?,
+                DataFormat::Ndjson => encode_ndjson(&rows)?,
+            };
+            first = false;
+            Ok(Bytes::from(bytes))
+        })
+        .boxed()
+}
+
+fn encode_csv<R: Serialize>(rows: &[R], header: bool) -> Result<Vec<u8>, ApiError> {
+    let mut writer = csv::WriterBuilder::new().has_headers(header).from_writer(Vec::new());
+    for row in rows {
+        writer.serialize(row).map_err(|e| ApiError::InternalServerError(e.to_string()))?;
+    }
+    writer.into_inner().map_err(|e| ApiError::InternalServerError(e.to_string()))
+}
+
+fn encode_ndjson<R: Serialize>(rows: &[R]) -> Result<Vec<u8>, ApiError> {
+    let mut bytes = Vec::new();
+    for row in rows {
+        serde_json::to_writer(&mut bytes, row).map_err(|e| ApiError::InternalServerError(e.to_string()))?;
+        bytes.push(b'\n');
+    }
+    Ok(bytes)
+}
+
+/// Parse an import file on the blocking pool and hand over batches of rows.
+/// A row that does not parse is passed on as its error; an unreadable file ends with an error.
+fn read_rows<R>(path: PathBuf, format: DataFormat) -> mpsc::Receiver<Result<Batch<R>, ApiError>>
+where
+    R: DeserializeOwned + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(2);
+
+    tokio::task::spawn_blocking(move || {
+        let result = match format {
+            DataFormat::Csv => read_csv(&path, &tx),
+            DataFormat::Ndjson => read_ndjson(&path, &tx),
+        };
+        if let Err(e) = result {
+            let _ = tx.blocking_send(Err(e));
+        }
+    });
+
+    rx
+}
+
+fn read_csv<R: DeserializeOwned>(path: &Path, tx: &mpsc::Sender<Result<Batch<R>, ApiError>>) -> Result<(), ApiError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(path)
+        .map_err(|e| ApiError::InternalServerError(format!("Failed to open import file: {}", e)))?;
+    let mut batch = Vec::with_capacity(IMPORT_BATCH);
+
+    for (index, record) in reader.deserialize::<R>().enumerate() {
+        batch.push((index + 1, record.map_err(|e| e.to_string())));
+        if batch.len() == IMPORT_BATCH && !send_batch(tx, &mut batch) {
+            return Ok(());
+        }
+    }
+
+    send_batch(tx, &mut batch);
+    Ok(())
+}
+
+fn read_ndjson<R: DeserializeOwned>(path: &Path, tx: &mpsc::Sender<Result<Batch<R>, ApiError>>) -> Result<(), ApiError> {
+    let file = File::open(path)
+        .map_err(|e| ApiError::InternalServerError(format!("Failed to open import file: {}", e)))?;
+    let mut batch = Vec::with_capacity(IMPORT_BATCH);
+
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| ApiError::ValidationError(format!("Failed to read line {}: {}", index + 1, e)))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        batch.push((index + 1, serde_json::from_str(&line).map_err(|e| e.to_string())));
+        if batch.len() == IMPORT_BATCH && !send_batch(tx, &mut batch) {
+            return Ok(());
+        }
+    }
+
+    send_batch(tx, &mut batch);
+    Ok(())
+}
+
+/// Send the rows read so far; false once the import stopped listening
+fn send_batch<R>(tx: &mpsc::Sender<Result<Batch<R>, ApiError>>, batch: &mut Batch<R>) -> bool {
+    batch.is_empty() || tx.blocking_send(Ok(std::mem::take(batch))).is_ok()
+}
+
+/// Append rejected rows to the error report (`row,id,error`), creating it with its header
+async fn append_report(path: &Path, rejected: &[Rejected], create: bool) -> Result<(), ApiError> {
+    let bytes = encode_csv(rejected, create)?;
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .truncate(false)
+        .open(path)
+        .await
+        .map_err(|e| ApiError::InternalServerError(format!("Failed to write error report: {}", e)))?;
+    file.write_all(&bytes)
+        .await
+        .map_err(|e| ApiError::InternalServerError(format!("Failed to write error report: {}", e)))
+}
+
+/// Rejected elements of a bulk response, mapped back to their rows
+fn rejected_rows(rows: &[usize], response: BulkResponse) -> impl Iterator<Item = Rejected> + '_ {
+    response
+        .results
+        .into_iter()
+        .filter(|result| !result.is_success())
+        .map(|result| Rejected {
+            row: rows[result.index],
+            id: result.id,
+            error: result.error.unwrap_or_default(),
+        })
+}
+
+/// Message of a per-row error (the variant's own message, not its HTTP title)
+fn row_error(error: ApiError) -> String {
+    match error {
+        ApiError::NotFound(msg)
+        | ApiError::Forbidden(msg)
+        | ApiError::ValidationError(msg)
+        | ApiError::Conflict(msg) => msg,
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_export_writes_the_header_once() {
+        let record = TestItemRecord {
+            id: Some("1".to_string()),
+            subject: Some("Hello, world".to_string()),
+            version: Some(1),
+            ..Default::default()
+        };
+
+        let first = String::from_utf8(encode_csv(std::slice::from_ref(&record), true).unwrap()).unwrap();
+        let next = String::from_utf8(encode_csv(&[record], false).unwrap()).unwrap();
+
+        assert_eq!(first, "id,subject,optional_field,version,created_at,updated_at\n1,\"Hello, world\",,1,,\n");
+        assert_eq!(next, "1,\"Hello, world\",,1,,\n");
+    }
+
+    #[test]
+    fn test_csv_import_reads_empty_fields_as_missing() {
+        let data = "id,subject,optional_field,version\n,New item,,\nabc,,note,3\n";
+        let mut reader = csv::Reader::from_reader(data.as_bytes());
+        let rows: Vec<TestItemRecord> = reader.deserialize().collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(rows[0].id, None);
+        assert_eq!(rows[0].subject.as_deref(), Some("New item"));
+        assert_eq!(rows[0].optional_field, None);
+        assert_eq!(rows[1].id.as_deref(), Some("abc"));
+        assert_eq!(rows[1].subject, None);
+        assert_eq!(rows[1].version, Some(3));
+    }
+}
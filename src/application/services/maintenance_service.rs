@@ -0,0 +1,250 @@
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+use chrono::Utc;
+use futures::StreamExt;
+use crate::application::dtos::{BypassRequest, BypassSecretResponse, MaintenanceRequest};
+use crate::config::AppConfig;
+use crate::domain::entities::user::Claims;
+use crate::domain::entities::{AuditEntry, BypassSecret, MaintenanceState, RequestOrigin};
+use crate::errors::{ApiError, DomainError};
+use crate::interfaces::MaintenanceStore;
+use crate::application::services::AuditLogger;
+use crate::utils::maintenance_bypass;
+
+/// Wait before subscribing again once the change stream ended
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+/// Maintenance mode of the cluster, cached in memory for the middleware
+pub struct MaintenanceService {
+    store: Arc<dyn MaintenanceStore>,
+    audit_logger: Arc<AuditLogger>,
+    current: RwLock<Option<Arc<MaintenanceState>>>,
+    /// Signs bypass cookies; derived from the JWT secret so every instance agrees
+    bypass_key: Vec<u8>,
+    bypass_ttl: u64,
+}
+
+impl MaintenanceService {
+    pub fn new(store: Arc<dyn MaintenanceStore>, audit_logger: Arc<AuditLogger>, config: Arc<AppConfig>) -> Self {
+        Self {
+            store,
+            audit_logger,
+            current: RwLock::new(None),
+            bypass_key: maintenance_bypass::signing_key(&config.jwt.secret),
+            bypass_ttl: config.maintenance.bypass_ttl,
+        }
+    }
+
+    /// Lifetime of bypass cookies, in seconds
+    pub fn bypass_ttl(&self) -> u64 {
+        self.bypass_ttl
+    }
+
+    /// Cached state, `None` while the application is up (no I/O, read on every request)
+    pub fn current(&self) -> Option<Arc<MaintenanceState>> {
+        self.current.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Reload the cached state from the store
+    pub async fn refresh(&self) -> Result<Option<Arc<MaintenanceState>>, ApiError> {
+        let state = self.load().await?.map(Arc::new);
+        self.set(state.clone());
+        Ok(state)
+    }
+
+    /// Whether a bypass cookie was signed for one of the current secrets and has not expired
+    pub fn bypass_allowed(&self, state: &MaintenanceState, cookie: &str) -> bool {
+        let Some((name, _)) = maintenance_bypass::parse_cookie(cookie) else {
+            return false;
+        };
+        state.bypass.iter().find(|bypass| bypass.name == name).is_some_and(|bypass| {
+            maintenance_bypass::verify_cookie(&self.bypass_key, cookie, &bypass.hash, Utc::now().timestamp())
+        })
+    }
+
+    /// Bypass cookie for a presented secret, `None` when it matches no bypass
+    pub async fn grant_bypass(
+        &self,
+        state: &MaintenanceState,
+        secret: &str,
+        origin: &RequestOrigin,
+    ) -> Option<String> {
+        let bypass = state
+            .bypass
+            .iter()
+            .find(|bypass| maintenance_bypass::verify_secret(secret, &bypass.hash))?;
+        let expires = Utc::now().timestamp() + self.bypass_ttl as i64;
+
+        self.audit_logger
+            .record(
+                AuditEntry::new("maintenance.bypass_granted")
+                    .origin(origin)
+                    .changes(serde_json::json!({ "name": bypass.name, "expires": expires })),
+            )
+            .await;
+        Some(maintenance_bypass::sign_cookie(&self.bypass_key, &bypass.name, &bypass.hash, expires))
+    }
+
+    /// Put every instance into maintenance mode, now or for a scheduled window
+    pub async fn down(
+        &self,
+        actor: &Claims,
+        origin: &RequestOrigin,
+        request: MaintenanceRequest,
+    ) -> Result<(Arc<MaintenanceState>, Vec<BypassSecretResponse>), ApiError> {
+        let bypass_requests = request.bypass_requests();
+        let mut state = request.into_state()?;
+        let mut issued = Vec::new();
+        for request in bypass_requests {
+            let (bypass, response) = new_bypass(request)?;
+            state.bypass.push(bypass);
+            issued.push(response);
+        }
+        state.validate()?;
+        self.store.put(&state).await?;
+
+        let state = Arc::new(state);
+        self.set(Some(state.clone()));
+        self.audit_logger
+            .record(
+                AuditEntry::new("maintenance.enabled")
+                    .actor(actor)
+                    .origin(origin)
+                    .changes(serde_json::json!({
+                        "message": state.message,
+                        "retry": state.retry,
+                        "bypass": issued.iter().map(|b| &b.name).collect::<Vec<_>>(),
+                        "render": state.render,
+                        "redirect": state.redirect,
+                        "starts_at": state.starts_at,
+                        "ends_at": state.ends_at,
+                        "paths": state.paths,
+                        "allow": state.allow,
+                    })),
+            )
+            .await;
+        Ok((state, issued))
+    }
+
+    /// Add a named bypass to the current window, replacing one of the same name
+    pub async fn add_bypass(
+        &self,
+        actor: &Claims,
+        origin: &RequestOrigin,
+        request: BypassRequest,
+    ) -> Result<BypassSecretResponse, ApiError> {
+        let mut state = self.load().await?.ok_or_else(not_in_maintenance)?;
+        let (bypass, response) = new_bypass(request)?;
+        state.add_bypass(bypass)?;
+        self.store.put(&state).await?;
+        self.set(Some(Arc::new(state)));
+
+        self.audit_logger
+            .record(
+                AuditEntry::new("maintenance.bypass_added")
+                    .actor(actor)
+                    .origin(origin)
+                    .changes(serde_json::json!({ "name": response.name })),
+            )
+            .await;
+        Ok(response)
+    }
+
+    /// Revoke a named bypass; the cookies it granted stop working at once
+    pub async fn revoke_bypass(&self, actor: &Claims, origin: &RequestOrigin, name: &str) -> Result<(), ApiError> {
+        let mut state = self.load().await?.ok_or_else(not_in_maintenance)?;
+        if !state.revoke_bypass(name) {
+            return Err(ApiError::NotFound(format!("No bypass named {}", name)));
+        }
+        self.store.put(&state).await?;
+        self.set(Some(Arc::new(state)));
+
+        self.audit_logger
+            .record(
+                AuditEntry::new("maintenance.bypass_revoked")
+                    .actor(actor)
+                    .origin(origin)
+                    .changes(serde_json::json!({ "name": name })),
+            )
+            .await;
+        Ok(())
+    }
+
+    /// Bring every instance back up, cancelling any scheduled window; `false` when there was none
+    pub async fn up(&self, actor: &Claims, origin: &RequestOrigin) -> Result<bool, ApiError> {
+        let was_down = self.store.clear().await?;
+        self.set(None);
+        if was_down {
+            self.audit_logger
+                .record(AuditEntry::new("maintenance.disabled").actor(actor).origin(origin))
+                .await;
+        }
+        Ok(was_down)
+    }
+
+    /// Keep the cache in sync with changes made by the CLI or other instances
+    pub fn start_watching(self: Arc<Self>) {
+        tokio::spawn(async move {
+            loop {
+                match self.store.changes().await {
+                    Ok(mut changes) => {
+                        // Catch up on whatever changed while unsubscribed
+                        self.reload().await;
+                        while changes.next().await.is_some() {
+                            self.reload().await;
+                        }
+                    }
+                    Err(e) => tracing::warn!("Cannot watch maintenance mode: {}", e),
+                }
+                tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+            }
+        });
+    }
+
+    async fn reload(&self) {
+        let previous = self.current();
+        match self.refresh().await {
+            Ok(Some(state)) if previous.as_deref() != Some(&*state) => match state.starts_at {
+                Some(starts_at) if starts_at > Utc::now() => {
+                    tracing::info!("🗓️ Maintenance window scheduled at {}", starts_at)
+                }
+                _ => tracing::warn!("🔧 Maintenance mode enabled"),
+            },
+            Ok(None) if previous.is_some() => tracing::info!("🚀 Maintenance mode disabled"),
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Cannot reload maintenance mode: {}", e),
+        }
+    }
+
+    /// Stored state, with the plain secret of older states hashed
+    async fn load(&self) -> Result<Option<MaintenanceState>, ApiError> {
+        Ok(self
+            .store
+            .get()
+            .await?
+            .map(|state| state.with_legacy_secret_hashed(maintenance_bypass::hash)))
+    }
+
+    fn set(&self, state: Option<Arc<MaintenanceState>>) {
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = state;
+    }
+}
+
+/// Hash the requested secret, or generate one
+fn new_bypass(request: BypassRequest) -> Result<(BypassSecret, BypassSecretResponse), DomainError> {
+    let secret = match request.secret {
+        Some(secret) if !maintenance_bypass::is_valid_secret(&secret) => {
+            return Err(DomainError::Validation(
+                "Bypass secret must be 8 to 128 letters, digits, -, _, . or ~".to_string(),
+            ))
+        }
+        Some(secret) => secret,
+        None => maintenance_bypass::generate_secret(),
+    };
+    let bypass = BypassSecret::new(request.name.clone(), maintenance_bypass::hash(&secret));
+    Ok((bypass, BypassSecretResponse::new(request.name, secret)))
+}
+
+fn not_in_maintenance() -> ApiError {
+    ApiError::NotFound("The application is not in maintenance mode".to_string())
+}
@@ -2,9 +2,24 @@ pub mod auth_service;
 pub mod user_service;
 pub mod test_item_service;
 pub mod alert_service;
+pub mod two_factor_service;
+pub mod permission_service;
+pub mod api_key_service;
+pub mod oauth_service;
+pub mod audit_logger;
+pub mod tenant_service;
+pub mod data_transfer_service;
+pub mod maintenance_service;
 
 pub use auth_service::AuthService;
 pub use user_service::UserService;
 pub use test_item_service::TestItemService;
 pub use alert_service::AlertService;
-        
\ No newline at end of file
+pub use two_factor_service::TwoFactorService;
+pub use permission_service::PermissionService;
+pub use api_key_service::ApiKeyService;
+pub use oauth_service::OAuthService;
+pub use audit_logger::AuditLogger;
+pub use tenant_service::TenantService;
+pub use data_transfer_service::DataTransferService;
+pub use maintenance_service::MaintenanceService;
@@ -0,0 +1,327 @@
+use std::sync::Arc;
+use rand::Rng;
+use crate::application::dtos::{LoginResponse, OAuthCallbackQuery, OAuthProviderResponse, UserIdentityResponse};
+use crate::application::services::{AuditLogger, AuthService};
+use crate::config::{AppConfig, OAuthProviderConfig};
+use crate::domain::entities::{AuditEntry, OAuthState, RequestOrigin, User, UserIdentity};
+use crate::domain::entities::user::Claims;
+use crate::domain::value_objects::{EmailAddress, Username};
+use crate::errors::ApiError;
+use crate::interfaces::{ExternalIdentity, IdentityProviderClient, UserIdentityRepository, UserRepository};
+use crate::utils::oauth;
+use crate::utils::password::PasswordHasher;
+
+/// Where to send the browser, and the state it must bring back to the callback
+pub struct AuthorizationRedirect {
+    pub url: String,
+    pub state: String,
+}
+
+/// OAuth2 / OIDC authorization-code login with PKCE
+pub struct OAuthService {
+    identity_repository: Arc<dyn UserIdentityRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    provider_client: Arc<dyn IdentityProviderClient>,
+    auth_service: Arc<AuthService>,
+    audit_logger: Arc<AuditLogger>,
+    config: Arc<AppConfig>,
+    password_hasher: PasswordHasher,
+}
+
+impl OAuthService {
+    pub fn new(
+        identity_repository: Arc<dyn UserIdentityRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        provider_client: Arc<dyn IdentityProviderClient>,
+        auth_service: Arc<AuthService>,
+        audit_logger: Arc<AuditLogger>,
+        config: Arc<AppConfig>,
+    ) -> Self {
+        Self {
+            identity_repository,
+            user_repository,
+            provider_client,
+            auth_service,
+            audit_logger,
+            password_hasher: PasswordHasher::new(&config),
+            config,
+        }
+    }
+
+    /// Enabled providers
+    pub fn providers(&self) -> Vec<OAuthProviderResponse> {
+        self.config
+            .oauth
+            .providers
+            .iter()
+            .map(|p| OAuthProviderResponse {
+                name: p.name.clone(),
+                authorize_url: format!("/api/auth/oauth/{}/authorize", p.name),
+            })
+            .collect()
+    }
+
+    /// Start the flow: persist state + PKCE verifier and build the provider URL.
+    /// The state remembers the tenant, so the shared callback URL signs into the right one.
+    pub async fn authorization_url(&self, tenant_id: &str, provider_name: &str) -> Result<AuthorizationRedirect, ApiError> {
+        let provider = self.provider(provider_name)?;
+
+        if let Err(e) = self.identity_repository.purge_expired_states().await {
+            tracing::warn!("Failed to purge expired OAuth states: {}", e);
+        }
+
+        let state = OAuthState::new(
+            oauth::random_token(32),
+            provider.name.clone(),
+            tenant_id.to_string(),
+            oauth::generate_code_verifier(),
+            self.config.oauth.state_ttl,
+        );
+        self.identity_repository.save_state(&state).await?;
+
+        let params = [
+            ("response_type", "code".to_string()),
+            ("client_id", provider.client_id.clone()),
+            ("redirect_uri", self.config.oauth.redirect_uri(&provider.name)),
+            ("scope", provider.scopes.join(" ")),
+            ("state", state.state.clone()),
+            ("code_challenge", oauth::code_challenge(&state.code_verifier)),
+            ("code_challenge_method", "S256".to_string()),
+        ];
+        let query = params
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, urlencoding::encode(value)))
+            .collect::<Vec<_>>()
+            .join("&");
+        let separator = if provider.authorization_url.contains('?') { '&' } else { '?' };
+
+        Ok(AuthorizationRedirect {
+            url: format!("{}{}{}", provider.authorization_url, separator, query),
+            state: state.state,
+        })
+    }
+
+    /// Finish the flow: check state, exchange the code and sign the user in.
+    /// `bound_state` is the state remembered by the browser that started the flow;
+    /// a callback from any other browser (login CSRF) is refused.
+    pub async fn callback(
+        &self,
+        provider_name: &str,
+        query: OAuthCallbackQuery,
+        bound_state: Option<&str>,
+        origin: &RequestOrigin,
+    ) -> Result<LoginResponse, ApiError> {
+        let provider = self.provider(provider_name)?;
+
+        if let Some(error) = query.error {
+            tracing::info!(
+                provider = %provider.name,
+                error = %error,
+                description = %query.error_description.unwrap_or_default(),
+                "OAuth authorization denied"
+            );
+            return Err(ApiError::Unauthorized);
+        }
+
+        let (code, state) = match (query.code, query.state) {
+            (Some(code), Some(state)) => (code, state),
+            _ => return Err(ApiError::ValidationError("Missing code or state".to_string())),
+        };
+
+        if bound_state != Some(state.as_str()) {
+            return Err(ApiError::ValidationError(
+                "OAuth state was not issued to this browser".to_string(),
+            ));
+        }
+
+        let pending = self
+            .identity_repository
+            .take_state(&state)
+            .await?
+            .filter(|pending| pending.provider == provider.name && !pending.is_expired())
+            .ok_or_else(|| ApiError::ValidationError("Invalid or expired OAuth state".to_string()))?;
+
+        let access_token = self
+            .provider_client
+            .exchange_code(provider, &code, &pending.code_verifier, &self.config.oauth.redirect_uri(&provider.name))
+            .await?;
+        let external = self.provider_client.fetch_identity(provider, &access_token).await?;
+
+        let user = self.resolve_user(&pending.tenant_id, provider, external).await?;
+
+        if !user.is_active() {
+            return Err(ApiError::Forbidden("Account is disabled".to_string()));
+        }
+        let response = self.auth_service.complete_login(&user)?;
+
+        self.audit_logger
+            .record(
+                AuditEntry::new("auth.login")
+                    .tenant(&user.tenant_id)
+                    .actor_id(&user.id)
+                    .origin(origin)
+                    .target("user", &user.id)
+                    .changes(serde_json::json!({
+                        "method": "oauth",
+                        "provider": provider.name,
+                        "two_factor_pending": user.has_two_factor()
+                    })),
+            )
+            .await;
+
+        Ok(response)
+    }
+
+    /// Identities linked to the user
+    pub async fn identities(&self, claims: &Claims) -> Result<Vec<UserIdentityResponse>, ApiError> {
+        let identities = self.identity_repository.get_by_user(&claims.tenant_id, &claims.sub).await?;
+        Ok(identities.iter().map(|i| i.to_response()).collect())
+    }
+
+    /// Find the linked user, link an existing account by verified email, or register a new one
+    async fn resolve_user(
+        &self,
+        tenant_id: &str,
+        provider: &OAuthProviderConfig,
+        external: ExternalIdentity,
+    ) -> Result<User, ApiError> {
+        if let Some(identity) = self
+            .identity_repository
+            .get_by_subject(tenant_id, &provider.name, &external.subject)
+            .await?
+        {
+            self.identity_repository.touch_last_login(&identity.id).await?;
+            return self
+                .user_repository
+                .get_by_id(tenant_id, &identity.user_id)
+                .await?
+                .ok_or(ApiError::Unauthorized);
+        }
+
+        // Linking or registering by email is only safe when the provider vouches for it
+        let email = match (&external.email, external.email_verified) {
+            (Some(email), true) => email.clone(),
+            _ => {
+                return Err(ApiError::Forbidden(
+                    "The identity provider did not return a verified email".to_string(),
+                ))
+            }
+        };
+
+        let user = match self.user_repository.get_by_email(tenant_id, &email).await? {
+            Some(user) => user,
+            None => self.register(tenant_id, &email, external.name.as_deref()).await?,
+        };
+
+        let identity = UserIdentity::new(
+            tenant_id.to_string(),
+            user.id.clone(),
+            provider.name.clone(),
+            external.subject,
+            Some(email),
+        );
+        self.identity_repository.create(&identity).await?;
+
+        tracing::info!(user_id = %user.id, provider = %provider.name, "External identity linked");
+        Ok(user)
+    }
+
+    /// New account for a first-time social login. The password is random and unknown to anyone.
+    async fn register(&self, tenant_id: &str, email: &str, name: Option<&str>) -> Result<User, ApiError> {
+        // A deleted account keeps its email until it is purged
+        if self.user_repository.exists_by_email(tenant_id, email).await? {
+            return Err(ApiError::Conflict("User already exists".to_string()));
+        }
+
+        let password_hash = self.password_hasher.hash(&oauth::random_token(32)).await?;
+        let user = User::new(
+            tenant_id.to_string(),
+            EmailAddress::new(email.to_string())?,
+            Username::new(Self::username_for(email, name))?,
+            password_hash,
+        )?;
+
+        self.user_repository.create(&user).await
+    }
+
+    /// `name` (or the email local part) sanitized, plus a random suffix to keep it unique
+    fn username_for(email: &str, name: Option<&str>) -> String {
+        let base: String = name
+            .unwrap_or_else(|| email.split('@').next().unwrap_or_default())
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+            .take(40)
+            .collect();
+        let base = if base.len() < 3 { "user".to_string() } else { base };
+        let suffix: u32 = rand::thread_rng().gen_range(100_000..1_000_000);
+
+        format!("{}_{}", base, suffix)
+    }
+
+    fn provider(&self, name: &str) -> Result<&OAuthProviderConfig, ApiError> {
+        self.config
+            .oauth
+            .provider(name)
+            .ok_or_else(|| ApiError::NotFound(format!("OAuth provider not found: {}", name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::postgres::PgPoolOptions;
+    use crate::config::Sources;
+    use crate::infrastructure::oauth::HttpIdentityProviderClient;
+    use crate::infrastructure::persistence::postgres::{
+        PostgresAuditLogRepository, PostgresUserIdentityRepository, PostgresUserRepository,
+    };
+
+    /// The state check runs before any storage access, so a lazy pool is enough
+    fn service() -> OAuthService {
+        let sources = Sources::new([
+            ("DATABASE_URL", "postgres://localhost/test"),
+            ("JWT_SECRET", "oauth-test-signing-key"),
+            ("OAUTH_PROVIDERS", "google"),
+            ("OAUTH_GOOGLE_CLIENT_ID", "client"),
+            ("OAUTH_GOOGLE_CLIENT_SECRET", "secret"),
+        ]);
+        let config = Arc::new(AppConfig::from_sources(sources).unwrap());
+        let pool = PgPoolOptions::new().connect_lazy("postgres://localhost/test").unwrap();
+        let user_repository = Arc::new(PostgresUserRepository::new(pool.clone()));
+        let audit_logger = Arc::new(AuditLogger::new(
+            Arc::new(PostgresAuditLogRepository::new(pool.clone())),
+            config.clone(),
+        ));
+        let auth_service = Arc::new(AuthService::new(user_repository.clone(), audit_logger.clone(), config.clone()));
+
+        OAuthService::new(
+            Arc::new(PostgresUserIdentityRepository::new(pool)),
+            user_repository,
+            Arc::new(HttpIdentityProviderClient::new()),
+            auth_service,
+            audit_logger,
+            config,
+        )
+    }
+
+    fn query(state: &str) -> OAuthCallbackQuery {
+        OAuthCallbackQuery {
+            code: Some("code".to_string()),
+            state: Some(state.to_string()),
+            error: None,
+            error_description: None,
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_callback_needs_the_state_of_this_browser() {
+        let service = service();
+        let origin = RequestOrigin::default();
+
+        let missing = service.callback("google", query("issued"), None, &origin).await;
+        assert!(matches!(missing, Err(ApiError::ValidationError(_))));
+
+        let foreign = service.callback("google", query("issued"), Some("other"), &origin).await;
+        assert!(matches!(foreign, Err(ApiError::ValidationError(_))));
+    }
+}
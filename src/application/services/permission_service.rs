@@ -0,0 +1,256 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::application::dtos::{CreatePermissionRequest, GrantPermissionRequest, RolePermissionsResponse};
+use crate::config::AppConfig;
+use crate::domain::entities::{PermissionDefinition, DEFAULT_TENANT_ID, user::Claims};
+use crate::domain::value_objects::{Permission, Role};
+use crate::errors::ApiError;
+use crate::interfaces::PermissionRepository;
+
+/// Cached permission set of a single role
+struct CachedGrants {
+    permissions: Arc<Vec<Permission>>,
+    loaded_at: Instant,
+}
+
+/// Role → permission resolution with an in-memory TTL cache.
+///
+/// Every change made through this service invalidates the affected role, so the
+/// TTL only bounds staleness for changes made directly in the database.
+pub struct PermissionService {
+    permission_repository: Arc<dyn PermissionRepository>,
+    cache: RwLock<HashMap<String, CachedGrants>>,
+    ttl: Duration,
+}
+
+impl PermissionService {
+    pub fn new(permission_repository: Arc<dyn PermissionRepository>, config: Arc<AppConfig>) -> Self {
+        Self {
+            permission_repository,
+            cache: RwLock::new(HashMap::new()),
+            ttl: Duration::from_secs(config.authorization.permission_cache_ttl),
+        }
+    }
+
+    /// Permissions granted to a role (cached)
+    pub async fn permissions_for_role(&self, role: &str) -> Result<Arc<Vec<Permission>>, ApiError> {
+        let role = role.to_lowercase();
+
+        if let Some(permissions) = self.cached(&role) {
+            return Ok(permissions);
+        }
+
+        let permissions = Arc::new(self.permission_repository.get_for_role(&role).await?);
+
+        if !self.ttl.is_zero() {
+            let mut cache = self.cache.write().unwrap_or_else(|e| e.into_inner());
+            cache.insert(
+                role,
+                CachedGrants {
+                    permissions: permissions.clone(),
+                    loaded_at: Instant::now(),
+                },
+            );
+        }
+
+        Ok(permissions)
+    }
+
+    /// Whether the role holds (directly or through a wildcard) the given permission
+    pub async fn role_has_permission(&self, role: &str, required: &str) -> Result<bool, ApiError> {
+        let permissions = self.permissions_for_role(role).await?;
+        Ok(permissions.iter().any(|granted| granted.grants(required)))
+    }
+
+    /// Fail with `Forbidden` unless the token's role holds the permission
+    /// (and, for API keys, one of the key's scopes covers it)
+    pub async fn authorize(&self, claims: &Claims, required: &str) -> Result<(), ApiError> {
+        if claims.scope_allows(required) && self.role_has_permission(&claims.role, required).await? {
+            Ok(())
+        } else {
+            Err(ApiError::Forbidden(format!("Missing permission: {}", required)))
+        }
+    }
+
+    /// Deployment-wide operations (role grants are shared by every tenant): the caller must
+    /// belong to the default tenant and their role must hold `required` by name. Wildcards
+    /// such as the `*` of tenant admins don't count.
+    pub async fn authorize_operator(&self, claims: &Claims, required: &str) -> Result<(), ApiError> {
+        let granted = self.permissions_for_role(&claims.role).await?;
+        let explicit = granted.iter().any(|permission| permission.as_str() == required);
+
+        if claims.tenant_id == DEFAULT_TENANT_ID && claims.scope_allows(required) && explicit {
+            Ok(())
+        } else {
+            Err(ApiError::Forbidden(format!("Missing platform permission: {}", required)))
+        }
+    }
+
+    /// Every role with its granted permissions
+    pub async fn list_roles(&self) -> Result<Vec<RolePermissionsResponse>, ApiError> {
+        let mut roles: BTreeMap<String, Vec<String>> = [Role::Admin, Role::Moderator, Role::Premium, Role::User]
+            .iter()
+            .map(|role| (role.as_str().to_string(), Vec::new()))
+            .collect();
+
+        for grant in self.permission_repository.get_all_grants().await? {
+            roles
+                .entry(grant.role)
+                .or_default()
+                .push(grant.permission.as_str().to_string());
+        }
+
+        Ok(roles
+            .into_iter()
+            .map(|(role, permissions)| RolePermissionsResponse { role, permissions })
+            .collect())
+    }
+
+    /// The permission catalogue
+    pub async fn list_permissions(&self) -> Result<Vec<PermissionDefinition>, ApiError> {
+        self.permission_repository.get_all_permissions().await
+    }
+
+    /// Add a permission to the catalogue
+    pub async fn create_permission(&self, request: CreatePermissionRequest) -> Result<PermissionDefinition, ApiError> {
+        let name = Permission::new(request.name)?;
+
+        if self.permission_repository.permission_exists(name.as_str()).await? {
+            return Err(ApiError::Conflict(format!("Permission already exists: {}", name)));
+        }
+
+        let permission = PermissionDefinition::new(name, request.description);
+        self.permission_repository.create_permission(&permission).await
+    }
+
+    /// Grant a catalogued permission to a role
+    pub async fn grant(&self, role: &str, request: GrantPermissionRequest) -> Result<RolePermissionsResponse, ApiError> {
+        let role = Self::parse_role(role)?;
+        let permission = Permission::new(request.permission)?;
+
+        if !self.permission_repository.permission_exists(permission.as_str()).await? {
+            return Err(ApiError::NotFound(format!("Permission not found: {}", permission)));
+        }
+
+        self.permission_repository.grant(role.as_str(), &permission).await?;
+        self.invalidate(role.as_str());
+
+        tracing::info!(role = %role, permission = %permission, "Permission granted");
+        self.role_permissions(role.as_str()).await
+    }
+
+    /// Revoke a permission from a role
+    pub async fn revoke(&self, role: &str, permission: &str) -> Result<(), ApiError> {
+        let role = Self::parse_role(role)?;
+
+        if !self.permission_repository.revoke(role.as_str(), permission).await? {
+            return Err(ApiError::NotFound(format!(
+                "Role {} does not have permission {}",
+                role, permission
+            )));
+        }
+        self.invalidate(role.as_str());
+
+        tracing::info!(role = %role, permission = %permission, "Permission revoked");
+        Ok(())
+    }
+
+    /// Drop a role from the cache
+    pub fn invalidate(&self, role: &str) {
+        let mut cache = self.cache.write().unwrap_or_else(|e| e.into_inner());
+        cache.remove(role);
+    }
+
+    async fn role_permissions(&self, role: &str) -> Result<RolePermissionsResponse, ApiError> {
+        let permissions = self.permissions_for_role(role).await?;
+        Ok(RolePermissionsResponse {
+            role: role.to_string(),
+            permissions: permissions.iter().map(|p| p.as_str().to_string()).collect(),
+        })
+    }
+
+    fn cached(&self, role: &str) -> Option<Arc<Vec<Permission>>> {
+        let cache = self.cache.read().unwrap_or_else(|e| e.into_inner());
+        cache
+            .get(role)
+            .filter(|entry| entry.loaded_at.elapsed() < self.ttl)
+            .map(|entry| entry.permissions.clone())
+    }
+
+    fn parse_role(role: &str) -> Result<Role, ApiError> {
+        Role::from_str(role).ok_or_else(|| ApiError::NotFound(format!("Role not found: {}", role)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use crate::config::Sources;
+    use crate::domain::entities::RolePermission;
+
+    /// In-memory grants: `admin` => `*`, `operator` => `roles.manage`
+    struct FakePermissionRepository;
+
+    #[async_trait]
+    impl PermissionRepository for FakePermissionRepository {
+        async fn get_for_role(&self, role: &str) -> Result<Vec<Permission>, ApiError> {
+            Ok(match role {
+                "admin" => vec![Permission::from_trusted("*".to_string())],
+                "operator" => vec![Permission::from_trusted("roles.manage".to_string())],
+                _ => Vec::new(),
+            })
+        }
+
+        async fn get_all_grants(&self) -> Result<Vec<RolePermission>, ApiError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_all_permissions(&self) -> Result<Vec<PermissionDefinition>, ApiError> {
+            Ok(Vec::new())
+        }
+
+        async fn create_permission(&self, permission: &PermissionDefinition) -> Result<PermissionDefinition, ApiError> {
+            Ok(permission.clone())
+        }
+
+        async fn permission_exists(&self, _name: &str) -> Result<bool, ApiError> {
+            Ok(true)
+        }
+
+        async fn grant(&self, _role: &str, _permission: &Permission) -> Result<(), ApiError> {
+            Ok(())
+        }
+
+        async fn revoke(&self, _role: &str, _permission: &str) -> Result<bool, ApiError> {
+            Ok(true)
+        }
+    }
+
+    fn service() -> PermissionService {
+        let sources = Sources::new([
+            ("DATABASE_URL", "postgres://localhost/test"),
+            ("JWT_SECRET", "permission-test-signing-key"),
+        ]);
+        let config = Arc::new(AppConfig::from_sources(sources).unwrap());
+        PermissionService::new(Arc::new(FakePermissionRepository), config)
+    }
+
+    fn claims(role: &str, tenant_id: &str) -> Claims {
+        Claims::new("user-1".to_string(), "user@test.com".to_string(), role.to_string(), tenant_id.to_string(), 1000)
+    }
+
+    #[actix_web::test]
+    async fn test_operator_needs_the_default_tenant_and_an_explicit_grant() {
+        let service = service();
+
+        assert!(service.authorize_operator(&claims("operator", DEFAULT_TENANT_ID), "roles.manage").await.is_ok());
+        assert!(service.authorize_operator(&claims("operator", "acme"), "roles.manage").await.is_err());
+        assert!(service.authorize_operator(&claims("admin", DEFAULT_TENANT_ID), "roles.manage").await.is_err());
+
+        // The wildcard still passes ordinary permission checks
+        assert!(service.authorize(&claims("admin", "acme"), "roles.manage").await.is_ok());
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::config::AppConfig;
+use crate::domain::entities::Tenant;
+use crate::errors::ApiError;
+use crate::interfaces::TenantRepository;
+
+/// Cache key: tenants are looked up by ID (tokens) or slug (header / subdomain)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum TenantKey {
+    Id(String),
+    Slug(String),
+}
+
+/// Cached lookup result (misses are cached too, so unknown slugs don't hit the database)
+struct CachedTenant {
+    tenant: Option<Tenant>,
+    loaded_at: Instant,
+}
+
+/// Tenant resolution with an in-memory TTL cache
+pub struct TenantService {
+    tenant_repository: Arc<dyn TenantRepository>,
+    cache: RwLock<HashMap<TenantKey, CachedTenant>>,
+    ttl: Duration,
+}
+
+impl TenantService {
+    pub fn new(tenant_repository: Arc<dyn TenantRepository>, config: Arc<AppConfig>) -> Self {
+        Self {
+            tenant_repository,
+            cache: RwLock::new(HashMap::new()),
+            ttl: Duration::from_secs(config.tenancy.cache_ttl),
+        }
+    }
+
+    /// Active tenant by ID
+    pub async fn get_by_id(&self, id: &str) -> Result<Tenant, ApiError> {
+        self.resolve(TenantKey::Id(id.to_string())).await
+    }
+
+    /// Active tenant by slug (case-insensitive)
+    pub async fn get_by_slug(&self, slug: &str) -> Result<Tenant, ApiError> {
+        self.resolve(TenantKey::Slug(slug.to_lowercase())).await
+    }
+
+    async fn resolve(&self, key: TenantKey) -> Result<Tenant, ApiError> {
+        let tenant = match self.cached(&key) {
+            Some(tenant) => tenant,
+            None => {
+                let tenant = match &key {
+                    TenantKey::Id(id) => self.tenant_repository.get_by_id(id).await?,
+                    TenantKey::Slug(slug) => self.tenant_repository.get_by_slug(slug).await?,
+                };
+
+                if !self.ttl.is_zero() {
+                    let mut cache = self.cache.write().unwrap_or_else(|e| e.into_inner());
+                    cache.insert(
+                        key,
+                        CachedTenant {
+                            tenant: tenant.clone(),
+                            loaded_at: Instant::now(),
+                        },
+                    );
+                }
+
+                tenant
+            }
+        };
+
+        match tenant {
+            Some(tenant) if tenant.is_active => Ok(tenant),
+            Some(_) => Err(ApiError::Forbidden("Tenant is disabled".to_string())),
+            None => Err(ApiError::NotFound("Unknown tenant".to_string())),
+        }
+    }
+
+    fn cached(&self, key: &TenantKey) -> Option<Option<Tenant>> {
+        let cache = self.cache.read().unwrap_or_else(|e| e.into_inner());
+        cache
+            .get(key)
+            .filter(|entry| entry.loaded_at.elapsed() < self.ttl)
+            .map(|entry| entry.tenant.clone())
+    }
+}
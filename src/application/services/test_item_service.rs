@@ -1,13 +1,22 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use validator::Validate;
 use crate::application::dtos::{
+    BulkItemResult,
+    BulkResponse,
+    BulkUpdateTestItem,
     CreateTestItemRequest,
     UpdateTestItemRequest,
     TestItemResponse,
-    PaginatedTestItemsResponse,
+    PaginatedResponse,
+    SearchHit,
 };
 use crate::domain::entities::TestItem;
 use crate::errors::ApiError;
 use crate::interfaces::TestItemRepository;
+use crate::interfaces::repositories::{QuerySpec, TextQuery, Trashed, TEST_ITEM_FIELDS};
+use crate::shared::validation_message;
 
 pub struct TestItemService {
     repository: Arc<dyn TestItemRepository>,
@@ -19,35 +28,75 @@ impl TestItemService {
     }
 
     /// Create new test item
-    pub async fn create(&self, request: CreateTestItemRequest) -> Result<TestItemResponse, ApiError> {
-        let item = TestItem::new(request.subject, request.optional_field);
+    pub async fn create(&self, tenant_id: &str, request: CreateTestItemRequest) -> Result<TestItemResponse, ApiError> {
+        let item = TestItem::new(tenant_id.to_string(), request.subject, request.optional_field);
         let created_item = self.repository.create(&item).await?;
         Ok(created_item.to_response())
     }
 
     /// Get test item by ID
-    pub async fn get_by_id(&self, id: &str) -> Result<Option<TestItemResponse>, ApiError> {
-        let item = self.repository.get_by_id(id).await?;
+    pub async fn get_by_id(&self, tenant_id: &str, id: &str) -> Result<Option<TestItemResponse>, ApiError> {
+        let item = self.repository.get_by_id(tenant_id, id).await?;
         Ok(item.map(|i| i.to_response()))
     }
 
-    /// Get all test items with pagination
-    pub async fn get_all(&self, page: i32, per_page: i32) -> Result<PaginatedTestItemsResponse, ApiError> {
-        if page < 1 || per_page < 1 || per_page > 100 {
-            return Err(ApiError::ValidationError("Invalid pagination parameters".to_string()));
-        }
+    /// List test items from query params: allow-listed filters, `sort`, page or cursor
+    /// pagination (see `QuerySpec::parse`). `trashed` is `with` or `only` to include deleted items.
+    pub async fn get_all(
+        &self,
+        tenant_id: &str,
+        mut params: HashMap<String, String>,
+    ) -> Result<PaginatedResponse<TestItemResponse>, ApiError> {
+        let trashed = match params.remove("trashed") {
+            Some(value) => Trashed::parse(&value)
+                .ok_or_else(|| ApiError::ValidationError(format!("Invalid trashed filter: {}", value)))?,
+            None => Trashed::default(),
+        };
+
+        let spec = QuerySpec::parse(&params, TEST_ITEM_FIELDS, "-created_at")?;
+        let items = self.repository.list(tenant_id, trashed, &spec).await?;
+
+        Ok(PaginatedResponse::from_paged(items, &spec.pagination, |i| i.to_response()))
+    }
+
+    /// Full-text search (`q`) over subject and optional field, best matches first.
+    /// Takes the same params as `get_all` (offset pages only).
+    pub async fn search(
+        &self,
+        tenant_id: &str,
+        mut params: HashMap<String, String>,
+    ) -> Result<PaginatedResponse<SearchHit<TestItemResponse>>, ApiError> {
+        let text = TextQuery::parse(&params.remove("q").unwrap_or_default())?;
+        let trashed = match params.remove("trashed") {
+            Some(value) => Trashed::parse(&value)
+                .ok_or_else(|| ApiError::ValidationError(format!("Invalid trashed filter: {}", value)))?,
+            None => Trashed::default(),
+        };
 
-        let (items, total) = self.repository.get_paginated(page, per_page).await?;
-        let item_responses: Vec<TestItemResponse> = items.into_iter().map(|i| i.to_response()).collect();
+        let spec = QuerySpec::parse(&params, TEST_ITEM_FIELDS, "-created_at")?;
+        let hits = self.repository.search_text(tenant_id, trashed, &text, &spec).await?;
 
-        Ok(PaginatedTestItemsResponse::new(item_responses, total, page, per_page))
+        Ok(PaginatedResponse::from_paged(hits, &spec.pagination, |hit| {
+            SearchHit::from_ranked(hit, |i| i.to_response())
+        }))
     }
 
-    /// Update test item
-    pub async fn update(&self, id: &str, request: UpdateTestItemRequest) -> Result<TestItemResponse, ApiError> {
-        let mut item = self.repository.get_by_id(id).await?
+    /// Update test item. `expected_version` (from `If-Match`) must still be current.
+    pub async fn update(
+        &self,
+        tenant_id: &str,
+        id: &str,
+        request: UpdateTestItemRequest,
+        expected_version: Option<i32>,
+    ) -> Result<TestItemResponse, ApiError> {
+        let mut item = self.repository.get_by_id(tenant_id, id).await?
             .ok_or_else(|| ApiError::NotFound("Test item not found".to_string()))?;
 
+        // The repository rejects the write unless the stored version still matches
+        if let Some(version) = expected_version {
+            item.version = version;
+        }
+
         if let Some(subject) = request.subject {
             item.update_subject(subject);
         }
@@ -56,16 +105,165 @@ impl TestItemService {
             item.update_optional_field(request.optional_field);
         }
 
-        self.repository.update(&item).await?;
+        let item = self.repository.update(&item).await?;
         Ok(item.to_response())
     }
 
-    /// Delete test item
-    pub async fn delete(&self, id: &str) -> Result<(), ApiError> {
-        let deleted = self.repository.delete(id).await?;
+    /// Delete test item (moves it to the trash)
+    pub async fn delete(&self, tenant_id: &str, id: &str) -> Result<(), ApiError> {
+        let deleted = self.repository.delete(tenant_id, id).await?;
         if !deleted {
             return Err(ApiError::NotFound("Test item not found".to_string()));
         }
         Ok(())
     }
+
+    /// Load a test item entity (for policy checks), trashed ones included when asked
+    pub async fn find_item(&self, tenant_id: &str, id: &str, with_trashed: bool) -> Result<TestItem, ApiError> {
+        let item = if with_trashed {
+            self.repository.get_by_id_with_trashed(tenant_id, id).await?
+        } else {
+            self.repository.get_by_id(tenant_id, id).await?
+        };
+        item.ok_or_else(|| ApiError::NotFound("Test item not found".to_string()))
+    }
+
+    /// Take a deleted test item out of the trash
+    pub async fn restore(&self, tenant_id: &str, id: &str) -> Result<TestItemResponse, ApiError> {
+        let item = self.repository.get_by_id_with_trashed(tenant_id, id).await?
+            .ok_or_else(|| ApiError::NotFound("Test item not found".to_string()))?;
+
+        if !item.is_trashed() {
+            return Err(ApiError::ValidationError("Test item is not deleted".to_string()));
+        }
+
+        self.repository.restore(tenant_id, id).await?;
+
+        self.repository.get_by_id(tenant_id, id).await?
+            .map(|i| i.to_response())
+            .ok_or_else(|| ApiError::NotFound("Test item not found".to_string()))
+    }
+
+    /// Create many items: each is validated on its own, the valid ones are inserted together
+    pub async fn bulk_create(&self, tenant_id: &str, requests: Vec<CreateTestItemRequest>) -> Result<BulkResponse, ApiError> {
+        let mut results = Vec::new();
+        let mut items = Vec::new();
+
+        for (index, request) in requests.into_iter().enumerate() {
+            match request.validate() {
+                Ok(_) => items.push((index, TestItem::new(tenant_id.to_string(), request.subject, request.optional_field))),
+                Err(errors) => results.push(BulkItemResult::failed(index, 422, None, validation_message(&errors))),
+            }
+        }
+
+        if !items.is_empty() {
+            let entities: Vec<TestItem> = items.iter().map(|(_, item)| item.clone()).collect();
+            let created: HashMap<String, TestItem> = self.repository.create_many(&entities).await?
+                .into_iter()
+                .map(|item| (item.id.clone(), item))
+                .collect();
+
+            for (index, item) in items {
+                let response = created.get(&item.id).map(|i| i.to_response());
+                results.push(BulkItemResult::ok(index, 201, item.id, response));
+            }
+        }
+
+        Ok(BulkResponse::new(results))
+    }
+
+    /// Update many items. Each change is validated and version-checked on its own,
+    /// the valid ones are written together.
+    pub async fn bulk_update(&self, tenant_id: &str, requests: Vec<BulkUpdateTestItem>) -> Result<BulkResponse, ApiError> {
+        let mut results = Vec::new();
+        let mut pending = Vec::new();
+        let mut seen = HashSet::new();
+
+        for (index, request) in requests.into_iter().enumerate() {
+            if let Err(errors) = request.changes.validate() {
+                results.push(BulkItemResult::failed(index, 422, Some(request.id), validation_message(&errors)));
+            } else if !seen.insert(request.id.clone()) {
+                results.push(BulkItemResult::failed(index, 422, Some(request.id), "Duplicate id in batch"));
+            } else {
+                pending.push((index, request));
+            }
+        }
+
+        let ids: Vec<String> = pending.iter().map(|(_, r)| r.id.clone()).collect();
+        let mut current: HashMap<String, TestItem> = self.repository.get_many(tenant_id, &ids).await?
+            .into_iter()
+            .map(|item| (item.id.clone(), item))
+            .collect();
+
+        let mut changed = Vec::new();
+        for (index, request) in pending {
+            let Some(mut item) = current.remove(&request.id) else {
+                results.push(BulkItemResult::failed(index, 404, Some(request.id), "Test item not found"));
+                continue;
+            };
+
+            // Same rules as `update`: the stored version must still match
+            if let Some(version) = request.version {
+                item.version = version;
+            }
+            if let Some(subject) = request.changes.subject {
+                item.update_subject(subject);
+            }
+            if request.changes.optional_field.is_some() {
+                item.update_optional_field(request.changes.optional_field);
+            }
+
+            changed.push((index, item));
+        }
+
+        if !changed.is_empty() {
+            let entities: Vec<TestItem> = changed.iter().map(|(_, item)| item.clone()).collect();
+            let updated: HashMap<String, TestItem> = self.repository.update_many(&entities).await?
+                .into_iter()
+                .map(|item| (item.id.clone(), item))
+                .collect();
+
+            for (index, item) in changed {
+                results.push(match updated.get(&item.id) {
+                    Some(updated) => BulkItemResult::ok(index, 200, item.id, Some(updated.to_response())),
+                    None => BulkItemResult::failed(
+                        index,
+                        409,
+                        Some(item.id),
+                        "Test item was modified or deleted by another request",
+                    ),
+                });
+            }
+        }
+
+        Ok(BulkResponse::new(results))
+    }
+
+    /// Move many items to the trash
+    pub async fn bulk_delete(&self, tenant_id: &str, ids: Vec<String>) -> Result<BulkResponse, ApiError> {
+        let unique: Vec<String> = ids.iter().collect::<HashSet<_>>().into_iter().cloned().collect();
+        let deleted: HashSet<String> = self.repository.delete_many(tenant_id, &unique).await?.into_iter().collect();
+
+        let mut seen = HashSet::new();
+        let results = ids
+            .into_iter()
+            .enumerate()
+            .map(|(index, id)| {
+                if !seen.insert(id.clone()) {
+                    BulkItemResult::failed(index, 422, Some(id), "Duplicate id in batch")
+                } else if deleted.contains(&id) {
+                    BulkItemResult::ok(index, 204, id, None)
+                } else {
+                    BulkItemResult::failed(index, 404, Some(id), "Test item not found")
+                }
+            })
+            .collect();
+
+        Ok(BulkResponse::new(results))
+    }
+
+    /// Permanently delete items trashed before the cutoff (all tenants)
+    pub async fn purge_trashed(&self, cutoff: DateTime<Utc>) -> Result<u64, ApiError> {
+        self.repository.purge_trashed_before(cutoff).await
+    }
 }
\ No newline at end of file
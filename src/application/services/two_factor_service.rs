@@ -0,0 +1,154 @@
+use std::sync::Arc;
+use chrono::Utc;
+use crate::application::dtos::{
+    DisableTwoFactorRequest, RecoveryCodesResponse, TwoFactorCodeRequest, TwoFactorSetupResponse,
+};
+use crate::config::AppConfig;
+use crate::domain::entities::User;
+use crate::domain::entities::user::Claims;
+use crate::errors::ApiError;
+use crate::interfaces::UserRepository;
+use crate::utils::password::PasswordHasher;
+use crate::utils::totp;
+
+/// TOTP enrollment and management for the authenticated user
+pub struct TwoFactorService {
+    user_repository: Arc<dyn UserRepository>,
+    config: Arc<AppConfig>,
+    password_hasher: PasswordHasher,
+}
+
+impl TwoFactorService {
+    pub fn new(user_repository: Arc<dyn UserRepository>, config: Arc<AppConfig>) -> Self {
+        Self {
+            user_repository,
+            password_hasher: PasswordHasher::new(&config),
+            config,
+        }
+    }
+
+    /// Generate a new secret and otpauth URI. 2FA stays off until `confirm` succeeds.
+    pub async fn setup(&self, claims: &Claims) -> Result<TwoFactorSetupResponse, ApiError> {
+        let mut user = self.find_user(claims).await?;
+
+        if user.has_two_factor() {
+            return Err(ApiError::Conflict(
+                "Two-factor authentication is already enabled".to_string(),
+            ));
+        }
+
+        let secret = totp::generate_secret();
+        user.begin_two_factor_enrollment(secret.clone())?;
+        self.user_repository.update(&user).await?;
+
+        Ok(TwoFactorSetupResponse {
+            otpauth_uri: totp::otpauth_uri(&secret, &self.config.two_factor.issuer, user.email.as_str()),
+            secret,
+        })
+    }
+
+    /// Confirm enrollment with a valid code and hand out the recovery codes
+    pub async fn confirm(
+        &self,
+        claims: &Claims,
+        request: TwoFactorCodeRequest,
+    ) -> Result<RecoveryCodesResponse, ApiError> {
+        let mut user = self.find_user(claims).await?;
+
+        if user.has_two_factor() {
+            return Err(ApiError::Conflict(
+                "Two-factor authentication is already enabled".to_string(),
+            ));
+        }
+
+        let secret = user.two_factor_secret.clone().ok_or_else(|| {
+            ApiError::ValidationError("Two-factor enrollment has not been started".to_string())
+        })?;
+
+        self.accept_code(&user, &secret, &request.code).await?;
+
+        let recovery_codes = totp::generate_recovery_codes();
+        user.confirm_two_factor(recovery_codes.iter().map(|c| totp::hash_recovery_code(c)).collect())?;
+        self.user_repository.update(&user).await?;
+
+        tracing::info!(user_id = %user.id, "Two-factor authentication enabled");
+
+        Ok(RecoveryCodesResponse { recovery_codes })
+    }
+
+    /// Disable 2FA. Requires both the password and a current code.
+    pub async fn disable(&self, claims: &Claims, request: DisableTwoFactorRequest) -> Result<(), ApiError> {
+        let mut user = self.find_user(claims).await?;
+
+        if !user.has_two_factor() {
+            return Err(ApiError::ValidationError(
+                "Two-factor authentication is not enabled".to_string(),
+            ));
+        }
+
+        if !self
+            .password_hasher
+            .verify(&request.password, &user.password_hash)
+            .await?
+            .is_match()
+        {
+            return Err(ApiError::Unauthorized);
+        }
+
+        self.verify_current_code(&user, &request.code).await?;
+
+        user.disable_two_factor();
+        self.user_repository.update(&user).await?;
+
+        tracing::info!(user_id = %user.id, "Two-factor authentication disabled");
+        Ok(())
+    }
+
+    /// Replace all recovery codes (the previous ones stop working)
+    pub async fn regenerate_recovery_codes(
+        &self,
+        claims: &Claims,
+        request: TwoFactorCodeRequest,
+    ) -> Result<RecoveryCodesResponse, ApiError> {
+        let mut user = self.find_user(claims).await?;
+
+        if !user.has_two_factor() {
+            return Err(ApiError::ValidationError(
+                "Two-factor authentication is not enabled".to_string(),
+            ));
+        }
+
+        self.verify_current_code(&user, &request.code).await?;
+
+        let recovery_codes = totp::generate_recovery_codes();
+        user.replace_recovery_codes(recovery_codes.iter().map(|c| totp::hash_recovery_code(c)).collect());
+        self.user_repository.update(&user).await?;
+
+        Ok(RecoveryCodesResponse { recovery_codes })
+    }
+
+    async fn find_user(&self, claims: &Claims) -> Result<User, ApiError> {
+        self.user_repository
+            .get_by_id(&claims.tenant_id, &claims.sub)
+            .await?
+            .ok_or_else(|| ApiError::NotFound("User not found".to_string()))
+    }
+
+    async fn verify_current_code(&self, user: &User, code: &str) -> Result<(), ApiError> {
+        let secret = user.two_factor_secret.as_deref().unwrap_or_default();
+        self.accept_code(user, secret, code).await
+    }
+
+    /// Check a code and record its step, so the same code cannot be used twice
+    async fn accept_code(&self, user: &User, secret: &str, code: &str) -> Result<(), ApiError> {
+        let accepted = match totp::verify_code(secret, code, Utc::now().timestamp()) {
+            Some(step) => self.user_repository.accept_two_factor_step(&user.tenant_id, &user.id, step).await?,
+            None => false,
+        };
+        if accepted {
+            Ok(())
+        } else {
+            Err(ApiError::ValidationError("Invalid two-factor code".to_string()))
+        }
+    }
+}
@@ -1,85 +1,117 @@
+use std::collections::HashMap;
 use std::sync::Arc;
+use chrono::{DateTime, Utc};
 use crate::application::dtos::{
+    ImpersonationResponse,
     PaginatedResponse, 
+    SearchHit,
     UpdateProfileRequest, 
     UpdateRoleRequest, 
-    UserResponse
+    UserResponse,
 };
-use crate::domain::entities::User;
+use crate::application::services::AuditLogger;
+use crate::domain::entities::{AuditEntry, RequestOrigin, User};
+use crate::domain::entities::user::Claims;
 use crate::domain::value_objects::Role;
 use crate::errors::ApiError;
 use crate::interfaces::UserRepository;
-use crate::utils::auth::hash_password;
+use crate::interfaces::repositories::{QuerySpec, TextQuery, Trashed, UserFilter, USER_FIELDS};
+use crate::utils::password::PasswordHasher;
+use crate::utils::jwt::create_impersonation_token;
 use crate::config::AppConfig;
 use crate::domain::value_objects::{EmailAddress, Username};
 
 pub struct UserService {
     user_repository: Arc<dyn UserRepository>,
+    audit_logger: Arc<AuditLogger>,
     config: Arc<AppConfig>,  
+    password_hasher: PasswordHasher,
 }
 
 impl UserService {
-    pub fn new(user_repository: Arc<dyn UserRepository>, config: Arc<AppConfig>) -> Self {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        audit_logger: Arc<AuditLogger>,
+        config: Arc<AppConfig>,
+    ) -> Self {
         Self { 
             user_repository,
+            audit_logger,
+            password_hasher: PasswordHasher::new(&config),
             config,  
         }
     }
 
     /// Get user by ID
-    pub async fn get_user(&self, user_id: &str) -> Result<Option<UserResponse>, ApiError> {
-        let user = self.user_repository.get_by_id(user_id).await?;
+    pub async fn get_user(&self, tenant_id: &str, user_id: &str) -> Result<Option<UserResponse>, ApiError> {
+        let user = self.user_repository.get_by_id(tenant_id, user_id).await?;
         Ok(user.map(|u| u.to_response()))
     }
 
-    /// Get all users with pagination
-    pub async fn get_all_users(
-        &self,
-        page: i32,
-        per_page: i32,
-    ) -> Result<PaginatedResponse<UserResponse>, ApiError> {
-        // Validate pagination parameters
-        if page < 1 {
-            return Err(ApiError::ValidationError(
-                "Page must be greater than 0".to_string()
-            ));
+    /// Load a user entity (for policy checks)
+    pub async fn find_user(&self, tenant_id: &str, user_id: &str) -> Result<User, ApiError> {
+        self.user_repository
+            .get_by_id(tenant_id, user_id)
+            .await?
+            .ok_or_else(|| ApiError::NotFound("User not found".to_string()))
+    }
+
+    /// Refuse a token whose user was deactivated, deleted or had their tokens revoked since it was issued
+    pub async fn verify_session(&self, claims: &Claims) -> Result<(), ApiError> {
+        let user = self
+            .user_repository
+            .get_by_id(&claims.tenant_id, &claims.sub)
+            .await?
+            .ok_or(ApiError::Unauthorized)?;
+
+        if !user.is_active() {
+            return Err(ApiError::Forbidden("Account is disabled".to_string()));
         }
-        if per_page < 1 || per_page > 100 {
-            return Err(ApiError::ValidationError(
-                "Per page must be between 1 and 100".to_string()
-            ));
+
+        if user.token_version != claims.ver {
+            return Err(ApiError::Unauthorized);
         }
 
-        let (users, total) = self
-            .user_repository
-            .get_paginated(page, per_page)
-            .await?;
+        Ok(())
+    }
 
-        let user_responses: Vec<UserResponse> = users
-            .into_iter()
-            .map(|u| u.to_response())
-            .collect();
+    /// List users from query params: allow-listed filters, `sort`, page or cursor pagination
+    pub async fn get_all_users(
+        &self,
+        tenant_id: &str,
+        params: HashMap<String, String>,
+    ) -> Result<PaginatedResponse<UserResponse>, ApiError> {
+        let spec = QuerySpec::parse(&params, USER_FIELDS, "-created_at")?;
+        let users = self.user_repository.list(tenant_id, &spec).await?;
 
-        Ok(PaginatedResponse::new(user_responses, total, page, per_page))
+        Ok(PaginatedResponse::from_paged(users, &spec.pagination, |u| u.to_response()))
     }
 
-    pub async fn get_all_users_no_pagination(&self) -> Result<Vec<UserResponse>, ApiError> {
-        let users = self.user_repository.get_all().await?;
+    pub async fn get_all_users_no_pagination(&self, tenant_id: &str) -> Result<Vec<UserResponse>, ApiError> {
+        let users = self.user_repository.get_all(tenant_id).await?;
         Ok(users.into_iter().map(|u| u.to_response()).collect())
     }
 
-pub async fn update_profile(
+    /// Update profile. `expected_version` (from `If-Match`) must still be current.
+    pub async fn update_profile(
         &self,
+        tenant_id: &str,
         user_id: &str,
         request: UpdateProfileRequest,
+        expected_version: Option<i32>,
     ) -> Result<UserResponse, ApiError> {
         // Buscar usuario
         let mut user = self
             .user_repository
-            .get_by_id(user_id)
+            .get_by_id(tenant_id, user_id)
             .await?
             .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
 
+        // The repository rejects the write unless the stored version still matches
+        if let Some(version) = expected_version {
+            user.version = version;
+        }
+
         // Update username if present
         if let Some(username_str) = request.username {
             // ✅ Build the Value Object. If invalid, returns error automatically.
@@ -90,7 +122,7 @@ pub async fn update_profile(
         // Update email if present
         if let Some(email_str) = request.email {
             // Validar si el email ya existe en BD para otro usuario
-            if let Some(existing_user) = self.user_repository.get_by_email(&email_str).await? {
+            if let Some(existing_user) = self.user_repository.get_by_email(tenant_id, &email_str).await? {
                 if existing_user.id != user_id {
                     return Err(ApiError::Conflict(
                         "Email is already in use by another user".to_string()
@@ -102,30 +134,108 @@ pub async fn update_profile(
             user.update_email(email_vo);
         }
 
-        // Update password if present
+        // Update password if present (only with the current one)
         if let Some(password) = request.password {
+            let current_password = request.current_password.ok_or_else(|| {
+                ApiError::ValidationError("Current password is required to change the password".to_string())
+            })?;
+            if !self.password_hasher.verify(&current_password, &user.password_hash).await?.is_match() {
+                return Err(ApiError::Forbidden("Current password is incorrect".to_string()));
+            }
+
             // ✅ You should add strong password validation here if applicable
             crate::shared::validator::validate_strong_password(&password)
                 .map_err(|_| ApiError::ValidationError("Weak password".to_string()))?;
 
-            let password_hash = hash_password(&password, &self.config)?;
+            let password_hash = self.password_hasher.hash(&password).await?;
             user.update_password_hash(password_hash);
         }
 
         // Persistir cambios
-        self.user_repository.update(&user).await?;
+        let user = self.user_repository.update(&user).await?;
 
         Ok(user.to_response())
     }
 
 
+    /// Search users (admin only): `search` matches email or username, `trashed` includes
+    /// deleted users; everything else is a regular list query on the user fields.
+    pub async fn search_users(
+        &self,
+        tenant_id: &str,
+        mut params: HashMap<String, String>,
+    ) -> Result<PaginatedResponse<UserResponse>, ApiError> {
+        let filter = Self::take_filter(&mut params)?;
+        let spec = QuerySpec::parse(&params, USER_FIELDS, "-created_at")?;
+        let users = self.user_repository.search(tenant_id, &filter, &spec).await?;
+
+        Ok(PaginatedResponse::from_paged(users, &spec.pagination, |u| u.to_response()))
+    }
+
+    /// Full-text search (`q`) over email and username, best matches first.
+    /// Takes the same params as `search_users` (offset pages only).
+    pub async fn search_users_text(
+        &self,
+        tenant_id: &str,
+        mut params: HashMap<String, String>,
+    ) -> Result<PaginatedResponse<SearchHit<UserResponse>>, ApiError> {
+        let text = TextQuery::parse(&params.remove("q").unwrap_or_default())?;
+        let filter = Self::take_filter(&mut params)?;
+        let spec = QuerySpec::parse(&params, USER_FIELDS, "-created_at")?;
+        let hits = self.user_repository.search_text(tenant_id, &filter, &text, &spec).await?;
+
+        Ok(PaginatedResponse::from_paged(hits, &spec.pagination, |hit| {
+            SearchHit::from_ranked(hit, |u| u.to_response())
+        }))
+    }
+
+    /// Remove the `search` and `trashed` params, which are not field filters
+    fn take_filter(params: &mut HashMap<String, String>) -> Result<UserFilter, ApiError> {
+        let trashed = match params.remove("trashed") {
+            Some(trashed) => Trashed::parse(&trashed).ok_or_else(|| {
+                ApiError::ValidationError(format!("Invalid trashed filter: {}", trashed))
+            })?,
+            None => Trashed::default(),
+        };
+
+        Ok(UserFilter {
+            search: params.remove("search"),
+            trashed,
+        })
+    }
+
+    /// Change a user's role (admin only). The last active admin cannot be demoted.
+    pub async fn update_user_role(
+        &self,
+        actor: &Claims,
+        origin: &RequestOrigin,
+        user_id: &str,
+        request: UpdateRoleRequest,
+    ) -> Result<UserResponse, ApiError> {
+        let role = Role::from_str(&request.role)
+            .ok_or_else(|| ApiError::ValidationError(format!("Invalid role: {}", request.role)))?;
+
+        let mut user = self.find_user(&actor.tenant_id, user_id).await?;
+
+        if user.role == role {
+            return Ok(user.to_response());
+        }
+
+        let previous = user.role.clone();
+        user.change_role(role);
+        let user = self.user_repository.update_keeping_an_admin(&user).await?;
+
+        self.record_user_change("user.role_changed", actor, origin, &user.id, serde_json::json!({
+            "role": { "from": previous.as_str(), "to": user.role.as_str() }
+        }))
+        .await;
+
+        Ok(user.to_response())
+    }
+
     /// Deactivate user (admin only)
-    pub async fn deactivate_user(&self, user_id: &str) -> Result<(), ApiError> {
-        let mut user = self
-            .user_repository
-            .get_by_id(user_id)
-            .await?
-            .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
+    pub async fn deactivate_user(&self, actor: &Claims, origin: &RequestOrigin, user_id: &str) -> Result<(), ApiError> {
+        let mut user = self.find_user(&actor.tenant_id, user_id).await?;
 
         if !user.is_active() {
             return Err(ApiError::ValidationError(
@@ -134,18 +244,18 @@ pub async fn update_profile(
         }
 
         user.deactivate();
-        self.user_repository.update(&user).await?;
+        self.user_repository.update_keeping_an_admin(&user).await?;
 
+        self.record_user_change("user.deactivated", actor, origin, &user.id, serde_json::json!({
+            "is_active": { "from": true, "to": false }
+        }))
+        .await;
         Ok(())
     }
 
     /// Activate user (admin only)
-    pub async fn activate_user(&self, user_id: &str) -> Result<(), ApiError> {
-        let mut user = self
-            .user_repository
-            .get_by_id(user_id)
-            .await?
-            .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
+    pub async fn activate_user(&self, actor: &Claims, origin: &RequestOrigin, user_id: &str) -> Result<(), ApiError> {
+        let mut user = self.find_user(&actor.tenant_id, user_id).await?;
 
         if user.is_active() {
             return Err(ApiError::ValidationError(
@@ -156,22 +266,195 @@ pub async fn update_profile(
         user.activate();
         self.user_repository.update(&user).await?;
 
+        self.record_user_change("user.activated", actor, origin, &user.id, serde_json::json!({
+            "is_active": { "from": false, "to": true }
+        }))
+        .await;
+        Ok(())
+    }
+
+    /// Require the user to change their password before the next login (admin only)
+    pub async fn force_password_reset(&self, actor: &Claims, origin: &RequestOrigin, user_id: &str) -> Result<(), ApiError> {
+        let mut user = self.find_user(&actor.tenant_id, user_id).await?;
+
+        user.require_password_reset();
+        self.user_repository.update(&user).await?;
+
+        self.record_user_change("user.password_reset_forced", actor, origin, &user.id, serde_json::json!({
+            "password_reset_required": true
+        }))
+        .await;
         Ok(())
     }
 
-    /// Delete user
-    pub async fn delete_user(&self, user_id: &str) -> Result<(), ApiError> {
-        let deleted = self.user_repository.delete(user_id).await?;
+    /// Issue a short-lived token acting as another user (admin only)
+    pub async fn impersonate(
+        &self,
+        actor: &Claims,
+        origin: &RequestOrigin,
+        user_id: &str,
+    ) -> Result<ImpersonationResponse, ApiError> {
+        if actor.impersonator.is_some() {
+            return Err(ApiError::Forbidden(
+                "Cannot impersonate while impersonating".to_string()
+            ));
+        }
+
+        if actor.sub == user_id {
+            return Err(ApiError::ValidationError("Cannot impersonate yourself".to_string()));
+        }
+
+        let user = self.find_user(&actor.tenant_id, user_id).await?;
+
+        if user.is_admin() {
+            return Err(ApiError::Forbidden("Admins cannot be impersonated".to_string()));
+        }
+
+        if !user.is_active() {
+            return Err(ApiError::ValidationError("User is inactive".to_string()));
+        }
+
+        let token = create_impersonation_token(&user, &actor.sub, &self.config)?;
+
+        self.record_user_change("user.impersonated", actor, origin, &user.id, serde_json::json!({
+            "expires_in": self.config.authorization.impersonation_ttl
+        }))
+        .await;
+
+        Ok(ImpersonationResponse {
+            user: user.to_response(),
+            token,
+            expires_in: self.config.authorization.impersonation_ttl,
+            impersonator_id: actor.sub.clone(),
+        })
+    }
+
+    /// Delete user (moves them to the trash). The last active admin cannot be deleted.
+    pub async fn delete_user(&self, actor: &Claims, origin: &RequestOrigin, user_id: &str) -> Result<(), ApiError> {
+        let user = self.find_user(&actor.tenant_id, user_id).await?;
+
+        let deleted = self.user_repository.delete_keeping_an_admin(&actor.tenant_id, user_id).await?;
 
         if !deleted {
             return Err(ApiError::NotFound("User not found".to_string()));
         }
 
+        self.record_user_change("user.deleted", actor, origin, &user.id, serde_json::json!({
+            "email": user.email.as_str(),
+            "role": user.role.as_str()
+        }))
+        .await;
+
         Ok(())
     }
 
+    /// Take a deleted user out of the trash (admin only)
+    pub async fn restore_user(&self, actor: &Claims, origin: &RequestOrigin, user_id: &str) -> Result<UserResponse, ApiError> {
+        let user = self
+            .user_repository
+            .get_by_id_with_trashed(&actor.tenant_id, user_id)
+            .await?
+            .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
+
+        if !user.is_trashed() {
+            return Err(ApiError::ValidationError("User is not deleted".to_string()));
+        }
+
+        self.user_repository.restore(&actor.tenant_id, user_id).await?;
+        let restored = self.find_user(&actor.tenant_id, user_id).await?;
+
+        self.record_user_change("user.restored", actor, origin, &user.id, serde_json::json!({
+            "deleted_at": user.deleted_at.map(|d| d.to_rfc3339())
+        }))
+        .await;
+
+        Ok(restored.to_response())
+    }
+
+    /// Permanently delete users trashed before the cutoff (all tenants)
+    pub async fn purge_trashed(&self, cutoff: DateTime<Utc>) -> Result<u64, ApiError> {
+        self.user_repository.purge_trashed_before(cutoff).await
+    }
+
     /// Get total user count
-    pub async fn get_user_count(&self) -> Result<i32, ApiError> {
-        self.user_repository.count().await
+    pub async fn get_user_count(&self, tenant_id: &str) -> Result<i32, ApiError> {
+        self.user_repository.count(tenant_id).await
+    }
+
+    async fn record_user_change(
+        &self,
+        action: &str,
+        actor: &Claims,
+        origin: &RequestOrigin,
+        user_id: &str,
+        changes: serde_json::Value,
+    ) {
+        self.audit_logger
+            .record(
+                AuditEntry::new(action)
+                    .actor(actor)
+                    .origin(origin)
+                    .target("user", user_id)
+                    .changes(changes),
+            )
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::PgPool;
+    use crate::config::Sources;
+    use crate::db::factories::UserFactory;
+    use crate::infrastructure::persistence::postgres::{PostgresAuditLogRepository, PostgresUserRepository};
+
+    /// Connects to `TEST_DATABASE_URL` and registers a throwaway tenant
+    async fn setup() -> (UserService, Arc<PostgresUserRepository>, String) {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let tenant = uuid::Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO tenants (id, slug, name) VALUES ($1, $1, $1)")
+            .bind(&tenant)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let sources = Sources::new([("DATABASE_URL", url.as_str()), ("JWT_SECRET", "user-service-test-key")]);
+        let config = Arc::new(AppConfig::from_sources(sources).unwrap());
+        let audit_logger = Arc::new(AuditLogger::new(
+            Arc::new(PostgresAuditLogRepository::new(pool.clone())),
+            config.clone(),
+        ));
+        let repo = Arc::new(PostgresUserRepository::new(pool));
+        (UserService::new(repo.clone(), audit_logger, config), repo, tenant)
+    }
+
+    fn password_change(current_password: Option<&str>) -> UpdateProfileRequest {
+        UpdateProfileRequest {
+            username: None,
+            email: None,
+            password: Some("N3w-Passw0rd!".to_string()),
+            current_password: current_password.map(str::to_string),
+        }
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+    async fn test_password_change_needs_the_current_password() {
+        let (service, repo, tenant) = setup().await;
+        let user = UserFactory::new(&tenant).create(repo.as_ref()).await.unwrap();
+
+        let missing = service.update_profile(&tenant, &user.id, password_change(None), None).await;
+        assert!(matches!(missing, Err(ApiError::ValidationError(_))));
+
+        let wrong = service.update_profile(&tenant, &user.id, password_change(Some("wrong")), None).await;
+        assert!(matches!(wrong, Err(ApiError::Forbidden(_))));
+
+        let change = password_change(Some(UserFactory::PASSWORD));
+        assert!(service.update_profile(&tenant, &user.id, change, None).await.is_ok());
+        let stored = repo.get_by_id(&tenant, &user.id).await.unwrap().unwrap();
+        assert_eq!(stored.token_version, user.token_version + 1);
     }
 }
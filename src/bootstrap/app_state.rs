@@ -3,13 +3,14 @@
 //! Manages dependency injection and service registration for the entire application.
 
 use std::sync::Arc;
+use std::time::Duration;
 use sqlx::{PgPool, MySqlPool};
 use actix_web::web;
 
-use crate::config::AppConfig;
-use crate::infrastructure::{PostgresUserRepository, PostgresTestItemRepository};
-use crate::application::{AuthService, UserService, TestItemService};
-use crate::interfaces::{UserRepository, TestItemRepository};
+use crate::config::{AppConfig, MaintenanceStoreKind};
+use crate::infrastructure::{PostgresUserRepository, PostgresTestItemRepository, PostgresPermissionRepository, PostgresApiKeyRepository, PostgresUserIdentityRepository, PostgresAuditLogRepository, PostgresTenantRepository, PostgresDataImportRepository, PostgresMaintenanceStore, FileMaintenanceStore, HttpIdentityProviderClient, TemplateEngine};
+use crate::application::{AuthService, UserService, TestItemService, TwoFactorService, PermissionService, ApiKeyService, OAuthService, AuditLogger, TenantService, DataTransferService, MaintenanceService};
+use crate::interfaces::{UserRepository, TestItemRepository, PermissionRepository, ApiKeyRepository, UserIdentityRepository, AuditLogRepository, TenantRepository, DataImportRepository, MaintenanceStore, IdentityProviderClient};
 use crate::queue::QueueManager;
 
 /// Global application state containing all services and dependencies
@@ -20,8 +21,17 @@ pub struct AppState {
     // pub mysql_pool: Option<MySqlPool>,
     pub queue_manager: Arc<QueueManager>,
     pub auth_service: Arc<AuthService>,
+    pub two_factor_service: Arc<TwoFactorService>,
+    pub permission_service: Arc<PermissionService>,
+    pub api_key_service: Arc<ApiKeyService>,
+    pub oauth_service: Arc<OAuthService>,
+    pub audit_logger: Arc<AuditLogger>,
+    pub tenant_service: Arc<TenantService>,
     pub user_service: Arc<UserService>,
     pub test_item_service: Arc<TestItemService>,
+    pub data_transfer_service: Arc<DataTransferService>,
+    pub maintenance_service: Arc<MaintenanceService>,
+    pub templates: Arc<TemplateEngine>,
 }
 
 impl AppState {
@@ -38,16 +48,82 @@ impl AppState {
         let test_item_repository: Arc<dyn TestItemRepository> =
             Arc::new(PostgresTestItemRepository::new(pg_pool.clone()));
 
+        let permission_repository: Arc<dyn PermissionRepository> =
+            Arc::new(PostgresPermissionRepository::new(pg_pool.clone()));
+
+        let api_key_repository: Arc<dyn ApiKeyRepository> =
+            Arc::new(PostgresApiKeyRepository::new(pg_pool.clone()));
+
+        let user_identity_repository: Arc<dyn UserIdentityRepository> =
+            Arc::new(PostgresUserIdentityRepository::new(pg_pool.clone()));
+
+        let audit_log_repository: Arc<dyn AuditLogRepository> =
+            Arc::new(PostgresAuditLogRepository::new(pg_pool.clone()));
+
+        let tenant_repository: Arc<dyn TenantRepository> =
+            Arc::new(PostgresTenantRepository::new(pg_pool.clone()));
+
+        let data_import_repository: Arc<dyn DataImportRepository> =
+            Arc::new(PostgresDataImportRepository::new(pg_pool.clone()));
+
+        let maintenance_store: Arc<dyn MaintenanceStore> = match config.maintenance.store {
+            MaintenanceStoreKind::File => Arc::new(FileMaintenanceStore::new(
+                &config.maintenance.file,
+                Duration::from_secs(config.maintenance.poll_interval),
+            )),
+            MaintenanceStoreKind::Database => Arc::new(PostgresMaintenanceStore::new(pg_pool.clone())),
+        };
+
+        let identity_provider_client: Arc<dyn IdentityProviderClient> =
+            Arc::new(HttpIdentityProviderClient::new());
+
         // ============================================
         // Services
         // ============================================
+        let audit_logger = Arc::new(AuditLogger::new(
+            audit_log_repository.clone(),
+            config.clone(),
+        ));
+
+        let tenant_service = Arc::new(TenantService::new(
+            tenant_repository.clone(),
+            config.clone(),
+        ));
+
         let auth_service = Arc::new(AuthService::new(
+            user_repository.clone(),
+            audit_logger.clone(),
+            config.clone(),
+        ));
+
+        let two_factor_service = Arc::new(TwoFactorService::new(
             user_repository.clone(),
             config.clone(),
         ));
 
+        let permission_service = Arc::new(PermissionService::new(
+            permission_repository.clone(),
+            config.clone(),
+        ));
+
+        let api_key_service = Arc::new(ApiKeyService::new(
+            api_key_repository.clone(),
+            user_repository.clone(),
+            config.clone(),
+        ));
+
+        let oauth_service = Arc::new(OAuthService::new(
+            user_identity_repository.clone(),
+            user_repository.clone(),
+            identity_provider_client.clone(),
+            auth_service.clone(),
+            audit_logger.clone(),
+            config.clone(),
+        ));
+
         let user_service = Arc::new(UserService::new(
             user_repository.clone(),
+            audit_logger.clone(),
             config.clone(),
         ));
 
@@ -55,10 +131,30 @@ impl AppState {
             test_item_repository.clone(),
         ));
 
+        let data_transfer_service = Arc::new(DataTransferService::new(
+            test_item_repository.clone(),
+            user_repository.clone(),
+            data_import_repository.clone(),
+            test_item_service.clone(),
+            user_service.clone(),
+            config.imports.clone(),
+        ));
+
+        let maintenance_service = Arc::new(MaintenanceService::new(
+            maintenance_store.clone(),
+            audit_logger.clone(),
+            config.clone(),
+        ));
+
         // ============================================
         // Queue Manager
         // ============================================
-        let queue_manager = Arc::new(QueueManager::new(pg_pool.clone()));
+        let queue_manager = Arc::new(QueueManager::new(pg_pool.clone(), config.queue.clone()));
+
+        // ============================================
+        // Templates
+        // ============================================
+        let templates = Arc::new(TemplateEngine::new(&config.templates));
 
         // ============================================
         // Return AppState
@@ -69,8 +165,17 @@ impl AppState {
             // mysql_pool,
             queue_manager,
             auth_service,
+            two_factor_service,
+            permission_service,
+            api_key_service,
+            oauth_service,
+            audit_logger,
+            tenant_service,
             user_service,
             test_item_service,
+            data_transfer_service,
+            maintenance_service,
+            templates,
         }
     }
 }
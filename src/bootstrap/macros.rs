@@ -5,7 +5,7 @@
 /// Simplifies registering all AppState fields as web::Data in the Actix App.
 ///
 /// # Example
-/// ```rust
+/// ```text
 /// let app = App::new();
 /// let app = register_services!(
 ///     app,
@@ -19,7 +19,7 @@
 /// ```
 ///
 /// # Equivalent to
-/// ```rust
+/// ```text
 /// app.app_data(web::Data::new(app_state.config.clone()))
 ///    .app_data(web::Data::new(app_state.pool.clone()))
 ///    .app_data(web::Data::new(app_state.auth_service.clone()))
@@ -4,6 +4,22 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::process;
 use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+mod make;
+
+use ironclad::db::migrator::{MigrationError, MigrationState, Migrator, MIGRATIONS_DIR};
+use ironclad::db::seeders;
+use ironclad::routes::openapi::OpenApiDocument;
+use ironclad::routes::RouteTable;
+use ironclad::config::{MaintenanceConfig, MaintenanceStoreKind, RateLimitConfig, Sources, TemplateConfig};
+use ironclad::domain::entities::maintenance::path_matches;
+use ironclad::domain::entities::{BypassSecret, MaintenanceState, DEFAULT_BYPASS_NAME};
+use ironclad::domain::value_objects::IpRange;
+use ironclad::infrastructure::{FileMaintenanceStore, PostgresMaintenanceStore, TemplateEngine};
+use ironclad::interfaces::MaintenanceStore;
+use ironclad::utils::maintenance_bypass;
 
 #[derive(Parser)]
 #[command(name = "ironclad")]
@@ -21,7 +37,123 @@ enum Commands {
     
     /// Check database connection
     DbCheck,
+
+    /// Run the pending database migrations
+    Migrate {
+        /// Record the migrations up to this version as applied without running them
+        /// (for a database whose schema was created by hand)
+        #[arg(long)]
+        baseline: Option<i64>,
+    },
+
+    /// Show which migrations have run
+    #[command(name = "migrate:status")]
+    MigrateStatus,
+
+    /// Revert the last batch of migrations
+    #[command(name = "migrate:rollback")]
+    MigrateRollback {
+        /// Revert this many migrations instead of the last batch
+        #[arg(long)]
+        step: Option<usize>,
+    },
+
+    /// Drop every table and run all migrations again
+    #[command(name = "migrate:fresh")]
+    MigrateFresh {
+        /// Required when ENVIRONMENT is production
+        #[arg(long)]
+        force: bool,
+
+        /// Run the database seeder afterwards
+        #[arg(long)]
+        seed: bool,
+    },
+
+    /// Fill the database with sample data
+    #[command(name = "db:seed")]
+    DbSeed {
+        /// Seeder to run (default: DatabaseSeeder)
+        #[arg(long)]
+        class: Option<String>,
+
+        /// Required when ENVIRONMENT is production
+        #[arg(long)]
+        force: bool,
+    },
     
+    /// Generate a resource: value object, entity, DTOs, repository, Postgres repository and service
+    #[command(name = "make:entity")]
+    MakeEntity {
+        /// Resource name (e.g. BlogPost)
+        name: String,
+
+        /// Also create the table migration
+        #[arg(long, short)]
+        migration: bool,
+
+        #[command(flatten)]
+        options: MakeOptions,
+    },
+
+    /// Generate a CRUD controller for a resource made with make:entity
+    #[command(name = "make:controller")]
+    MakeController {
+        /// Resource name (e.g. BlogPost)
+        name: String,
+
+        #[command(flatten)]
+        options: MakeOptions,
+    },
+
+    /// Add a queued job type and its worker handler
+    #[command(name = "make:job")]
+    MakeJob {
+        /// Job name (e.g. SendWelcomeEmail)
+        name: String,
+
+        #[command(flatten)]
+        options: MakeOptions,
+    },
+
+    /// Create the next numbered migration and its .down.sql
+    #[command(name = "make:migration")]
+    MakeMigration {
+        /// snake_case name; create_<table>_table gets a table skeleton
+        name: String,
+
+        #[command(flatten)]
+        options: MakeOptions,
+    },
+
+    /// List the registered routes with their handler, guards and middleware
+    #[command(name = "route:list")]
+    RouteList {
+        /// Only routes with this HTTP method
+        #[arg(long)]
+        method: Option<String>,
+
+        /// Only routes whose path contains this text
+        #[arg(long)]
+        path: Option<String>,
+
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Write the OpenAPI document of the API (served at /api/docs/openapi.json)
+    #[command(name = "docs:generate")]
+    DocsGenerate {
+        /// File to write, '-' for stdout (default: openapi.json or openapi.yaml)
+        #[arg(long)]
+        output: Option<String>,
+
+        /// Write YAML instead of JSON
+        #[arg(long)]
+        yaml: bool,
+    },
+
     /// Put the application into maintenance mode
     Down {
         /// Optional maintenance message
@@ -32,9 +164,13 @@ enum Commands {
         #[arg(long, default_value = "60")]
         retry: u32,
         
-        /// Secret token to bypass maintenance mode
-        #[arg(long)]
+        /// Secret of the `default` bypass: visiting /{secret} grants a signed cookie (random when no value is given)
+        #[arg(long, num_args = 0..=1, default_missing_value = "")]
         secret: Option<String>,
+
+        /// Named bypass, as NAME or NAME=SECRET (random secret when omitted; repeatable)
+        #[arg(long)]
+        bypass: Vec<String>,
         
         /// Template to render (e.g., "emergency" or "emergency::low")
         #[arg(long)]
@@ -47,11 +183,45 @@ enum Commands {
         /// Redirect all requests to this path
         #[arg(long)]
         redirect: Option<String>,
+
+        /// Start of a scheduled window, announced until then (RFC 3339 or +30m, +2h, +1d)
+        #[arg(long)]
+        at: Option<String>,
+
+        /// End of the window, when the application comes back up by itself (RFC 3339 or +30m, +2h, +1d)
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Only put this path prefix or route group under maintenance (repeatable)
+        #[arg(long = "path")]
+        paths: Vec<String>,
+
+        /// Keep serving this IP address or CIDR block, e.g. the office or health checkers (repeatable)
+        #[arg(long)]
+        allow: Vec<String>,
     },
     
     /// Bring the application out of maintenance mode
     Up,
 
+    /// Add or rotate a named bypass secret of the current maintenance window
+    #[command(name = "maintenance:bypass")]
+    MaintenanceBypass {
+        /// Who the secret is for, e.g. office
+        name: String,
+
+        /// Secret to use (random when omitted)
+        #[arg(long)]
+        secret: Option<String>,
+    },
+
+    /// Revoke a named bypass secret and the cookies it granted
+    #[command(name = "maintenance:revoke")]
+    MaintenanceRevoke {
+        /// Name of the bypass
+        name: String,
+    },
+
     /// Storage management commands
     Storage {
         #[command(subcommand)]
@@ -81,7 +251,16 @@ enum StorageAction {
     Init,
 }
 
-const MAINTENANCE_FILE: &str = "storage/framework/maintenance.json";
+#[derive(clap::Args)]
+struct MakeOptions {
+    /// Show what would be written without touching any file
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Overwrite files that already exist
+    #[arg(long)]
+    force: bool,
+}
 
 // Storage directories configuration
 const STORAGE_DIRS: &[&str] = &[
@@ -115,13 +294,78 @@ async fn main() {
         Some(Commands::DbCheck) => {
             check_database().await;
         }
+
+        Some(Commands::Migrate { baseline }) => {
+            migrate(baseline).await;
+        }
+
+        Some(Commands::MigrateStatus) => {
+            migrate_status().await;
+        }
+
+        Some(Commands::MigrateRollback { step }) => {
+            migrate_rollback(step).await;
+        }
+
+        Some(Commands::MigrateFresh { force, seed }) => {
+            migrate_fresh(force).await;
+            if seed {
+                println!();
+                db_seed(None, force).await;
+            }
+        }
+
+        Some(Commands::DbSeed { class, force }) => {
+            db_seed(class, force).await;
+        }
         
-        Some(Commands::Down { message, retry, secret, render, norender, redirect }) => {
-            maintenance_down(message, retry, secret, render, norender, redirect);
+        Some(Commands::MakeEntity { name, migration, options }) => {
+            generate(&options, make::Names::parse(&name).and_then(|n| make::entity(&n, migration)));
+        }
+
+        Some(Commands::MakeController { name, options }) => {
+            generate(&options, make::Names::parse(&name).and_then(|n| make::controller(&n)));
+        }
+
+        Some(Commands::MakeJob { name, options }) => {
+            generate(&options, make::Names::parse(&name).and_then(|n| make::job(&n)));
+        }
+
+        Some(Commands::MakeMigration { name, options }) => {
+            generate(&options, make::migration(&name));
+        }
+
+        Some(Commands::RouteList { method, path, json }) => {
+            route_list(method, path, json);
+        }
+
+        Some(Commands::DocsGenerate { output, yaml }) => {
+            docs_generate(output, yaml);
+        }
+
+        Some(Commands::Down { message, retry, secret, bypass, render, norender, redirect, at, until, paths, allow }) => {
+            let window = MaintenanceWindow { at, until, paths, allow };
+            let default = secret.map(|secret| (DEFAULT_BYPASS_NAME.to_string(), Some(secret).filter(|s| !s.is_empty())));
+            let bypass = default
+                .into_iter()
+                .chain(bypass.iter().map(|spec| match spec.split_once('=') {
+                    Some((name, secret)) => (name.to_string(), Some(secret.to_string())),
+                    None => (spec.to_string(), None),
+                }))
+                .collect();
+            maintenance_down(message, retry, bypass, render, norender, redirect, window).await;
         }
         
         Some(Commands::Up) => {
-            maintenance_up();
+            maintenance_up().await;
+        }
+
+        Some(Commands::MaintenanceBypass { name, secret }) => {
+            maintenance_bypass_add(name, secret).await;
+        }
+
+        Some(Commands::MaintenanceRevoke { name }) => {
+            maintenance_bypass_revoke(name).await;
         }
 
         Some(Commands::Storage { action }) => match action {
@@ -181,145 +425,291 @@ fn ensure_project_root() {
     }
 }
 
-fn maintenance_down(
+/// When and where `down` applies
+struct MaintenanceWindow {
+    at: Option<String>,
+    until: Option<String>,
+    paths: Vec<String>,
+    allow: Vec<String>,
+}
+
+async fn maintenance_down(
     message: Option<String>, 
     retry: u32,
-    secret: Option<String>,
+    bypass: Vec<(String, Option<String>)>,
     render: Option<String>,
     norender: bool,
     redirect: Option<String>,
+    window: MaintenanceWindow,
 ) {
     println!("🔧 Putting application into maintenance mode...");
     println!();
 
-    // Create storage directory if it doesn't exist
-    if let Err(e) = fs::create_dir_all("storage/framework") {
-        eprintln!("❌ Failed to create storage directory: {}", e);
-        process::exit(1);
-    }
+    let allow = window
+        .allow
+        .iter()
+        .map(|range| range.parse())
+        .collect::<Result<Vec<IpRange>, _>>()
+        .unwrap_or_else(|e| {
+            eprintln!("❌ {}", e);
+            process::exit(1);
+        });
+
+    let (bypass, secrets): (Vec<_>, Vec<_>) = bypass
+        .into_iter()
+        .map(|(name, secret)| bypass_secret(name, secret))
+        .unzip();
+
+    let state = MaintenanceState {
+        bypass,
+        render,
+        norender,
+        redirect,
+        starts_at: window.at.as_deref().map(|at| parse_time("--at", at)),
+        ends_at: window.until.as_deref().map(|until| parse_time("--until", until)),
+        paths: window.paths,
+        allow,
+        ..MaintenanceState::new(message, retry)
+    };
 
     // Validate conflicting options
-    if norender && render.is_some() {
-        eprintln!("❌ Cannot use both --norender and --render options");
-        process::exit(1);
-    }
-    
-    if render.is_some() && redirect.is_some() {
-        eprintln!("❌ Cannot use both --render and --redirect options");
+    if let Err(e) = state.validate() {
+        eprintln!("❌ {}", e);
         process::exit(1);
     }
 
-    // Create maintenance payload
-    let mut maintenance_data = serde_json::json!({
-        "time": chrono::Utc::now().timestamp(),
-        "message": message.unwrap_or_else(|| "Application is down for maintenance".to_string()),
-        "retry": retry,
-        "created_at": chrono::Utc::now().to_rfc3339(),
-    });
-
-    // Add optional fields
-    if let Some(secret_value) = secret {
-        maintenance_data["secret"] = serde_json::json!(secret_value);
-        println!("🔑 Secret bypass enabled: /{}", secret_value);
+    for bypass in &state.bypass {
+        println!("🔑 Bypass: {}", bypass.name);
     }
 
-    if norender {
-        maintenance_data["norender"] = serde_json::json!(true);
+    if state.norender {
         println!("📋 JSON-only mode (no HTML rendering)");
     }
 
-    if let Some(render_template) = &render {
-        // Validate template path exists
-        let template_path = parse_template_path(render_template);
-        if Path::new(&template_path).exists() {
-            println!("✅ Template found: {}", template_path);
+    if let Some(render_template) = &state.render {
+        // Validate template exists
+        let template = state.template();
+        let templates = TemplateConfig::from_env().map(|config| TemplateEngine::new(&config));
+        if templates.is_ok_and(|templates| templates.exists(&template)) {
+            println!("✅ Template found: {}", template);
         } else {
-            eprintln!("⚠️  Warning: Template not found: {}", template_path);
+            eprintln!("⚠️  Warning: Template not found: {}", template);
             eprintln!("   Will fall back to default template");
         }
         
-        maintenance_data["render"] = serde_json::json!(render_template);
         println!("🎨 HTML template: {}", render_template);
     }
 
-    if let Some(redirect_path) = redirect {
-        maintenance_data["redirect"] = serde_json::json!(redirect_path);
+    if let Some(redirect_path) = &state.redirect {
         println!("↪️  Redirect to: {}", redirect_path);
     }
 
-    // Write maintenance file
-    let maintenance_file_path = Path::new(MAINTENANCE_FILE);
-    println!("📝 Writing to: {:?}", maintenance_file_path.canonicalize().unwrap_or(maintenance_file_path.to_path_buf()));
+    if let Some(starts_at) = state.starts_at {
+        println!("🗓️  Starts at: {} (announced until then)", starts_at.to_rfc3339());
+    }
+
+    if let Some(ends_at) = state.ends_at {
+        println!("⏱️  Ends at: {} (back up automatically)", ends_at.to_rfc3339());
+    }
+
+    if !state.paths.is_empty() {
+        let routes = route_table();
+        for prefix in &state.paths {
+            let matched = routes.0.iter().filter(|route| path_matches(prefix, &route.path)).count();
+            if matched == 0 {
+                eprintln!("⚠️  Warning: No registered route under {}", prefix);
+            } else {
+                println!("🧭 Path: {} ({} route(s))", prefix, matched);
+            }
+        }
+    }
+
+    for range in &state.allow {
+        println!("✅ Allowed: {}", range);
+    }
+
+    let (store, location) = maintenance_store().await;
+    println!("📝 Writing to: {}", location);
     
-    match fs::write(MAINTENANCE_FILE, maintenance_data.to_string()) {
-        Ok(_) => {
-            println!();
-            println!("✅ Application is now in maintenance mode");
+    match store.put(&state).await {
+        Ok(()) => {
             println!();
-            println!("   All requests will receive a 503 response");
-            if maintenance_data.get("secret").is_some() {
-                println!("   Bypass: Add /{} to any URL", maintenance_data["secret"].as_str().unwrap());
+            if state.starts_at.is_some_and(|starts_at| starts_at > chrono::Utc::now()) {
+                println!("✅ Maintenance window scheduled");
+                println!();
+                println!("   Until it starts, affected responses carry X-Maintenance-Starts-At");
+                println!("   and GET /api/maintenance describes it");
+            } else {
+                println!("✅ Application is now in maintenance mode");
+                println!();
+            }
+            if state.paths.is_empty() {
+                println!("   All requests will receive a 503 response");
+            } else {
+                println!("   Requests under {} will receive a 503 response", state.paths.join(", "));
             }
+            print_bypass_secrets(&state.bypass, &secrets);
             println!("   To bring the application back up, run:");
             println!("   cargo run --bin ironclad -- up");
         }
         Err(e) => {
-            eprintln!("❌ Failed to create maintenance file: {}", e);
+            eprintln!("❌ Failed to enable maintenance mode: {}", e);
             process::exit(1);
         }
     }
 }
 
-fn parse_template_path(template: &str) -> String {
-    if template.contains("::") {
-        let parts: Vec<&str> = template.split("::").collect();
-        format!("templates/render/down/{}/{}.html", parts[0], parts[1])
-    } else {
-        format!("templates/render/down/{}/default.html", template)
-    }
+/// RFC 3339 timestamp, or an offset from now such as `+30m`, `+2h` or `+1d`
+fn parse_time(option: &str, value: &str) -> chrono::DateTime<chrono::Utc> {
+    let relative = value.strip_prefix('+').and_then(|offset| {
+        let (amount, unit) = offset.split_at(offset.len().checked_sub(1)?);
+        let amount: i64 = amount.parse().ok()?;
+        let duration = match unit {
+            "s" => chrono::Duration::seconds(amount),
+            "m" => chrono::Duration::minutes(amount),
+            "h" => chrono::Duration::hours(amount),
+            "d" => chrono::Duration::days(amount),
+            _ => return None,
+        };
+        Some(chrono::Utc::now() + duration)
+    });
+
+    relative
+        .or_else(|| chrono::DateTime::parse_from_rfc3339(value).ok().map(|t| t.with_timezone(&chrono::Utc)))
+        .unwrap_or_else(|| {
+            eprintln!("❌ Invalid {} value: {} (use RFC 3339, e.g. 2026-03-01T22:00:00Z, or +30m / +2h / +1d)", option, value);
+            process::exit(1);
+        })
 }
 
-fn maintenance_up() {
+async fn maintenance_up() {
     println!("🚀 Bringing application out of maintenance mode...");
     println!();
 
-    if !Path::new(MAINTENANCE_FILE).exists() {
-        println!("ℹ️  Application is not in maintenance mode");
-        println!("   (No maintenance file found at: {})", MAINTENANCE_FILE);
-        return;
-    }
+    let (store, location) = maintenance_store().await;
+    println!("📝 Clearing: {}", location);
 
-    let maintenance_file_path = Path::new(MAINTENANCE_FILE);
-    println!("📝 Removing: {:?}", maintenance_file_path.canonicalize().unwrap_or(maintenance_file_path.to_path_buf()));
-
-    match fs::remove_file(MAINTENANCE_FILE) {
-        Ok(_) => {
-            println!("✅ Application is now live");
+    match store.clear().await {
+        Ok(true) => {
+            println!("✅ Application is now live (any scheduled window is cancelled)");
             println!();
             println!("   All requests will be processed normally");
         }
+        Ok(false) => {
+            println!("ℹ️  Application is not in maintenance mode");
+        }
         Err(e) => {
-            eprintln!("❌ Failed to remove maintenance file: {}", e);
+            eprintln!("❌ Failed to disable maintenance mode: {}", e);
             process::exit(1);
         }
     }
 }
 
-async fn check_database() {
-    println!("🔍 Checking database connection...");
-    println!();
+/// Add (or rotate) a bypass of the current window
+async fn maintenance_bypass_add(name: String, secret: Option<String>) {
+    let (store, location) = maintenance_store().await;
+    let mut state = current_maintenance_state(&*store, &location).await;
+
+    let (bypass, secret) = bypass_secret(name, secret);
+    if let Err(e) = state.add_bypass(bypass.clone()) {
+        eprintln!("❌ {}", e);
+        process::exit(1);
+    }
+
+    match store.put(&state).await {
+        Ok(()) => {
+            println!("✅ Bypass added (a previous secret of the same name no longer works)");
+            print_bypass_secrets(&[bypass], &[secret]);
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to add the bypass: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Revoke a bypass of the current window
+async fn maintenance_bypass_revoke(name: String) {
+    let (store, location) = maintenance_store().await;
+    let mut state = current_maintenance_state(&*store, &location).await;
+
+    if !state.revoke_bypass(&name) {
+        eprintln!("❌ No bypass named {}", name);
+        process::exit(1);
+    }
+
+    match store.put(&state).await {
+        Ok(()) => println!("✅ Bypass {} revoked; the cookies it granted no longer work", name),
+        Err(e) => {
+            eprintln!("❌ Failed to revoke the bypass: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Stored state, exiting when the application is not in maintenance mode
+async fn current_maintenance_state(store: &dyn MaintenanceStore, location: &str) -> MaintenanceState {
+    match store.get().await {
+        Ok(Some(state)) => state.with_legacy_secret_hashed(maintenance_bypass::hash),
+        Ok(None) => {
+            eprintln!("❌ Application is not in maintenance mode ({})", location);
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to read maintenance mode: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Hash the given secret, or generate one; returns the plain secret too
+fn bypass_secret(name: String, secret: Option<String>) -> (BypassSecret, String) {
+    let secret = secret.unwrap_or_else(maintenance_bypass::generate_secret);
+    if !maintenance_bypass::is_valid_secret(&secret) {
+        eprintln!("❌ Bypass secret must be 8 to 128 letters, digits, -, _, . or ~");
+        process::exit(1);
+    }
+    (BypassSecret::new(name, maintenance_bypass::hash(&secret)), secret)
+}
 
+/// Secrets are only stored hashed: this is the one chance to copy them
+fn print_bypass_secrets(bypass: &[BypassSecret], secrets: &[String]) {
+    for (bypass, secret) in bypass.iter().zip(secrets) {
+        println!("   Bypass {}: visit /{} (keep it, it is not shown again)", bypass.name, secret);
+    }
+}
+
+/// Store selected by MAINTENANCE_STORE, and a description of where it lives
+async fn maintenance_store() -> (Arc<dyn MaintenanceStore>, String) {
     dotenv::dotenv().ok();
 
-    let database_url = match std::env::var("DATABASE_URL") {
-        Ok(url) => url,
-        Err(_) => {
-            eprintln!("❌ DATABASE_URL not found in environment");
-            eprintln!("   Make sure .env file exists with DATABASE_URL");
+    let config = match MaintenanceConfig::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("❌ {}", e);
             process::exit(1);
         }
     };
 
+    match config.store {
+        MaintenanceStoreKind::File => {
+            let location = format!("{} (this host only)", config.file);
+            let store = FileMaintenanceStore::new(config.file, Duration::from_secs(config.poll_interval));
+            (Arc::new(store), location)
+        }
+        MaintenanceStoreKind::Database => {
+            let store = PostgresMaintenanceStore::new(connect().await);
+            (Arc::new(store), "maintenance_state table (every instance)".to_string())
+        }
+    }
+}
+
+async fn check_database() {
+    println!("🔍 Checking database connection...");
+    println!();
+
+    let database_url = database_url();
+
     println!("📍 Database: {}", mask_connection_string(&database_url));
     println!();
 
@@ -363,6 +753,277 @@ async fn check_database() {
     pool.close().await;
 }
 
+/// DATABASE_URL from the environment, .env or the config profile, or exit
+fn database_url() -> String {
+    let mut sources = Sources::load();
+    let url = sources.required("db_postgres.postgres_url", "DATABASE_URL");
+    match sources.finish(url) {
+        Ok(url) => url,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            eprintln!("   Set it in .env or as db_postgres.postgres_url in config/<ENVIRONMENT>.toml");
+            process::exit(1);
+        }
+    }
+}
+
+/// Every route, with the rate limits of the current configuration, or exit
+fn route_table() -> RouteTable {
+    match RateLimitConfig::from_env() {
+        Ok(rate_limits) => ironclad::routes::table(&rate_limits),
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Connect to DATABASE_URL, or exit
+async fn connect() -> PgPool {
+    let database_url = database_url();
+
+    match PgPool::connect(&database_url).await {
+        Ok(pool) => pool,
+        Err(e) => {
+            eprintln!("❌ Failed to connect to {}: {}", mask_connection_string(&database_url), e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Connect to DATABASE_URL and load the migrations, or exit
+async fn migrator() -> Migrator {
+    match Migrator::new(connect().await, MIGRATIONS_DIR) {
+        Ok(migrator) => migrator,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+async fn migrate(baseline: Option<i64>) {
+    let migrator = migrator().await;
+
+    let (result, verb) = match baseline {
+        Some(version) => {
+            println!("📌 Recording migrations up to {} as applied...", version);
+            (migrator.baseline(version).await, "recorded")
+        }
+        None => {
+            println!("🗄️  Running migrations...");
+            (migrator.run().await, "applied")
+        }
+    };
+    println!();
+
+    match result {
+        Ok(migrations) if migrations.is_empty() => println!("✅ Nothing to migrate"),
+        Ok(migrations) => {
+            for migration in &migrations {
+                println!("  ✅ {}", migration.name);
+            }
+            println!();
+            println!("🎉 {} migration(s) {}", migrations.len(), verb);
+        }
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            if matches!(e, MigrationError::Failed { .. }) {
+                eprintln!("   Migrations before the failing one stay applied, see 'ironclad migrate:status'");
+            }
+            process::exit(1);
+        }
+    }
+}
+
+async fn migrate_status() {
+    let migrator = migrator().await;
+
+    let status = match migrator.status().await {
+        Ok(status) => status,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            process::exit(1);
+        }
+    };
+
+    println!("🗄️  Migration Status");
+    println!();
+    println!("{:<12} {:<6} {:<45} Applied at", "Status", "Batch", "Migration");
+    println!("{}", "─".repeat(90));
+
+    for s in &status {
+        let state = match s.state {
+            MigrationState::Applied => "✅ Ran",
+            MigrationState::Pending => "⏳ Pending",
+            MigrationState::Modified => "⚠️  Modified",
+            MigrationState::Missing => "❌ Missing",
+        };
+        println!(
+            "{:<12} {:<6} {:<45} {}",
+            state,
+            s.batch.map(|b| b.to_string()).unwrap_or_default(),
+            s.name,
+            s.applied_at.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_default(),
+        );
+    }
+
+    println!();
+    if status.iter().any(|s| matches!(s.state, MigrationState::Modified | MigrationState::Missing)) {
+        println!("⚠️  Applied migrations were changed or removed; add a new migration instead of editing one that ran");
+    }
+}
+
+async fn migrate_rollback(step: Option<usize>) {
+    let migrator = migrator().await;
+
+    match step {
+        Some(step) => println!("⏪ Rolling back {} migration(s)...", step),
+        None => println!("⏪ Rolling back the last batch..."),
+    }
+    println!();
+
+    match migrator.rollback(step).await {
+        Ok(migrations) if migrations.is_empty() => println!("✅ Nothing to roll back"),
+        Ok(migrations) => {
+            for migration in &migrations {
+                println!("  ⏪ {}", migration.name);
+            }
+            println!();
+            println!("🎉 {} migration(s) rolled back", migrations.len());
+        }
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+async fn migrate_fresh(force: bool) {
+    dotenv::dotenv().ok();
+    if env::var("ENVIRONMENT").as_deref() == Ok("production") && !force {
+        eprintln!("❌ Refusing to drop every table in production");
+        eprintln!("   Run 'ironclad migrate:fresh --force' if you really mean it");
+        process::exit(1);
+    }
+
+    let migrator = migrator().await;
+
+    println!("🧨 Dropping all tables and running every migration...");
+    println!();
+
+    match migrator.fresh().await {
+        Ok(migrations) => {
+            for migration in &migrations {
+                println!("  ✅ {}", migration.name);
+            }
+            println!();
+            println!("🎉 Database rebuilt with {} migration(s)", migrations.len());
+        }
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+async fn db_seed(class: Option<String>, force: bool) {
+    dotenv::dotenv().ok();
+    if env::var("ENVIRONMENT").as_deref() == Ok("production") && !force {
+        eprintln!("❌ Refusing to seed a production database");
+        eprintln!("   Run 'ironclad db:seed --force' if you really mean it");
+        process::exit(1);
+    }
+
+    let name = class.as_deref().unwrap_or("DatabaseSeeder");
+    let seeder = match seeders::find(name) {
+        Some(seeder) => seeder,
+        None => {
+            let names: Vec<_> = seeders::all().iter().map(|s| s.name()).collect();
+            eprintln!("❌ Unknown seeder '{}'", name);
+            eprintln!("   Available: {}", names.join(", "));
+            process::exit(1);
+        }
+    };
+
+    let pool = connect().await;
+
+    println!("🌱 Seeding: {}", seeder.name());
+    match seeder.run(&pool).await {
+        Ok(()) => println!("✅ Seeded: {}", seeder.name()),
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            process::exit(1);
+        }
+    }
+
+    pool.close().await;
+}
+
+/// Apply (or show, with --dry-run) the changes planned by a make:* command, or exit
+fn generate(options: &MakeOptions, plan: Result<make::Plan, String>) {
+    let result = plan.and_then(|plan| plan.apply(options.dry_run, options.force));
+    if let Err(e) = result {
+        eprintln!("❌ {}", e);
+        process::exit(1);
+    }
+}
+
+fn route_list(method: Option<String>, path: Option<String>, json: bool) {
+    let table = route_table();
+    let routes = table.filter(method.as_deref(), path.as_deref());
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&routes).expect("routes serialize to JSON"));
+        return;
+    }
+
+    let join = |labels: &[String]| if labels.is_empty() { "-".to_string() } else { labels.join(", ") };
+    let rows: Vec<[String; 5]> = routes
+        .iter()
+        .map(|r| [r.method.clone(), r.path.clone(), r.handler.clone(), join(&r.guards), join(&r.middleware)])
+        .collect();
+
+    let headers = ["Method", "Path", "Handler", "Guards", "Middleware"];
+    let mut widths = headers.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: [&str; 5]| {
+        let padded: Vec<String> = cells.iter().zip(widths).map(|(cell, width)| format!("{:<width$}", cell)).collect();
+        println!("{}", padded.join("  ").trim_end());
+    };
+
+    line(headers);
+    println!("{}", "─".repeat(widths.iter().sum::<usize>() + 2 * (widths.len() - 1)));
+    for row in &rows {
+        line(row.each_ref().map(String::as_str));
+    }
+    println!();
+    println!("{} route(s)", rows.len());
+}
+
+fn docs_generate(output: Option<String>, yaml: bool) {
+    let document = OpenApiDocument::build(&route_table());
+    let contents = if yaml { document.to_yaml() } else { document.to_json() };
+    let output = output.unwrap_or_else(|| if yaml { "openapi.yaml" } else { "openapi.json" }.to_string());
+
+    if output == "-" {
+        println!("{}", contents);
+        return;
+    }
+
+    if let Err(e) = fs::write(&output, contents) {
+        eprintln!("❌ Failed to write {}: {}", output, e);
+        process::exit(1);
+    }
+    let paths = document.value()["paths"].as_object().map_or(0, |paths| paths.len());
+    println!("✅ OpenAPI document written to {} ({} paths)", output, paths);
+}
+
 fn mask_connection_string(url: &str) -> String {
     if let Some(at_pos) = url.rfind('@') {
         if let Some(colon_pos) = url[..at_pos].rfind(':') {
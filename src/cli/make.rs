@@ -0,0 +1,477 @@
+//! `make:*` generators.
+//!
+//! Files are rendered from the stubs in `src/cli/stubs`, following the layering of
+//! `IMPLEMENTATION.md`, and registered in the `mod.rs` of their layer. Every change is
+//! planned first and written at the end, so a conflict leaves the tree untouched.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ironclad::db::migrator::{Migrator, MIGRATIONS_DIR};
+
+const VALUE_OBJECT: &str = include_str!("stubs/value_object.stub");
+const ENTITY: &str = include_str!("stubs/entity.stub");
+const DTO: &str = include_str!("stubs/dto.stub");
+const REPOSITORY: &str = include_str!("stubs/repository.stub");
+const POSTGRES_REPOSITORY: &str = include_str!("stubs/postgres_repository.stub");
+const SERVICE: &str = include_str!("stubs/service.stub");
+const CONTROLLER: &str = include_str!("stubs/controller.stub");
+const MIGRATION: &str = include_str!("stubs/migration.stub");
+const MIGRATION_DOWN: &str = include_str!("stubs/migration.down.stub");
+const CREATE_TABLE_MIGRATION: &str = include_str!("stubs/create_table_migration.stub");
+const CREATE_TABLE_MIGRATION_DOWN: &str = include_str!("stubs/create_table_migration.down.stub");
+
+/// Spellings of the resource name given to a `make:*` command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Names {
+    /// `BlogPost`
+    pub pascal: String,
+    /// `blog_post`
+    pub snake: String,
+    /// `blog_posts` (table name)
+    pub plural: String,
+    /// `blog post`
+    pub label: String,
+}
+
+impl Names {
+    /// Accepts `BlogPost`, `blogPost`, `blog_post` or `blog-post`
+    pub fn parse(input: &str) -> Result<Self, String> {
+        if input.is_empty()
+            || !input.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+            || !input.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(format!("Invalid name '{}': use letters, digits, '_' or '-'", input));
+        }
+
+        let mut words: Vec<String> = Vec::new();
+        let mut current = String::new();
+        for c in input.chars() {
+            if c == '_' || c == '-' {
+                words.push(std::mem::take(&mut current));
+            } else if c.is_ascii_uppercase() && !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+                current.push(c.to_ascii_lowercase());
+            } else {
+                current.push(c.to_ascii_lowercase());
+            }
+        }
+        words.push(current);
+        words.retain(|w| !w.is_empty());
+
+        let pascal = words
+            .iter()
+            .map(|w| w[..1].to_ascii_uppercase() + &w[1..])
+            .collect::<String>();
+        let snake = words.join("_");
+
+        Ok(Self {
+            plural: pluralize(&snake),
+            label: words.join(" "),
+            pascal,
+            snake,
+        })
+    }
+
+    /// Replace the `{{...}}` placeholders of a stub
+    fn render(&self, stub: &str) -> String {
+        let labels = pluralize(&self.label);
+        stub.replace("{{Name}}", &self.pascal)
+            .replace("{{names}}", &self.plural)
+            .replace("{{name}}", &self.snake)
+            .replace("{{Labels}}", &capitalize(&labels))
+            .replace("{{labels}}", &labels)
+            .replace("{{Label}}", &capitalize(&self.label))
+            .replace("{{label}}", &self.label)
+    }
+}
+
+fn pluralize(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix('y') {
+        if !stem.ends_with(['a', 'e', 'i', 'o', 'u']) {
+            return format!("{}ies", stem);
+        }
+    }
+    if word.ends_with('s') || word.ends_with('x') || word.ends_with("ch") || word.ends_with("sh") {
+        return format!("{}es", word);
+    }
+    format!("{}s", word)
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Change to an existing file, with the lines it adds (shown by `--dry-run`)
+struct Edit {
+    path: PathBuf,
+    content: String,
+    added: Vec<String>,
+}
+
+/// Files to create and edits to existing ones, applied together
+#[derive(Default)]
+pub struct Plan {
+    creates: Vec<(PathBuf, String)>,
+    edits: Vec<Edit>,
+    /// Wiring left to the developer, printed at the end
+    steps: Vec<String>,
+}
+
+impl Plan {
+    fn create(&mut self, path: impl Into<PathBuf>, content: String) {
+        self.creates.push((path.into(), content));
+    }
+
+    fn step(&mut self, step: impl Into<String>) {
+        self.steps.push(step.into());
+    }
+
+    /// Current content of a file, including the edits already planned
+    fn current(&self, path: &Path) -> Result<String, String> {
+        match self.edits.iter().find(|e| e.path == path) {
+            Some(edit) => Ok(edit.content.clone()),
+            None => fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e)),
+        }
+    }
+
+    fn edit(&mut self, path: &Path, content: String, added: Vec<String>) {
+        match self.edits.iter_mut().find(|e| e.path == path) {
+            Some(edit) => {
+                edit.content = content;
+                edit.added.extend(added);
+            }
+            None => self.edits.push(Edit { path: path.to_path_buf(), content, added }),
+        }
+    }
+
+    /// `pub mod <module>;` and `pub use <module>::<export>;` in a layer's `mod.rs`
+    fn register(&mut self, mod_rs: &str, module: &str, export: &str) -> Result<(), String> {
+        let path = Path::new(mod_rs);
+        let source = self.current(path)?;
+        let mod_line = format!("pub mod {};", module);
+        if source.lines().any(|l| l.trim() == mod_line) {
+            return Ok(());
+        }
+
+        let use_line = format!("pub use {}::{};", module, export);
+        let content = register_module(&source, &mod_line, &use_line);
+        self.edit(path, content, vec![mod_line, use_line]);
+        Ok(())
+    }
+
+    /// Write everything, or print it with `dry_run`. Existing files are only
+    /// overwritten with `force`.
+    pub fn apply(self, dry_run: bool, force: bool) -> Result<(), String> {
+        let existing: Vec<_> = self.creates.iter().filter(|(path, _)| path.exists()).collect();
+        if !existing.is_empty() && !force && !dry_run {
+            let paths: Vec<_> = existing.iter().map(|(p, _)| p.display().to_string()).collect();
+            return Err(format!("Already exists: {} (use --force to overwrite)", paths.join(", ")));
+        }
+
+        if dry_run {
+            println!("🔍 Dry run, nothing is written");
+            for (path, content) in &self.creates {
+                let verb = if path.exists() { "overwrite" } else { "create" };
+                println!();
+                println!("── {} {}", verb, path.display());
+                print!("{}", content);
+            }
+            for edit in &self.edits {
+                println!();
+                println!("── update {}", edit.path.display());
+                for line in edit.added.iter().flat_map(|added| added.lines()) {
+                    println!("+ {}", line);
+                }
+            }
+        } else {
+            for (path, content) in &self.creates {
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+                }
+                fs::write(path, content).map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+                println!("  ✅ Created {}", path.display());
+            }
+            for edit in &self.edits {
+                fs::write(&edit.path, &edit.content)
+                    .map_err(|e| format!("Cannot write {}: {}", edit.path.display(), e))?;
+                println!("  ✏️  Updated {}", edit.path.display());
+            }
+        }
+
+        if !self.steps.is_empty() {
+            println!();
+            println!("📋 Next steps:");
+            for (i, step) in self.steps.iter().enumerate() {
+                println!("  {}. {}", i + 1, step);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Insert `mod_line` after the last `mod` declaration and `use_line` after the last
+/// `pub use` (or at the end)
+fn register_module(source: &str, mod_line: &str, use_line: &str) -> String {
+    let mut lines: Vec<String> = source.lines().map(str::to_string).collect();
+
+    let last_mod = lines.iter().rposition(|l| l.starts_with("pub mod ") || l.starts_with("mod "));
+    match last_mod {
+        Some(i) => lines.insert(i + 1, mod_line.to_string()),
+        None => lines.insert(0, mod_line.to_string()),
+    }
+
+    match lines.iter().rposition(|l| l.starts_with("pub use ")) {
+        Some(i) => lines.insert(i + 1, use_line.to_string()),
+        None => {
+            lines.push(String::new());
+            lines.push(use_line.to_string());
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// Insert `text` (whole lines) at the end of the `{ ... }` block opened after `anchor`.
+/// With `separate`, a blank line is kept before it.
+fn insert_in_block(source: &str, anchor: &str, text: &str, separate: bool) -> Result<String, String> {
+    let start = source.find(anchor).ok_or_else(|| format!("'{}' not found", anchor))?;
+    let open = start + source[start..].find('{').ok_or_else(|| format!("No block after '{}'", anchor))?;
+
+    let mut depth = 0;
+    let mut close = None;
+    for (i, c) in source[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(open + i);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let close = close.ok_or_else(|| format!("Unclosed block after '{}'", anchor))?;
+
+    // End of the last non-blank line of the block
+    let body_end = source[..close].trim_end().len();
+    let separator = if separate { "\n\n" } else { "\n" };
+    Ok(format!(
+        "{}{}{}\n{}",
+        &source[..body_end],
+        separator,
+        text.trim_end(),
+        &source[source[..close].rfind('\n').map_or(close, |p| p + 1)..]
+    ))
+}
+
+/// Value object, entity, DTOs, repository trait, Postgres repository and service
+pub fn entity(names: &Names, with_migration: bool) -> Result<Plan, String> {
+    let mut plan = Plan::default();
+    let n = names;
+
+    plan.create(format!("src/domain/value_objects/{}_name.rs", n.snake), n.render(VALUE_OBJECT));
+    plan.register("src/domain/value_objects/mod.rs", &format!("{}_name", n.snake), &format!("{}Name", n.pascal))?;
+
+    plan.create(format!("src/domain/entities/{}.rs", n.snake), n.render(ENTITY));
+    plan.register("src/domain/entities/mod.rs", &n.snake, &n.pascal)?;
+
+    plan.create(format!("src/application/dtos/{}_dto.rs", n.snake), n.render(DTO));
+    plan.register("src/application/dtos/mod.rs", &format!("{}_dto", n.snake), "*")?;
+
+    let repository = format!("{}_repository", n.snake);
+    plan.create(format!("src/interfaces/repositories/{}.rs", repository), n.render(REPOSITORY));
+    plan.register("src/interfaces/repositories/mod.rs", &repository, &format!("{}Repository", n.pascal))?;
+
+    plan.create(format!("src/infrastructure/persistence/postgres/{}.rs", repository), n.render(POSTGRES_REPOSITORY));
+    plan.register(
+        "src/infrastructure/persistence/postgres/mod.rs",
+        &repository,
+        &format!("Postgres{}Repository", n.pascal),
+    )?;
+
+    plan.create(format!("src/application/services/{}_service.rs", n.snake), n.render(SERVICE));
+    plan.register("src/application/services/mod.rs", &format!("{}_service", n.snake), &format!("{}Service", n.pascal))?;
+
+    if with_migration {
+        migration_files(&mut plan, &format!("create_{}_table", n.plural))?;
+    }
+
+    plan.step(format!(
+        "Wire the repository and service in src/bootstrap/app_state.rs: \
+         Arc::new(Postgres{0}Repository::new(pg_pool.clone())) as Arc<dyn {0}Repository>, \
+         then a `pub {1}_service: Arc<{0}Service>` field",
+        n.pascal, n.snake
+    ));
+    plan.step(format!("Add `{}_service` to register_services! in src/main.rs", n.snake));
+    if !with_migration {
+        plan.step(format!("Create the table: ironclad make:migration create_{}_table", n.plural));
+    }
+    plan.step("Apply it: ironclad migrate");
+    plan.step(format!("Expose it over HTTP: ironclad make:controller {}", n.pascal));
+    Ok(plan)
+}
+
+/// CRUD controller calling the resource's service
+pub fn controller(names: &Names) -> Result<Plan, String> {
+    let mut plan = Plan::default();
+    let n = names;
+
+    let service = format!("src/application/services/{}_service.rs", n.snake);
+    if !Path::new(&service).exists() {
+        return Err(format!("{} not found, run 'ironclad make:entity {}' first", service, n.pascal));
+    }
+
+    plan.create(format!("src/infrastructure/http/controllers/{}_controller.rs", n.snake), n.render(CONTROLLER));
+    plan.register(
+        "src/infrastructure/http/controllers/mod.rs",
+        &format!("{}_controller", n.snake),
+        &format!("{}Controller", n.pascal),
+    )?;
+
+    let scope = n.plural.replace('_', "-");
+    plan.step(format!(
+        "Add the routes in src/routes/api.rs:\n\
+         \x20   api.scope(\"/{1}\", |items| {{\n\
+         \x20       items.post(\"\", {0}Controller::create).returns::<{0}Response>(201)\n\
+         \x20           .get(\"\", {0}Controller::get_all).returns::<Vec<{0}Response>>(200)\n\
+         \x20           .get(\"/{{id}}\", {0}Controller::get_by_id).returns::<{0}Response>(200)\n\
+         \x20           .put(\"/{{id}}\", {0}Controller::update).returns::<{0}Response>(200)\n\
+         \x20           .delete(\"/{{id}}\", {0}Controller::delete);\n\
+         \x20   }});",
+        n.pascal, scope
+    ));
+    plan.step(format!(
+        "Import {0}Controller and {0}Response in src/routes/api.rs (re-export the controller from src/infrastructure/http/mod.rs)",
+        n.pascal
+    ));
+    Ok(plan)
+}
+
+/// New `JobPayload` variant, its job type and a worker arm
+pub fn job(names: &Names) -> Result<Plan, String> {
+    let mut plan = Plan::default();
+    let n = names;
+
+    let job_rs = Path::new("src/queue/job.rs");
+    let worker_rs = Path::new("src/queue/worker.rs");
+
+    let source = plan.current(job_rs)?;
+    if source.contains(&format!("JobPayload::{} ", n.pascal)) {
+        return Err(format!("Job {} already exists", n.pascal));
+    }
+
+    let job_type = format!("    {},", n.pascal);
+    let source = insert_in_block(&source, "pub enum JobType", &job_type, false)?;
+
+    let variant = format!("    {} {{}},", n.pascal);
+    let source = insert_in_block(&source, "pub enum JobPayload", &variant, false)?;
+
+    let arm = format!("            JobPayload::{0} {{ .. }} => \"{0}\",", n.pascal);
+    let source = insert_in_block(&source, "match self", &arm, false)?;
+    plan.edit(job_rs, source, vec![job_type, variant, arm]);
+
+    let handler = format!(
+        "            JobPayload::{0} {{}} => {{\n\
+         \x20               tracing::info!(job_id = %job.id, tenant_id = %job.tenant_id, \"Processing {0} job\");\n\
+         \x20               Ok(())\n\
+         \x20           }}",
+        n.pascal
+    );
+    let source = plan.current(worker_rs)?;
+    let source = insert_in_block(&source, "match payload", &handler, true)?;
+    plan.edit(worker_rs, source, vec![handler]);
+
+    plan.step(format!("Give JobPayload::{} its fields in src/queue/job.rs", n.pascal));
+    plan.step(format!("Do the work in the JobPayload::{} arm of Worker::process_job (src/queue/worker.rs)", n.pascal));
+    plan.step(format!("Queue it: queue_manager.enqueue(tenant_id, JobPayload::{} {{ .. }})", n.pascal));
+    Ok(plan)
+}
+
+/// Numbered `.sql` / `.down.sql` pair; `create_<table>_table` gets a table skeleton
+pub fn migration(name: &str) -> Result<Plan, String> {
+    let mut plan = Plan::default();
+    migration_files(&mut plan, name)?;
+    plan.step("Write the SQL (and how to revert it in the .down.sql)");
+    plan.step("Apply it: ironclad migrate");
+    Ok(plan)
+}
+
+fn migration_files(plan: &mut Plan, name: &str) -> Result<(), String> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_') {
+        return Err(format!("Invalid migration name '{}': use snake_case", name));
+    }
+
+    let migrations = Migrator::load(Path::new(MIGRATIONS_DIR)).map_err(|e| e.to_string())?;
+    if migrations.iter().any(|m| m.name.split_once('_').map(|(_, n)| n) == Some(name)) {
+        return Err(format!("A migration named '{}' already exists", name));
+    }
+    let version = migrations.last().map_or(1, |m| m.version + 1);
+    let file = format!("{:03}_{}", version, name);
+
+    let (up, down) = match name.strip_prefix("create_").and_then(|n| n.strip_suffix("_table")) {
+        Some(table) => {
+            let render = |stub: &str| {
+                stub.replace("{{names}}", table)
+                    .replace("{{Labels}}", &capitalize(&table.replace('_', " ")))
+            };
+            (render(CREATE_TABLE_MIGRATION), render(CREATE_TABLE_MIGRATION_DOWN))
+        }
+        None => {
+            let description = capitalize(&name.replace('_', " "));
+            (MIGRATION.replace("{{Description}}", &description), MIGRATION_DOWN.replace("{{migration}}", &file))
+        }
+    };
+
+    plan.create(Path::new(MIGRATIONS_DIR).join(format!("{}.sql", file)), up);
+    plan.create(Path::new(MIGRATIONS_DIR).join(format!("{}.down.sql", file)), down);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_are_derived_from_any_spelling() {
+        for input in ["BlogPost", "blogPost", "blog_post", "blog-post"] {
+            let names = Names::parse(input).unwrap();
+            assert_eq!(names.pascal, "BlogPost");
+            assert_eq!(names.snake, "blog_post");
+            assert_eq!(names.plural, "blog_posts");
+            assert_eq!(names.label, "blog post");
+        }
+
+        assert_eq!(Names::parse("Category").unwrap().plural, "categories");
+        assert_eq!(Names::parse("Box").unwrap().plural, "boxes");
+        assert!(Names::parse("1item").is_err());
+        assert!(Names::parse("bad name").is_err());
+    }
+
+    #[test]
+    fn test_modules_and_blocks_are_extended_in_place() {
+        let source = "pub mod a;\npub mod b;\n\npub use a::A;\npub use b::B;";
+        assert_eq!(
+            register_module(source, "pub mod c;", "pub use c::C;"),
+            "pub mod a;\npub mod b;\npub mod c;\n\npub use a::A;\npub use b::B;\npub use c::C;\n"
+        );
+
+        let source = "enum E {\n    A,\n    B { x: u8 },\n}\n\nfn f() {}\n";
+        assert_eq!(
+            insert_in_block(source, "enum E", "    C,", false).unwrap(),
+            "enum E {\n    A,\n    B { x: u8 },\n    C,\n}\n\nfn f() {}\n"
+        );
+
+        let source = "match p {\n    A => {\n        x()\n    }\n    \n}\n";
+        assert_eq!(
+            insert_in_block(source, "match p", "    B => y(),", true).unwrap(),
+            "match p {\n    A => {\n        x()\n    }\n\n    B => y(),\n}\n"
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Layered lookup of configuration values.
+//!
+//! Each setting has a key in the profile file (the field path in `AppConfig`,
+//! e.g. `server.port`) and an environment variable (`SERVER_PORT`). The first
+//! one found wins, in this order:
+//!
+//! 1. environment variables
+//! 2. `.env` (never overrides a variable that is already set)
+//! 3. `config/{ENVIRONMENT}.toml` (directory overridable with `CONFIG_DIR`)
+//! 4. the default given by the caller
+//!
+//! Parse errors do not stop the lookup: they are collected and reported
+//! together by [`Sources::finish`].
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use config::{Config, File, FileFormat};
+
+/// Every problem found while loading or validating the configuration
+#[derive(Debug)]
+pub struct ConfigErrors(pub Vec<String>);
+
+impl fmt::Display for ConfigErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid configuration ({} errors):", self.0.len())?;
+        for error in &self.0 {
+            write!(f, "\n  - {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigErrors {}
+
+/// Environment variables over the profile file
+pub struct Sources {
+    vars: HashMap<String, String>,
+    file: Option<(PathBuf, Config)>,
+    errors: Vec<String>,
+}
+
+impl Sources {
+    /// Read `.env`, the process environment and the profile file of `ENVIRONMENT`
+    pub fn load() -> Self {
+        dotenv::dotenv().ok();
+
+        let mut sources = Self::new(env::vars());
+        let dir = sources.vars.get("CONFIG_DIR").cloned().unwrap_or_else(|| "config".to_string());
+        let path = Path::new(&dir).join(format!("{}.toml", sources.environment()));
+
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => sources = sources.with_file(&path, &contents),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => sources.errors.push(format!("{}: {}", path.display(), e)),
+        }
+
+        sources
+    }
+
+    /// Sources made of the given variables only
+    pub fn new<K: Into<String>, V: Into<String>>(vars: impl IntoIterator<Item = (K, V)>) -> Self {
+        Self {
+            vars: vars.into_iter().map(|(k, v)| (k.into(), v.into())).collect(),
+            file: None,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Add a TOML profile below the variables
+    pub fn with_file(mut self, path: &Path, contents: &str) -> Self {
+        match Config::builder().add_source(File::from_str(contents, FileFormat::Toml)).build() {
+            Ok(config) => self.file = Some((path.to_path_buf(), config)),
+            Err(e) => self.errors.push(format!("{}: {}", path.display(), e)),
+        }
+        self
+    }
+
+    /// Name of the profile: `ENVIRONMENT`, `development` when unset
+    pub fn environment(&self) -> String {
+        self.vars
+            .get("ENVIRONMENT")
+            .filter(|e| !e.is_empty())
+            .cloned()
+            .unwrap_or_else(|| "development".to_string())
+    }
+
+    /// Raw value of a setting, or `None` when no source sets it
+    pub fn get(&mut self, key: &str, var: &str) -> Option<String> {
+        if let Some(value) = self.vars.get(var) {
+            return Some(value.clone());
+        }
+
+        let (path, file) = self.file.as_ref()?;
+        if let Ok(value) = file.get_string(key) {
+            return Some(value);
+        }
+        match file.get_array(key) {
+            Ok(values) => {
+                let values: Result<Vec<_>, _> = values.into_iter().map(|v| v.into_string()).collect();
+                match values {
+                    Ok(values) => Some(values.join(",")),
+                    Err(e) => {
+                        self.errors.push(format!("{} in {}: {}", key, path.display(), e));
+                        None
+                    }
+                }
+            }
+            Err(_) => None,
+        }
+    }
+
+    /// String setting; empty values count as unset
+    pub fn optional(&mut self, key: &str, var: &str) -> Option<String> {
+        self.get(key, var).filter(|v| !v.trim().is_empty())
+    }
+
+    /// String setting with a default
+    pub fn string(&mut self, key: &str, var: &str, default: &str) -> String {
+        self.optional(key, var).unwrap_or_else(|| default.to_string())
+    }
+
+    /// String setting without a default; its absence is an error
+    pub fn required(&mut self, key: &str, var: &str) -> String {
+        self.optional(key, var).unwrap_or_else(|| {
+            self.error(key, var, "is required");
+            String::new()
+        })
+    }
+
+    /// Parsed setting; an invalid value is reported and replaced by the default
+    pub fn parse<T>(&mut self, key: &str, var: &str, default: T) -> T
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        match self.optional(key, var) {
+            Some(value) => match value.trim().parse() {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    self.error(key, var, &format!("has an invalid value \"{}\": {}", value, e));
+                    default
+                }
+            },
+            None => default,
+        }
+    }
+
+    /// Comma separated list (or TOML array) of parsed values, empty when unset
+    pub fn list<T>(&mut self, key: &str, var: &str) -> Vec<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let value = self.get(key, var).unwrap_or_default();
+        let mut items = Vec::new();
+        for item in value.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+            match item.parse() {
+                Ok(parsed) => items.push(parsed),
+                Err(e) => self.error(key, var, &format!("has an invalid item \"{}\": {}", item, e)),
+            }
+        }
+        items
+    }
+
+    /// Report a problem with a setting
+    pub fn error(&mut self, key: &str, var: &str, message: &str) {
+        self.errors.push(format!("{} ({}) {}", var, key, message));
+    }
+
+    /// `value` when nothing went wrong, every collected error otherwise
+    pub fn finish<T>(self, value: T) -> Result<T, ConfigErrors> {
+        if self.errors.is_empty() {
+            Ok(value)
+        } else {
+            Err(ConfigErrors(self.errors))
+        }
+    }
+
+    /// Like `finish`, adding the errors of a validation pass
+    pub fn finish_with<T>(mut self, value: T, validation: Result<(), Vec<String>>) -> Result<T, ConfigErrors> {
+        if let Err(errors) = validation {
+            self.errors.extend(errors);
+        }
+        self.finish(value)
+    }
+}
@@ -1,9 +1,12 @@
 use crate::config::AppConfig;
 
-/// Validates security configuration on startup
+/// Logs how the security settings fit the environment on startup.
+///
+/// Settings that must block startup are rejected by `validate_all_config`.
 pub fn validate_security_config(config: &AppConfig) {
     validate_bcrypt_cost(config);
     validate_jwt_config(config);
+    validate_cors_config(config);
 }
 
 /// Validate bcrypt cost based on environment
@@ -36,8 +39,6 @@ fn validate_production_bcrypt(config: &AppConfig) {
             "🔴 BCRYPT_COST={} is TOO LOW for production (minimum: 10, recommended: 12)", 
             config.bcrypt.cost
         );
-        tracing::error!("   Production deployment blocked for security reasons");
-        std::process::exit(1);  
     } else if config.bcrypt.cost >= 12 {
         tracing::info!(
             "✅ BCRYPT_COST={} is IDEAL for production", 
@@ -79,37 +80,137 @@ fn validate_development_bcrypt(config: &AppConfig) {
 /// Validate JWT configuration
 fn validate_jwt_config(config: &AppConfig) {
     if config.server.env == "production" {
-        if config.jwt.secret.len() < 32 {
-            tracing::error!("🔴 JWT_SECRET is too short for production (minimum: 32 characters)");
-            std::process::exit(1);
-        }
-        
-        if config.jwt.secret.contains("change") || config.jwt.secret.contains("secret") {
-            tracing::error!("🔴 JWT_SECRET appears to be a default value - change it!");
-            std::process::exit(1);
-        }
-        
         tracing::info!("✅ JWT configuration is secure for production");
     }
 }
 
-/// Validate all configuration settings
+/// Warn about CORS settings that open the API to every site
+fn validate_cors_config(config: &AppConfig) {
+    if config.server.env == "production" && config.cors.allows_any_origin() {
+        tracing::warn!("⚠️  CORS_ALLOWED_ORIGINS allows any origin in production");
+    }
+}
+
+/// Validate all configuration settings.
+///
+/// Returns every problem found, each naming the variable to fix.
 pub fn validate_all_config(config: &AppConfig) -> Result<(), Vec<String>> {
     let mut errors = Vec::new();
+    let production = config.server.env == "production";
 
     // Bcrypt validation
-    if config.bcrypt.cost < 4 {
-        errors.push(format!("BCRYPT_COST={} is too low (minimum: 4)", config.bcrypt.cost));
+    if !(4..=31).contains(&config.bcrypt.cost) {
+        errors.push(format!("BCRYPT_COST={} is out of range (4 to 31)", config.bcrypt.cost));
+    } else if production && config.bcrypt.cost < 10 {
+        errors.push(format!(
+            "BCRYPT_COST={} is too low for production (minimum: 10, recommended: 12)",
+            config.bcrypt.cost
+        ));
+    }
+
+    // Password hashing validation
+    if config.password.argon2_iterations == 0 || config.password.argon2_parallelism == 0 {
+        errors.push("ARGON2_ITERATIONS and ARGON2_PARALLELISM must be at least 1".to_string());
+    } else if config.password.argon2_memory_kib < 8 * config.password.argon2_parallelism {
+        errors.push("ARGON2_MEMORY_KIB must be at least 8 times ARGON2_PARALLELISM".to_string());
+    }
+
+    // JWT validation (a missing secret is already reported by the loader)
+    let secret = &config.jwt.secret;
+    if production && !secret.is_empty() {
+        if secret.len() < 32 {
+            errors.push("JWT_SECRET is too short for production (minimum: 32 characters)".to_string());
+        } else if secret.contains("change") || secret.contains("secret") {
+            errors.push("JWT_SECRET appears to be a default value for production, generate a random one".to_string());
+        }
+    }
+    if config.jwt.expiration <= 0 {
+        errors.push(format!("JWT_EXPIRATION={} must be positive", config.jwt.expiration));
+    }
+
+    // Two-factor validation
+    if config.two_factor.max_failed_attempts < 1 || config.two_factor.lockout_seconds < 1 {
+        errors.push("TWO_FACTOR_MAX_ATTEMPTS and TWO_FACTOR_LOCKOUT_SECONDS must be at least 1".to_string());
+    }
+
+    // Postgres validation
+    let url = &config.db_postgres.postgres_url;
+    if !url.is_empty() && !url.starts_with("postgres://") && !url.starts_with("postgresql://") {
+        errors.push("DATABASE_URL must start with postgres:// or postgresql://".to_string());
+    }
+    if config.db_postgres.max_connections == 0 {
+        errors.push("DB_MAX_CONNECTIONS must be at least 1".to_string());
+    } else if config.db_postgres.min_connections > config.db_postgres.max_connections {
+        errors.push(format!(
+            "DB_MIN_CONNECTIONS={} is above DB_MAX_CONNECTIONS={}",
+            config.db_postgres.min_connections, config.db_postgres.max_connections
+        ));
     }
 
-    // JWT validation
-    if config.server.env == "production" && config.jwt.secret.len() < 32 {
-        errors.push("JWT_SECRET is too short for production".to_string());
+    // Bulk validation
+    if config.bulk.sync_limit > config.bulk.max_items {
+        errors.push(format!(
+            "BULK_SYNC_LIMIT={} is above BULK_MAX_ITEMS={}",
+            config.bulk.sync_limit, config.bulk.max_items
+        ));
     }
 
-    // Postgress validation
-    if config.db_postgres.postgres_url.is_empty() {
-        errors.push("DATABASE_URL is not set".to_string());
+    // Queue validation
+    let queue = &config.queue;
+    for (name, value) in [
+        ("QUEUE_WORKERS", queue.workers as i64),
+        ("QUEUE_POLL_INTERVAL", queue.poll_interval as i64),
+        ("QUEUE_MAX_ATTEMPTS", queue.max_attempts as i64),
+        ("QUEUE_BATCH_SIZE", queue.batch_size),
+    ] {
+        if value < 1 {
+            errors.push(format!("{}={} must be at least 1", name, value));
+        }
+    }
+    if queue.backoff_base < 0 || queue.backoff_max < queue.backoff_base {
+        errors.push(format!(
+            "QUEUE_BACKOFF_BASE={} and QUEUE_BACKOFF_MAX={} must satisfy 0 <= base <= max",
+            queue.backoff_base, queue.backoff_max
+        ));
+    }
+
+    // CORS validation
+    let cors = &config.cors;
+    for origin in cors.allowed_origins.iter().filter(|origin| *origin != "*") {
+        let host = origin.strip_prefix("https://").or_else(|| origin.strip_prefix("http://"));
+        if host.is_none_or(|host| host.is_empty() || host.contains('/')) {
+            errors.push(format!(
+                "CORS_ALLOWED_ORIGINS: \"{}\" is not an origin (scheme://host[:port], no path)",
+                origin
+            ));
+        }
+    }
+    for method in &cors.allowed_methods {
+        if method.parse::<actix_web::http::Method>().is_err() {
+            errors.push(format!("CORS_ALLOWED_METHODS: \"{}\" is not an HTTP method", method));
+        }
+    }
+    for header in &cors.allowed_headers {
+        if header.parse::<actix_web::http::header::HeaderName>().is_err() {
+            errors.push(format!("CORS_ALLOWED_HEADERS: \"{}\" is not a header name", header));
+        }
+    }
+    if cors.allow_credentials && cors.allows_any_origin() {
+        errors.push("CORS_ALLOW_CREDENTIALS cannot be combined with any origin (*), list the origins".to_string());
+    }
+
+    // Rate limit validation
+    let system = config.rate_limits.system;
+    if system.seconds == 0 || system.burst == 0 {
+        errors.push("RATE_LIMIT_SYSTEM_SECONDS and RATE_LIMIT_SYSTEM_BURST must be at least 1".to_string());
+    }
+
+    // Maintenance validation
+    if config.maintenance.poll_interval == 0 {
+        errors.push("MAINTENANCE_POLL_INTERVAL must be at least 1".to_string());
+    }
+    if config.maintenance.bypass_ttl == 0 {
+        errors.push("MAINTENANCE_BYPASS_TTL must be at least 1".to_string());
     }
 
     // Mysql MariaDB validation TODO
@@ -122,4 +223,4 @@ pub fn validate_all_config(config: &AppConfig) -> Result<(), Vec<String>> {
     } else {
         Err(errors)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,71 @@
+//! Small generators of fake data for the factories
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const FIRST_NAMES: &[&str] = &[
+    "Alice", "Bruno", "Carmen", "Daniel", "Elena", "Farid", "Grace", "Hugo", "Irene", "Jonas",
+    "Kenji", "Laura", "Mateo", "Nadia", "Oscar", "Paula", "Rafael", "Sofia", "Tomas", "Valeria",
+];
+
+const LAST_NAMES: &[&str] = &[
+    "Alvarez", "Becker", "Castro", "Dubois", "Evans", "Fischer", "Garcia", "Hansen", "Ito", "Jensen",
+    "Kowalski", "Lopez", "Martin", "Novak", "Okafor", "Perez", "Rossi", "Silva", "Tanaka", "Weber",
+];
+
+const DOMAINS: &[&str] = &["example.com", "example.org", "example.net"];
+
+const VERBS: &[&str] = &[
+    "Review", "Update", "Prepare", "Archive", "Schedule", "Draft", "Check", "Plan", "Fix", "Publish",
+];
+
+const ADJECTIVES: &[&str] = &[
+    "quarterly", "pending", "annual", "urgent", "shared", "weekly", "internal", "draft", "final", "new",
+];
+
+const NOUNS: &[&str] = &[
+    "report", "invoice", "budget", "roadmap", "release", "contract", "meeting", "backlog", "survey", "newsletter",
+];
+
+const WORDS: &[&str] = &[
+    "lorem", "ipsum", "dolor", "sit", "amet", "consectetur", "adipiscing", "elit", "sed", "do",
+    "eiusmod", "tempor", "incididunt", "ut", "labore", "et", "dolore", "magna", "aliqua", "enim",
+];
+
+fn pick(values: &[&'static str]) -> &'static str {
+    values.choose(&mut rand::thread_rng()).copied().unwrap_or_default()
+}
+
+/// `(first, last)` name pair
+pub fn name() -> (&'static str, &'static str) {
+    (pick(FIRST_NAMES), pick(LAST_NAMES))
+}
+
+pub fn domain() -> &'static str {
+    pick(DOMAINS)
+}
+
+/// Six hex characters keeping generated emails and usernames unique
+pub fn suffix() -> String {
+    format!("{:06x}", rand::thread_rng().gen_range(0..0x100_0000))
+}
+
+/// Short task-like title ("Review quarterly report")
+pub fn title() -> String {
+    format!("{} {} {}", pick(VERBS), pick(ADJECTIVES), pick(NOUNS))
+}
+
+/// Sentence of 6 to 12 lorem ipsum words
+pub fn sentence() -> String {
+    let count = rand::thread_rng().gen_range(6..=12);
+    let words: Vec<&str> = (0..count).map(|_| pick(WORDS)).collect();
+    let mut sentence = words.join(" ");
+    sentence[..1].make_ascii_uppercase();
+    sentence.push('.');
+    sentence
+}
+
+/// `true` with the given probability
+pub fn chance(probability: f64) -> bool {
+    rand::thread_rng().gen_bool(probability)
+}
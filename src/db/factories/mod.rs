@@ -0,0 +1,16 @@
+//! Model factories: valid entities filled with fake but realistic data.
+//!
+//! Entities go through their smart constructors, and every attribute can be
+//! overridden per call:
+//!
+//! ```text
+//! let admin = UserFactory::new(&tenant).role(Role::Admin).create(&repo).await?;
+//! let items = TestItemFactory::new(&tenant).subject("Pinned").make_many(3);
+//! ```
+
+mod fake;
+mod test_item_factory;
+mod user_factory;
+
+pub use test_item_factory::TestItemFactory;
+pub use user_factory::UserFactory;
@@ -0,0 +1,58 @@
+use crate::domain::entities::TestItem;
+use crate::errors::ApiError;
+use crate::interfaces::repositories::TestItemRepository;
+use super::fake;
+
+/// Builds test items with a task-like subject and, half of the time, a note
+#[derive(Debug, Clone)]
+pub struct TestItemFactory {
+    tenant_id: String,
+    subject: Option<String>,
+    /// `Some(None)` forces an item without note
+    optional_field: Option<Option<String>>,
+}
+
+impl TestItemFactory {
+    pub fn new(tenant_id: impl Into<String>) -> Self {
+        Self {
+            tenant_id: tenant_id.into(),
+            subject: None,
+            optional_field: None,
+        }
+    }
+
+    pub fn subject(mut self, subject: impl Into<String>) -> Self {
+        self.subject = Some(subject.into());
+        self
+    }
+
+    pub fn optional_field(mut self, optional_field: Option<String>) -> Self {
+        self.optional_field = Some(optional_field);
+        self
+    }
+
+    /// Build an item without storing it
+    pub fn make(&self) -> TestItem {
+        let subject = self.subject.clone().unwrap_or_else(fake::title);
+        let optional_field = self
+            .optional_field
+            .clone()
+            .unwrap_or_else(|| fake::chance(0.5).then(fake::sentence));
+
+        TestItem::new(self.tenant_id.clone(), subject, optional_field)
+    }
+
+    pub fn make_many(&self, count: usize) -> Vec<TestItem> {
+        (0..count).map(|_| self.make()).collect()
+    }
+
+    /// Build an item and store it
+    pub async fn create(&self, repo: &dyn TestItemRepository) -> Result<TestItem, ApiError> {
+        repo.create(&self.make()).await
+    }
+
+    /// Build items and store them in one statement
+    pub async fn create_many(&self, repo: &dyn TestItemRepository, count: usize) -> Result<Vec<TestItem>, ApiError> {
+        repo.create_many(&self.make_many(count)).await
+    }
+}
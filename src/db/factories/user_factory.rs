@@ -0,0 +1,163 @@
+use std::sync::OnceLock;
+
+use crate::domain::entities::User;
+use crate::domain::value_objects::{EmailAddress, Role, Username};
+use crate::errors::{ApiError, DomainError};
+use crate::interfaces::repositories::UserRepository;
+use super::fake;
+
+/// Builds users with a fake name, email and username.
+///
+/// Overrides go through `EmailAddress::new` and `Username::new`, so a value that
+/// breaks a domain rule fails here as it would in the application.
+#[derive(Debug, Clone)]
+pub struct UserFactory {
+    tenant_id: String,
+    email: Option<String>,
+    username: Option<String>,
+    password_hash: Option<String>,
+    role: Role,
+    is_active: bool,
+}
+
+impl UserFactory {
+    /// Password of every factory user unless `password_hash` is overridden
+    pub const PASSWORD: &'static str = "password";
+
+    pub fn new(tenant_id: impl Into<String>) -> Self {
+        Self {
+            tenant_id: tenant_id.into(),
+            email: None,
+            username: None,
+            password_hash: None,
+            role: Role::default(),
+            is_active: true,
+        }
+    }
+
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    pub fn username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    pub fn password_hash(mut self, password_hash: impl Into<String>) -> Self {
+        self.password_hash = Some(password_hash.into());
+        self
+    }
+
+    pub fn role(mut self, role: Role) -> Self {
+        self.role = role;
+        self
+    }
+
+    pub fn inactive(mut self) -> Self {
+        self.is_active = false;
+        self
+    }
+
+    /// Build a user without storing it
+    pub fn make(&self) -> Result<User, DomainError> {
+        let (first, last) = fake::name();
+        let suffix = fake::suffix();
+
+        let email = self.email.clone().unwrap_or_else(|| {
+            format!("{}.{}.{}@{}", first, last, suffix, fake::domain()).to_lowercase()
+        });
+        let username = self
+            .username
+            .clone()
+            .unwrap_or_else(|| format!("{}_{}_{}", first, last, suffix).to_lowercase());
+
+        let mut user = User::new_with_role(
+            self.tenant_id.clone(),
+            EmailAddress::new(email)?,
+            Username::new(username)?,
+            self.password_hash.clone().unwrap_or_else(|| default_password_hash().to_string()),
+            self.role.clone(),
+        )?;
+        if !self.is_active {
+            user.deactivate();
+        }
+        Ok(user)
+    }
+
+    pub fn make_many(&self, count: usize) -> Result<Vec<User>, DomainError> {
+        (0..count).map(|_| self.make()).collect()
+    }
+
+    /// Build a user and store it
+    pub async fn create(&self, repo: &dyn UserRepository) -> Result<User, ApiError> {
+        let user = self.make()?;
+        repo.create(&user).await
+    }
+
+    pub async fn create_many(&self, repo: &dyn UserRepository, count: usize) -> Result<Vec<User>, ApiError> {
+        let mut users = Vec::with_capacity(count);
+        for _ in 0..count {
+            users.push(self.create(repo).await?);
+        }
+        Ok(users)
+    }
+}
+
+/// bcrypt hash of `PASSWORD` at the lowest cost, computed once: factories make many
+/// users and the login upgrades the hash to the configured algorithm anyway
+fn default_password_hash() -> &'static str {
+    static HASH: OnceLock<String> = OnceLock::new();
+    HASH.get_or_init(|| bcrypt::hash(UserFactory::PASSWORD, 4).expect("bcrypt cost 4 is valid"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::DEFAULT_TENANT_ID;
+
+    #[test]
+    fn test_made_users_are_unique_and_overridable() {
+        let users = UserFactory::new("tenant").make_many(2).unwrap();
+        assert_ne!(users[0].email.as_str(), users[1].email.as_str());
+        assert_ne!(users[0].username.as_str(), users[1].username.as_str());
+        assert!(users.iter().all(|u| u.tenant_id == "tenant" && u.is_active && u.role == Role::User));
+        assert!(bcrypt::verify(UserFactory::PASSWORD, &users[0].password_hash).unwrap());
+
+        let admin = UserFactory::new("tenant")
+            .email("root@example.com")
+            .role(Role::Admin)
+            .inactive()
+            .make()
+            .unwrap();
+        assert_eq!(admin.email.as_str(), "root@example.com");
+        assert!(admin.is_admin() && !admin.is_active);
+
+        assert!(UserFactory::new("tenant").email("not-an-email").make().is_err());
+        assert!(UserFactory::new("tenant").username("no spaces").make().is_err());
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+    async fn test_created_users_are_stored_in_their_tenant() {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        let pool = sqlx::PgPool::connect(&url).await.unwrap();
+        let tenant = uuid::Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO tenants (id, slug, name) VALUES ($1, $1, $1)")
+            .bind(&tenant)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let repo = crate::infrastructure::persistence::PostgresUserRepository::new(pool);
+
+        let users = UserFactory::new(&tenant).create_many(&repo, 3).await.unwrap();
+        let admin = UserFactory::new(&tenant).role(Role::Admin).create(&repo).await.unwrap();
+
+        assert_eq!(repo.get_all(&tenant).await.unwrap().len(), 4);
+        let stored = repo.get_by_email(&tenant, admin.email.as_str()).await.unwrap().unwrap();
+        assert_eq!(stored.id, admin.id);
+        assert!(stored.is_admin());
+        assert!(repo.get_by_id(DEFAULT_TENANT_ID, &users[0].id).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,494 @@
+//! SQL migrations runner.
+//!
+//! Migrations are `migrations/NNN_name.sql` files, optionally paired with a
+//! `NNN_name.down.sql` that reverts them. Applied migrations are recorded in
+//! `schema_migrations` with the SHA-256 of their file, so a migration edited after
+//! it ran is reported instead of silently diverging from the database.
+//!
+//! Shared by the server (pending check at boot) and the `ironclad migrate*` commands.
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::pool::PoolConnection;
+use sqlx::{Connection, Executor, PgPool, Postgres};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Default migrations directory, relative to the project root
+pub const MIGRATIONS_DIR: &str = "migrations";
+
+/// Key of the advisory lock held while migrating, so two deployments do not migrate at once
+const LOCK_KEY: i64 = 0x6972_6f6e_636c_6164;
+
+#[derive(Debug, thiserror::Error)]
+pub enum MigrationError {
+    #[error("Failed to read migrations: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid migration file name: {0} (expected NNN_name.sql)")]
+    InvalidName(String),
+
+    #[error("Duplicate migration version {0}")]
+    Duplicate(i64),
+
+    #[error("Migration {0} was changed after it was applied")]
+    Modified(String),
+
+    #[error("Migration {0} was applied but its file is missing")]
+    Missing(String),
+
+    #[error("{0} migration(s) pending, run `ironclad migrate`")]
+    Pending(usize),
+
+    #[error("Migration {0} has no down file")]
+    Irreversible(String),
+
+    #[error("Migration {name} failed: {source}")]
+    Failed { name: String, source: sqlx::Error },
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// A migration file (and its down file, if any)
+#[derive(Debug, Clone)]
+pub struct Migration {
+    pub version: i64,
+    /// File name without extension (`004_create_jobs_table`)
+    pub name: String,
+    pub up: String,
+    pub down: Option<String>,
+    /// Hex SHA-256 of the up file
+    pub checksum: String,
+}
+
+/// A row of `schema_migrations`
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub name: String,
+    pub checksum: String,
+    /// Migrations applied by the same `migrate` run share a batch; rollback undoes the last one
+    pub batch: i32,
+    pub applied_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    Pending,
+    Applied,
+    /// Applied, but the file changed since
+    Modified,
+    /// Applied, but the file is gone
+    Missing,
+}
+
+/// One line of `migrate:status`
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub name: String,
+    pub state: MigrationState,
+    pub batch: Option<i32>,
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+pub struct Migrator {
+    pool: PgPool,
+    migrations: Vec<Migration>,
+}
+
+impl Migrator {
+    /// Load the migrations of `dir`
+    pub fn new(pool: PgPool, dir: impl AsRef<Path>) -> Result<Self, MigrationError> {
+        Ok(Self {
+            pool,
+            migrations: Self::load(dir.as_ref())?,
+        })
+    }
+
+    /// Read `NNN_name.sql` / `NNN_name.down.sql` files, ordered by version
+    pub fn load(dir: &Path) -> Result<Vec<Migration>, MigrationError> {
+        let mut ups = Vec::new();
+        let mut downs = HashMap::new();
+
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+
+            if let Some(name) = file_name.strip_suffix(".down.sql") {
+                downs.insert(name.to_string(), fs::read_to_string(&path)?);
+            } else if let Some(name) = file_name.strip_suffix(".sql") {
+                let version = parse_version(name)
+                    .ok_or_else(|| MigrationError::InvalidName(file_name.to_string()))?;
+                ups.push((version, name.to_string(), fs::read_to_string(&path)?));
+            }
+        }
+
+        ups.sort_by_key(|(version, _, _)| *version);
+        if let Some(pair) = ups.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return Err(MigrationError::Duplicate(pair[0].0));
+        }
+
+        Ok(ups
+            .into_iter()
+            .map(|(version, name, up)| Migration {
+                version,
+                down: downs.remove(&name),
+                checksum: checksum(&up),
+                name,
+                up,
+            })
+            .collect())
+    }
+
+    /// Every migration file and every recorded migration, by version
+    pub async fn status(&self) -> Result<Vec<MigrationStatus>, MigrationError> {
+        let mut applied: HashMap<i64, AppliedMigration> = self
+            .applied()
+            .await?
+            .into_iter()
+            .map(|m| (m.version, m))
+            .collect();
+
+        let mut status: Vec<MigrationStatus> = self
+            .migrations
+            .iter()
+            .map(|migration| match applied.remove(&migration.version) {
+                Some(row) => MigrationStatus {
+                    version: migration.version,
+                    name: migration.name.clone(),
+                    state: if row.checksum == migration.checksum {
+                        MigrationState::Applied
+                    } else {
+                        MigrationState::Modified
+                    },
+                    batch: Some(row.batch),
+                    applied_at: Some(row.applied_at),
+                },
+                None => MigrationStatus {
+                    version: migration.version,
+                    name: migration.name.clone(),
+                    state: MigrationState::Pending,
+                    batch: None,
+                    applied_at: None,
+                },
+            })
+            .collect();
+
+        status.extend(applied.into_values().map(|row| MigrationStatus {
+            version: row.version,
+            name: row.name,
+            state: MigrationState::Missing,
+            batch: Some(row.batch),
+            applied_at: Some(row.applied_at),
+        }));
+        status.sort_by_key(|s| s.version);
+
+        Ok(status)
+    }
+
+    /// Fails if a migration is pending, or was changed or removed after it ran
+    pub async fn check(&self) -> Result<(), MigrationError> {
+        let status = self.status().await?;
+        verify(&status)?;
+
+        match status.iter().filter(|s| s.state == MigrationState::Pending).count() {
+            0 => Ok(()),
+            pending => Err(MigrationError::Pending(pending)),
+        }
+    }
+
+    /// Apply the pending migrations in one batch, each in its own transaction.
+    /// Refuses to run while an applied migration was changed or removed.
+    pub async fn run(&self) -> Result<Vec<&Migration>, MigrationError> {
+        let mut conn = self.lock().await?;
+        let result = self.run_locked(&mut conn).await;
+        unlock(&mut conn).await?;
+        result
+    }
+
+    async fn run_locked(&self, conn: &mut PoolConnection<Postgres>) -> Result<Vec<&Migration>, MigrationError> {
+        let status = self.status().await?;
+        verify(&status)?;
+
+        let batch = next_batch(conn).await?;
+        let mut applied = Vec::new();
+
+        for migration in self.pending(&status) {
+            let mut tx = conn.begin().await?;
+            tx.execute(migration.up.as_str())
+                .await
+                .map_err(|source| MigrationError::Failed { name: migration.name.clone(), source })?;
+            record(&mut tx, migration, batch).await?;
+            tx.commit().await?;
+
+            applied.push(migration);
+        }
+
+        Ok(applied)
+    }
+
+    /// Revert the last batch, or the last `steps` migrations, newest first.
+    /// Every one of them needs a down file; nothing is reverted otherwise.
+    pub async fn rollback(&self, steps: Option<usize>) -> Result<Vec<&Migration>, MigrationError> {
+        let mut conn = self.lock().await?;
+        let result = self.rollback_locked(&mut conn, steps).await;
+        unlock(&mut conn).await?;
+        result
+    }
+
+    async fn rollback_locked(
+        &self,
+        conn: &mut PoolConnection<Postgres>,
+        steps: Option<usize>,
+    ) -> Result<Vec<&Migration>, MigrationError> {
+        let status = self.status().await?;
+        verify(&status)?;
+
+        let mut applied: Vec<&MigrationStatus> = status.iter().filter(|s| s.batch.is_some()).collect();
+        applied.sort_by_key(|s| std::cmp::Reverse((s.batch, s.version)));
+
+        let targets: Vec<&MigrationStatus> = match steps {
+            Some(steps) => applied.into_iter().take(steps).collect(),
+            None => {
+                let last = applied.first().and_then(|s| s.batch);
+                applied.into_iter().take_while(|s| s.batch == last).collect()
+            }
+        };
+
+        let mut migrations = Vec::new();
+        for target in targets {
+            let migration = self
+                .migrations
+                .iter()
+                .find(|m| m.version == target.version)
+                .ok_or_else(|| MigrationError::Missing(target.name.clone()))?;
+            if migration.down.is_none() {
+                return Err(MigrationError::Irreversible(migration.name.clone()));
+            }
+            migrations.push(migration);
+        }
+
+        for migration in &migrations {
+            let mut tx = conn.begin().await?;
+            tx.execute(migration.down.as_deref().unwrap_or_default())
+                .await
+                .map_err(|source| MigrationError::Failed { name: migration.name.clone(), source })?;
+            sqlx::query("DELETE FROM schema_migrations WHERE version = $1")
+                .bind(migration.version)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+        }
+
+        Ok(migrations)
+    }
+
+    /// Drop every table of the current schema, then run all migrations
+    pub async fn fresh(&self) -> Result<Vec<&Migration>, MigrationError> {
+        let mut conn = self.lock().await?;
+        let result = async {
+            let tables: Vec<String> =
+                sqlx::query_scalar("SELECT tablename::text FROM pg_tables WHERE schemaname = current_schema()")
+                    .fetch_all(&mut *conn)
+                    .await?;
+
+            if !tables.is_empty() {
+                let list: Vec<String> = tables.iter().map(|t| format!("\"{}\"", t.replace('"', "\"\""))).collect();
+                conn.execute(format!("DROP TABLE IF EXISTS {} CASCADE", list.join(", ")).as_str())
+                    .await?;
+            }
+            create_table(&mut conn).await?;
+
+            self.run_locked(&mut conn).await
+        }
+        .await;
+        unlock(&mut conn).await?;
+        result
+    }
+
+    /// Record the migrations up to `version` as applied without running them,
+    /// for a database whose schema was created by hand
+    pub async fn baseline(&self, version: i64) -> Result<Vec<&Migration>, MigrationError> {
+        let mut conn = self.lock().await?;
+        let result = async {
+            let status = self.status().await?;
+            let batch = next_batch(&mut conn).await?;
+            let mut recorded = Vec::new();
+
+            for migration in self.pending(&status).into_iter().filter(|m| m.version <= version) {
+                record(&mut conn, migration, batch).await?;
+                recorded.push(migration);
+            }
+
+            Ok(recorded)
+        }
+        .await;
+        unlock(&mut conn).await?;
+        result
+    }
+
+    fn pending(&self, status: &[MigrationStatus]) -> Vec<&Migration> {
+        self.migrations
+            .iter()
+            .filter(|m| {
+                status
+                    .iter()
+                    .any(|s| s.version == m.version && s.state == MigrationState::Pending)
+            })
+            .collect()
+    }
+
+    /// Recorded migrations (none while `schema_migrations` does not exist yet)
+    async fn applied(&self) -> Result<Vec<AppliedMigration>, MigrationError> {
+        let exists: bool = sqlx::query_scalar("SELECT to_regclass('schema_migrations') IS NOT NULL")
+            .fetch_one(&self.pool)
+            .await?;
+        if !exists {
+            return Ok(Vec::new());
+        }
+
+        Ok(sqlx::query_as::<_, AppliedMigration>(
+            "SELECT version, name, checksum, batch, applied_at FROM schema_migrations ORDER BY version",
+        )
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Connection holding the migration lock, with `schema_migrations` created
+    async fn lock(&self) -> Result<PoolConnection<Postgres>, MigrationError> {
+        let mut conn = self.pool.acquire().await?;
+        sqlx::query("SELECT pg_advisory_lock($1)")
+            .bind(LOCK_KEY)
+            .execute(&mut *conn)
+            .await?;
+
+        // The lock belongs to the session, not the call: release it before the
+        // connection goes back to the pool, or close the session if that fails
+        if let Err(e) = create_table(&mut conn).await {
+            if unlock(&mut conn).await.is_err() {
+                drop(conn.detach());
+            }
+            return Err(e);
+        }
+
+        Ok(conn)
+    }
+}
+
+async fn create_table(conn: &mut PoolConnection<Postgres>) -> Result<(), MigrationError> {
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS schema_migrations (
+            version BIGINT PRIMARY KEY,
+            name TEXT NOT NULL,
+            checksum TEXT NOT NULL,
+            batch INTEGER NOT NULL,
+            applied_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+        )
+        "#,
+    )
+    .await?;
+    Ok(())
+}
+
+async fn unlock(conn: &mut PoolConnection<Postgres>) -> Result<(), MigrationError> {
+    sqlx::query("SELECT pg_advisory_unlock($1)")
+        .bind(LOCK_KEY)
+        .execute(&mut **conn)
+        .await?;
+    Ok(())
+}
+
+async fn next_batch(conn: &mut PoolConnection<Postgres>) -> Result<i32, MigrationError> {
+    let batch: i32 = sqlx::query_scalar("SELECT COALESCE(MAX(batch), 0) + 1 FROM schema_migrations")
+        .fetch_one(&mut **conn)
+        .await?;
+    Ok(batch)
+}
+
+async fn record(conn: &mut sqlx::PgConnection, migration: &Migration, batch: i32) -> Result<(), MigrationError> {
+    sqlx::query("INSERT INTO schema_migrations (version, name, checksum, batch) VALUES ($1, $2, $3, $4)")
+        .bind(migration.version)
+        .bind(&migration.name)
+        .bind(&migration.checksum)
+        .bind(batch)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+/// Fails on the first changed or removed migration
+fn verify(status: &[MigrationStatus]) -> Result<(), MigrationError> {
+    for s in status {
+        match s.state {
+            MigrationState::Modified => return Err(MigrationError::Modified(s.name.clone())),
+            MigrationState::Missing => return Err(MigrationError::Missing(s.name.clone())),
+            MigrationState::Pending | MigrationState::Applied => {}
+        }
+    }
+    Ok(())
+}
+
+/// Leading digits of a migration name (`004_create_jobs_table` -> 4)
+fn parse_version(name: &str) -> Option<i64> {
+    let (version, rest) = name.split_once('_')?;
+    if rest.is_empty() || !version.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    version.parse().ok()
+}
+
+fn checksum(sql: &str) -> String {
+    hex::encode(Sha256::digest(sql.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migration_versions_come_from_the_file_name() {
+        assert_eq!(parse_version("004_create_jobs_table"), Some(4));
+        assert_eq!(parse_version("20240101120000_add_index"), Some(20240101120000));
+        assert_eq!(parse_version("create_jobs_table"), None);
+        assert_eq!(parse_version("004_"), None);
+    }
+
+    #[test]
+    fn test_repository_migrations_load_in_order_with_down_files() {
+        let migrations = Migrator::load(Path::new(MIGRATIONS_DIR)).unwrap();
+
+        assert!(migrations.windows(2).all(|pair| pair[0].version < pair[1].version));
+        assert!(migrations.iter().all(|m| m.down.is_some()));
+        assert_eq!(migrations[3].name, "004_create_jobs_table");
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+    async fn test_lock_is_released_when_the_table_cannot_be_created() {
+        use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+        use std::str::FromStr;
+
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        let read_only = PgConnectOptions::from_str(&url)
+            .unwrap()
+            .options([("default_transaction_read_only", "on")]);
+        let pool = PgPoolOptions::new().max_connections(1).connect_with(read_only).await.unwrap();
+        let migrator = Migrator::new(pool, MIGRATIONS_DIR).unwrap();
+
+        assert!(migrator.lock().await.is_err());
+
+        let other = PgPool::connect(&url).await.unwrap();
+        let acquired: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+            .bind(LOCK_KEY)
+            .fetch_one(&other)
+            .await
+            .unwrap();
+        assert!(acquired);
+    }
+}
@@ -1,3 +1,6 @@
 pub mod postgres;
 pub mod mongo;
-pub mod mysql;
\ No newline at end of file
+pub mod mysql;
+pub mod migrator;
+pub mod factories;
+pub mod seeders;
\ No newline at end of file
@@ -0,0 +1,50 @@
+//! Database seeders, run with `ironclad db:seed [--class <name>]`.
+//!
+//! A seeder fills the database through the repositories and factories, so seeded
+//! rows go through the same constructors as the application's. To add one,
+//! implement `Seeder` and list it in `all()` (and in `DatabaseSeeder` if it should
+//! run by default).
+
+mod test_item_seeder;
+mod user_seeder;
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::errors::ApiError;
+
+pub use test_item_seeder::TestItemSeeder;
+pub use user_seeder::UserSeeder;
+
+#[async_trait]
+pub trait Seeder: Send + Sync {
+    /// Name given to `--class`
+    fn name(&self) -> &'static str;
+
+    async fn run(&self, pool: &PgPool) -> Result<(), ApiError>;
+}
+
+/// Default seeder: runs the others in order
+pub struct DatabaseSeeder;
+
+#[async_trait]
+impl Seeder for DatabaseSeeder {
+    fn name(&self) -> &'static str {
+        "DatabaseSeeder"
+    }
+
+    async fn run(&self, pool: &PgPool) -> Result<(), ApiError> {
+        UserSeeder.run(pool).await?;
+        TestItemSeeder.run(pool).await
+    }
+}
+
+/// Every seeder that can be picked with `--class`
+pub fn all() -> Vec<Box<dyn Seeder>> {
+    vec![Box::new(DatabaseSeeder), Box::new(UserSeeder), Box::new(TestItemSeeder)]
+}
+
+/// Seeder by name, ignoring case
+pub fn find(name: &str) -> Option<Box<dyn Seeder>> {
+    all().into_iter().find(|seeder| seeder.name().eq_ignore_ascii_case(name))
+}
@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::db::factories::TestItemFactory;
+use crate::domain::entities::DEFAULT_TENANT_ID;
+use crate::errors::ApiError;
+use crate::infrastructure::persistence::PostgresTestItemRepository;
+use super::Seeder;
+
+/// 25 test items in the default tenant
+pub struct TestItemSeeder;
+
+#[async_trait]
+impl Seeder for TestItemSeeder {
+    fn name(&self) -> &'static str {
+        "TestItemSeeder"
+    }
+
+    async fn run(&self, pool: &PgPool) -> Result<(), ApiError> {
+        let repo = PostgresTestItemRepository::new(pool.clone());
+        TestItemFactory::new(DEFAULT_TENANT_ID).create_many(&repo, 25).await?;
+        Ok(())
+    }
+}
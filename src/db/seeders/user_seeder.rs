@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::db::factories::UserFactory;
+use crate::domain::entities::DEFAULT_TENANT_ID;
+use crate::domain::value_objects::Role;
+use crate::errors::ApiError;
+use crate::infrastructure::persistence::PostgresUserRepository;
+use crate::interfaces::repositories::UserRepository;
+use super::Seeder;
+
+/// An admin (`admin@example.com`, created once) and ten users in the default tenant,
+/// all with the factory password
+pub struct UserSeeder;
+
+impl UserSeeder {
+    pub const ADMIN_EMAIL: &'static str = "admin@example.com";
+}
+
+#[async_trait]
+impl Seeder for UserSeeder {
+    fn name(&self) -> &'static str {
+        "UserSeeder"
+    }
+
+    async fn run(&self, pool: &PgPool) -> Result<(), ApiError> {
+        let repo = PostgresUserRepository::new(pool.clone());
+
+        if repo.get_by_email(DEFAULT_TENANT_ID, Self::ADMIN_EMAIL).await?.is_none() {
+            UserFactory::new(DEFAULT_TENANT_ID)
+                .email(Self::ADMIN_EMAIL)
+                .username("admin")
+                .role(Role::Admin)
+                .create(&repo)
+                .await?;
+        }
+
+        UserFactory::new(DEFAULT_TENANT_ID).create_many(&repo, 10).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use crate::domain::value_objects::Permission;
+
+/// Named, scoped API key bound to a user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub id: String,
+    pub tenant_id: String,
+    pub user_id: String,
+    pub name: String,
+    /// Public part of the key (`ick_<prefix>_...`)
+    pub prefix: String,
+    /// SHA-256 of the full key
+    pub key_hash: String,
+    /// Permissions the key may exercise (intersected with the owner's role)
+    pub scopes: Vec<Permission>,
+    /// Created from a session that completed the second factor; only such keys
+    /// pass the `TWO_FACTOR_REQUIRED_ROLES` check
+    pub created_with_mfa: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiKey {
+    pub fn new(
+        tenant_id: String,
+        user_id: String,
+        name: String,
+        prefix: String,
+        key_hash: String,
+        scopes: Vec<Permission>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            tenant_id,
+            user_id,
+            name,
+            prefix,
+            key_hash,
+            scopes,
+            created_with_mfa: false,
+            expires_at,
+            last_used_at: None,
+            revoked_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Usable = not revoked and not expired
+    pub fn is_usable(&self, now: DateTime<Utc>) -> bool {
+        !self.is_revoked() && !self.is_expired(now)
+    }
+
+    /// Convert to response DTO (never includes the hash)
+    pub fn to_response(&self) -> crate::application::dtos::ApiKeyResponse {
+        crate::application::dtos::ApiKeyResponse {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            prefix: self.prefix.clone(),
+            scopes: self.scopes.iter().map(|s| s.as_str().to_string()).collect(),
+            created_with_mfa: self.created_with_mfa,
+            expires_at: self.expires_at.map(|t| t.to_rfc3339()),
+            last_used_at: self.last_used_at.map(|t| t.to_rfc3339()),
+            revoked_at: self.revoked_at.map(|t| t.to_rfc3339()),
+            created_at: self.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl sqlx::FromRow<'_, PgRow> for ApiKey {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let scopes: Vec<String> = row.try_get("scopes")?;
+
+        Ok(ApiKey {
+            id: row.try_get("id")?,
+            tenant_id: row.try_get("tenant_id")?,
+            user_id: row.try_get("user_id")?,
+            name: row.try_get("name")?,
+            prefix: row.try_get("prefix")?,
+            key_hash: row.try_get("key_hash")?,
+            scopes: scopes.into_iter().map(Permission::from_trusted).collect(),
+            created_with_mfa: row.try_get("created_with_mfa")?,
+            expires_at: row.try_get("expires_at")?,
+            last_used_at: row.try_get("last_used_at")?,
+            revoked_at: row.try_get("revoked_at")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
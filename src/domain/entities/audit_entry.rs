@@ -0,0 +1,123 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use crate::domain::entities::user::Claims;
+
+/// Where a request came from (client IP and user agent)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RequestOrigin {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+/// One append-only audit log record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: String,
+    /// `None` when the action could not be tied to a tenant
+    pub tenant_id: Option<String>,
+    /// `None` for anonymous actions (failed logins)
+    pub actor_id: Option<String>,
+    /// Admin behind an impersonation token
+    pub impersonator_id: Option<String>,
+    /// Dotted action name, e.g. `user.role_changed`
+    pub action: String,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    /// Structured diff or details
+    pub changes: Option<Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditEntry {
+    pub fn new(action: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            tenant_id: None,
+            actor_id: None,
+            impersonator_id: None,
+            action: action.into(),
+            target_type: None,
+            target_id: None,
+            ip_address: None,
+            user_agent: None,
+            changes: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Attribute the action to the token holder (and the impersonating admin, if any)
+    pub fn actor(mut self, claims: &Claims) -> Self {
+        self.tenant_id = Some(claims.tenant_id.clone());
+        self.actor_id = Some(claims.sub.clone());
+        self.impersonator_id = claims.impersonator.clone();
+        self
+    }
+
+    pub fn tenant(mut self, tenant_id: impl Into<String>) -> Self {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    pub fn actor_id(mut self, actor_id: impl Into<String>) -> Self {
+        self.actor_id = Some(actor_id.into());
+        self
+    }
+
+    pub fn target(mut self, target_type: impl Into<String>, target_id: impl Into<String>) -> Self {
+        self.target_type = Some(target_type.into());
+        self.target_id = Some(target_id.into());
+        self
+    }
+
+    pub fn changes(mut self, changes: Value) -> Self {
+        self.changes = Some(changes);
+        self
+    }
+
+    pub fn origin(mut self, origin: &RequestOrigin) -> Self {
+        self.ip_address = origin.ip_address.clone();
+        self.user_agent = origin.user_agent.clone();
+        self
+    }
+
+    /// Convert to response DTO
+    pub fn to_response(&self) -> crate::application::dtos::AuditEntryResponse {
+        crate::application::dtos::AuditEntryResponse {
+            id: self.id.clone(),
+            tenant_id: self.tenant_id.clone(),
+            actor_id: self.actor_id.clone(),
+            impersonator_id: self.impersonator_id.clone(),
+            action: self.action.clone(),
+            target_type: self.target_type.clone(),
+            target_id: self.target_id.clone(),
+            ip_address: self.ip_address.clone(),
+            user_agent: self.user_agent.clone(),
+            changes: self.changes.clone(),
+            created_at: self.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl sqlx::FromRow<'_, PgRow> for AuditEntry {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(AuditEntry {
+            id: row.try_get("id")?,
+            tenant_id: row.try_get("tenant_id")?,
+            actor_id: row.try_get("actor_id")?,
+            impersonator_id: row.try_get("impersonator_id")?,
+            action: row.try_get("action")?,
+            target_type: row.try_get("target_type")?,
+            target_id: row.try_get("target_id")?,
+            ip_address: row.try_get("ip_address")?,
+            user_agent: row.try_get("user_agent")?,
+            changes: row.try_get("changes")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
@@ -0,0 +1,170 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+
+/// File format of exports and imports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DataFormat {
+    Csv,
+    /// One JSON object per line
+    Ndjson,
+}
+
+impl DataFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "ndjson" | "jsonl" => Some(Self::Ndjson),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Ndjson => "ndjson",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Ndjson => "application/x-ndjson",
+        }
+    }
+}
+
+/// Lifecycle of an import: `pending` until the job picks it up, then `running`,
+/// then `completed` (possibly with rejected rows) or `failed` (the file could not be applied)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl ImportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+        }
+    }
+
+    fn from_trusted(s: &str) -> Self {
+        match s {
+            "running" => Self::Running,
+            "completed" => Self::Completed,
+            "failed" => Self::Failed,
+            _ => Self::Pending,
+        }
+    }
+}
+
+/// An uploaded file being applied to `entity` (`test_items`, `users`) by a queued job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataImport {
+    pub id: String,
+    pub tenant_id: String,
+    pub entity: String,
+    pub format: DataFormat,
+    pub status: ImportStatus,
+    /// Uploaded file, under the import directory
+    pub file_path: String,
+    pub processed_rows: i32,
+    pub failed_rows: i32,
+    /// CSV of the rejected rows
+    pub error_report_path: Option<String>,
+    pub error_message: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl DataImport {
+    /// Importable entities
+    pub const TEST_ITEMS: &'static str = "test_items";
+    pub const USERS: &'static str = "users";
+
+    pub fn new(
+        id: String,
+        tenant_id: String,
+        entity: &str,
+        format: DataFormat,
+        file_path: String,
+        created_by: Option<String>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id,
+            tenant_id,
+            entity: entity.to_string(),
+            format,
+            status: ImportStatus::Pending,
+            file_path,
+            processed_rows: 0,
+            failed_rows: 0,
+            error_report_path: None,
+            error_message: None,
+            created_by,
+            created_at: now,
+            updated_at: now,
+            finished_at: None,
+        }
+    }
+
+    /// Fresh id for an import (also names its files)
+    pub fn new_id() -> String {
+        Uuid::new_v4().to_string()
+    }
+
+    /// Convert to response DTO (file paths stay internal)
+    pub fn to_response(&self) -> crate::application::dtos::DataImportResponse {
+        crate::application::dtos::DataImportResponse {
+            id: self.id.clone(),
+            entity: self.entity.clone(),
+            format: self.format,
+            status: self.status,
+            processed_rows: self.processed_rows,
+            failed_rows: self.failed_rows,
+            has_error_report: self.error_report_path.is_some(),
+            error_message: self.error_message.clone(),
+            created_at: self.created_at.to_rfc3339(),
+            updated_at: self.updated_at.to_rfc3339(),
+            finished_at: self.finished_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+impl sqlx::FromRow<'_, PgRow> for DataImport {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let format: String = row.try_get("format")?;
+        let status: String = row.try_get("status")?;
+
+        Ok(DataImport {
+            id: row.try_get("id")?,
+            tenant_id: row.try_get("tenant_id")?,
+            entity: row.try_get("entity")?,
+            format: DataFormat::parse(&format).unwrap_or(DataFormat::Csv),
+            status: ImportStatus::from_trusted(&status),
+            file_path: row.try_get("file_path")?,
+            processed_rows: row.try_get("processed_rows")?,
+            failed_rows: row.try_get("failed_rows")?,
+            error_report_path: row.try_get("error_report_path")?,
+            error_message: row.try_get("error_message")?,
+            created_by: row.try_get("created_by")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+            finished_at: row.try_get("finished_at")?,
+        })
+    }
+}
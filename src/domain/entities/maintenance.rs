@@ -0,0 +1,291 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+use crate::domain::value_objects::IpRange;
+use crate::errors::DomainError;
+
+pub const DEFAULT_MAINTENANCE_MESSAGE: &str = "Application is down for maintenance";
+
+/// Maintenance page without `render`
+pub const DEFAULT_TEMPLATE: &str = "render/down/default.html";
+
+/// Maintenance mode, as written by `ironclad down` or the admin endpoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaintenanceState {
+    pub message: String,
+    /// Retry-After, in seconds, when the window has no end
+    pub retry: u32,
+    /// Named bypass secrets; visiting `/{secret}` grants a signed cookie
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bypass: Vec<BypassSecret>,
+    /// Plain secret of states written before named bypasses; `with_legacy_secret_hashed` moves it to `bypass`
+    #[serde(default, skip_serializing)]
+    pub secret: Option<String>,
+    /// Template under templates/render/down (`emergency` or `emergency::low`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub render: Option<String>,
+    /// Answer browsers with JSON too
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub norender: bool,
+    /// Send every request to this path or URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redirect: Option<String>,
+    /// Start of a scheduled window; `None` starts at once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub starts_at: Option<DateTime<Utc>>,
+    /// End of the window, after which the application is up again by itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ends_at: Option<DateTime<Utc>>,
+    /// Path prefixes under maintenance (`/api/test-items`); empty covers every path
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+    /// Clients served as usual (office, health checkers)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<IpRange>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Bypass secret; only its SHA-256 is kept
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BypassSecret {
+    /// Who it was given to (`office`, `qa`...), shown in the audit log
+    pub name: String,
+    pub hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl BypassSecret {
+    pub fn new(name: impl Into<String>, hash: String) -> Self {
+        Self { name: name.into(), hash, created_at: Utc::now() }
+    }
+}
+
+/// Name of the bypass created by a plain `--secret`
+pub const DEFAULT_BYPASS_NAME: &str = "default";
+
+/// Where a maintenance window stands at a given time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaintenancePhase {
+    /// Announced, not started yet
+    Scheduled,
+    Active,
+    /// Past `ends_at`: the application is up
+    Ended,
+}
+
+impl MaintenanceState {
+    pub fn new(message: Option<String>, retry: u32) -> Self {
+        Self {
+            message: message.unwrap_or_else(|| DEFAULT_MAINTENANCE_MESSAGE.to_string()),
+            retry,
+            bypass: Vec::new(),
+            secret: None,
+            render: None,
+            norender: false,
+            redirect: None,
+            starts_at: None,
+            ends_at: None,
+            paths: Vec::new(),
+            allow: Vec::new(),
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Refuse option combinations the middleware cannot honour
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.norender && self.render.is_some() {
+            return Err(DomainError::Validation("Cannot use both norender and render".to_string()));
+        }
+        if self.render.is_some() && self.redirect.is_some() {
+            return Err(DomainError::Validation("Cannot use both render and redirect".to_string()));
+        }
+        if let Some(render) = self.render.as_deref() {
+            let mut parts = render.split("::");
+            let valid = parts.next().is_some_and(is_identifier) && parts.next().is_none_or(is_identifier) && parts.next().is_none();
+            if !valid {
+                return Err(DomainError::Validation(format!(
+                    "render must be a template name such as emergency or emergency::low: {}",
+                    render
+                )));
+            }
+        }
+        for (i, bypass) in self.bypass.iter().enumerate() {
+            validate_bypass_name(&bypass.name)?;
+            if self.bypass[..i].iter().any(|other| other.name == bypass.name) {
+                return Err(DomainError::Validation(format!("Duplicate bypass name: {}", bypass.name)));
+            }
+        }
+        if let Some(ends_at) = self.ends_at {
+            if ends_at <= self.starts_at.unwrap_or(self.created_at) {
+                return Err(DomainError::Validation("ends_at must be after the start of the window".to_string()));
+            }
+        }
+        if let Some(path) = self.paths.iter().find(|path| !path.starts_with('/')) {
+            return Err(DomainError::Validation(format!("Path prefix must start with /: {}", path)));
+        }
+        Ok(())
+    }
+
+    /// Add a bypass, replacing (rotating) any bypass of the same name
+    pub fn add_bypass(&mut self, bypass: BypassSecret) -> Result<(), DomainError> {
+        validate_bypass_name(&bypass.name)?;
+        self.bypass.retain(|other| other.name != bypass.name);
+        self.bypass.push(bypass);
+        Ok(())
+    }
+
+    /// Remove a bypass; the cookies it granted stop working. `false` when unknown
+    pub fn revoke_bypass(&mut self, name: &str) -> bool {
+        let before = self.bypass.len();
+        self.bypass.retain(|bypass| bypass.name != name);
+        self.bypass.len() < before
+    }
+
+    /// Turn the plain `secret` of an older state into the `default` bypass
+    pub fn with_legacy_secret_hashed(mut self, hash: impl Fn(&str) -> String) -> Self {
+        if let Some(secret) = self.secret.take() {
+            if !self.bypass.iter().any(|bypass| bypass.name == DEFAULT_BYPASS_NAME) {
+                self.bypass.push(BypassSecret {
+                    name: DEFAULT_BYPASS_NAME.to_string(),
+                    hash: hash(&secret),
+                    created_at: self.created_at,
+                });
+            }
+        }
+        self
+    }
+
+    /// Template under the template directory: `emergency` is `render/down/emergency/default.html`,
+    /// `emergency::low` is `render/down/emergency/low.html`
+    pub fn template(&self) -> String {
+        match self.render.as_deref().map(|render| render.split_once("::")) {
+            None => DEFAULT_TEMPLATE.to_string(),
+            Some(Some((group, view))) => format!("render/down/{}/{}.html", group, view),
+            Some(None) => format!("render/down/{}/default.html", self.render.as_deref().unwrap_or_default()),
+        }
+    }
+
+    pub fn phase(&self, now: DateTime<Utc>) -> MaintenancePhase {
+        if self.ends_at.is_some_and(|ends_at| now >= ends_at) {
+            MaintenancePhase::Ended
+        } else if self.starts_at.is_some_and(|starts_at| now < starts_at) {
+            MaintenancePhase::Scheduled
+        } else {
+            MaintenancePhase::Active
+        }
+    }
+
+    /// Whether the window applies to a request path
+    pub fn covers(&self, path: &str) -> bool {
+        self.paths.is_empty() || self.paths.iter().any(|prefix| path_matches(prefix, path))
+    }
+
+    /// Whether the client is on the allow-list
+    pub fn allows(&self, ip: Option<IpAddr>) -> bool {
+        ip.is_some_and(|ip| self.allow.iter().any(|range| range.contains(&ip)))
+    }
+
+    /// Seconds until the end of the window, else `retry`
+    pub fn retry_after(&self, now: DateTime<Utc>) -> u32 {
+        match self.ends_at {
+            Some(ends_at) => (ends_at - now).num_seconds().clamp(1, u32::MAX as i64) as u32,
+            None => self.retry,
+        }
+    }
+}
+
+/// 1 to 50 characters of `[A-Za-z0-9_-]`
+fn is_identifier(name: &str) -> bool {
+    (1..=50).contains(&name.len()) && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Bypass names are part of the cookie
+fn validate_bypass_name(name: &str) -> Result<(), DomainError> {
+    if !is_identifier(name) {
+        return Err(DomainError::Validation(format!(
+            "Bypass name must be 1 to 50 letters, digits, - or _: {}",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// `/api/test-items` matches itself and `/api/test-items/...`, not `/api/test-items-archive`
+pub fn path_matches(prefix: &str, path: &str) -> bool {
+    let prefix = prefix.trim_end_matches('/');
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_window_phase_scope_and_allow_list() {
+        let now = Utc::now();
+        let mut state = MaintenanceState::new(None, 60);
+        state.starts_at = Some(now + Duration::hours(1));
+        state.ends_at = Some(now + Duration::hours(2));
+        state.paths = vec!["/api/test-items".to_string()];
+        state.allow = vec!["10.0.0.0/8".parse().unwrap()];
+        assert!(state.validate().is_ok());
+
+        assert_eq!(state.phase(now), MaintenancePhase::Scheduled);
+        assert_eq!(state.phase(now + Duration::minutes(90)), MaintenancePhase::Active);
+        assert_eq!(state.phase(now + Duration::hours(2)), MaintenancePhase::Ended);
+        assert_eq!(state.retry_after(now + Duration::minutes(90)), 1800);
+
+        assert!(state.covers("/api/test-items"));
+        assert!(state.covers("/api/test-items/42"));
+        assert!(!state.covers("/api/test-items-archive"));
+        assert!(!state.covers("/api/user/profile"));
+
+        assert!(state.allows(Some("10.3.2.1".parse().unwrap())));
+        assert!(!state.allows(Some("192.0.2.1".parse().unwrap())));
+        assert!(!state.allows(None));
+
+        state.ends_at = Some(now + Duration::minutes(30));
+        assert!(state.validate().is_err());
+    }
+
+    #[test]
+    fn test_render_names_map_inside_the_template_directory() {
+        let mut state = MaintenanceState::new(None, 60);
+        assert_eq!(state.template(), DEFAULT_TEMPLATE);
+        state.render = Some("emergency".to_string());
+        assert_eq!(state.template(), "render/down/emergency/default.html");
+        state.render = Some("emergency::low".to_string());
+        assert_eq!(state.template(), "render/down/emergency/low.html");
+        assert!(state.validate().is_ok());
+
+        for render in ["../../etc", "emergency::../x", "a::b::c", "", "emergency::"] {
+            state.render = Some(render.to_string());
+            assert!(state.validate().is_err(), "{}", render);
+        }
+    }
+
+    #[test]
+    fn test_bypasses_are_rotated_revoked_and_upgraded() {
+        let mut state: MaintenanceState = serde_json::from_str(
+            r#"{"message":"Old","retry":60,"secret":"letmein","created_at":"2024-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+        state = state.with_legacy_secret_hashed(|secret| secret.to_uppercase());
+        assert_eq!(state.secret, None);
+        assert_eq!(state.bypass[0].name, DEFAULT_BYPASS_NAME);
+        assert_eq!(state.bypass[0].hash, "LETMEIN");
+
+        state.add_bypass(BypassSecret::new("office", "a".to_string())).unwrap();
+        state.add_bypass(BypassSecret::new("office", "b".to_string())).unwrap();
+        assert_eq!(state.bypass.len(), 2);
+        assert_eq!(state.bypass[1].hash, "b");
+        assert!(state.add_bypass(BypassSecret::new("the office", "c".to_string())).is_err());
+
+        assert!(state.revoke_bypass("office"));
+        assert!(!state.revoke_bypass("office"));
+        assert!(!serde_json::to_string(&state).unwrap().contains("letmein"));
+    }
+}
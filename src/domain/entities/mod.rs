@@ -1,5 +1,19 @@
 pub mod user;
 pub mod test_item;
+pub mod permission;
+pub mod api_key;
+pub mod user_identity;
+pub mod audit_entry;
+pub mod tenant;
+pub mod data_import;
+pub mod maintenance;
 
 pub use user::User;
-pub use test_item::TestItem;
\ No newline at end of file
+pub use test_item::TestItem;
+pub use permission::{PermissionDefinition, RolePermission};
+pub use api_key::ApiKey;
+pub use user_identity::{OAuthState, UserIdentity};
+pub use audit_entry::{AuditEntry, RequestOrigin};
+pub use tenant::{Tenant, DEFAULT_TENANT_ID};
+pub use data_import::{DataFormat, DataImport, ImportStatus};
+pub use maintenance::{BypassSecret, MaintenancePhase, MaintenanceState, DEFAULT_BYPASS_NAME, DEFAULT_MAINTENANCE_MESSAGE, DEFAULT_TEMPLATE};
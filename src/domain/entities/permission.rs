@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use crate::domain::value_objects::Permission;
+
+/// Entry of the permission catalogue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionDefinition {
+    pub name: Permission,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl sqlx::FromRow<'_, PgRow> for PermissionDefinition {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(PermissionDefinition {
+            name: Permission::from_trusted(row.try_get("name")?),
+            description: row.try_get("description")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+impl PermissionDefinition {
+    pub fn new(name: Permission, description: Option<String>) -> Self {
+        Self {
+            name,
+            description,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// A permission granted to a role
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RolePermission {
+    pub role: String,
+    pub permission: Permission,
+    pub granted_at: DateTime<Utc>,
+}
+
+impl sqlx::FromRow<'_, PgRow> for RolePermission {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(RolePermission {
+            role: row.try_get("role")?,
+            permission: Permission::from_trusted(row.try_get("permission")?),
+            granted_at: row.try_get("granted_at")?,
+        })
+    }
+}
@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+
+/// Tenant that owns rows created before multi-tenancy (and single-tenant deployments)
+pub const DEFAULT_TENANT_ID: &str = "default";
+
+/// Customer hosted on this deployment; every tenant-owned row carries its `id`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tenant {
+    pub id: String,
+    /// Value matched against the subdomain and the `X-Tenant-ID` header
+    pub slug: String,
+    pub name: String,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl sqlx::FromRow<'_, PgRow> for Tenant {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Tenant {
+            id: row.try_get("id")?,
+            slug: row.try_get("slug")?,
+            name: row.try_get("name")?,
+            is_active: row.try_get("is_active")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
@@ -8,25 +8,38 @@ use sqlx::Row;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestItem {
     pub id: String,
+    pub tenant_id: String,
     pub subject: String,
     pub optional_field: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set while the item is in the trash
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Bumped on every update; an update based on an older version is rejected
+    pub version: i32,
 }
 
 impl TestItem {
     /// Create new test item
-    pub fn new(subject: String, optional_field: Option<String>) -> Self {
+    pub fn new(tenant_id: String, subject: String, optional_field: Option<String>) -> Self {
         let now = Utc::now();
         Self {
             id: Uuid::new_v4().to_string(),
+            tenant_id,
             subject,
             optional_field,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
+            version: 1,
         }
     }
 
+    /// Whether the item is in the trash
+    pub fn is_trashed(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
     /// Update subject
     pub fn update_subject(&mut self, subject: String) {
         self.subject = subject;
@@ -47,6 +60,8 @@ impl TestItem {
             optional_field: self.optional_field.clone(),
             created_at: self.created_at.to_rfc3339(),
             updated_at: self.updated_at.to_rfc3339(),
+            deleted_at: self.deleted_at.map(|d| d.to_rfc3339()),
+            version: self.version,
         }
     }
 }
@@ -56,10 +71,13 @@ impl sqlx::FromRow<'_, PgRow> for TestItem {
     fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
         Ok(TestItem {
             id: row.try_get("id")?,
+            tenant_id: row.try_get("tenant_id")?,
             subject: row.try_get("subject")?,
             optional_field: row.try_get("optional_field")?,
             created_at: row.try_get("created_at")?,
             updated_at: row.try_get("updated_at")?,
+            deleted_at: row.try_get("deleted_at")?,
+            version: row.try_get("version")?,
         })
     }
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use crate::domain::value_objects::{Role, Username, EmailAddress};
+use crate::domain::value_objects::{Permission, Role, Username, EmailAddress};
 use crate::errors::DomainError;
 
 // Imports para SQLx
@@ -12,13 +12,35 @@ use sqlx::Row;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     pub id: String,
+    /// Owning tenant; email and username are unique within it
+    pub tenant_id: String,
     pub email: EmailAddress,
     pub username: Username,
     pub password_hash: String,
     pub role: Role,
     pub is_active: bool,
+    /// Set by an admin; the user must change their password before logging in
+    pub password_reset_required: bool,
+    /// Base32 TOTP secret (set on enrollment, before confirmation)
+    pub two_factor_secret: Option<String>,
+    pub two_factor_enabled: bool,
+    pub two_factor_confirmed_at: Option<DateTime<Utc>>,
+    /// SHA-256 hashes of the unused recovery codes
+    pub two_factor_recovery_codes: Vec<String>,
+    /// TOTP time step of the last accepted code; it cannot be used again
+    pub two_factor_last_step: Option<i64>,
+    /// Consecutive wrong second-factor codes
+    pub two_factor_failed_attempts: i32,
+    /// Second-factor codes are refused until then after too many failures
+    pub two_factor_locked_until: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set while the user is in the trash
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Bumped on every update; an update based on an older version is rejected
+    pub version: i32,
+    /// Bumped when issued tokens must stop working; tokens carry it as `ver`
+    pub token_version: i32,
 }
 
 impl sqlx::FromRow<'_, PgRow> for User {
@@ -31,36 +53,66 @@ impl sqlx::FromRow<'_, PgRow> for User {
 
         Ok(User {
             id: row.try_get("id")?,
+            tenant_id: row.try_get("tenant_id")?,
             // We assume the DB is a trusted source, we skip re-validation
             email: EmailAddress::from_trusted(row.try_get("email")?), 
             username: Username::from_trusted(row.try_get("username")?),
             password_hash: row.try_get("password_hash")?,
             role,
             is_active: row.try_get("is_active")?,
+            password_reset_required: row.try_get("password_reset_required")?,
+            two_factor_secret: row.try_get("two_factor_secret")?,
+            two_factor_enabled: row.try_get("two_factor_enabled")?,
+            two_factor_confirmed_at: row.try_get("two_factor_confirmed_at")?,
+            two_factor_recovery_codes: row.try_get("two_factor_recovery_codes")?,
+            two_factor_last_step: row.try_get("two_factor_last_step")?,
+            two_factor_failed_attempts: row.try_get("two_factor_failed_attempts")?,
+            two_factor_locked_until: row.try_get("two_factor_locked_until")?,
             created_at: row.try_get("created_at")?,
             updated_at: row.try_get("updated_at")?,
+            deleted_at: row.try_get("deleted_at")?,
+            version: row.try_get("version")?,
+            token_version: row.try_get("token_version")?,
         })
     }
 }
 
 impl User {
     /// Smart Constructor: Returns Result guaranteeing a valid state
-    pub fn new(email: EmailAddress, username: Username, password_hash: String) -> Result<Self, DomainError> {
+    pub fn new(
+        tenant_id: String,
+        email: EmailAddress,
+        username: Username,
+        password_hash: String,
+    ) -> Result<Self, DomainError> {
         let now = Utc::now();
         Ok(Self {
             id: Uuid::new_v4().to_string(),
+            tenant_id,
             email,
             username,
             password_hash,
             role: Role::default(),
             is_active: true,
+            password_reset_required: false,
+            two_factor_secret: None,
+            two_factor_enabled: false,
+            two_factor_confirmed_at: None,
+            two_factor_recovery_codes: Vec::new(),
+            two_factor_last_step: None,
+            two_factor_failed_attempts: 0,
+            two_factor_locked_until: None,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
+            version: 1,
+            token_version: 0,
         })
     }
 
     /// Smart Constructor with specific role
     pub fn new_with_role(
+        tenant_id: String,
         email: EmailAddress,
         username: Username,
         password_hash: String,
@@ -69,13 +121,25 @@ impl User {
         let now = Utc::now();
         Ok(Self {
             id: Uuid::new_v4().to_string(),
+            tenant_id,
             email,
             username,
             password_hash,
             role,
             is_active: true,
+            password_reset_required: false,
+            two_factor_secret: None,
+            two_factor_enabled: false,
+            two_factor_confirmed_at: None,
+            two_factor_recovery_codes: Vec::new(),
+            two_factor_last_step: None,
+            two_factor_failed_attempts: 0,
+            two_factor_locked_until: None,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
+            version: 1,
+            token_version: 0,
         })
     }
 
@@ -95,6 +159,20 @@ impl User {
         self.is_active
     }
 
+    /// Whether the user is in the trash
+    pub fn is_trashed(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    pub fn has_two_factor(&self) -> bool {
+        self.two_factor_enabled && self.two_factor_secret.is_some()
+    }
+
+    /// Whether second-factor codes are refused after too many failures
+    pub fn is_two_factor_locked(&self, now: DateTime<Utc>) -> bool {
+        self.two_factor_locked_until.is_some_and(|until| until > now)
+    }
+
     // ============================================
     // Mutation Methods (Update timestamp)
     // ============================================
@@ -109,13 +187,30 @@ impl User {
         self.updated_at = Utc::now();
     }
 
+    /// Setting a new password also satisfies a forced reset and signs out every session
     pub fn update_password_hash(&mut self, password_hash: String) {
         self.password_hash = password_hash;
+        self.password_reset_required = false;
+        self.revoke_tokens();
         self.updated_at = Utc::now();
     }
 
+    /// Replace the hash with a stronger one for the same password (leaves the reset flag alone)
+    pub fn upgrade_password_hash(&mut self, password_hash: String) {
+        self.password_hash = password_hash;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn require_password_reset(&mut self) {
+        self.password_reset_required = true;
+        self.revoke_tokens();
+        self.updated_at = Utc::now();
+    }
+
+    /// Tokens issued for the previous role stop working
     pub fn change_role(&mut self, role: Role) {
         self.role = role;
+        self.revoke_tokens();
         self.updated_at = Utc::now();
     }
 
@@ -126,7 +221,68 @@ impl User {
 
     pub fn deactivate(&mut self) {
         self.is_active = false;
+        self.revoke_tokens();
+        self.updated_at = Utc::now();
+    }
+
+    /// Invalidate every JWT issued so far
+    pub fn revoke_tokens(&mut self) {
+        self.token_version += 1;
+    }
+
+    // ============================================
+    // Two-Factor Authentication
+    // ============================================
+
+    /// Store a new (unconfirmed) TOTP secret; 2FA stays disabled until confirmed
+    pub fn begin_two_factor_enrollment(&mut self, secret: String) -> Result<(), DomainError> {
+        if self.two_factor_enabled {
+            return Err(DomainError::Validation(
+                "Two-factor authentication is already enabled".into(),
+            ));
+        }
+        self.two_factor_secret = Some(secret);
         self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Activate 2FA once the user proved possession of the secret
+    pub fn confirm_two_factor(&mut self, recovery_code_hashes: Vec<String>) -> Result<(), DomainError> {
+        if self.two_factor_secret.is_none() {
+            return Err(DomainError::Validation(
+                "Two-factor enrollment has not been started".into(),
+            ));
+        }
+        let now = Utc::now();
+        self.two_factor_enabled = true;
+        self.two_factor_confirmed_at = Some(now);
+        self.two_factor_recovery_codes = recovery_code_hashes;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    pub fn disable_two_factor(&mut self) {
+        self.two_factor_secret = None;
+        self.two_factor_enabled = false;
+        self.two_factor_confirmed_at = None;
+        self.two_factor_recovery_codes.clear();
+        self.updated_at = Utc::now();
+    }
+
+    pub fn replace_recovery_codes(&mut self, recovery_code_hashes: Vec<String>) {
+        self.two_factor_recovery_codes = recovery_code_hashes;
+        self.updated_at = Utc::now();
+    }
+
+    /// Burn a recovery code. Returns false if the code is unknown or already used.
+    pub fn consume_recovery_code(&mut self, code_hash: &str) -> bool {
+        let before = self.two_factor_recovery_codes.len();
+        self.two_factor_recovery_codes.retain(|h| h != code_hash);
+        let consumed = self.two_factor_recovery_codes.len() < before;
+        if consumed {
+            self.updated_at = Utc::now();
+        }
+        consumed
     }
 
     // ============================================
@@ -142,29 +298,90 @@ impl User {
             username: self.username.as_str().to_string(),
             role: self.role.to_string(),
             is_active: self.is_active,
+            password_reset_required: self.password_reset_required,
+            two_factor_enabled: self.two_factor_enabled,
             created_at: self.created_at.to_rfc3339(),
             updated_at: self.updated_at.to_rfc3339(),
+            deleted_at: self.deleted_at.map(|d| d.to_rfc3339()),
+            version: self.version,
         }
     }
 }
 
+/// Purpose of a JWT. Only `Access` tokens are accepted by the HTTP extractors.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenType {
+    #[default]
+    Access,
+    /// Short-lived token issued after the password step while a TOTP code is pending
+    MfaPending,
+}
+
 /// JWT Claims - for authentication token
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String,
     pub email: String,
     pub role: String,
+    /// Tenant the subject belongs to (tokens issued before multi-tenancy map to the default tenant)
+    #[serde(default = "default_tenant_id")]
+    pub tenant_id: String,
     pub exp: i64,
     pub iat: i64,
+    #[serde(default)]
+    pub token_type: TokenType,
+    /// True when the session was established with a second factor
+    #[serde(default)]
+    pub mfa: bool,
+    /// Admin user ID when this token was issued through impersonation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impersonator: Option<String>,
+    /// Set when the request authenticated with an API key instead of a JWT
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_id: Option<String>,
+    /// Permissions the API key is limited to (`None` = unrestricted session)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<String>>,
+    /// `token_version` of the user when issued; a mismatch means the token was revoked
+    #[serde(default)]
+    pub ver: i32,
 }
 
 impl Claims {
-    pub fn new(user_id: String, email: String, role: String, exp: i64) -> Self {
+    pub fn new(user_id: String, email: String, role: String, tenant_id: String, exp: i64) -> Self {
         let iat = Utc::now().timestamp();
-        Self { sub: user_id, email, role, exp, iat }
+        Self {
+            sub: user_id,
+            email,
+            role,
+            tenant_id,
+            exp,
+            iat,
+            token_type: TokenType::Access,
+            mfa: false,
+            impersonator: None,
+            api_key_id: None,
+            scopes: None,
+            ver: 0,
+        }
     }
 
     pub fn is_admin(&self) -> bool { self.role == "admin" }
     pub fn has_role(&self, required_role: &str) -> bool { self.role == required_role }
     pub fn has_any_role(&self, roles: &[&str]) -> bool { roles.contains(&self.role.as_str()) }
-}
\ No newline at end of file
+
+    /// Whether the credential's scopes (if any) cover the permission
+    pub fn scope_allows(&self, permission: &str) -> bool {
+        match &self.scopes {
+            None => true,
+            Some(scopes) => scopes
+                .iter()
+                .any(|scope| Permission::from_trusted(scope.clone()).grants(permission)),
+        }
+    }
+}
+
+fn default_tenant_id() -> String {
+    crate::domain::entities::DEFAULT_TENANT_ID.to_string()
+}
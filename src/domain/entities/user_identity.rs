@@ -0,0 +1,113 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+
+/// External (OAuth2 / OIDC) identity linked to a local user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserIdentity {
+    pub id: String,
+    pub tenant_id: String,
+    pub user_id: String,
+    pub provider: String,
+    /// Stable subject identifier issued by the provider
+    pub subject: String,
+    pub email: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_login_at: Option<DateTime<Utc>>,
+}
+
+impl UserIdentity {
+    pub fn new(
+        tenant_id: String,
+        user_id: String,
+        provider: String,
+        subject: String,
+        email: Option<String>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            tenant_id,
+            user_id,
+            provider,
+            subject,
+            email,
+            created_at: now,
+            last_login_at: Some(now),
+        }
+    }
+
+    /// Convert to response DTO
+    pub fn to_response(&self) -> crate::application::dtos::UserIdentityResponse {
+        crate::application::dtos::UserIdentityResponse {
+            id: self.id.clone(),
+            provider: self.provider.clone(),
+            email: self.email.clone(),
+            created_at: self.created_at.to_rfc3339(),
+            last_login_at: self.last_login_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+impl sqlx::FromRow<'_, PgRow> for UserIdentity {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(UserIdentity {
+            id: row.try_get("id")?,
+            tenant_id: row.try_get("tenant_id")?,
+            user_id: row.try_get("user_id")?,
+            provider: row.try_get("provider")?,
+            subject: row.try_get("subject")?,
+            email: row.try_get("email")?,
+            created_at: row.try_get("created_at")?,
+            last_login_at: row.try_get("last_login_at")?,
+        })
+    }
+}
+
+/// Pending authorization request, consumed once by the callback
+#[derive(Debug, Clone)]
+pub struct OAuthState {
+    pub state: String,
+    pub provider: String,
+    /// Tenant the login was started in; the callback signs the user into it
+    pub tenant_id: String,
+    /// PKCE verifier sent with the token request
+    pub code_verifier: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl OAuthState {
+    pub fn new(
+        state: String,
+        provider: String,
+        tenant_id: String,
+        code_verifier: String,
+        ttl_seconds: i64,
+    ) -> Self {
+        Self {
+            state,
+            provider,
+            tenant_id,
+            code_verifier,
+            expires_at: Utc::now() + Duration::seconds(ttl_seconds),
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+}
+
+impl sqlx::FromRow<'_, PgRow> for OAuthState {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(OAuthState {
+            state: row.try_get("state")?,
+            provider: row.try_get("provider")?,
+            tenant_id: row.try_get("tenant_id")?,
+            code_verifier: row.try_get("code_verifier")?,
+            expires_at: row.try_get("expires_at")?,
+        })
+    }
+}
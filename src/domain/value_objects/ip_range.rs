@@ -0,0 +1,84 @@
+use ipnet::IpNet;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+use crate::errors::DomainError;
+
+/// IP address or CIDR block, e.g. `203.0.113.7` or `10.0.0.0/8`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct IpRange(IpNet);
+
+impl IpRange {
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        // IPv4 clients may show up as IPv4-mapped IPv6 addresses
+        match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(self.0.contains(ip), |v4| {
+                self.0.contains(ip) || self.0.contains(&IpAddr::V4(v4))
+            }),
+            IpAddr::V4(_) => self.0.contains(ip),
+        }
+    }
+}
+
+impl FromStr for IpRange {
+    type Err = DomainError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        value
+            .parse::<IpNet>()
+            .or_else(|_| value.parse::<IpAddr>().map(IpNet::from))
+            .map(|net| Self(net.trunc()))
+            .map_err(|_| DomainError::Validation(format!("Invalid IP address or CIDR range: {}", value)))
+    }
+}
+
+impl TryFrom<String> for IpRange {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<IpRange> for String {
+    fn from(range: IpRange) -> Self {
+        range.to_string()
+    }
+}
+
+impl fmt::Display for IpRange {
+    /// Single addresses are shown without their prefix length
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.prefix_len() == self.0.max_prefix_len() {
+            write!(f, "{}", self.0.addr())
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_addresses_and_blocks_match_clients() {
+        let office: IpRange = "10.1.0.0/16".parse().unwrap();
+        assert!(office.contains(&"10.1.42.7".parse().unwrap()));
+        assert!(office.contains(&"::ffff:10.1.42.7".parse().unwrap()));
+        assert!(!office.contains(&"10.2.0.1".parse().unwrap()));
+
+        let checker: IpRange = " 203.0.113.7 ".parse().unwrap();
+        assert_eq!(checker.to_string(), "203.0.113.7");
+        assert!(checker.contains(&"203.0.113.7".parse().unwrap()));
+
+        // host bits are dropped
+        assert_eq!("10.1.2.3/16".parse::<IpRange>().unwrap().to_string(), "10.1.0.0/16");
+        assert!("2001:db8::/32".parse::<IpRange>().is_ok());
+        assert!("10.0.0.0/33".parse::<IpRange>().is_err());
+        assert!("office".parse::<IpRange>().is_err());
+    }
+}
@@ -3,6 +3,10 @@ mod role;
 pub use role::Role;
 pub mod username;
 pub mod email_address;
+pub mod permission;
+pub mod ip_range;
 
 pub use username::Username;
-pub use email_address::EmailAddress;
\ No newline at end of file
+pub use email_address::EmailAddress;
+pub use permission::Permission;
+pub use ip_range::IpRange;
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use crate::errors::DomainError;
+
+/// Permission name such as `users.view` or `queue.manage`.
+///
+/// `*` grants everything and `prefix.*` grants every permission under `prefix.`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Permission(String);
+
+impl Permission {
+    /// Smart Constructor: lowercase segments of `[a-z0-9_]` separated by dots
+    pub fn new(value: String) -> Result<Self, DomainError> {
+        let value = value.trim().to_lowercase();
+        let segments: Vec<&str> = value.split('.').collect();
+        let last = segments.len() - 1;
+
+        let valid = value == "*"
+            || (value.len() <= 100
+                && segments.iter().enumerate().all(|(i, segment)| {
+                    (i == last && i > 0 && *segment == "*") || is_valid_segment(segment)
+                }));
+
+        if !valid {
+            return Err(DomainError::Validation(format!("Invalid permission name: {}", value)));
+        }
+        Ok(Self(value))
+    }
+
+    /// Hydration from a trusted source (database)
+    pub fn from_trusted(value: String) -> Self {
+        Self(value)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether this (granted) permission satisfies the required one
+    pub fn grants(&self, required: &str) -> bool {
+        match self.0.as_str() {
+            "*" => true,
+            granted => match granted.strip_suffix(".*") {
+                Some(prefix) => required
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with('.')),
+                None => granted == required,
+            },
+        }
+    }
+}
+
+fn is_valid_segment(segment: &str) -> bool {
+    !segment.is_empty()
+        && segment.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permission_name_validation() {
+        assert!(Permission::new("Users.View".to_string()).is_ok());
+        assert!(Permission::new("*".to_string()).is_ok());
+        assert!(Permission::new("queue.*".to_string()).is_ok());
+        assert!(Permission::new("*.view".to_string()).is_err());
+        assert!(Permission::new("users..view".to_string()).is_err());
+        assert!(Permission::new("users view".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_permission_grants_wildcards() {
+        let all = Permission::from_trusted("*".to_string());
+        let queue = Permission::from_trusted("queue.*".to_string());
+        let view = Permission::from_trusted("users.view".to_string());
+
+        assert!(all.grants("roles.manage"));
+        assert!(queue.grants("queue.manage"));
+        assert!(!queue.grants("queues.manage"));
+        assert!(!queue.grants("queue"));
+        assert!(view.grants("users.view"));
+        assert!(!view.grants("users.manage"));
+    }
+}
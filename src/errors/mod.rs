@@ -1,5 +1,6 @@
 /// BAD REQUEST IS COMMENTED OUT FOR NOW, UNCOMMENT IF NEEDED IN THE FUTURE
 use actix_web::{error::ResponseError, http::StatusCode, HttpResponse};
+use schemars::JsonSchema;
 use serde::Serialize;
 use thiserror::Error;
 
@@ -38,6 +39,9 @@ pub enum ApiError {
 
     #[error("Conflict: {0}")]
     Conflict(String),
+
+    #[error("Too Many Requests: {0}")]
+    TooManyRequests(String),
 }
 
 // Automatically convert DomainError to ApiError::ValidationError (HTTP 400)
@@ -47,7 +51,8 @@ impl From<DomainError> for ApiError {
     }
 }
 
-#[derive(Serialize)]
+/// Body of every error response
+#[derive(Serialize, JsonSchema)]
 pub struct ErrorResponse {
     pub error: String,
     pub message: String,
@@ -68,6 +73,7 @@ impl ResponseError for ApiError {
             ),
             ApiError::JwtError(msg) => (StatusCode::UNAUTHORIZED, format!("JWT error: {}", msg)),
             ApiError::Conflict(msg) => (StatusCode::CONFLICT, msg.clone()),
+            ApiError::TooManyRequests(msg) => (StatusCode::TOO_MANY_REQUESTS, msg.clone()),
             ApiError::InternalServerError(msg) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 format!("Internal server error: {}", msg),
@@ -93,6 +99,7 @@ impl ResponseError for ApiError {
             ApiError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             ApiError::JwtError(_) => StatusCode::UNAUTHORIZED,
             ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
             ApiError::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
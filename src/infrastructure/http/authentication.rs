@@ -1,8 +1,12 @@
-use actix_web::{dev::Payload, FromRequest, HttpRequest};
-use futures::future::{ready, Ready};
+use actix_web::{dev::Payload, web, FromRequest, HttpRequest};
+use futures::future::LocalBoxFuture;
+use std::sync::Arc;
+use crate::application::services::{ApiKeyService, UserService};
 use crate::errors::ApiError;
-use crate::domain::entities::user::Claims;
+use crate::domain::entities::user::{Claims, TokenType};
 use crate::config::AppConfig;
+use crate::utils::api_key;
+use super::tenant::{requested_tenant, tenant_service};
 
 // ============================================
 // BASE: Extractor de usuario autenticado
@@ -12,10 +16,27 @@ pub struct AuthUser(pub Claims);
 
 impl FromRequest for AuthUser {
     type Error = ApiError;
-    type Future = Ready<Result<Self, Self::Error>>;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
 
     fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
-        ready(extract_claims(req).map(AuthUser))
+        let claims = extract_claims(req);
+        Box::pin(async move { unrestricted(claims.await?).map(AuthUser) })
+    }
+}
+
+// ============================================
+// SESSION: The user's own interactive session, no API keys or impersonation
+// ============================================
+#[derive(Clone)]
+pub struct SessionUser(pub Claims);
+
+impl FromRequest for SessionUser {
+    type Error = ApiError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let claims = extract_claims(req);
+        Box::pin(async move { own_session(claims.await?).map(SessionUser) })
     }
 }
 
@@ -27,18 +48,18 @@ pub struct AdminUser(pub Claims);
 
 impl FromRequest for AdminUser {
     type Error = ApiError;
-    type Future = Ready<Result<Self, Self::Error>>;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
 
     fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
-        ready(
-            extract_claims(req).and_then(|claims| {
-                if claims.is_admin() {
-                    Ok(AdminUser(claims))
-                } else {
-                    Err(ApiError::Forbidden("Admin access required".to_string()))
-                }
-            })
-        )
+        let claims = extract_privileged_claims(req);
+        Box::pin(async move {
+            let claims = unrestricted(claims.await?)?;
+            if claims.is_admin() {
+                Ok(AdminUser(claims))
+            } else {
+                Err(ApiError::Forbidden("Admin access required".to_string()))
+            }
+        })
     }
 }
 
@@ -50,18 +71,18 @@ pub struct ModeratorUser(pub Claims);
 
 impl FromRequest for ModeratorUser {
     type Error = ApiError;
-    type Future = Ready<Result<Self, Self::Error>>;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
 
     fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
-        ready(
-            extract_claims(req).and_then(|claims| {
-                if claims.has_any_role(&["admin", "moderator"]) {
-                    Ok(ModeratorUser(claims))
-                } else {
-                    Err(ApiError::Forbidden("Moderator access required".to_string()))
-                }
-            })
-        )
+        let claims = extract_privileged_claims(req);
+        Box::pin(async move {
+            let claims = unrestricted(claims.await?)?;
+            if claims.has_any_role(&["admin", "moderator"]) {
+                Ok(ModeratorUser(claims))
+            } else {
+                Err(ApiError::Forbidden("Moderator access required".to_string()))
+            }
+        })
     }
 }
 
@@ -73,18 +94,18 @@ pub struct PremiumUser(pub Claims);
 
 impl FromRequest for PremiumUser {
     type Error = ApiError;
-    type Future = Ready<Result<Self, Self::Error>>;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
 
     fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
-        ready(
-            extract_claims(req).and_then(|claims| {
-                if claims.has_any_role(&["admin", "moderator", "premium"]) {
-                    Ok(PremiumUser(claims))
-                } else {
-                    Err(ApiError::Forbidden("Premium access required".to_string()))
-                }
-            })
-        )
+        let claims = extract_privileged_claims(req);
+        Box::pin(async move {
+            let claims = unrestricted(claims.await?)?;
+            if claims.has_any_role(&["admin", "moderator", "premium"]) {
+                Ok(PremiumUser(claims))
+            } else {
+                Err(ApiError::Forbidden("Premium access required".to_string()))
+            }
+        })
     }
 }
 
@@ -122,29 +143,183 @@ impl RoleUser {
 
 impl FromRequest for RoleUser {
     type Error = ApiError;
-    type Future = Ready<Result<Self, Self::Error>>;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
 
     fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
-        ready(extract_claims(req).map(RoleUser))
+        let claims = extract_claims(req);
+        Box::pin(async move { unrestricted(claims.await?).map(RoleUser) })
     }
 }
 
 // ============================================
 // HELPER: Extract claims DRY
 // ============================================
-fn extract_claims(req: &HttpRequest) -> Result<Claims, ApiError> {
-    let config = req
-        .app_data::<actix_web::web::Data<AppConfig>>()
-        .ok_or_else(|| ApiError::InternalServerError("Config not found".to_string()))?;
+fn app_config(req: &HttpRequest) -> Result<Arc<AppConfig>, ApiError> {
+    req.app_data::<web::Data<Arc<AppConfig>>>()
+        .map(|data| data.get_ref().clone())
+        .ok_or_else(|| ApiError::InternalServerError("Config not found".to_string()))
+}
+
+/// Credential presented by the client
+enum Credential {
+    Jwt(String),
+    ApiKey(String),
+}
 
-    let token = req
-        .headers()
-        .get("Authorization")
-        .and_then(|h| h.to_str().ok())
-        .and_then(|h| h.strip_prefix("Bearer "))
-        .ok_or(ApiError::Unauthorized)?;
+/// `Authorization: Bearer <jwt|api key>` or `X-Api-Key: <api key>`
+fn credential(req: &HttpRequest) -> Option<Credential> {
+    let header = |name: &str| req.headers().get(name).and_then(|h| h.to_str().ok());
 
-    crate::utils::jwt::verify_token(token, config.get_ref())
+    if let Some(token) = header("Authorization").and_then(|h| h.strip_prefix("Bearer ")) {
+        let token = token.trim().to_string();
+        return Some(if api_key::is_api_key(&token) {
+            Credential::ApiKey(token)
+        } else {
+            Credential::Jwt(token)
+        });
+    }
+
+    header("X-Api-Key").map(|key| Credential::ApiKey(key.trim().to_string()))
+}
+
+/// Whether the request carries a credential at all
+pub(crate) fn has_credential(req: &HttpRequest) -> bool {
+    credential(req).is_some()
+}
+
+/// Resolve the request credential (JWT or API key) to claims.
+/// App data is read up front so the returned future does not borrow the request.
+///
+/// The credential's tenant must be active and, when the request names a tenant
+/// (header or subdomain), must be that tenant.
+pub(crate) fn extract_claims(req: &HttpRequest) -> LocalBoxFuture<'static, Result<Claims, ApiError>> {
+    let claims = extract_tenant_claims(req);
+    let users = req
+        .app_data::<web::Data<Arc<UserService>>>()
+        .map(|data| data.get_ref().clone())
+        .ok_or_else(|| ApiError::InternalServerError("User service not found".to_string()));
+
+    Box::pin(async move {
+        let claims = claims.await?;
+
+        // API keys load the user on every request already
+        if claims.api_key_id.is_none() {
+            users?.verify_session(&claims).await?;
+        }
+
+        Ok(claims)
+    })
+}
+
+/// Verified credential valid for the requested tenant, without checking that the
+/// token was not revoked since (enough to tell which tenant a request belongs to)
+pub(crate) fn extract_tenant_claims(req: &HttpRequest) -> LocalBoxFuture<'static, Result<Claims, ApiError>> {
+    let config = app_config(req);
+    let credential = credential(req);
+    let api_keys = req
+        .app_data::<web::Data<Arc<ApiKeyService>>>()
+        .map(|data| data.get_ref().clone());
+    let requested = config.as_ref().ok().and_then(|config| requested_tenant(req, config));
+    let tenants = tenant_service(req);
+
+    Box::pin(async move {
+        let claims = claims_for(config?, credential, api_keys).await?;
+
+        let tenant = match tenants?.get_by_id(&claims.tenant_id).await {
+            Err(ApiError::NotFound(_)) => return Err(ApiError::Unauthorized),
+            result => result?,
+        };
+
+        if requested.is_some_and(|slug| slug != tenant.slug) {
+            return Err(ApiError::Forbidden(
+                "Credentials are not valid for this tenant".to_string(),
+            ));
+        }
+
+        Ok(claims)
+    })
+}
+
+/// Verify the credential itself (JWT signature and type, or API key lookup)
+async fn claims_for(
+    config: Arc<AppConfig>,
+    credential: Option<Credential>,
+    api_keys: Option<Arc<ApiKeyService>>,
+) -> Result<Claims, ApiError> {
+    match credential.ok_or(ApiError::Unauthorized)? {
+        Credential::Jwt(token) => {
+            let claims = crate::utils::jwt::verify_token(&token, &config)?;
+
+            // MFA pending tokens are only good for the second login step
+            if claims.token_type != TokenType::Access {
+                return Err(ApiError::Unauthorized);
+            }
+
+            Ok(claims)
+        }
+        Credential::ApiKey(key) => {
+            let service = api_keys.ok_or_else(|| {
+                ApiError::InternalServerError("API key service not found".to_string())
+            })?;
+            service.authenticate(&key).await
+        }
+    }
+}
+
+// ============================================
+// HELPER: Claims for role-gated extractors (2FA policy)
+// ============================================
+pub(crate) fn extract_privileged_claims(req: &HttpRequest) -> LocalBoxFuture<'static, Result<Claims, ApiError>> {
+    let claims = extract_claims(req);
+    let config = app_config(req);
+
+    Box::pin(async move {
+        let claims = claims.await?;
+        let config = config?;
+
+        if config.two_factor.is_required_for(&claims.role) && !claims.mfa {
+            return Err(ApiError::Forbidden(
+                "Two-factor authentication is required for this account".to_string(),
+            ));
+        }
+
+        Ok(claims)
+    })
+}
+
+// ============================================
+// HELPER: API key scopes for extractors without a permission
+// ============================================
+/// Identity and role extractors don't name a permission, so they stand for everything
+/// the owner's role may do. An API key only passes them when its scopes are
+/// unrestricted (`*`); scoped keys are limited to `Require<P>` and policy routes.
+fn unrestricted(claims: Claims) -> Result<Claims, ApiError> {
+    if claims.scope_allows("*") {
+        Ok(claims)
+    } else {
+        Err(ApiError::Forbidden(
+            "This endpoint needs an API key with the \"*\" scope".to_string(),
+        ))
+    }
+}
+
+// ============================================
+// HELPER: Credential endpoints
+// ============================================
+/// Credentials (API keys, 2FA, passwords) may only be managed by the user in person:
+/// neither an API key nor an administrator impersonating the user qualifies.
+pub(crate) fn own_session(claims: Claims) -> Result<Claims, ApiError> {
+    if claims.api_key_id.is_some() {
+        return Err(ApiError::Forbidden(
+            "This endpoint is not available to API keys".to_string(),
+        ));
+    }
+    if claims.impersonator.is_some() {
+        return Err(ApiError::Forbidden(
+            "This endpoint is not available while impersonating".to_string(),
+        ));
+    }
+    Ok(claims)
 }
 
 // ============================================
@@ -172,14 +347,40 @@ mod tests {
 
     #[test]
     fn test_claims_is_admin() {
-        let claims = Claims::new("user1".to_string(), "user@test.com".to_string(), "admin".to_string(), 1000);
+        let claims = Claims::new("user1".to_string(), "user@test.com".to_string(), "admin".to_string(), "default".to_string(), 1000);
         assert!(claims.is_admin());
     }
 
     #[test]
     fn test_claims_has_any_role() {
-        let claims = Claims::new("user1".to_string(), "user@test.com".to_string(), "moderator".to_string(), 1000);
+        let claims = Claims::new("user1".to_string(), "user@test.com".to_string(), "moderator".to_string(), "default".to_string(), 1000);
         assert!(claims.has_any_role(&["admin", "moderator"]));
         assert!(!claims.has_any_role(&["admin", "premium"]));
     }
+
+    #[test]
+    fn test_only_unrestricted_keys_pass_role_extractors() {
+        let mut claims = Claims::new("user1".to_string(), "user@test.com".to_string(), "admin".to_string(), "default".to_string(), 1000);
+        assert!(unrestricted(claims.clone()).is_ok());
+
+        claims.scopes = Some(vec!["users.view".to_string()]);
+        assert!(matches!(unrestricted(claims.clone()), Err(ApiError::Forbidden(_))));
+
+        claims.scopes = Some(vec!["*".to_string()]);
+        assert!(unrestricted(claims).is_ok());
+    }
+
+    #[test]
+    fn test_own_session_rejects_api_keys_and_impersonation() {
+        let claims = Claims::new("user1".to_string(), "user@test.com".to_string(), "user".to_string(), "default".to_string(), 1000);
+        assert!(own_session(claims.clone()).is_ok());
+
+        let mut key = claims.clone();
+        key.api_key_id = Some("key1".to_string());
+        assert!(matches!(own_session(key), Err(ApiError::Forbidden(_))));
+
+        let mut impersonated = claims;
+        impersonated.impersonator = Some("admin1".to_string());
+        assert!(matches!(own_session(impersonated), Err(ApiError::Forbidden(_))));
+    }
 }
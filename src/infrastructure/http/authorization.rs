@@ -0,0 +1,126 @@
+use actix_web::{dev::Payload, web, FromRequest, HttpRequest};
+use futures::future::LocalBoxFuture;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use crate::application::policies::Actor;
+use crate::application::services::PermissionService;
+use crate::domain::entities::user::Claims;
+use crate::errors::ApiError;
+use super::authentication::extract_privileged_claims;
+
+// ============================================
+// PERMISSION MARKERS
+// ============================================
+
+/// Compile-time name of a permission checked by `Require<P>`
+pub trait PermissionName {
+    const NAME: &'static str;
+}
+
+/// Declare marker types for permissions
+///
+/// # Example
+/// ```text
+/// define_permissions! {
+///     ViewReports => "reports.view",
+/// }
+///
+/// pub async fn reports(auth: Require<ViewReports>) -> ApiResult<HttpResponse> { ... }
+/// ```
+#[macro_export]
+macro_rules! define_permissions {
+    ($($marker:ident => $name:literal),+ $(,)?) => {
+        $(
+            pub struct $marker;
+
+            impl $crate::infrastructure::http::authorization::PermissionName for $marker {
+                const NAME: &'static str = $name;
+            }
+        )+
+    };
+}
+
+pub mod permissions {
+    define_permissions! {
+        ViewUsers => "users.view",
+        ManageUsers => "users.manage",
+        ImpersonateUsers => "users.impersonate",
+        ManageRoles => "roles.manage",
+        ViewQueue => "queue.view",
+        ManageQueue => "queue.manage",
+        ViewAuditLog => "audit.view",
+        ManageMaintenance => "maintenance.manage",
+        CreateTestItems => "test_items.create",
+        UpdateTestItems => "test_items.update",
+        DeleteTestItems => "test_items.delete",
+        ExportTestItems => "test_items.export",
+        ImportTestItems => "test_items.import",
+    }
+}
+
+// ============================================
+// REQUIRE: Authenticated user holding permission P
+// ============================================
+pub struct Require<P: PermissionName>(pub Claims, PhantomData<P>);
+
+impl<P: PermissionName> FromRequest for Require<P> {
+    type Error = ApiError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let claims = extract_privileged_claims(req);
+        let service = permission_service(req);
+
+        Box::pin(async move {
+            let claims = claims.await?;
+            service?.authorize(&claims, P::NAME).await?;
+            Ok(Require(claims, PhantomData))
+        })
+    }
+}
+
+// ============================================
+// OPERATOR: Default-tenant user explicitly granted P (deployment-wide actions)
+// ============================================
+pub struct Operator<P: PermissionName>(pub Claims, PhantomData<P>);
+
+impl<P: PermissionName> FromRequest for Operator<P> {
+    type Error = ApiError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let claims = extract_privileged_claims(req);
+        let service = permission_service(req);
+
+        Box::pin(async move {
+            let claims = claims.await?;
+            service?.authorize_operator(&claims, P::NAME).await?;
+            Ok(Operator(claims, PhantomData))
+        })
+    }
+}
+
+// ============================================
+// ACTOR: Authenticated user + role permissions (for policies)
+// ============================================
+impl FromRequest for Actor {
+    type Error = ApiError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let claims = extract_privileged_claims(req);
+        let service = permission_service(req);
+
+        Box::pin(async move {
+            let claims = claims.await?;
+            let permissions = service?.permissions_for_role(&claims.role).await?;
+            Ok(Actor::new(claims, permissions))
+        })
+    }
+}
+
+fn permission_service(req: &HttpRequest) -> Result<Arc<PermissionService>, ApiError> {
+    req.app_data::<web::Data<Arc<PermissionService>>>()
+        .map(|data| data.get_ref().clone())
+        .ok_or_else(|| ApiError::InternalServerError("Permission service not found".to_string()))
+}
@@ -0,0 +1,105 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::application::dtos::UpdateRoleRequest;
+use crate::application::services::UserService;
+use crate::domain::entities::RequestOrigin;
+use crate::errors::ApiResult;
+use crate::infrastructure::http::authorization::{
+    permissions::{ImpersonateUsers, ManageUsers, ViewUsers},
+    Require,
+};
+use crate::infrastructure::http::pagination::paginated;
+use crate::shared::ValidatedJson;
+
+pub struct AdminUserController;
+
+impl AdminUserController {
+    /// Search users (`?q=&search=&trashed=&role=&is_active=&sort=-email&page=&per_page=&cursor=`)
+    pub async fn search(
+        service: web::Data<Arc<UserService>>,
+        auth: Require<ViewUsers>,
+        req: HttpRequest,
+        query: web::Query<HashMap<String, String>>,
+    ) -> ApiResult<HttpResponse> {
+        let params = query.into_inner();
+        if params.contains_key("q") {
+            let response = service.search_users_text(&auth.0.tenant_id, params).await?;
+            return Ok(paginated(&req, &response));
+        }
+
+        let response = service.search_users(&auth.0.tenant_id, params).await?;
+        Ok(paginated(&req, &response))
+    }
+
+    /// Change a user's role
+    pub async fn update_role(
+        service: web::Data<Arc<UserService>>,
+        auth: Require<ManageUsers>,
+        origin: RequestOrigin,
+        user_id: web::Path<String>,
+        req: ValidatedJson<UpdateRoleRequest>,
+    ) -> ApiResult<HttpResponse> {
+        let user = service
+            .update_user_role(&auth.0, &origin, &user_id.into_inner(), req.0)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(user))
+    }
+
+    /// Re-enable a deactivated account
+    pub async fn activate(
+        service: web::Data<Arc<UserService>>,
+        auth: Require<ManageUsers>,
+        origin: RequestOrigin,
+        user_id: web::Path<String>,
+    ) -> ApiResult<HttpResponse> {
+        service.activate_user(&auth.0, &origin, &user_id.into_inner()).await?;
+        Ok(HttpResponse::NoContent().finish())
+    }
+
+    /// Disable an account (login is refused)
+    pub async fn deactivate(
+        service: web::Data<Arc<UserService>>,
+        auth: Require<ManageUsers>,
+        origin: RequestOrigin,
+        user_id: web::Path<String>,
+    ) -> ApiResult<HttpResponse> {
+        service.deactivate_user(&auth.0, &origin, &user_id.into_inner()).await?;
+        Ok(HttpResponse::NoContent().finish())
+    }
+
+    /// Require a password change on next login
+    pub async fn force_password_reset(
+        service: web::Data<Arc<UserService>>,
+        auth: Require<ManageUsers>,
+        origin: RequestOrigin,
+        user_id: web::Path<String>,
+    ) -> ApiResult<HttpResponse> {
+        service.force_password_reset(&auth.0, &origin, &user_id.into_inner()).await?;
+        Ok(HttpResponse::NoContent().finish())
+    }
+
+    /// Bring back a deleted account
+    pub async fn restore(
+        service: web::Data<Arc<UserService>>,
+        auth: Require<ManageUsers>,
+        origin: RequestOrigin,
+        user_id: web::Path<String>,
+    ) -> ApiResult<HttpResponse> {
+        let user = service.restore_user(&auth.0, &origin, &user_id.into_inner()).await?;
+        Ok(HttpResponse::Ok().json(user))
+    }
+
+    /// Get a short-lived token for a non-admin user
+    pub async fn impersonate(
+        service: web::Data<Arc<UserService>>,
+        auth: Require<ImpersonateUsers>,
+        origin: RequestOrigin,
+        user_id: web::Path<String>,
+    ) -> ApiResult<HttpResponse> {
+        let response = service.impersonate(&auth.0, &origin, &user_id.into_inner()).await?;
+        Ok(HttpResponse::Ok().json(response))
+    }
+}
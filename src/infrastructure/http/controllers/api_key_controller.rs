@@ -0,0 +1,67 @@
+use actix_web::{web, HttpResponse};
+use std::sync::Arc;
+
+use crate::application::dtos::CreateApiKeyRequest;
+use crate::application::services::{ApiKeyService, AuditLogger};
+use crate::domain::entities::{AuditEntry, RequestOrigin};
+use crate::errors::ApiResult;
+use crate::infrastructure::http::authentication::SessionUser;
+use crate::shared::ValidatedJson;
+
+pub struct ApiKeyController;
+
+impl ApiKeyController {
+    /// List the current user's API keys
+    pub async fn list(
+        service: web::Data<Arc<ApiKeyService>>,
+        auth: SessionUser,
+    ) -> ApiResult<HttpResponse> {
+        let keys = service.list(&auth.0).await?;
+        Ok(HttpResponse::Ok().json(keys))
+    }
+
+    /// Create an API key (the plain key is only returned once)
+    pub async fn create(
+        service: web::Data<Arc<ApiKeyService>>,
+        audit: web::Data<Arc<AuditLogger>>,
+        auth: SessionUser,
+        origin: RequestOrigin,
+        req: ValidatedJson<CreateApiKeyRequest>,
+    ) -> ApiResult<HttpResponse> {
+        let response = service.create(&auth.0, req.0).await?;
+
+        audit
+            .record(
+                AuditEntry::new("api_key.created")
+                    .actor(&auth.0)
+                    .origin(&origin)
+                    .target("api_key", &response.api_key.id)
+                    .changes(serde_json::json!({
+                        "name": response.api_key.name,
+                        "prefix": response.api_key.prefix,
+                        "scopes": response.api_key.scopes,
+                        "expires_at": response.api_key.expires_at
+                    })),
+            )
+            .await;
+
+        Ok(HttpResponse::Created().json(response))
+    }
+
+    /// Revoke an API key
+    pub async fn revoke(
+        service: web::Data<Arc<ApiKeyService>>,
+        audit: web::Data<Arc<AuditLogger>>,
+        auth: SessionUser,
+        origin: RequestOrigin,
+        api_key_id: web::Path<String>,
+    ) -> ApiResult<HttpResponse> {
+        let api_key_id = api_key_id.into_inner();
+        service.revoke(&auth.0, &api_key_id).await?;
+
+        audit
+            .record(AuditEntry::new("api_key.revoked").actor(&auth.0).origin(&origin).target("api_key", api_key_id))
+            .await;
+        Ok(HttpResponse::NoContent().finish())
+    }
+}
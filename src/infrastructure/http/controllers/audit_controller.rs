@@ -0,0 +1,23 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+
+use crate::application::dtos::AuditLogQuery;
+use crate::application::services::AuditLogger;
+use crate::errors::ApiResult;
+use crate::infrastructure::http::authorization::{permissions::ViewAuditLog, Require};
+use crate::infrastructure::http::pagination::paginated;
+
+pub struct AuditController;
+
+impl AuditController {
+    /// Query the audit log (`?actor_id=&action=&target_type=&target_id=&ip_address=&from=&to=&page=&per_page=&cursor=`)
+    pub async fn search(
+        audit: web::Data<Arc<AuditLogger>>,
+        auth: Require<ViewAuditLog>,
+        req: HttpRequest,
+        query: web::Query<AuditLogQuery>,
+    ) -> ApiResult<HttpResponse> {
+        let response = audit.search(&auth.0.tenant_id, query.into_inner()).await?;
+        Ok(paginated(&req, &response))
+    }
+}
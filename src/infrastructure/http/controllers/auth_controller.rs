@@ -1,10 +1,12 @@
 use actix_web::{web, HttpResponse};
 use std::sync::Arc;
 
-use crate::application::dtos::{LoginRequest, RegisterUserRequest};
+use crate::application::dtos::{ChangePasswordRequest, LoginRequest, RegisterUserRequest, TwoFactorLoginRequest};
 use crate::application::services::AuthService;
+use crate::domain::entities::RequestOrigin;
 use crate::errors::ApiResult;
 use crate::infrastructure::http::authentication::{AdminUser, AuthUser};
+use crate::infrastructure::http::tenant::CurrentTenant;
 use crate::shared::ValidatedJson;  
 
 pub struct AuthController;
@@ -13,18 +15,42 @@ impl AuthController {
     /// Register with automatic validation
     pub async fn register(
         service: web::Data<Arc<AuthService>>,
+        tenant: CurrentTenant,
         req: ValidatedJson<RegisterUserRequest>,  
     ) -> ApiResult<HttpResponse> {
-        let response = service.register(req.0).await?;  
+        let response = service.register(tenant.id(), req.0).await?;  
         Ok(HttpResponse::Created().json(response))
     }
 
     /// Login with automatic validation
     pub async fn login(
         service: web::Data<Arc<AuthService>>,
+        tenant: CurrentTenant,
+        origin: RequestOrigin,
         req: ValidatedJson<LoginRequest>,  
     ) -> ApiResult<HttpResponse> {
-        let response = service.login(req.0).await?;
+        let response = service.login(tenant.id(), req.0, &origin).await?;
+        Ok(HttpResponse::Ok().json(response))
+    }
+
+    /// Second login step for accounts with 2FA enabled
+    pub async fn login_two_factor(
+        service: web::Data<Arc<AuthService>>,
+        origin: RequestOrigin,
+        req: ValidatedJson<TwoFactorLoginRequest>,
+    ) -> ApiResult<HttpResponse> {
+        let response = service.verify_two_factor_login(req.0, &origin).await?;
+        Ok(HttpResponse::Ok().json(response))
+    }
+
+    /// Change a password flagged for reset (returns the login response)
+    pub async fn change_password(
+        service: web::Data<Arc<AuthService>>,
+        tenant: CurrentTenant,
+        origin: RequestOrigin,
+        req: ValidatedJson<ChangePasswordRequest>,
+    ) -> ApiResult<HttpResponse> {
+        let response = service.change_password(tenant.id(), req.0, &origin).await?;
         Ok(HttpResponse::Ok().json(response))
     }
 
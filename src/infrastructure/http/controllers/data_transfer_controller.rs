@@ -0,0 +1,168 @@
+use actix_files::NamedFile;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{web, HttpRequest, HttpResponse};
+use futures::stream::BoxStream;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::application::dtos::{DataImportResponse, ExportQuery, ImportQuery};
+use crate::application::services::DataTransferService;
+use crate::domain::entities::{DataFormat, DataImport};
+use crate::errors::{ApiError, ApiResult};
+use crate::infrastructure::http::authorization::{
+    permissions::{ExportTestItems, ImportTestItems, ManageUsers, ViewUsers},
+    Require,
+};
+use crate::interfaces::repositories::Trashed;
+use crate::queue::{JobPayload, QueueManager};
+
+pub struct DataTransferController;
+
+impl DataTransferController {
+    /// Download every test item (`?format=csv|ndjson&trashed=with|only`)
+    pub async fn export_test_items(
+        service: web::Data<Arc<DataTransferService>>,
+        auth: Require<ExportTestItems>,
+        query: web::Query<ExportQuery>,
+    ) -> ApiResult<HttpResponse> {
+        let (format, trashed) = Self::export_options(&query)?;
+        let body = service.export_test_items(&auth.0.tenant_id, format, trashed);
+        Ok(Self::download(DataImport::TEST_ITEMS, format, body))
+    }
+
+    /// Upload a file of test items (`?format=csv|ndjson`, the file is the request body).
+    /// Returns `202 Accepted` with the import; poll it for progress.
+    pub async fn import_test_items(
+        service: web::Data<Arc<DataTransferService>>,
+        queue: web::Data<Arc<QueueManager>>,
+        auth: Require<ImportTestItems>,
+        query: web::Query<ImportQuery>,
+        body: web::Payload,
+    ) -> ApiResult<HttpResponse> {
+        let format = Self::format(query.format.as_deref())?;
+        let import = service
+            .store_upload(&auth.0.tenant_id, DataImport::TEST_ITEMS, format, Some(auth.0.sub.clone()), body)
+            .await?;
+        Self::queued(&queue, &auth.0.tenant_id, import).await
+    }
+
+    /// Progress of a test item import
+    pub async fn test_item_import(
+        service: web::Data<Arc<DataTransferService>>,
+        auth: Require<ImportTestItems>,
+        id: web::Path<String>,
+    ) -> ApiResult<HttpResponse> {
+        let import = service.get_import(&auth.0.tenant_id, DataImport::TEST_ITEMS, &id.into_inner()).await?;
+        Ok(HttpResponse::Ok().json(import))
+    }
+
+    /// Rejected rows of a test item import (`row,id,error` CSV)
+    pub async fn test_item_import_errors(
+        service: web::Data<Arc<DataTransferService>>,
+        auth: Require<ImportTestItems>,
+        id: web::Path<String>,
+        req: HttpRequest,
+    ) -> ApiResult<HttpResponse> {
+        let path = service.error_report(&auth.0.tenant_id, DataImport::TEST_ITEMS, &id.into_inner()).await?;
+        Self::report(path, &req).await
+    }
+
+    /// Download every user of the tenant (`?format=csv|ndjson&trashed=with|only`)
+    pub async fn export_users(
+        service: web::Data<Arc<DataTransferService>>,
+        auth: Require<ViewUsers>,
+        query: web::Query<ExportQuery>,
+    ) -> ApiResult<HttpResponse> {
+        let (format, trashed) = Self::export_options(&query)?;
+        let body = service.export_users(&auth.0.tenant_id, format, trashed);
+        Ok(Self::download(DataImport::USERS, format, body))
+    }
+
+    /// Upload a file of profile changes for existing users (`?format=csv|ndjson`)
+    pub async fn import_users(
+        service: web::Data<Arc<DataTransferService>>,
+        queue: web::Data<Arc<QueueManager>>,
+        auth: Require<ManageUsers>,
+        query: web::Query<ImportQuery>,
+        body: web::Payload,
+    ) -> ApiResult<HttpResponse> {
+        let format = Self::format(query.format.as_deref())?;
+        let import = service
+            .store_upload(&auth.0.tenant_id, DataImport::USERS, format, Some(auth.0.sub.clone()), body)
+            .await?;
+        Self::queued(&queue, &auth.0.tenant_id, import).await
+    }
+
+    /// Progress of a user import
+    pub async fn user_import(
+        service: web::Data<Arc<DataTransferService>>,
+        auth: Require<ManageUsers>,
+        id: web::Path<String>,
+    ) -> ApiResult<HttpResponse> {
+        let import = service.get_import(&auth.0.tenant_id, DataImport::USERS, &id.into_inner()).await?;
+        Ok(HttpResponse::Ok().json(import))
+    }
+
+    /// Rejected rows of a user import (`row,id,error` CSV)
+    pub async fn user_import_errors(
+        service: web::Data<Arc<DataTransferService>>,
+        auth: Require<ManageUsers>,
+        id: web::Path<String>,
+        req: HttpRequest,
+    ) -> ApiResult<HttpResponse> {
+        let path = service.error_report(&auth.0.tenant_id, DataImport::USERS, &id.into_inner()).await?;
+        Self::report(path, &req).await
+    }
+
+    fn format(value: Option<&str>) -> Result<DataFormat, ApiError> {
+        match value {
+            Some(value) => DataFormat::parse(value)
+                .ok_or_else(|| ApiError::ValidationError(format!("Unsupported format: {}", value))),
+            None => Ok(DataFormat::Csv),
+        }
+    }
+
+    fn export_options(query: &ExportQuery) -> Result<(DataFormat, Trashed), ApiError> {
+        let trashed = match query.trashed.as_deref() {
+            Some(value) => Trashed::parse(value)
+                .ok_or_else(|| ApiError::ValidationError(format!("Invalid trashed filter: {}", value)))?,
+            None => Trashed::default(),
+        };
+        Ok((Self::format(query.format.as_deref())?, trashed))
+    }
+
+    /// Streamed attachment named after the entity (`test_items.csv`)
+    fn download(entity: &str, format: DataFormat, body: BoxStream<'static, Result<web::Bytes, ApiError>>) -> HttpResponse {
+        HttpResponse::Ok()
+            .content_type(format.content_type())
+            .insert_header(ContentDisposition {
+                disposition: DispositionType::Attachment,
+                parameters: vec![DispositionParam::Filename(format!("{}.{}", entity, format.as_str()))],
+            })
+            .streaming(body)
+    }
+
+    async fn queued(queue: &QueueManager, tenant_id: &str, import: DataImportResponse) -> ApiResult<HttpResponse> {
+        let job_id = queue.enqueue(tenant_id, JobPayload::RunImport { import_id: import.id.clone() }).await?;
+
+        Ok(HttpResponse::Accepted().json(serde_json::json!({
+            "status": "queued",
+            "job_id": job_id,
+            "import": import,
+        })))
+    }
+
+    async fn report(path: PathBuf, req: &HttpRequest) -> ApiResult<HttpResponse> {
+        let file = NamedFile::open_async(&path)
+            .await
+            .map_err(|_| ApiError::NotFound("Error report not found".to_string()))?;
+
+        // Served as text/csv (from the extension), as a download
+        Ok(file
+            .set_content_disposition(ContentDisposition {
+                disposition: DispositionType::Attachment,
+                parameters: vec![DispositionParam::Filename("errors.csv".to_string())],
+            })
+            .into_response(req))
+    }
+}
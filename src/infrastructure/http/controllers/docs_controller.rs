@@ -0,0 +1,32 @@
+use actix_web::{web, HttpResponse};
+use std::sync::Arc;
+
+use crate::errors::ApiResult;
+use crate::infrastructure::templates::TemplateEngine;
+use crate::routes::openapi::OpenApiDocument;
+
+pub struct DocsController;
+
+impl DocsController {
+    /// Swagger UI for the OpenAPI document
+    pub async fn ui(templates: web::Data<Arc<TemplateEngine>>) -> ApiResult<HttpResponse> {
+        let html = templates.render("framework/swagger.html", ())?;
+        Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(html))
+    }
+
+    /// OpenAPI 3.1 document as JSON
+    pub async fn json(document: web::Data<OpenApiDocument>) -> ApiResult<HttpResponse> {
+        Ok(HttpResponse::Ok()
+            .content_type("application/json")
+            .body(document.to_json()))
+    }
+
+    /// OpenAPI 3.1 document as YAML
+    pub async fn yaml(document: web::Data<OpenApiDocument>) -> ApiResult<HttpResponse> {
+        Ok(HttpResponse::Ok()
+            .content_type("application/yaml")
+            .body(document.to_yaml()))
+    }
+}
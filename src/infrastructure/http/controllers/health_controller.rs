@@ -2,7 +2,7 @@ use actix_web::{web, HttpResponse};
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 use sqlx::{FromRow, PgPool};
-use std::sync::{Mutex, OnceLock};
+use std::sync::{Arc, Mutex, OnceLock};
 use sysinfo::{
     Components, CpuRefreshKind, Disks, MemoryRefreshKind, Networks, Pid, ProcessRefreshKind,
     RefreshKind, System,
@@ -10,6 +10,7 @@ use sysinfo::{
 
 use crate::errors::ApiResult;
 use crate::errors::ApiError;
+use crate::infrastructure::templates::TemplateEngine;
 
 // --- UTILITY FUNCTIONS ---
 
@@ -368,8 +369,8 @@ impl HealthController {
     }
 
     /// Endpoint 4: Dashboard HTML
-    pub async fn system_dashboard() -> ApiResult<HttpResponse> {
-        let html = include_str!("../../../../templates/framework/dashboard.html");
+    pub async fn system_dashboard(templates: web::Data<Arc<TemplateEngine>>) -> ApiResult<HttpResponse> {
+        let html = templates.render("framework/dashboard.html", ())?;
         Ok(HttpResponse::Ok()
             .content_type("text/html; charset=utf-8")
             .body(html))
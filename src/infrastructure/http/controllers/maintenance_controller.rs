@@ -0,0 +1,73 @@
+use actix_web::{web, HttpResponse};
+use chrono::Utc;
+use std::sync::Arc;
+
+use crate::application::dtos::{BypassRequest, MaintenanceRequest, MaintenanceResponse, MaintenanceWindowResponse};
+use crate::application::services::MaintenanceService;
+use crate::domain::entities::RequestOrigin;
+use crate::errors::ApiResult;
+use crate::infrastructure::http::authorization::{permissions::ManageMaintenance, Operator};
+use crate::shared::ValidatedJson;
+
+pub struct MaintenanceController;
+
+impl MaintenanceController {
+    /// Current or upcoming maintenance window, for clients (public, reachable during maintenance)
+    pub async fn window(service: web::Data<Arc<MaintenanceService>>) -> ApiResult<HttpResponse> {
+        let state = service.current();
+        Ok(HttpResponse::Ok().json(MaintenanceWindowResponse::from_state(state.as_deref(), Utc::now())))
+    }
+
+    /// Whether the application is in maintenance mode
+    pub async fn status(
+        service: web::Data<Arc<MaintenanceService>>,
+        _auth: Operator<ManageMaintenance>,
+    ) -> ApiResult<HttpResponse> {
+        let state = service.current();
+        Ok(HttpResponse::Ok().json(MaintenanceResponse::from_state(state.as_deref(), Utc::now())))
+    }
+
+    /// Put every instance into maintenance mode, now or for a window (`ironclad down`)
+    pub async fn down(
+        service: web::Data<Arc<MaintenanceService>>,
+        auth: Operator<ManageMaintenance>,
+        origin: RequestOrigin,
+        req: ValidatedJson<MaintenanceRequest>,
+    ) -> ApiResult<HttpResponse> {
+        let (state, secrets) = service.down(&auth.0, &origin, req.0).await?;
+        let response = MaintenanceResponse { secrets, ..MaintenanceResponse::from_state(Some(&state), Utc::now()) };
+        Ok(HttpResponse::Ok().json(response))
+    }
+
+    /// Add or rotate a named bypass secret (`ironclad maintenance:bypass`); the secret is only returned here
+    pub async fn add_bypass(
+        service: web::Data<Arc<MaintenanceService>>,
+        auth: Operator<ManageMaintenance>,
+        origin: RequestOrigin,
+        req: ValidatedJson<BypassRequest>,
+    ) -> ApiResult<HttpResponse> {
+        let response = service.add_bypass(&auth.0, &origin, req.0).await?;
+        Ok(HttpResponse::Created().json(response))
+    }
+
+    /// Revoke a named bypass secret and the cookies it granted (`ironclad maintenance:revoke`)
+    pub async fn revoke_bypass(
+        service: web::Data<Arc<MaintenanceService>>,
+        auth: Operator<ManageMaintenance>,
+        origin: RequestOrigin,
+        name: web::Path<String>,
+    ) -> ApiResult<HttpResponse> {
+        service.revoke_bypass(&auth.0, &origin, &name).await?;
+        Ok(HttpResponse::NoContent().finish())
+    }
+
+    /// Bring every instance back up (`ironclad up`)
+    pub async fn up(
+        service: web::Data<Arc<MaintenanceService>>,
+        auth: Operator<ManageMaintenance>,
+        origin: RequestOrigin,
+    ) -> ApiResult<HttpResponse> {
+        service.up(&auth.0, &origin).await?;
+        Ok(HttpResponse::Ok().json(MaintenanceResponse::from_state(None, Utc::now())))
+    }
+}
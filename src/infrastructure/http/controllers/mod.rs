@@ -3,9 +3,29 @@ pub mod user_controller;
 pub mod test_item_controller;
 pub mod health_controller;
 pub mod queue_controller;  
+pub mod two_factor_controller;
+pub mod permission_controller;
+pub mod admin_user_controller;
+pub mod api_key_controller;
+pub mod oauth_controller;
+pub mod audit_controller;
+pub mod data_transfer_controller;
+pub mod route_controller;
+pub mod docs_controller;
+pub mod maintenance_controller;
 
 pub use auth_controller::AuthController;
 pub use user_controller::UserController;
 pub use test_item_controller::TestItemController;
 pub use health_controller::HealthController;
-pub use queue_controller::QueueController;  
\ No newline at end of file
+pub use queue_controller::QueueController;  
+pub use two_factor_controller::TwoFactorController;
+pub use permission_controller::PermissionController;
+pub use admin_user_controller::AdminUserController;
+pub use api_key_controller::ApiKeyController;
+pub use oauth_controller::OAuthController;
+pub use audit_controller::AuditController;
+pub use data_transfer_controller::DataTransferController;
+pub use route_controller::RouteController;
+pub use docs_controller::DocsController;
+pub use maintenance_controller::MaintenanceController;
@@ -0,0 +1,89 @@
+use actix_web::{
+    cookie::{time::Duration as CookieDuration, Cookie, SameSite},
+    web, HttpRequest, HttpResponse,
+};
+use std::sync::Arc;
+
+use crate::application::dtos::OAuthCallbackQuery;
+use crate::application::services::OAuthService;
+use crate::config::AppConfig;
+use crate::domain::entities::RequestOrigin;
+use crate::errors::ApiResult;
+use crate::infrastructure::http::authentication::SessionUser;
+use crate::infrastructure::http::tenant::CurrentTenant;
+
+/// Ties the pending authorization to the browser that started it
+const STATE_COOKIE: &str = "oauth_state";
+
+pub struct OAuthController;
+
+impl OAuthController {
+    /// List enabled providers
+    pub async fn providers(service: web::Data<Arc<OAuthService>>) -> ApiResult<HttpResponse> {
+        Ok(HttpResponse::Ok().json(service.providers()))
+    }
+
+    /// Redirect the browser to the provider's consent screen
+    pub async fn authorize(
+        service: web::Data<Arc<OAuthService>>,
+        config: web::Data<Arc<AppConfig>>,
+        tenant: CurrentTenant,
+        provider: web::Path<String>,
+    ) -> ApiResult<HttpResponse> {
+        let redirect = service.authorization_url(tenant.id(), &provider.into_inner()).await?;
+        Ok(HttpResponse::Found()
+            .insert_header(("Location", redirect.url))
+            .cookie(Self::state_cookie(&config, redirect.state, config.oauth.state_ttl))
+            .finish())
+    }
+
+    /// Provider redirect target: returns the same payload as `/auth/login`
+    pub async fn callback(
+        service: web::Data<Arc<OAuthService>>,
+        config: web::Data<Arc<AppConfig>>,
+        req: HttpRequest,
+        origin: RequestOrigin,
+        provider: web::Path<String>,
+        query: web::Query<OAuthCallbackQuery>,
+    ) -> ApiResult<HttpResponse> {
+        let bound_state = req.cookie(STATE_COOKIE);
+        let response = service
+            .callback(
+                &provider.into_inner(),
+                query.into_inner(),
+                bound_state.as_ref().map(|cookie| cookie.value()),
+                &origin,
+            )
+            .await?;
+
+        // The state is single-use
+        let mut expired = Self::state_cookie(&config, String::new(), 0);
+        expired.make_removal();
+        Ok(HttpResponse::Ok().cookie(expired).json(response))
+    }
+
+    fn state_cookie(config: &AppConfig, state: String, ttl: i64) -> Cookie<'static> {
+        let mut cookie = Cookie::build(STATE_COOKIE, state)
+            .path("/api/auth/oauth")
+            .http_only(true)
+            .secure(true)
+            // Lax still sends it on the provider's top-level redirect back to the callback
+            .same_site(SameSite::Lax)
+            .max_age(CookieDuration::seconds(ttl))
+            .finish();
+        // Tenant subdomains start the flow, the shared callback URL finishes it
+        if let Some(base_domain) = &config.tenancy.base_domain {
+            cookie.set_domain(base_domain.clone());
+        }
+        cookie
+    }
+
+    /// Identities linked to the current user
+    pub async fn identities(
+        service: web::Data<Arc<OAuthService>>,
+        auth: SessionUser,
+    ) -> ApiResult<HttpResponse> {
+        let identities = service.identities(&auth.0).await?;
+        Ok(HttpResponse::Ok().json(identities))
+    }
+}
@@ -0,0 +1,101 @@
+use actix_web::{web, HttpResponse};
+use std::sync::Arc;
+
+use crate::application::dtos::{CreatePermissionRequest, GrantPermissionRequest};
+use crate::application::services::{AuditLogger, PermissionService};
+use crate::domain::entities::{AuditEntry, RequestOrigin};
+use crate::errors::ApiResult;
+use crate::infrastructure::http::authorization::{permissions::ManageRoles, Operator};
+use crate::shared::ValidatedJson;
+
+pub struct PermissionController;
+
+impl PermissionController {
+    /// List every role with its granted permissions
+    pub async fn list_roles(
+        service: web::Data<Arc<PermissionService>>,
+        _auth: Operator<ManageRoles>,
+    ) -> ApiResult<HttpResponse> {
+        let roles = service.list_roles().await?;
+        Ok(HttpResponse::Ok().json(roles))
+    }
+
+    /// List the permission catalogue
+    pub async fn list_permissions(
+        service: web::Data<Arc<PermissionService>>,
+        _auth: Operator<ManageRoles>,
+    ) -> ApiResult<HttpResponse> {
+        let permissions = service.list_permissions().await?;
+        Ok(HttpResponse::Ok().json(permissions))
+    }
+
+    /// Add a permission to the catalogue
+    pub async fn create_permission(
+        service: web::Data<Arc<PermissionService>>,
+        audit: web::Data<Arc<AuditLogger>>,
+        auth: Operator<ManageRoles>,
+        origin: RequestOrigin,
+        req: ValidatedJson<CreatePermissionRequest>,
+    ) -> ApiResult<HttpResponse> {
+        let permission = service.create_permission(req.0).await?;
+
+        audit
+            .record(
+                AuditEntry::new("permission.created")
+                    .actor(&auth.0)
+                    .origin(&origin)
+                    .target("permission", permission.name.as_str()),
+            )
+            .await;
+
+        Ok(HttpResponse::Created().json(permission))
+    }
+
+    /// Grant a permission to a role
+    pub async fn grant(
+        service: web::Data<Arc<PermissionService>>,
+        audit: web::Data<Arc<AuditLogger>>,
+        auth: Operator<ManageRoles>,
+        origin: RequestOrigin,
+        role: web::Path<String>,
+        req: ValidatedJson<GrantPermissionRequest>,
+    ) -> ApiResult<HttpResponse> {
+        let permission = req.0.permission.clone();
+        let response = service.grant(&role.into_inner(), req.0).await?;
+
+        audit
+            .record(
+                AuditEntry::new("role.permission_granted")
+                    .actor(&auth.0)
+                    .origin(&origin)
+                    .target("role", &response.role)
+                    .changes(serde_json::json!({ "permission": permission })),
+            )
+            .await;
+
+        Ok(HttpResponse::Ok().json(response))
+    }
+
+    /// Revoke a permission from a role
+    pub async fn revoke(
+        service: web::Data<Arc<PermissionService>>,
+        audit: web::Data<Arc<AuditLogger>>,
+        auth: Operator<ManageRoles>,
+        origin: RequestOrigin,
+        path: web::Path<(String, String)>,
+    ) -> ApiResult<HttpResponse> {
+        let (role, permission) = path.into_inner();
+        service.revoke(&role, &permission).await?;
+
+        audit
+            .record(
+                AuditEntry::new("role.permission_revoked")
+                    .actor(&auth.0)
+                    .origin(&origin)
+                    .target("role", role.to_lowercase())
+                    .changes(serde_json::json!({ "permission": permission })),
+            )
+            .await;
+        Ok(HttpResponse::NoContent().finish())
+    }
+}
@@ -2,7 +2,10 @@ use actix_web::{web, HttpResponse};
 use std::sync::Arc;
 
 use crate::errors::ApiResult;
+use crate::infrastructure::http::authorization::{permissions::{ManageQueue, ViewQueue}, Require};
 use crate::queue::QueueManager;
+use crate::application::services::AuditLogger;
+use crate::domain::entities::{AuditEntry, RequestOrigin};
 
 use crate::errors::ApiError;
 use sqlx::PgPool;
@@ -10,17 +13,24 @@ use sqlx::PgPool;
 pub struct QueueController;
 
 impl QueueController {
-    pub async fn jobs_info(pool: web::Data<PgPool>) -> ApiResult<HttpResponse> {
+    pub async fn jobs_info(
+        pool: web::Data<PgPool>,
+        auth: Require<ViewQueue>,
+    ) -> ApiResult<HttpResponse> {
         use sqlx::Row;
 
+        let tenant_id = &auth.0.tenant_id;
+
         // 1. Counters by status
         let status_counts = sqlx::query(
             r#"
             SELECT status, COUNT(*) as count
             FROM job_queue
+            WHERE tenant_id = $1
             GROUP BY status
             "#
         )
+        .bind(tenant_id)
         .fetch_all(pool.get_ref())
         .await
         .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
@@ -48,15 +58,17 @@ impl QueueController {
             r#"
             SELECT COUNT(*)
             FROM job_queue
-            WHERE status = 'processing'
+            WHERE tenant_id = $1
+            AND status = 'processing'
             AND lock_expires_at < NOW()
             "#
         )
+        .bind(tenant_id)
         .fetch_one(pool.get_ref())
         .await
         .unwrap_or(0);
 
-        // 3. Active workers
+        // 3. Active workers (shared by all tenants)
         let active_workers: i64 = sqlx::query_scalar(
             r#"
             SELECT COUNT(DISTINCT worker_id)
@@ -74,9 +86,11 @@ impl QueueController {
             r#"
             SELECT AVG(EXTRACT(EPOCH FROM (completed_at - started_at)))
             FROM job_queue
-            WHERE completed_at IS NOT NULL
+            WHERE tenant_id = $1
+            AND completed_at IS NOT NULL
             "#
         )
+        .bind(tenant_id)
         .fetch_one(pool.get_ref())
         .await
         .ok();
@@ -108,17 +122,27 @@ impl QueueController {
     /// Get job statistics
     pub async fn get_stats(
         queue: web::Data<Arc<QueueManager>>,
+        auth: Require<ViewQueue>,
     ) -> ApiResult<HttpResponse> {
-        let stats = queue.get_statistics().await?;
+        let stats = queue.get_statistics(&auth.0.tenant_id).await?;
         Ok(HttpResponse::Ok().json(stats))
     }
 
     /// Retry failed job
     pub async fn retry_job(
         queue: web::Data<Arc<QueueManager>>,
+        audit: web::Data<Arc<AuditLogger>>,
         job_id: web::Path<String>,  
+        auth: Require<ManageQueue>,
+        origin: RequestOrigin,
     ) -> ApiResult<HttpResponse> {
-        queue.retry_job(&job_id.into_inner()).await?;
+        let job_id = job_id.into_inner();
+        queue.retry_job(&auth.0.tenant_id, &job_id).await?;
+
+        audit
+            .record(AuditEntry::new("queue.job_retried").actor(&auth.0).origin(&origin).target("job", job_id))
+            .await;
+
         Ok(HttpResponse::Ok().json(serde_json::json!({
             "status": "ok",
             "message": "Job queued for retry"
@@ -128,9 +152,18 @@ impl QueueController {
     /// Cancel pending job
     pub async fn cancel_job(
         queue: web::Data<Arc<QueueManager>>,
+        audit: web::Data<Arc<AuditLogger>>,
         job_id: web::Path<String>,  
+        auth: Require<ManageQueue>,
+        origin: RequestOrigin,
     ) -> ApiResult<HttpResponse> {
-        queue.cancel_job(&job_id.into_inner()).await?;
+        let job_id = job_id.into_inner();
+        queue.cancel_job(&auth.0.tenant_id, &job_id).await?;
+
+        audit
+            .record(AuditEntry::new("queue.job_cancelled").actor(&auth.0).origin(&origin).target("job", job_id))
+            .await;
+
         Ok(HttpResponse::Ok().json(serde_json::json!({
             "status": "ok",
             "message": "Job cancelled"
@@ -140,11 +173,12 @@ impl QueueController {
     pub async fn requeue_dlq(
         queue: web::Data<QueueManager>,
         path: web::Path<String>,
+        auth: Require<ManageQueue>,
     ) -> ApiResult<HttpResponse> {
 
         let dlq_id = path.into_inner();
 
-        let new_job_id = queue.requeue_from_dlq(&dlq_id).await?;
+        let new_job_id = queue.requeue_from_dlq(&auth.0.tenant_id, &dlq_id).await?;
 
         Ok(HttpResponse::Ok().json(serde_json::json!({
             "message": "Job requeued successfully",
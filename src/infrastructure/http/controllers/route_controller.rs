@@ -0,0 +1,23 @@
+use actix_web::{web, HttpResponse};
+
+use crate::application::dtos::RouteListQuery;
+use crate::errors::ApiResult;
+use crate::infrastructure::http::authentication::AdminUser;
+use crate::routes::RouteTable;
+
+pub struct RouteController;
+
+impl RouteController {
+    /// Registered routes with their handler, guards and middleware (`?method=&path=`)
+    pub async fn list(
+        table: web::Data<RouteTable>,
+        _admin: AdminUser,
+        query: web::Query<RouteListQuery>,
+    ) -> ApiResult<HttpResponse> {
+        let routes = table.filter(query.method.as_deref(), query.path.as_deref());
+        Ok(HttpResponse::Ok().json(serde_json::json!({
+            "total": routes.len(),
+            "routes": routes,
+        })))
+    }
+}
@@ -1,81 +1,207 @@
-use actix_web::{web, HttpResponse};
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::application::dtos::{CreateTestItemRequest, UpdateTestItemRequest};
+use crate::application::dtos::{
+    BulkCreateTestItemsRequest, BulkDeleteTestItemsRequest, BulkUpdateTestItemsRequest, CreateTestItemRequest,
+    UpdateTestItemRequest,
+};
+use crate::application::policies::{Actor, Policy, TestItemPolicy};
 use crate::application::services::TestItemService;
+use crate::config::AppConfig;
 use crate::errors::{ApiError, ApiResult};
-use crate::infrastructure::http::authentication::AuthUser;
+use crate::infrastructure::http::authorization::permissions::{CreateTestItems, DeleteTestItems, UpdateTestItems};
+use crate::infrastructure::http::authorization::Require;
+use crate::infrastructure::http::etag::{etag, IfMatch};
+use crate::infrastructure::http::pagination::paginated;
+use crate::infrastructure::http::tenant::CurrentTenant;
 use crate::shared::ValidatedJson;
 use crate::queue::{QueueManager, JobPayload};  
 
+/// Body limit of the bulk endpoints (the default JSON limit is 2 MB)
+pub const BULK_BODY_LIMIT: usize = 32 * 1024 * 1024;
+
 pub struct TestItemController;
 
 impl TestItemController {
-    /// Create new test item
+    /// Create new test item (test_items.create)
     pub async fn create(
         service: web::Data<Arc<TestItemService>>,
+        auth: Require<CreateTestItems>,
         req: ValidatedJson<CreateTestItemRequest>,
     ) -> ApiResult<HttpResponse> {
-        let item = service.create(req.0).await?;
-        Ok(HttpResponse::Created().json(item))
+        let item = service.create(&auth.0.tenant_id, req.0).await?;
+        Ok(HttpResponse::Created().insert_header(etag(item.version)).json(item))
     }
 
-    /// Get all test items with pagination
+    /// List test items (`?subject[contains]=&created_at[gte]=&sort=-created_at&page=&per_page=&cursor=`,
+    /// `?trashed=with|only` includes deleted items, `?q=` ranks them by full-text relevance)
     pub async fn get_all(
         service: web::Data<Arc<TestItemService>>,
-        query: web::Query<PaginationQuery>,
+        tenant: CurrentTenant,
+        req: HttpRequest,
+        query: web::Query<HashMap<String, String>>,
     ) -> ApiResult<HttpResponse> {
-        let page = query.page.unwrap_or(1);
-        let per_page = query.per_page.unwrap_or(20).min(100);
-
-        let response = service.get_all(page, per_page).await?;
-        Ok(HttpResponse::Ok().json(response))
+        let params = query.into_inner();
+        if params.contains_key("q") {
+            let response = service.search(tenant.id(), params).await?;
+            return Ok(paginated(&req, &response));
+        }
+
+        let response = service.get_all(tenant.id(), params).await?;
+        Ok(paginated(&req, &response))
     }
 
     /// Get test item by ID
     pub async fn get_by_id(
         service: web::Data<Arc<TestItemService>>,
+        tenant: CurrentTenant,
         id: web::Path<String>,
     ) -> ApiResult<HttpResponse> {
-        let item = service.get_by_id(&id.into_inner()).await?
+        let item = service.get_by_id(tenant.id(), &id.into_inner()).await?
             .ok_or_else(|| ApiError::NotFound("Test item not found".to_string()))?;
 
-        Ok(HttpResponse::Ok().json(item))
+        Ok(HttpResponse::Ok().insert_header(etag(item.version)).json(item))
     }
 
-    /// Update test item
+    /// Update test item (test_items.update). `If-Match: "<version>"` guards against lost updates.
     pub async fn update(
         service: web::Data<Arc<TestItemService>>,
+        actor: Actor,
+        if_match: IfMatch,
         id: web::Path<String>,
         req: ValidatedJson<UpdateTestItemRequest>,
     ) -> ApiResult<HttpResponse> {
-        let item = service.update(&id.into_inner(), req.0).await?;
-        Ok(HttpResponse::Ok().json(item))
+        let target = service.find_item(actor.tenant_id(), &id.into_inner(), false).await?;
+        TestItemPolicy.update(&actor, &target).authorize("test item")?;
+
+        let item = service.update(&target.tenant_id, &target.id, req.0, if_match.0).await?;
+        Ok(HttpResponse::Ok().insert_header(etag(item.version)).json(item))
     }
 
-    /// Delete test item
+    /// Delete test item (test_items.delete, moves it to the trash)
     pub async fn delete(
         service: web::Data<Arc<TestItemService>>,
+        actor: Actor,
         id: web::Path<String>,
     ) -> ApiResult<HttpResponse> {
-        service.delete(&id.into_inner()).await?;
+        let target = service.find_item(actor.tenant_id(), &id.into_inner(), false).await?;
+        TestItemPolicy.delete(&actor, &target).authorize("test item")?;
+
+        service.delete(&target.tenant_id, &target.id).await?;
         Ok(HttpResponse::NoContent().finish())
     }
 
-    /// Schedule test item deletion using centralized queue
+    /// Restore a deleted test item (test_items.delete)
+    pub async fn restore(
+        service: web::Data<Arc<TestItemService>>,
+        actor: Actor,
+        id: web::Path<String>,
+    ) -> ApiResult<HttpResponse> {
+        let target = service.find_item(actor.tenant_id(), &id.into_inner(), true).await?;
+        TestItemPolicy.restore(&actor, &target).authorize("test item")?;
+
+        let item = service.restore(&target.tenant_id, &target.id).await?;
+        Ok(HttpResponse::Ok().insert_header(etag(item.version)).json(item))
+    }
+
+    /// Create many test items (`{ "items": [...] }`, test_items.create). Returns one result per element,
+    /// or `202 Accepted` with a job id when the batch is above `config.bulk.sync_limit`.
+    pub async fn bulk_create(
+        service: web::Data<Arc<TestItemService>>,
+        queue: web::Data<Arc<QueueManager>>,
+        config: web::Data<Arc<AppConfig>>,
+        auth: Require<CreateTestItems>,
+        req: ValidatedJson<BulkCreateTestItemsRequest>,
+    ) -> ApiResult<HttpResponse> {
+        let tenant_id = &auth.0.tenant_id;
+        let items = req.0.items;
+        if Self::runs_inline(&config, items.len())? {
+            return Ok(HttpResponse::Ok().json(service.bulk_create(tenant_id, items).await?));
+        }
+
+        let count = items.len();
+        let job_id = queue.enqueue(tenant_id, JobPayload::BulkCreateTestItems { items }).await?;
+        Ok(Self::queued(job_id, count))
+    }
+
+    /// Update many test items (`{ "items": [{ "id", "version"?, "subject"?, "optional_field"? }] }`, test_items.update)
+    pub async fn bulk_update(
+        service: web::Data<Arc<TestItemService>>,
+        queue: web::Data<Arc<QueueManager>>,
+        config: web::Data<Arc<AppConfig>>,
+        auth: Require<UpdateTestItems>,
+        req: ValidatedJson<BulkUpdateTestItemsRequest>,
+    ) -> ApiResult<HttpResponse> {
+        let tenant_id = &auth.0.tenant_id;
+        let items = req.0.items;
+        if Self::runs_inline(&config, items.len())? {
+            return Ok(HttpResponse::Ok().json(service.bulk_update(tenant_id, items).await?));
+        }
+
+        let count = items.len();
+        let job_id = queue.enqueue(tenant_id, JobPayload::BulkUpdateTestItems { items }).await?;
+        Ok(Self::queued(job_id, count))
+    }
+
+    /// Move many test items to the trash (`{ "ids": [...] }`, test_items.delete)
+    pub async fn bulk_delete(
+        service: web::Data<Arc<TestItemService>>,
+        queue: web::Data<Arc<QueueManager>>,
+        config: web::Data<Arc<AppConfig>>,
+        auth: Require<DeleteTestItems>,
+        req: ValidatedJson<BulkDeleteTestItemsRequest>,
+    ) -> ApiResult<HttpResponse> {
+        let tenant_id = &auth.0.tenant_id;
+        let ids = req.0.ids;
+        if Self::runs_inline(&config, ids.len())? {
+            return Ok(HttpResponse::Ok().json(service.bulk_delete(tenant_id, ids).await?));
+        }
+
+        let count = ids.len();
+        let job_id = queue.enqueue(tenant_id, JobPayload::BulkDeleteTestItems { ids }).await?;
+        Ok(Self::queued(job_id, count))
+    }
+
+    /// Whether a batch runs within the request; above `config.bulk.sync_limit` it goes to the queue
+    fn runs_inline(config: &AppConfig, len: usize) -> Result<bool, ApiError> {
+        if len > config.bulk.max_items {
+            return Err(ApiError::ValidationError(format!(
+                "A bulk request cannot have more than {} items",
+                config.bulk.max_items
+            )));
+        }
+        Ok(len <= config.bulk.sync_limit)
+    }
+
+    fn queued(job_id: String, items: usize) -> HttpResponse {
+        HttpResponse::Accepted().json(serde_json::json!({
+            "status": "queued",
+            "job_id": job_id,
+            "items": items,
+        }))
+    }
+
+    /// Schedule test item deletion using centralized queue (test_items.delete)
     pub async fn schedule_delete(
+        service: web::Data<Arc<TestItemService>>,
         queue: web::Data<Arc<QueueManager>>,
+        actor: Actor,
         id: web::Path<String>,
     ) -> ApiResult<HttpResponse> {
+        let target = service.find_item(actor.tenant_id(), &id.into_inner(), false).await?;
+        TestItemPolicy.delete(&actor, &target).authorize("test item")?;
 
-        let item_id = id.into_inner();
+        let item_id = target.id;
 
         // 🎯 Definir prioridad y cola según contexto
         // Define priority and queue based on context
         let priority = 10;              // HIGH
         let queue_name = "critical";    // critical | default | low
 
+        // The job runs in the tenant that scheduled it
         let job_id = queue.enqueue_in(
+            &target.tenant_id,
             JobPayload::DeleteTestItem { item_id: item_id.clone() },
             10,
             priority,
@@ -96,12 +222,14 @@ impl TestItemController {
     // Yoy can uncomment this method and add the corresponding route in src/routes/api.rs if you want to support scheduling at specific times instead of just a fixed delay.
     // pub async fn schedule_delete_at(
     //     queue: web::Data<Arc<QueueManager>>,
+    //     tenant: CurrentTenant,
     //     id: web::Path<String>,
     //     req: web::Json<ScheduleRequest>,
     // ) -> ApiResult<HttpResponse> {
     //     let item_id = id.into_inner();
         
     //     let job_id = queue.schedule(
+    //         tenant.id(),
     //         JobPayload::DeleteTestItem { item_id: item_id.clone() },
     //         req.scheduled_at,
     //         3  // max 3 attempts
@@ -117,12 +245,6 @@ impl TestItemController {
 
 }
 
-#[derive(serde::Deserialize)]
-pub struct PaginationQuery {
-    pub page: Option<i32>,
-    pub per_page: Option<i32>,
-}
-
 #[derive(serde::Deserialize)]
 pub struct ScheduleRequest {
     pub scheduled_at: chrono::DateTime<chrono::Utc>,
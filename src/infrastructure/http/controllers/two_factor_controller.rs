@@ -0,0 +1,77 @@
+use actix_web::{web, HttpResponse};
+use std::sync::Arc;
+
+use crate::application::dtos::{DisableTwoFactorRequest, TwoFactorCodeRequest};
+use crate::application::services::{AuditLogger, TwoFactorService};
+use crate::domain::entities::{AuditEntry, RequestOrigin};
+use crate::errors::ApiResult;
+use crate::infrastructure::http::authentication::SessionUser;
+use crate::shared::ValidatedJson;
+
+pub struct TwoFactorController;
+
+impl TwoFactorController {
+    /// Start TOTP enrollment (returns secret + otpauth URI)
+    pub async fn setup(
+        service: web::Data<Arc<TwoFactorService>>,
+        auth: SessionUser,
+    ) -> ApiResult<HttpResponse> {
+        let response = service.setup(&auth.0).await?;
+        Ok(HttpResponse::Ok().json(response))
+    }
+
+    /// Confirm enrollment with a code; returns the recovery codes once
+    pub async fn confirm(
+        service: web::Data<Arc<TwoFactorService>>,
+        audit: web::Data<Arc<AuditLogger>>,
+        auth: SessionUser,
+        origin: RequestOrigin,
+        req: ValidatedJson<TwoFactorCodeRequest>,
+    ) -> ApiResult<HttpResponse> {
+        let response = service.confirm(&auth.0, req.0).await?;
+
+        audit
+            .record(AuditEntry::new("two_factor.enabled").actor(&auth.0).origin(&origin).target("user", &auth.0.sub))
+            .await;
+
+        Ok(HttpResponse::Ok().json(response))
+    }
+
+    /// Disable 2FA (password + current code)
+    pub async fn disable(
+        service: web::Data<Arc<TwoFactorService>>,
+        audit: web::Data<Arc<AuditLogger>>,
+        auth: SessionUser,
+        origin: RequestOrigin,
+        req: ValidatedJson<DisableTwoFactorRequest>,
+    ) -> ApiResult<HttpResponse> {
+        service.disable(&auth.0, req.0).await?;
+
+        audit
+            .record(AuditEntry::new("two_factor.disabled").actor(&auth.0).origin(&origin).target("user", &auth.0.sub))
+            .await;
+        Ok(HttpResponse::NoContent().finish())
+    }
+
+    /// Regenerate recovery codes
+    pub async fn regenerate_recovery_codes(
+        service: web::Data<Arc<TwoFactorService>>,
+        audit: web::Data<Arc<AuditLogger>>,
+        auth: SessionUser,
+        origin: RequestOrigin,
+        req: ValidatedJson<TwoFactorCodeRequest>,
+    ) -> ApiResult<HttpResponse> {
+        let response = service.regenerate_recovery_codes(&auth.0, req.0).await?;
+
+        audit
+            .record(
+                AuditEntry::new("two_factor.recovery_codes_regenerated")
+                    .actor(&auth.0)
+                    .origin(&origin)
+                    .target("user", &auth.0.sub),
+            )
+            .await;
+
+        Ok(HttpResponse::Ok().json(response))
+    }
+}
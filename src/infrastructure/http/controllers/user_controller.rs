@@ -1,10 +1,17 @@
-use actix_web::{web, HttpResponse};
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::application::dtos::{UpdateProfileRequest, UpdateRoleRequest};
+use crate::application::dtos::UpdateProfileRequest;
+use crate::application::policies::{Actor, Policy, UserPolicy};
 use crate::application::services::UserService;
+use crate::domain::entities::RequestOrigin;
 use crate::errors::{ApiError, ApiResult};
-use crate::infrastructure::http::authentication::{AdminUser, AuthUser};
+use crate::infrastructure::http::authentication::{own_session, AuthUser};
+use crate::infrastructure::http::authorization::{permissions::ViewUsers, Require};
+use crate::infrastructure::http::etag::{etag, IfMatch};
+use crate::infrastructure::http::pagination::paginated;
+use crate::infrastructure::http::tenant::CurrentTenant;
 use crate::shared::ValidatedJson;  // 🆕
 
 pub struct UserController;
@@ -16,117 +23,91 @@ impl UserController {
         auth: AuthUser,
     ) -> ApiResult<HttpResponse> {
         let user = service
-            .get_user(&auth.0.sub)
+            .get_user(&auth.0.tenant_id, &auth.0.sub)
             .await?
             .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
 
-        Ok(HttpResponse::Ok().json(user))
+        Ok(HttpResponse::Ok().insert_header(etag(user.version)).json(user))
     }
 
-    /// Get all users (admin only)
+    /// Get all users (admin only), with the same filter/sort/page params as other lists
+    /// and `?q=` for full-text search
     pub async fn get_all_users(
         service: web::Data<Arc<UserService>>,
-        _admin: AdminUser,
-        query: web::Query<PaginationQuery>,
+        auth: Require<ViewUsers>,
+        req: HttpRequest,
+        query: web::Query<HashMap<String, String>>,
     ) -> ApiResult<HttpResponse> {
-        let page = query.page.unwrap_or(1);
-        let per_page = query.per_page.unwrap_or(20).min(100); // Max 100 per page
+        let params = query.into_inner();
+        if params.contains_key("q") {
+            let response = service.search_users_text(&auth.0.tenant_id, params).await?;
+            return Ok(paginated(&req, &response));
+        }
 
-        let response = service.get_all_users(page, per_page).await?;
-        Ok(HttpResponse::Ok().json(response))
+        let response = service.get_all_users(&auth.0.tenant_id, params).await?;
+        Ok(paginated(&req, &response))
     }
 
-    /// Get user by ID (self or admin)
+    /// Get user by ID (self or users.view)
     pub async fn get_user(
         service: web::Data<Arc<UserService>>,
-        auth: AuthUser,
+        actor: Actor,
         user_id: web::Path<String>,
     ) -> ApiResult<HttpResponse> {
-        let requested_id = user_id.into_inner();
-
-        // Only admin or the same user can view
-        if auth.0.sub != requested_id && !auth.0.is_admin() {
-            return Err(ApiError::Forbidden(
-                "You don't have permission to view this user".to_string(),
-            ));
-        }
-
-        let user = service
-            .get_user(&requested_id)
-            .await?
-            .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
+        let user = service.find_user(actor.tenant_id(), &user_id.into_inner()).await?;
+        UserPolicy.view(&actor, &user).authorize("user")?;
 
-        Ok(HttpResponse::Ok().json(user))
+        Ok(HttpResponse::Ok().insert_header(etag(user.version)).json(user.to_response()))
     }
 
     /// Get all users without session (public endpoint)
     /// This is just an example and should be used with caution in a real application
     pub async fn get_all_users_no_session(
         service: web::Data<Arc<UserService>>,
+        tenant: CurrentTenant,
     ) -> ApiResult<HttpResponse> {
-        let users = service.get_all_users_no_pagination().await?;
+        let users = service.get_all_users_no_pagination(tenant.id()).await?;
         Ok(HttpResponse::Ok().json(users))
     }
 
-    /// Update user profile (self only)
+    /// Update user profile (self or users.manage). Honors `If-Match`.
     pub async fn update_profile(
         service: web::Data<Arc<UserService>>,
-        auth: AuthUser,
+        actor: Actor,
+        if_match: IfMatch,
         user_id: web::Path<String>,
         req: ValidatedJson<UpdateProfileRequest>,  // 🆕
     ) -> ApiResult<HttpResponse> {
-        let requested_id = user_id.into_inner();
-
-        if auth.0.sub != requested_id {
-            return Err(ApiError::Forbidden(
-                "You can only update your own profile".to_string(),
-            ));
+        let target = service.find_user(actor.tenant_id(), &user_id.into_inner()).await?;
+        UserPolicy.update(&actor, &target).authorize("user")?;
+        if req.0.password.is_some() {
+            own_session(actor.claims.clone())?;
+            // Administrators force a reset instead of choosing another user's password
+            if actor.claims.sub != target.id {
+                return Err(ApiError::Forbidden(
+                    "Use force-password-reset to change another user's password".to_string(),
+                ));
+            }
         }
 
         let user = service
-            .update_profile(&requested_id, req.0)  // 🆕 req.0
+            .update_profile(&target.tenant_id, &target.id, req.0, if_match.0)  // 🆕 req.0
             .await?;
 
-        Ok(HttpResponse::Ok().json(user))
+        Ok(HttpResponse::Ok().insert_header(etag(user.version)).json(user))
     }
 
-    /// Update user role (admin only)
-    // pub async fn update_user_role(
-    //     service: web::Data<Arc<UserService>>,
-    //     _admin: AdminUser,
-    //     user_id: web::Path<String>,
-    //     req: ValidatedJson<UpdateRoleRequest>,  // 🆕
-    // ) -> ApiResult<HttpResponse> {
-    //     let user = service
-    //         .update_user_role(&user_id.into_inner(), req.0)  // 🆕
-    //         .await?;
-
-    //     Ok(HttpResponse::Ok().json(user))
-    // }
-
-    /// Delete user (self or admin)
+    /// Delete user (self or users.manage)
     pub async fn delete_user(
         service: web::Data<Arc<UserService>>,
-        auth: AuthUser,
+        actor: Actor,
+        origin: RequestOrigin,
         user_id: web::Path<String>,
     ) -> ApiResult<HttpResponse> {
-        let requested_id = user_id.into_inner();
-
-        // Only admin or the same user can delete
-        if auth.0.sub != requested_id && !auth.0.is_admin() {
-            return Err(ApiError::Forbidden(
-                "You don't have permission to delete this user".to_string(),
-            ));
-        }
+        let target = service.find_user(actor.tenant_id(), &user_id.into_inner()).await?;
+        UserPolicy.delete(&actor, &target).authorize("user")?;
 
-        service.delete_user(&requested_id).await?;
+        service.delete_user(&actor.claims, &origin, &target.id).await?;
         Ok(HttpResponse::NoContent().finish())
     }
 }
-
-/// Query params for pagination
-#[derive(serde::Deserialize)]
-pub struct PaginationQuery {
-    pub page: Option<i32>,
-    pub per_page: Option<i32>,
-}
\ No newline at end of file
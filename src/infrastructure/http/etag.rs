@@ -0,0 +1,85 @@
+use actix_web::{dev::Payload, http::header, FromRequest, HttpRequest};
+use futures::future::{ready, Ready};
+use crate::errors::ApiError;
+
+// ============================================
+// ETAGS: the entity version as a strong tag (`"3"`)
+// ============================================
+
+/// `ETag` header for a resource at `version`
+pub fn etag(version: i32) -> header::ETag {
+    header::ETag(header::EntityTag::new_strong(version.to_string()))
+}
+
+/// Version the client based its write on, from `If-Match`.
+///
+/// `None` when the header is absent or `*`; the update is then checked against the
+/// version loaded by the handler. A stale version ends in `409 Conflict`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IfMatch(pub Option<i32>);
+
+impl FromRequest for IfMatch {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let value = match req.headers().get(header::IF_MATCH) {
+            Some(value) => value,
+            None => return ready(Ok(IfMatch(None))),
+        };
+
+        ready(
+            value
+                .to_str()
+                .ok()
+                .and_then(parse_if_match)
+                .map(IfMatch)
+                .ok_or_else(|| {
+                    ApiError::ValidationError("If-Match must be a single ETag returned by this API".to_string())
+                }),
+        )
+    }
+}
+
+/// `*` -> Some(None), `"<version>"` -> Some(Some(version)), anything else (weak or several tags) -> None
+fn parse_if_match(value: &str) -> Option<Option<i32>> {
+    let value = value.trim();
+    if value == "*" {
+        return Some(None);
+    }
+
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .and_then(|v| v.parse().ok())
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    async fn if_match(value: &str) -> Result<IfMatch, ApiError> {
+        let req = TestRequest::default()
+            .insert_header((header::IF_MATCH, value))
+            .to_http_request();
+        IfMatch::extract(&req).await
+    }
+
+    #[actix_web::test]
+    async fn test_if_match_reads_the_version_we_sent_as_etag() {
+        assert_eq!(if_match(&etag(7).to_string()).await.unwrap(), IfMatch(Some(7)));
+        assert_eq!(if_match("*").await.unwrap(), IfMatch(None));
+
+        let req = TestRequest::default().to_http_request();
+        assert_eq!(IfMatch::extract(&req).await.unwrap(), IfMatch(None));
+    }
+
+    #[actix_web::test]
+    async fn test_if_match_rejects_foreign_tags() {
+        assert!(if_match("W/\"7\"").await.is_err());
+        assert!(if_match("\"7\", \"8\"").await.is_err());
+        assert!(if_match("\"abc\"").await.is_err());
+    }
+}
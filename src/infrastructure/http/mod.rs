@@ -1,6 +1,11 @@
 pub mod authentication;
+pub mod authorization;
 pub mod controllers;
+pub mod etag;
 pub mod handlers;
+pub mod pagination;
+pub mod request_origin;
+pub mod tenant;
 
 // Re-export controllers
 pub use controllers::{
@@ -9,6 +14,16 @@ pub use controllers::{
     TestItemController,
     HealthController,
     QueueController,
+    TwoFactorController,
+    PermissionController,
+    AdminUserController,
+    ApiKeyController,
+    OAuthController,
+    AuditController,
+    DataTransferController,
+    RouteController,
+    DocsController,
+    MaintenanceController,
 };
 
 pub use handlers::handle_not_found; // Default 404 handler
\ No newline at end of file
@@ -0,0 +1,110 @@
+use actix_web::{http::header, web, HttpRequest, HttpResponse};
+use serde::Serialize;
+
+use crate::application::dtos::PaginatedResponse;
+
+// ============================================
+// PAGINATION LINKS: RFC 8288 `Link` header for list responses
+// ============================================
+
+/// `200 OK` with the page as JSON and a `Link` header pointing at the neighbouring pages:
+/// `first`/`prev`/`next`/`last` for offset pages, `next` for cursor pages.
+pub fn paginated<T: Serialize>(req: &HttpRequest, page: &PaginatedResponse<T>) -> HttpResponse {
+    let mut response = HttpResponse::Ok();
+
+    let links = links(req, page);
+    if !links.is_empty() {
+        response.insert_header((header::LINK, links.join(", ")));
+    }
+
+    response.json(page)
+}
+
+fn links<T>(req: &HttpRequest, page: &PaginatedResponse<T>) -> Vec<String> {
+    let link = |key: &str, value: String, rel: &str| {
+        format!("<{}>; rel=\"{}\"", page_url(req, key, &value), rel)
+    };
+
+    let mut links = Vec::new();
+
+    if let Some(cursor) = &page.next_cursor {
+        links.push(link("cursor", cursor.clone(), "next"));
+    }
+
+    if let (Some(current), Some(total_pages)) = (page.page, page.total_pages) {
+        let last = total_pages.max(1);
+        links.push(link("page", "1".to_string(), "first"));
+        if current > 1 {
+            links.push(link("page", (current - 1).to_string(), "prev"));
+        }
+        if (current as i64) < last {
+            links.push(link("page", (current + 1).to_string(), "next"));
+        }
+        links.push(link("page", last.to_string(), "last"));
+    }
+
+    links
+}
+
+/// The request's path and query with `page`/`cursor` replaced by `key=value`
+fn page_url(req: &HttpRequest, key: &str, value: &str) -> String {
+    let params = web::Query::<Vec<(String, String)>>::from_query(req.query_string())
+        .map(|q| q.into_inner())
+        .unwrap_or_default();
+
+    let query = params
+        .iter()
+        .filter(|(k, _)| k != "page" && k != "cursor")
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .chain(std::iter::once((key, value)))
+        .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    format!("{}?{}", req.path(), query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    fn page(page: Option<i32>, total_pages: Option<i64>, next_cursor: Option<&str>) -> PaginatedResponse<()> {
+        PaginatedResponse {
+            data: Vec::new(),
+            total: None,
+            page,
+            per_page: 20,
+            total_pages,
+            next_cursor: next_cursor.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_offset_links_keep_filters_and_replace_page() {
+        let req = TestRequest::get()
+            .uri("/api/test-items?subject%5Bcontains%5D=a%20b&page=2&sort=-created_at")
+            .to_http_request();
+
+        assert_eq!(
+            links(&req, &page(Some(2), Some(3), None)),
+            vec![
+                "</api/test-items?subject%5Bcontains%5D=a%20b&sort=-created_at&page=1>; rel=\"first\"",
+                "</api/test-items?subject%5Bcontains%5D=a%20b&sort=-created_at&page=1>; rel=\"prev\"",
+                "</api/test-items?subject%5Bcontains%5D=a%20b&sort=-created_at&page=3>; rel=\"next\"",
+                "</api/test-items?subject%5Bcontains%5D=a%20b&sort=-created_at&page=3>; rel=\"last\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cursor_links_only_point_forward() {
+        let req = TestRequest::get().uri("/api/users?cursor=abc&per_page=5").to_http_request();
+
+        assert_eq!(
+            links(&req, &page(None, None, Some("def"))),
+            vec!["</api/users?per_page=5&cursor=def>; rel=\"next\""]
+        );
+        assert!(links(&req, &page(None, None, None)).is_empty());
+    }
+}
@@ -0,0 +1,131 @@
+use actix_web::{dev::Payload, http::header, web, FromRequest, HttpRequest};
+use futures::future::{ready, Ready};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use crate::config::AppConfig;
+use crate::domain::entities::RequestOrigin;
+use crate::domain::value_objects::IpRange;
+use crate::errors::ApiError;
+
+/// Longest textual IPv6 address (matches `audit_log.ip_address`)
+const MAX_IP_LENGTH: usize = 45;
+const MAX_USER_AGENT_LENGTH: usize = 512;
+
+// ============================================
+// REQUEST ORIGIN: Client IP + user agent (for the audit log)
+// ============================================
+impl FromRequest for RequestOrigin {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let trusted_proxies = req
+            .app_data::<web::Data<Arc<AppConfig>>>()
+            .map_or(&[][..], |config| &config.maintenance.trusted_proxies);
+        let ip_address = client_ip(req, trusted_proxies)
+            .map(|ip| ip.to_string().chars().take(MAX_IP_LENGTH).collect());
+
+        let user_agent = req
+            .headers()
+            .get(header::USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(|ua| ua.chars().take(MAX_USER_AGENT_LENGTH).collect());
+
+        ready(Ok(RequestOrigin { ip_address, user_agent }))
+    }
+}
+
+// ============================================
+// HELPER: Client address behind trusted proxies
+// ============================================
+/// The peer address, unless it is a trusted proxy: then `X-Forwarded-For` is walked
+/// from the right (each proxy appends the address it saw) and the first hop that is
+/// not a trusted proxy is the client. Entries further left are set by the client
+/// and never trusted.
+pub fn client_ip(req: &HttpRequest, trusted_proxies: &[IpRange]) -> Option<IpAddr> {
+    let is_trusted = |ip: &IpAddr| trusted_proxies.iter().any(|proxy| proxy.contains(ip));
+    let mut client = req.peer_addr()?.ip();
+
+    let hops: Vec<&str> = req
+        .headers()
+        .get_all("x-forwarded-for")
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .collect();
+
+    for hop in hops.into_iter().rev() {
+        if !is_trusted(&client) {
+            break;
+        }
+        match parse_hop(hop.trim()) {
+            Some(ip) => client = ip,
+            // A malformed entry ends the chain at the proxy that passed it on
+            None => break,
+        }
+    }
+    Some(client)
+}
+
+fn parse_hop(hop: &str) -> Option<IpAddr> {
+    hop.parse::<IpAddr>()
+        .or_else(|_| hop.parse::<SocketAddr>().map(|socket| socket.ip()))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+    use crate::config::Sources;
+
+    fn proxies() -> Vec<IpRange> {
+        vec!["10.0.0.0/8".parse().unwrap()]
+    }
+
+    fn request(peer: &str, forwarded_for: &str) -> HttpRequest {
+        TestRequest::default()
+            .peer_addr(format!("{peer}:40000").parse().unwrap())
+            .insert_header(("X-Forwarded-For", forwarded_for))
+            .to_http_request()
+    }
+
+    #[actix_web::test]
+    async fn test_origin_reads_forwarded_ip_and_user_agent() {
+        let sources = Sources::new([
+            ("DATABASE_URL", "postgres://localhost/test"),
+            ("JWT_SECRET", "origin-test-signing-key"),
+            ("MAINTENANCE_TRUSTED_PROXIES", "10.0.0.2"),
+        ]);
+        let config = Arc::new(AppConfig::from_sources(sources).unwrap());
+        let req = TestRequest::default()
+            .app_data(web::Data::new(config))
+            .peer_addr("10.0.0.2:40000".parse().unwrap())
+            .insert_header(("X-Forwarded-For", "203.0.113.7"))
+            .insert_header((header::USER_AGENT, "curl/8.0"))
+            .to_http_request();
+
+        let origin = RequestOrigin::extract(&req).await.unwrap();
+
+        assert_eq!(origin.ip_address.as_deref(), Some("203.0.113.7"));
+        assert_eq!(origin.user_agent.as_deref(), Some("curl/8.0"));
+    }
+
+    #[test]
+    fn test_client_ip_ignores_addresses_prepended_by_the_client() {
+        // The client claims to be an allow-listed address; the proxy appends the real one
+        let req = request("10.0.0.2", "192.0.2.10, 198.51.100.66");
+        assert_eq!(client_ip(&req, &proxies()), Some("198.51.100.66".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_client_ip_skips_every_trusted_proxy() {
+        let req = request("10.0.0.2", "198.51.100.66, 10.0.1.5");
+        assert_eq!(client_ip(&req, &proxies()), Some("198.51.100.66".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_client_ip_ignores_forwarded_for_from_untrusted_peers() {
+        let req = request("198.51.100.66", "192.0.2.10");
+        assert_eq!(client_ip(&req, &proxies()), Some("198.51.100.66".parse().unwrap()));
+    }
+}
@@ -0,0 +1,212 @@
+use actix_web::{dev::Payload, web, FromRequest, HttpRequest};
+use futures::future::LocalBoxFuture;
+use std::sync::Arc;
+use crate::application::services::TenantService;
+use crate::config::AppConfig;
+use crate::domain::entities::{Tenant, DEFAULT_TENANT_ID};
+use crate::errors::ApiError;
+use super::authentication::{extract_tenant_claims, has_credential};
+
+/// Header naming the tenant (by slug) on requests without a tenant subdomain
+pub const TENANT_HEADER: &str = "X-Tenant-ID";
+
+// ============================================
+// CURRENT TENANT: Tenant the request is served for
+// ============================================
+/// Resolved from, in order: the access token, the `X-Tenant-ID` header, the
+/// subdomain of `TENANT_BASE_DOMAIN`, then the default tenant. A token only
+/// works for its own tenant (see `extract_tenant_claims`), so an authenticated
+/// request can never be steered into another tenant.
+#[derive(Debug, Clone)]
+pub struct CurrentTenant(pub Tenant);
+
+impl CurrentTenant {
+    pub fn id(&self) -> &str {
+        &self.0.id
+    }
+}
+
+impl FromRequest for CurrentTenant {
+    type Error = ApiError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let claims = has_credential(req).then(|| extract_tenant_claims(req));
+        let requested = app_config(req).map(|config| requested_tenant(req, &config));
+        let service = tenant_service(req);
+
+        Box::pin(async move {
+            let service = service?;
+
+            let tenant = match (claims, requested?) {
+                (Some(claims), _) => service.get_by_id(&claims.await?.tenant_id).await?,
+                (None, Some(slug)) => service.get_by_slug(&slug).await?,
+                (None, None) => service.get_by_id(DEFAULT_TENANT_ID).await?,
+            };
+
+            Ok(CurrentTenant(tenant))
+        })
+    }
+}
+
+/// Tenant slug named by the request itself (header first, then subdomain)
+pub(crate) fn requested_tenant(req: &HttpRequest, config: &AppConfig) -> Option<String> {
+    if let Some(slug) = req
+        .headers()
+        .get(TENANT_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .map(|h| h.trim().to_lowercase())
+        .filter(|h| !h.is_empty())
+    {
+        return Some(slug);
+    }
+
+    let base_domain = config.tenancy.base_domain.as_deref()?;
+    subdomain_of(req.connection_info().host(), base_domain)
+}
+
+/// `acme.example.com:8080` under `example.com` -> `acme` (single label only)
+fn subdomain_of(host: &str, base_domain: &str) -> Option<String> {
+    let host = host.rsplit_once(':').map_or(host, |(name, _)| name).to_lowercase();
+    let label = host.strip_suffix(base_domain)?.strip_suffix('.')?;
+
+    (!label.is_empty() && !label.contains('.')).then(|| label.to_string())
+}
+
+fn app_config(req: &HttpRequest) -> Result<Arc<AppConfig>, ApiError> {
+    req.app_data::<web::Data<Arc<AppConfig>>>()
+        .map(|data| data.get_ref().clone())
+        .ok_or_else(|| ApiError::InternalServerError("Config not found".to_string()))
+}
+
+pub(crate) fn tenant_service(req: &HttpRequest) -> Result<Arc<TenantService>, ApiError> {
+    req.app_data::<web::Data<Arc<TenantService>>>()
+        .map(|data| data.get_ref().clone())
+        .ok_or_else(|| ApiError::InternalServerError("Tenant service not found".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use crate::config::Sources;
+    use crate::domain::entities::User;
+    use crate::domain::value_objects::{EmailAddress, Username};
+    use crate::infrastructure::http::authentication::AuthUser;
+    use crate::interfaces::TenantRepository;
+
+    /// In-memory tenants: `default` and `acme`
+    struct FakeTenantRepository(Vec<Tenant>);
+
+    #[async_trait]
+    impl TenantRepository for FakeTenantRepository {
+        async fn get_by_id(&self, id: &str) -> Result<Option<Tenant>, ApiError> {
+            Ok(self.0.iter().find(|t| t.id == id).cloned())
+        }
+
+        async fn get_by_slug(&self, slug: &str) -> Result<Option<Tenant>, ApiError> {
+            Ok(self.0.iter().find(|t| t.slug == slug).cloned())
+        }
+    }
+
+    fn tenant(id: &str, slug: &str) -> Tenant {
+        Tenant {
+            id: id.to_string(),
+            slug: slug.to_string(),
+            name: slug.to_string(),
+            is_active: true,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn config() -> AppConfig {
+        let sources = Sources::new([
+            ("DATABASE_URL", "postgres://localhost/test"),
+            ("JWT_SECRET", "tenant-test-signing-key"),
+        ]);
+        AppConfig::from_sources(sources).unwrap()
+    }
+
+    fn request(base_domain: Option<&str>) -> TestRequest {
+        let mut config = config();
+        config.tenancy.base_domain = base_domain.map(str::to_string);
+        let config = Arc::new(config);
+
+        let repository = Arc::new(FakeTenantRepository(vec![
+            tenant(DEFAULT_TENANT_ID, DEFAULT_TENANT_ID),
+            tenant("tenant-acme", "acme"),
+        ]));
+        let service = Arc::new(TenantService::new(repository, config.clone()));
+
+        TestRequest::default()
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(service))
+    }
+
+    fn token_for(tenant_id: &str) -> String {
+        let mut user = User::new(
+            tenant_id.to_string(),
+            EmailAddress::new("user@test.com".to_string()).unwrap(),
+            Username::new("tenant_user".to_string()).unwrap(),
+            "hash".to_string(),
+        )
+        .unwrap();
+        user.id = "user-1".to_string();
+        crate::utils::jwt::create_token(&user, &config()).unwrap()
+    }
+
+    #[actix_web::test]
+    async fn test_tenant_from_header_subdomain_or_default() {
+        let req = request(None).insert_header((TENANT_HEADER, "ACME")).to_http_request();
+        assert_eq!(CurrentTenant::extract(&req).await.unwrap().id(), "tenant-acme");
+
+        let req = request(Some("example.com"))
+            .insert_header(("Host", "acme.example.com:8080"))
+            .to_http_request();
+        assert_eq!(CurrentTenant::extract(&req).await.unwrap().id(), "tenant-acme");
+
+        let req = request(Some("example.com")).insert_header(("Host", "example.com")).to_http_request();
+        assert_eq!(CurrentTenant::extract(&req).await.unwrap().id(), DEFAULT_TENANT_ID);
+
+        let req = request(None).insert_header((TENANT_HEADER, "unknown")).to_http_request();
+        assert!(matches!(CurrentTenant::extract(&req).await, Err(ApiError::NotFound(_))));
+    }
+
+    #[actix_web::test]
+    async fn test_token_tenant_takes_precedence() {
+        let req = request(None)
+            .insert_header(("Authorization", format!("Bearer {}", token_for("tenant-acme"))))
+            .to_http_request();
+
+        assert_eq!(CurrentTenant::extract(&req).await.unwrap().id(), "tenant-acme");
+    }
+
+    #[actix_web::test]
+    async fn test_token_is_rejected_on_another_tenant() {
+        let token = format!("Bearer {}", token_for("tenant-acme"));
+
+        let req = request(None)
+            .insert_header(("Authorization", token.clone()))
+            .insert_header((TENANT_HEADER, DEFAULT_TENANT_ID))
+            .to_http_request();
+        assert!(matches!(AuthUser::extract(&req).await, Err(ApiError::Forbidden(_))));
+        assert!(matches!(CurrentTenant::extract(&req).await, Err(ApiError::Forbidden(_))));
+
+        let req = request(Some("example.com"))
+            .insert_header(("Authorization", token))
+            .insert_header(("Host", "default.example.com"))
+            .to_http_request();
+        assert!(matches!(AuthUser::extract(&req).await, Err(ApiError::Forbidden(_))));
+    }
+
+    #[test]
+    fn test_subdomain_of() {
+        assert_eq!(subdomain_of("acme.example.com", "example.com").as_deref(), Some("acme"));
+        assert_eq!(subdomain_of("ACME.example.com:443", "example.com").as_deref(), Some("acme"));
+        assert_eq!(subdomain_of("example.com", "example.com"), None);
+        assert_eq!(subdomain_of("a.b.example.com", "example.com"), None);
+        assert_eq!(subdomain_of("acmeexample.com", "example.com"), None);
+    }
+}
@@ -1,9 +1,25 @@
 pub mod http;
 pub mod persistence;
+pub mod oauth;
+pub mod templates;
 
 // Persistence layer
 pub use persistence::PostgresUserRepository;
 pub use persistence::PostgresTestItemRepository;
+pub use persistence::PostgresPermissionRepository;
+pub use persistence::PostgresApiKeyRepository;
+pub use persistence::PostgresUserIdentityRepository;
+pub use persistence::PostgresAuditLogRepository;
+pub use persistence::PostgresTenantRepository;
+pub use persistence::PostgresDataImportRepository;
+pub use persistence::PostgresMaintenanceStore;
+pub use persistence::FileMaintenanceStore;
+
+// OAuth layer
+pub use oauth::HttpIdentityProviderClient;
+
+// Templates
+pub use templates::TemplateEngine;
 
 // HTTP layer - Authentication
 pub use http::authentication::{
@@ -0,0 +1,290 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::config::{OAuthProviderConfig, OAuthProviderKind};
+use crate::errors::ApiError;
+use crate::interfaces::{ExternalIdentity, IdentityProviderClient};
+
+const USER_AGENT: &str = concat!("ironclad/", env!("CARGO_PKG_VERSION"));
+
+/// `reqwest` based back-channel client for OIDC and GitHub providers
+pub struct HttpIdentityProviderClient {
+    client: reqwest::Client,
+}
+
+impl HttpIdentityProviderClient {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .user_agent(USER_AGENT)
+                .timeout(std::time::Duration::from_secs(10))
+                .build()
+                .expect("Failed to build HTTP client"),
+        }
+    }
+
+    async fn get_json(&self, url: &str, access_token: &str) -> Result<Value, ApiError> {
+        let response = self
+            .client
+            .get(url)
+            .bearer_auth(access_token)
+            .header("Accept", "application/json")
+            .send()
+            .await
+            .map_err(|e| ApiError::InternalServerError(format!("Identity provider unreachable: {}", e)))?;
+
+        if !response.status().is_success() {
+            tracing::warn!(url = %url, status = %response.status(), "Identity provider rejected access token");
+            return Err(ApiError::Unauthorized);
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| ApiError::InternalServerError(format!("Invalid identity provider response: {}", e)))
+    }
+
+    /// Standard OIDC userinfo (`sub`, `email`, `email_verified`)
+    fn parse_oidc(userinfo: &Value) -> Result<ExternalIdentity, ApiError> {
+        let subject = userinfo["sub"].as_str().ok_or(ApiError::Unauthorized)?.to_string();
+
+        // Some providers send `email_verified` as a string
+        let email_verified = match &userinfo["email_verified"] {
+            Value::Bool(verified) => *verified,
+            Value::String(verified) => verified.eq_ignore_ascii_case("true"),
+            _ => false,
+        };
+
+        Ok(ExternalIdentity {
+            subject,
+            email: userinfo["email"].as_str().map(str::to_lowercase),
+            email_verified,
+            name: userinfo["preferred_username"]
+                .as_str()
+                .or_else(|| userinfo["name"].as_str())
+                .map(str::to_string),
+        })
+    }
+
+    /// GitHub `/user` + `/user/emails` (the primary verified address)
+    async fn fetch_github(&self, provider: &OAuthProviderConfig, access_token: &str) -> Result<ExternalIdentity, ApiError> {
+        #[derive(Deserialize)]
+        struct GithubEmail {
+            email: String,
+            primary: bool,
+            verified: bool,
+        }
+
+        let user = self.get_json(&provider.userinfo_url, access_token).await?;
+        let subject = user["id"].as_i64().ok_or(ApiError::Unauthorized)?.to_string();
+
+        let emails_url = format!("{}/emails", provider.userinfo_url.trim_end_matches('/'));
+        let emails: Vec<GithubEmail> = serde_json::from_value(self.get_json(&emails_url, access_token).await?)
+            .map_err(|e| ApiError::InternalServerError(format!("Invalid identity provider response: {}", e)))?;
+        let primary = emails.into_iter().find(|e| e.primary);
+
+        Ok(ExternalIdentity {
+            subject,
+            email: primary.as_ref().map(|e| e.email.to_lowercase()),
+            email_verified: primary.is_some_and(|e| e.verified),
+            name: user["login"].as_str().map(str::to_string),
+        })
+    }
+}
+
+impl Default for HttpIdentityProviderClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl IdentityProviderClient for HttpIdentityProviderClient {
+    async fn exchange_code(
+        &self,
+        provider: &OAuthProviderConfig,
+        code: &str,
+        code_verifier: &str,
+        redirect_uri: &str,
+    ) -> Result<String, ApiError> {
+        let response = self
+            .client
+            .post(&provider.token_url)
+            .header("Accept", "application/json")
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", redirect_uri),
+                ("client_id", provider.client_id.as_str()),
+                ("client_secret", provider.client_secret.as_str()),
+                ("code_verifier", code_verifier),
+            ])
+            .send()
+            .await
+            .map_err(|e| ApiError::InternalServerError(format!("Identity provider unreachable: {}", e)))?;
+
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or(Value::Null);
+
+        // GitHub answers errors with 200 + {"error": ...}
+        match body["access_token"].as_str() {
+            Some(token) if status.is_success() => Ok(token.to_string()),
+            _ => {
+                tracing::warn!(
+                    provider = %provider.name,
+                    status = %status,
+                    error = %body["error"].as_str().unwrap_or("unknown"),
+                    "Authorization code exchange failed"
+                );
+                Err(ApiError::Unauthorized)
+            }
+        }
+    }
+
+    async fn fetch_identity(
+        &self,
+        provider: &OAuthProviderConfig,
+        access_token: &str,
+    ) -> Result<ExternalIdentity, ApiError> {
+        match provider.kind {
+            OAuthProviderKind::Oidc => {
+                let userinfo = self.get_json(&provider.userinfo_url, access_token).await?;
+                Self::parse_oidc(&userinfo)
+            }
+            OAuthProviderKind::Github => self.fetch_github(provider, access_token).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    use crate::utils::oauth::{code_challenge, generate_code_verifier};
+
+    const CODE: &str = "stub-code";
+    const TOKEN: &str = "stub-access-token";
+
+    /// Minimal identity provider: accepts `CODE` only when the PKCE verifier matches
+    /// the challenge in the `challenge` app data, and serves OIDC + GitHub style user endpoints.
+    async fn start_stub_provider(challenge: String) -> String {
+        async fn token(form: web::Form<HashMap<String, String>>, challenge: web::Data<String>) -> HttpResponse {
+            let verifier = form.get("code_verifier").map(String::as_str).unwrap_or_default();
+            if form.get("code").map(String::as_str) == Some(CODE)
+                && form.get("grant_type").map(String::as_str) == Some("authorization_code")
+                && code_challenge(verifier) == *challenge.get_ref()
+            {
+                HttpResponse::Ok().json(json!({ "access_token": TOKEN, "token_type": "Bearer" }))
+            } else {
+                HttpResponse::BadRequest().json(json!({ "error": "invalid_grant" }))
+            }
+        }
+
+        fn authorized(req: &HttpRequest) -> bool {
+            req.headers().get("Authorization").and_then(|h| h.to_str().ok()) == Some(&format!("Bearer {}", TOKEN))
+        }
+
+        async fn userinfo(req: HttpRequest) -> HttpResponse {
+            if !authorized(&req) {
+                return HttpResponse::Unauthorized().finish();
+            }
+            HttpResponse::Ok().json(json!({
+                "sub": "stub|42",
+                "email": "Stub.User@Example.com",
+                "email_verified": "true",
+                "preferred_username": "stub-user"
+            }))
+        }
+
+        async fn github_user(req: HttpRequest) -> HttpResponse {
+            if !authorized(&req) {
+                return HttpResponse::Unauthorized().finish();
+            }
+            HttpResponse::Ok().json(json!({ "id": 4242, "login": "octo" }))
+        }
+
+        async fn github_emails(req: HttpRequest) -> HttpResponse {
+            if !authorized(&req) {
+                return HttpResponse::Unauthorized().finish();
+            }
+            HttpResponse::Ok().json(json!([
+                { "email": "old@example.com", "primary": false, "verified": true },
+                { "email": "octo@example.com", "primary": true, "verified": true }
+            ]))
+        }
+
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(web::Data::new(challenge.clone()))
+                .route("/token", web::post().to(token))
+                .route("/userinfo", web::get().to(userinfo))
+                .route("/user", web::get().to(github_user))
+                .route("/user/emails", web::get().to(github_emails))
+        })
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .unwrap();
+
+        let address = server.addrs()[0];
+        actix_web::rt::spawn(server.run());
+        format!("http://{}", address)
+    }
+
+    fn provider(base_url: &str, kind: OAuthProviderKind) -> OAuthProviderConfig {
+        OAuthProviderConfig {
+            name: "stub".to_string(),
+            kind,
+            client_id: "client".to_string(),
+            client_secret: "secret".to_string(),
+            authorization_url: format!("{}/authorize", base_url),
+            token_url: format!("{}/token", base_url),
+            userinfo_url: match kind {
+                OAuthProviderKind::Oidc => format!("{}/userinfo", base_url),
+                OAuthProviderKind::Github => format!("{}/user", base_url),
+            },
+            scopes: vec!["openid".to_string(), "email".to_string()],
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_oidc_code_exchange_with_pkce() {
+        let verifier = generate_code_verifier();
+        let base_url = start_stub_provider(code_challenge(&verifier)).await;
+        let provider = provider(&base_url, OAuthProviderKind::Oidc);
+        let client = HttpIdentityProviderClient::new();
+
+        let rejected = client.exchange_code(&provider, CODE, &generate_code_verifier(), "http://app/cb").await;
+        assert!(matches!(rejected, Err(ApiError::Unauthorized)));
+
+        let token = client.exchange_code(&provider, CODE, &verifier, "http://app/cb").await.unwrap();
+        let identity = client.fetch_identity(&provider, &token).await.unwrap();
+
+        assert_eq!(
+            identity,
+            ExternalIdentity {
+                subject: "stub|42".to_string(),
+                email: Some("stub.user@example.com".to_string()),
+                email_verified: true,
+                name: Some("stub-user".to_string()),
+            }
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_github_identity_uses_primary_email() {
+        let base_url = start_stub_provider(String::new()).await;
+        let provider = provider(&base_url, OAuthProviderKind::Github);
+        let client = HttpIdentityProviderClient::new();
+
+        let identity = client.fetch_identity(&provider, TOKEN).await.unwrap();
+        assert_eq!(identity.subject, "4242");
+        assert_eq!(identity.email.as_deref(), Some("octo@example.com"));
+        assert!(identity.email_verified);
+
+        assert!(client.fetch_identity(&provider, "wrong-token").await.is_err());
+    }
+}
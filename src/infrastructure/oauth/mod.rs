@@ -0,0 +1,3 @@
+pub mod http_identity_provider;
+
+pub use http_identity_provider::HttpIdentityProviderClient;
@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+use futures::stream::{BoxStream, StreamExt};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use tokio::fs;
+
+use crate::domain::entities::MaintenanceState;
+use crate::errors::ApiError;
+use crate::interfaces::MaintenanceStore;
+
+/// Maintenance mode in a JSON file on the local disk (one instance only)
+pub struct FileMaintenanceStore {
+    path: PathBuf,
+    poll_interval: Duration,
+}
+
+impl FileMaintenanceStore {
+    pub fn new(path: impl Into<PathBuf>, poll_interval: Duration) -> Self {
+        Self { path: path.into(), poll_interval }
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
+
+/// Modification time of the file, `None` when it does not exist
+async fn modified(path: &PathBuf) -> Option<SystemTime> {
+    fs::metadata(path).await.and_then(|metadata| metadata.modified()).ok()
+}
+
+#[async_trait]
+impl MaintenanceStore for FileMaintenanceStore {
+    async fn get(&self) -> Result<Option<MaintenanceState>, ApiError> {
+        let content = match fs::read_to_string(&self.path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(ApiError::InternalServerError(e.to_string())),
+        };
+
+        // An unreadable file leaves the application up, as it always did
+        match serde_json::from_str(&content) {
+            Ok(state) => Ok(Some(state)),
+            Err(e) => {
+                tracing::warn!("Ignoring invalid maintenance file {}: {}", self.path.display(), e);
+                Ok(None)
+            }
+        }
+    }
+
+    async fn put(&self, state: &MaintenanceState) -> Result<(), ApiError> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .await
+                .map_err(|e| ApiError::InternalServerError(e.to_string()))?;
+        }
+        let content = serde_json::to_string(state)
+            .map_err(|e| ApiError::InternalServerError(e.to_string()))?;
+
+        // Write then rename, so a poller never reads half a file
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, content)
+            .await
+            .map_err(|e| ApiError::InternalServerError(e.to_string()))?;
+        fs::rename(&tmp, &self.path)
+            .await
+            .map_err(|e| ApiError::InternalServerError(e.to_string()))
+    }
+
+    async fn clear(&self) -> Result<bool, ApiError> {
+        match fs::remove_file(&self.path).await {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(ApiError::InternalServerError(e.to_string())),
+        }
+    }
+
+    /// Polls the modification time every `poll_interval`
+    async fn changes(&self) -> Result<BoxStream<'static, ()>, ApiError> {
+        let path = self.path.clone();
+        let last = modified(&path).await;
+        let mut interval = tokio::time::interval(self.poll_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        Ok(futures::stream::unfold((path, last, interval), |(path, mut last, mut interval)| async move {
+            loop {
+                interval.tick().await;
+                let current = modified(&path).await;
+                if current != last {
+                    last = current;
+                    return Some(((), (path, last, interval)));
+                }
+            }
+        })
+        .boxed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::BypassSecret;
+
+    #[actix_web::test]
+    async fn test_state_round_trips_and_changes_are_noticed() {
+        let dir = std::env::temp_dir().join(format!("ironclad-maintenance-{}", uuid::Uuid::new_v4()));
+        let store = FileMaintenanceStore::new(dir.join("maintenance.json"), Duration::from_millis(20));
+        let mut changes = store.changes().await.unwrap();
+        assert_eq!(store.get().await.unwrap(), None);
+
+        let mut state = MaintenanceState::new(Some("Upgrading".to_string()), 120);
+        state.add_bypass(BypassSecret::new("office", "0a1b".to_string())).unwrap();
+        store.put(&state).await.unwrap();
+        assert_eq!(store.get().await.unwrap(), Some(state));
+        changes.next().await.unwrap();
+
+        assert!(store.clear().await.unwrap());
+        assert!(!store.clear().await.unwrap());
+        assert_eq!(store.get().await.unwrap(), None);
+        changes.next().await.unwrap();
+
+        // Files written by earlier versions of `ironclad down` are still understood
+        fs::write(store.path(), r#"{"time":1,"message":"Old","retry":60,"created_at":"2024-01-01T00:00:00+00:00"}"#)
+            .await
+            .unwrap();
+        assert_eq!(store.get().await.unwrap().unwrap().message, "Old");
+
+        fs::remove_dir_all(dir).await.unwrap();
+    }
+}
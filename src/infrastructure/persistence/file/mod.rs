@@ -0,0 +1,3 @@
+pub mod maintenance_store;
+
+pub use maintenance_store::FileMaintenanceStore;
@@ -1,7 +1,16 @@
 pub mod postgres;
+pub mod file;
 
 pub use postgres::PostgresUserRepository;
 pub use postgres::PostgresTestItemRepository;
+pub use postgres::PostgresPermissionRepository;
+pub use postgres::PostgresApiKeyRepository;
+pub use postgres::PostgresUserIdentityRepository;
+pub use postgres::PostgresAuditLogRepository;
+pub use postgres::PostgresTenantRepository;
+pub use postgres::PostgresDataImportRepository;
+pub use postgres::PostgresMaintenanceStore;
+pub use file::FileMaintenanceStore;
 
 // TODO - Add Redis repositories for caching (e.g., UserCacheRepository)
 // TODO - ADJUST MULTIPLE DATABASE SUPPORT (e.g., MySQL, SQLite) if needed in the future
\ No newline at end of file
@@ -0,0 +1,100 @@
+use sqlx::PgPool;
+use async_trait::async_trait;
+
+use crate::domain::entities::ApiKey;
+use crate::errors::ApiError;
+use crate::interfaces::repositories::ApiKeyRepository;
+
+pub struct PostgresApiKeyRepository {
+    pool: PgPool,
+}
+
+impl PostgresApiKeyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for PostgresApiKeyRepository {
+    async fn create(&self, api_key: &ApiKey) -> Result<ApiKey, ApiError> {
+        let query = r#"
+            INSERT INTO api_keys (id, tenant_id, user_id, name, prefix, key_hash, scopes, expires_at, created_at, created_with_mfa)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING *
+        "#;
+
+        let scopes: Vec<String> = api_key.scopes.iter().map(|s| s.as_str().to_string()).collect();
+
+        let created = sqlx::query_as::<_, ApiKey>(query)
+            .bind(&api_key.id)
+            .bind(&api_key.tenant_id)
+            .bind(&api_key.user_id)
+            .bind(&api_key.name)
+            .bind(&api_key.prefix)
+            .bind(&api_key.key_hash)
+            .bind(&scopes)
+            .bind(api_key.expires_at)
+            .bind(api_key.created_at)
+            .bind(api_key.created_with_mfa)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(created)
+    }
+
+    async fn get_by_prefix(&self, prefix: &str) -> Result<Option<ApiKey>, ApiError> {
+        let query = "SELECT * FROM api_keys WHERE prefix = $1";
+
+        let api_key = sqlx::query_as::<_, ApiKey>(query)
+            .bind(prefix)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(api_key)
+    }
+
+    async fn get_by_user(&self, tenant_id: &str, user_id: &str) -> Result<Vec<ApiKey>, ApiError> {
+        let query = "SELECT * FROM api_keys WHERE tenant_id = $1 AND user_id = $2 ORDER BY created_at DESC";
+
+        let api_keys = sqlx::query_as::<_, ApiKey>(query)
+            .bind(tenant_id)
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(api_keys)
+    }
+
+    async fn revoke(&self, tenant_id: &str, id: &str, user_id: &str) -> Result<bool, ApiError> {
+        let query = r#"
+            UPDATE api_keys SET revoked_at = NOW()
+            WHERE tenant_id = $1 AND id = $2 AND user_id = $3 AND revoked_at IS NULL
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(tenant_id)
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn touch_last_used(&self, id: &str) -> Result<(), ApiError> {
+        let query = "UPDATE api_keys SET last_used_at = NOW() WHERE id = $1";
+
+        sqlx::query(query)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+}
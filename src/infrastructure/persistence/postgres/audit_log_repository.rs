@@ -0,0 +1,125 @@
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::entities::AuditEntry;
+use crate::errors::ApiError;
+use crate::interfaces::repositories::{AuditFilter, AuditLogRepository, FieldKind, Paged, Pagination, QuerySpec, Sort, Value};
+use super::query_spec::{fetch_page, Keyset};
+
+pub struct PostgresAuditLogRepository {
+    pool: PgPool,
+}
+
+impl PostgresAuditLogRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Append the WHERE clause for an audit search (values are always bound)
+    fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, filter: &AuditFilter) {
+        builder.push(" WHERE 1 = 1");
+
+        if let Some(tenant_id) = &filter.tenant_id {
+            builder.push(" AND tenant_id = ").push_bind(tenant_id.clone());
+        }
+
+        if let Some(actor_id) = &filter.actor_id {
+            builder.push(" AND actor_id = ").push_bind(actor_id.clone());
+        }
+
+        if let Some(action) = &filter.action {
+            match action.strip_suffix(".*") {
+                Some(prefix) => {
+                    let pattern = format!("{}.%", prefix.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+                    builder.push(" AND action LIKE ").push_bind(pattern);
+                }
+                None => {
+                    builder.push(" AND action = ").push_bind(action.clone());
+                }
+            }
+        }
+
+        if let Some(target_type) = &filter.target_type {
+            builder.push(" AND target_type = ").push_bind(target_type.clone());
+        }
+
+        if let Some(target_id) = &filter.target_id {
+            builder.push(" AND target_id = ").push_bind(target_id.clone());
+        }
+
+        if let Some(ip_address) = &filter.ip_address {
+            builder.push(" AND ip_address = ").push_bind(ip_address.clone());
+        }
+
+        if let Some(from) = filter.from {
+            builder.push(" AND created_at >= ").push_bind(from);
+        }
+
+        if let Some(to) = filter.to {
+            builder.push(" AND created_at < ").push_bind(to);
+        }
+    }
+}
+
+#[async_trait]
+impl AuditLogRepository for PostgresAuditLogRepository {
+    async fn append(&self, entry: &AuditEntry) -> Result<(), ApiError> {
+        let query = r#"
+            INSERT INTO audit_log (id, tenant_id, actor_id, impersonator_id, action, target_type, target_id,
+                ip_address, user_agent, changes, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        "#;
+
+        sqlx::query(query)
+            .bind(&entry.id)
+            .bind(&entry.tenant_id)
+            .bind(&entry.actor_id)
+            .bind(&entry.impersonator_id)
+            .bind(&entry.action)
+            .bind(&entry.target_type)
+            .bind(&entry.target_id)
+            .bind(&entry.ip_address)
+            .bind(&entry.user_agent)
+            .bind(&entry.changes)
+            .bind(entry.created_at)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn search(&self, filter: &AuditFilter, pagination: &Pagination) -> Result<Paged<AuditEntry>, ApiError> {
+        let spec = QuerySpec {
+            filters: Vec::new(),
+            sort: vec![Sort { column: "created_at", kind: FieldKind::Timestamp, descending: true }],
+            pagination: pagination.clone(),
+        };
+
+        fetch_page(&self.pool, "audit_log", |builder| Self::push_filters(builder, filter), &spec).await
+    }
+
+    async fn purge_before(&self, cutoff: DateTime<Utc>) -> Result<u64, ApiError> {
+        let result = sqlx::query("DELETE FROM audit_log WHERE created_at < $1")
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+impl Keyset for AuditEntry {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn sort_value(&self, column: &str) -> Option<Value> {
+        match column {
+            "created_at" => Some(Value::Timestamp(self.created_at)),
+            _ => None,
+        }
+    }
+}
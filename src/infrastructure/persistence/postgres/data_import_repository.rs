@@ -0,0 +1,164 @@
+use sqlx::PgPool;
+use async_trait::async_trait;
+
+use crate::domain::entities::{DataImport, ImportStatus};
+use crate::errors::ApiError;
+use crate::interfaces::DataImportRepository;
+
+pub struct PostgresDataImportRepository {
+    pool: PgPool,
+}
+
+impl PostgresDataImportRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DataImportRepository for PostgresDataImportRepository {
+    async fn create(&self, import: &DataImport) -> Result<DataImport, ApiError> {
+        let query = r#"
+            INSERT INTO data_imports (id, tenant_id, entity, format, status, file_path, created_by, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING *
+        "#;
+
+        sqlx::query_as::<_, DataImport>(query)
+            .bind(&import.id)
+            .bind(&import.tenant_id)
+            .bind(&import.entity)
+            .bind(import.format.as_str())
+            .bind(import.status.as_str())
+            .bind(&import.file_path)
+            .bind(&import.created_by)
+            .bind(import.created_at)
+            .bind(import.updated_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))
+    }
+
+    async fn get_by_id(&self, tenant_id: &str, entity: &str, id: &str) -> Result<Option<DataImport>, ApiError> {
+        let query = "SELECT * FROM data_imports WHERE tenant_id = $1 AND entity = $2 AND id = $3";
+
+        sqlx::query_as::<_, DataImport>(query)
+            .bind(tenant_id)
+            .bind(entity)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))
+    }
+
+    async fn claim(&self, id: &str) -> Result<Option<DataImport>, ApiError> {
+        // A single conditional write, so two runs of the same job cannot both start it
+        let query = r#"
+            UPDATE data_imports SET status = 'running', updated_at = NOW()
+            WHERE id = $1 AND status = 'pending'
+            RETURNING *
+        "#;
+
+        sqlx::query_as::<_, DataImport>(query)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))
+    }
+
+    async fn set_status(&self, id: &str, status: ImportStatus, error_message: Option<&str>) -> Result<(), ApiError> {
+        let query = r#"
+            UPDATE data_imports
+            SET status = $1, error_message = $2, updated_at = NOW(),
+                finished_at = CASE WHEN $1 = 'failed' THEN NOW() ELSE finished_at END
+            WHERE id = $3
+        "#;
+
+        sqlx::query(query)
+            .bind(status.as_str())
+            .bind(error_message)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn update_progress(&self, id: &str, processed_rows: i32, failed_rows: i32) -> Result<(), ApiError> {
+        let query = r#"
+            UPDATE data_imports SET processed_rows = $1, failed_rows = $2, updated_at = NOW()
+            WHERE id = $3
+        "#;
+
+        sqlx::query(query)
+            .bind(processed_rows)
+            .bind(failed_rows)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn complete(&self, id: &str, error_report_path: Option<&str>) -> Result<(), ApiError> {
+        let query = r#"
+            UPDATE data_imports
+            SET status = 'completed', error_report_path = $1, updated_at = NOW(), finished_at = NOW()
+            WHERE id = $2
+        "#;
+
+        sqlx::query(query)
+            .bind(error_report_path)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::DataFormat;
+
+    /// Connects to `TEST_DATABASE_URL` and registers a throwaway tenant
+    async fn setup() -> (PostgresDataImportRepository, String) {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let tenant = uuid::Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO tenants (id, slug, name) VALUES ($1, $1, $1)")
+            .bind(&tenant)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        (PostgresDataImportRepository::new(pool), tenant)
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+    async fn test_an_import_is_claimed_once() {
+        let (repo, tenant) = setup().await;
+        let import = DataImport::new(
+            DataImport::new_id(),
+            tenant,
+            DataImport::TEST_ITEMS,
+            DataFormat::Csv,
+            "/tmp/import.csv".to_string(),
+            None,
+        );
+        repo.create(&import).await.unwrap();
+
+        let (first, second) = futures::join!(repo.claim(&import.id), repo.claim(&import.id));
+        let claimed: Vec<_> = [first.unwrap(), second.unwrap()].into_iter().flatten().collect();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].status, ImportStatus::Running);
+
+        assert!(repo.claim("missing").await.unwrap().is_none());
+    }
+}
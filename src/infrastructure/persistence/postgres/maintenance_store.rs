@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use futures::stream::{BoxStream, StreamExt};
+use sqlx::postgres::PgListener;
+use sqlx::types::Json;
+use sqlx::PgPool;
+
+use crate::domain::entities::MaintenanceState;
+use crate::errors::ApiError;
+use crate::interfaces::MaintenanceStore;
+
+/// Channel notified by the `maintenance_state` trigger
+const CHANNEL: &str = "maintenance_changed";
+
+/// Maintenance mode shared by every instance connected to the database
+pub struct PostgresMaintenanceStore {
+    pool: PgPool,
+}
+
+impl PostgresMaintenanceStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl MaintenanceStore for PostgresMaintenanceStore {
+    async fn get(&self) -> Result<Option<MaintenanceState>, ApiError> {
+        sqlx::query_scalar::<_, Json<MaintenanceState>>("SELECT state FROM maintenance_state WHERE id = 1")
+            .fetch_optional(&self.pool)
+            .await
+            .map(|state| state.map(|Json(state)| state))
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))
+    }
+
+    async fn put(&self, state: &MaintenanceState) -> Result<(), ApiError> {
+        let query = r#"
+            INSERT INTO maintenance_state (id, state, updated_at)
+            VALUES (1, $1, NOW())
+            ON CONFLICT (id) DO UPDATE SET state = EXCLUDED.state, updated_at = EXCLUDED.updated_at
+        "#;
+
+        sqlx::query(query)
+            .bind(Json(state))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<bool, ApiError> {
+        let result = sqlx::query("DELETE FROM maintenance_state WHERE id = 1")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn changes(&self) -> Result<BoxStream<'static, ()>, ApiError> {
+        let mut listener = PgListener::connect_with(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+        listener
+            .listen(CHANNEL)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        // `try_recv` yields `None` when the connection drops (and reconnects on the next call):
+        // notifications sent meanwhile are lost, so that is a change signal too
+        Ok(futures::stream::unfold(listener, |mut listener| async move {
+            match listener.try_recv().await {
+                Ok(_) => Some(((), listener)),
+                Err(e) => {
+                    tracing::warn!("Maintenance listener failed: {}", e);
+                    None
+                }
+            }
+        })
+        .boxed())
+    }
+}
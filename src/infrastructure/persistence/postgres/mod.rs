@@ -1,5 +1,21 @@
 pub mod user_repository;
 pub mod test_item_repository;
+pub mod permission_repository;
+pub mod api_key_repository;
+pub mod user_identity_repository;
+pub mod audit_log_repository;
+pub mod tenant_repository;
+pub mod data_import_repository;
+pub mod maintenance_store;
+pub mod query_spec;
+pub mod stream;
 
 pub use user_repository::PostgresUserRepository;
 pub use test_item_repository::PostgresTestItemRepository;
+pub use permission_repository::PostgresPermissionRepository;
+pub use api_key_repository::PostgresApiKeyRepository;
+pub use user_identity_repository::PostgresUserIdentityRepository;
+pub use audit_log_repository::PostgresAuditLogRepository;
+pub use tenant_repository::PostgresTenantRepository;
+pub use data_import_repository::PostgresDataImportRepository;
+pub use maintenance_store::PostgresMaintenanceStore;
@@ -0,0 +1,114 @@
+use sqlx::PgPool;
+use async_trait::async_trait;
+
+use crate::domain::entities::{PermissionDefinition, RolePermission};
+use crate::domain::value_objects::Permission;
+use crate::errors::ApiError;
+use crate::interfaces::repositories::PermissionRepository;
+
+pub struct PostgresPermissionRepository {
+    pool: PgPool,
+}
+
+impl PostgresPermissionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PermissionRepository for PostgresPermissionRepository {
+    async fn get_for_role(&self, role: &str) -> Result<Vec<Permission>, ApiError> {
+        let query = "SELECT permission FROM role_permissions WHERE role = $1 ORDER BY permission";
+
+        let rows: Vec<(String,)> = sqlx::query_as(query)
+            .bind(role)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(rows.into_iter().map(|(p,)| Permission::from_trusted(p)).collect())
+    }
+
+    async fn get_all_grants(&self) -> Result<Vec<RolePermission>, ApiError> {
+        let query = "SELECT * FROM role_permissions ORDER BY role, permission";
+
+        let grants = sqlx::query_as::<_, RolePermission>(query)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(grants)
+    }
+
+    async fn get_all_permissions(&self) -> Result<Vec<PermissionDefinition>, ApiError> {
+        let query = "SELECT * FROM permissions ORDER BY name";
+
+        let permissions = sqlx::query_as::<_, PermissionDefinition>(query)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(permissions)
+    }
+
+    async fn create_permission(&self, permission: &PermissionDefinition) -> Result<PermissionDefinition, ApiError> {
+        let query = r#"
+            INSERT INTO permissions (name, description, created_at)
+            VALUES ($1, $2, $3)
+            RETURNING *
+        "#;
+
+        let created = sqlx::query_as::<_, PermissionDefinition>(query)
+            .bind(permission.name.as_str())
+            .bind(&permission.description)
+            .bind(permission.created_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(created)
+    }
+
+    async fn permission_exists(&self, name: &str) -> Result<bool, ApiError> {
+        let query = "SELECT EXISTS(SELECT 1 FROM permissions WHERE name = $1)";
+
+        let row: (bool,) = sqlx::query_as(query)
+            .bind(name)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(row.0)
+    }
+
+    async fn grant(&self, role: &str, permission: &Permission) -> Result<(), ApiError> {
+        let query = r#"
+            INSERT INTO role_permissions (role, permission)
+            VALUES ($1, $2)
+            ON CONFLICT (role, permission) DO NOTHING
+        "#;
+
+        sqlx::query(query)
+            .bind(role)
+            .bind(permission.as_str())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn revoke(&self, role: &str, permission: &str) -> Result<bool, ApiError> {
+        let query = "DELETE FROM role_permissions WHERE role = $1 AND permission = $2";
+
+        let result = sqlx::query(query)
+            .bind(role)
+            .bind(permission)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,275 @@
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgPool, Postgres, QueryBuilder, Row};
+
+use crate::errors::ApiError;
+use crate::interfaces::repositories::{
+    Cursor, FilterOp, Paged, Pagination, QuerySpec, Ranked, Sort, TextQuery, Value,
+};
+
+/// Rows listed through a `QuerySpec`: exposes what a cursor needs
+pub trait Keyset {
+    fn id(&self) -> &str;
+
+    /// Value of a sortable column (as named in the field allow-list)
+    fn sort_value(&self, column: &str) -> Option<Value>;
+}
+
+/// Run a list query: `SELECT * FROM <table>`, the caller's `scope` (must push a `WHERE` clause),
+/// then the spec's filters, keyset condition, ordering and limit.
+///
+/// Column names come from the allow-list and every value is bound, so nothing from the
+/// request is interpolated. Offset pages also run a `COUNT(*)`; cursor pages fetch one
+/// extra row instead to tell whether there is a next page.
+pub async fn fetch_page<T, F>(
+    pool: &PgPool,
+    table: &str,
+    scope: F,
+    spec: &QuerySpec,
+) -> Result<Paged<T>, ApiError>
+where
+    T: for<'r> FromRow<'r, PgRow> + Keyset + Send + Unpin,
+    F: Fn(&mut QueryBuilder<'_, Postgres>),
+{
+    let total = match spec.pagination {
+        Pagination::Offset { .. } => {
+            let mut count = QueryBuilder::<Postgres>::new(format!("SELECT COUNT(*) FROM {}", table));
+            scope(&mut count);
+            push_filters(&mut count, spec);
+
+            let total: i64 = count
+                .build_query_scalar()
+                .fetch_one(pool)
+                .await
+                .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+            Some(total)
+        }
+        Pagination::Cursor { .. } => None,
+    };
+
+    let mut query = QueryBuilder::<Postgres>::new(format!("SELECT * FROM {}", table));
+    scope(&mut query);
+    push_filters(&mut query, spec);
+
+    if let Pagination::Cursor { after: Some(cursor), .. } = &spec.pagination {
+        if !cursor.fits(&spec.sort) {
+            return Err(ApiError::ValidationError("Cursor does not match the sort order".to_string()));
+        }
+        push_keyset(&mut query, &spec.sort, cursor);
+    }
+
+    query.push(" ORDER BY ");
+    push_order(&mut query, &spec.sort);
+
+    let per_page = spec.pagination.per_page();
+    match spec.pagination {
+        Pagination::Offset { page, .. } => {
+            query
+                .push(" LIMIT ")
+                .push_bind(per_page as i64)
+                .push(" OFFSET ")
+                .push_bind((page as i64 - 1) * per_page as i64);
+        }
+        Pagination::Cursor { .. } => {
+            query.push(" LIMIT ").push_bind(per_page as i64 + 1);
+        }
+    }
+
+    let mut items: Vec<T> = query
+        .build_query_as::<T>()
+        .fetch_all(pool)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    let mut next_cursor = None;
+    if let Pagination::Cursor { .. } = spec.pagination {
+        if items.len() > per_page as usize {
+            items.truncate(per_page as usize);
+            next_cursor = items.last().map(|last| cursor_after(last, &spec.sort)).transpose()?;
+        }
+    }
+
+    Ok(Paged { items, total, next_cursor })
+}
+
+/// Full-text index of a table: the generated `search_vector` column (see `015_add_full_text_search.sql`)
+pub struct TextIndex {
+    /// Text search configuration the vector was built with (`english`, `simple`)
+    pub config: &'static str,
+    /// SQL expression of the text shown in the headline
+    pub headline: &'static str,
+}
+
+/// Like `fetch_page`, restricted to rows matching `text` and ordered by relevance first
+/// (the spec's sort breaks ties). Ranks are not stable across writes, so only offset
+/// pages are supported.
+pub async fn fetch_ranked<T, F>(
+    pool: &PgPool,
+    table: &str,
+    index: &TextIndex,
+    scope: F,
+    text: &TextQuery,
+    spec: &QuerySpec,
+) -> Result<Paged<Ranked<T>>, ApiError>
+where
+    T: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+    F: Fn(&mut QueryBuilder<'_, Postgres>),
+{
+    let (page, per_page) = match spec.pagination {
+        Pagination::Offset { page, per_page } => (page, per_page),
+        Pagination::Cursor { .. } => {
+            return Err(ApiError::ValidationError(
+                "Search results are paged with page, not cursor".to_string(),
+            ))
+        }
+    };
+
+    let tsquery = |builder: &mut QueryBuilder<'_, Postgres>| {
+        builder
+            .push("to_tsquery('")
+            .push(index.config)
+            .push("', ")
+            .push_bind(text.to_tsquery())
+            .push(")");
+    };
+
+    let mut count = QueryBuilder::<Postgres>::new(format!("SELECT COUNT(*) FROM {}", table));
+    scope(&mut count);
+    count.push(" AND search_vector @@ ");
+    tsquery(&mut count);
+    push_filters(&mut count, spec);
+
+    let total: i64 = count
+        .build_query_scalar()
+        .fetch_one(pool)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    let mut query = QueryBuilder::<Postgres>::new("SELECT *, ts_rank(search_vector, ");
+    tsquery(&mut query);
+    query.push(") AS rank, ts_headline('").push(index.config).push("', ").push(index.headline).push(", ");
+    tsquery(&mut query);
+    query.push(format!(", 'StartSel=<mark>, StopSel=</mark>') AS headline FROM {}", table));
+    scope(&mut query);
+    query.push(" AND search_vector @@ ");
+    tsquery(&mut query);
+    push_filters(&mut query, spec);
+
+    query.push(" ORDER BY rank DESC, ");
+    push_order(&mut query, &spec.sort);
+    query
+        .push(" LIMIT ")
+        .push_bind(per_page as i64)
+        .push(" OFFSET ")
+        .push_bind((page as i64 - 1) * per_page as i64);
+
+    let items = query
+        .build_query_as::<Ranked<T>>()
+        .fetch_all(pool)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    Ok(Paged { items, total: Some(total), next_cursor: None })
+}
+
+impl<'r, T: FromRow<'r, PgRow>> FromRow<'r, PgRow> for Ranked<T> {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            item: T::from_row(row)?,
+            rank: row.try_get("rank")?,
+            headline: row.try_get("headline")?,
+        })
+    }
+}
+
+/// `<sort>, ..., id ASC`
+fn push_order(builder: &mut QueryBuilder<'_, Postgres>, sort: &[Sort]) {
+    for s in sort {
+        builder
+            .push(s.column)
+            .push(if s.descending { " DESC, " } else { " ASC, " });
+    }
+    builder.push("id ASC");
+}
+
+/// ` AND <column> <op> <value>` for every filter
+fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, spec: &QuerySpec) {
+    for filter in &spec.filters {
+        builder.push(" AND ").push(filter.column);
+
+        match (filter.op, &filter.value) {
+            (FilterOp::Contains, Value::Text(text)) => {
+                let pattern = format!("%{}%", text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+                builder.push(" ILIKE ").push_bind(pattern);
+            }
+            (op, value) => {
+                builder.push(match op {
+                    FilterOp::Eq | FilterOp::Contains => " = ",
+                    FilterOp::Ne => " <> ",
+                    FilterOp::Lt => " < ",
+                    FilterOp::Lte => " <= ",
+                    FilterOp::Gt => " > ",
+                    FilterOp::Gte => " >= ",
+                });
+                push_value(builder, value);
+            }
+        }
+    }
+}
+
+/// Rows strictly after the cursor in `ORDER BY sort..., id ASC`:
+/// `(s1 > v1) OR (s1 = v1 AND s2 > v2) OR ... OR (s1 = v1 AND ... AND id > last_id)`
+/// with `<` for descending keys.
+fn push_keyset(builder: &mut QueryBuilder<'_, Postgres>, sort: &[Sort], cursor: &Cursor) {
+    builder.push(" AND (");
+
+    for i in 0..=sort.len() {
+        if i > 0 {
+            builder.push(" OR ");
+        }
+        builder.push("(");
+
+        for (s, value) in sort.iter().zip(&cursor.values).take(i) {
+            builder.push(s.column).push(" = ");
+            push_value(builder, value);
+            builder.push(" AND ");
+        }
+
+        match sort.get(i) {
+            Some(s) => {
+                builder
+                    .push(s.column)
+                    .push(if s.descending { " < " } else { " > " });
+                push_value(builder, &cursor.values[i]);
+            }
+            None => {
+                builder.push("id > ").push_bind(cursor.id.clone());
+            }
+        }
+
+        builder.push(")");
+    }
+
+    builder.push(")");
+}
+
+fn push_value(builder: &mut QueryBuilder<'_, Postgres>, value: &Value) {
+    match value {
+        Value::Text(v) => builder.push_bind(v.clone()),
+        Value::Bool(v) => builder.push_bind(*v),
+        Value::Integer(v) => builder.push_bind(*v),
+        Value::Timestamp(v) => builder.push_bind(*v),
+    };
+}
+
+fn cursor_after<T: Keyset>(row: &T, sort: &[Sort]) -> Result<Cursor, ApiError> {
+    let values = sort
+        .iter()
+        .map(|s| {
+            row.sort_value(s.column).ok_or_else(|| {
+                ApiError::InternalServerError(format!("No cursor value for column {}", s.column))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Cursor { values, id: row.id().to_string() })
+}
@@ -0,0 +1,37 @@
+use futures::channel::mpsc;
+use futures::stream::BoxStream;
+use futures::{SinkExt, StreamExt};
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgPool};
+
+use crate::errors::ApiError;
+
+/// Rows handed over at a time; the query waits while the consumer is this far behind
+const BUFFER: usize = 64;
+
+/// Stream the rows of `sql` (with `$1` bound to `tenant_id`) without collecting them.
+///
+/// A task drives the query and passes rows over a bounded channel, so a slow consumer
+/// (e.g. an HTTP client downloading an export) holds back the query instead of filling memory.
+/// The task stops when the receiver is dropped.
+pub fn stream_rows<T>(pool: PgPool, sql: String, tenant_id: String) -> BoxStream<'static, Result<T, ApiError>>
+where
+    T: for<'r> FromRow<'r, PgRow> + Send + Unpin + 'static,
+{
+    let (mut tx, rx) = mpsc::channel(BUFFER);
+
+    tokio::spawn(async move {
+        let mut rows = sqlx::query_as::<_, T>(&sql).bind(tenant_id).fetch(&pool);
+
+        while let Some(row) = rows.next().await {
+            let row = row.map_err(|e| ApiError::DatabaseError(e.to_string()));
+            let failed = row.is_err();
+
+            if tx.send(row).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+
+    rx.boxed()
+}
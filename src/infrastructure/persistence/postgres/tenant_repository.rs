@@ -0,0 +1,43 @@
+use sqlx::PgPool;
+use async_trait::async_trait;
+
+use crate::domain::entities::Tenant;
+use crate::errors::ApiError;
+use crate::interfaces::repositories::TenantRepository;
+
+pub struct PostgresTenantRepository {
+    pool: PgPool,
+}
+
+impl PostgresTenantRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TenantRepository for PostgresTenantRepository {
+    async fn get_by_id(&self, id: &str) -> Result<Option<Tenant>, ApiError> {
+        let query = "SELECT * FROM tenants WHERE id = $1";
+
+        let tenant = sqlx::query_as::<_, Tenant>(query)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(tenant)
+    }
+
+    async fn get_by_slug(&self, slug: &str) -> Result<Option<Tenant>, ApiError> {
+        let query = "SELECT * FROM tenants WHERE slug = $1";
+
+        let tenant = sqlx::query_as::<_, Tenant>(query)
+            .bind(slug)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(tenant)
+    }
+}
@@ -1,10 +1,18 @@
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, QueryBuilder};
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
 
 use crate::domain::entities::TestItem;
 use crate::errors::ApiError;
-use crate::interfaces::repositories::TestItemRepository;
+use crate::interfaces::repositories::{Paged, QuerySpec, Ranked, TestItemRepository, TextQuery, Trashed, Value};
+use super::query_spec::{fetch_page, fetch_ranked, Keyset, TextIndex};
+use super::stream::stream_rows;
+
+const TEXT_INDEX: TextIndex = TextIndex {
+    config: "english",
+    headline: "concat_ws(' ', subject, optional_field)",
+};
 
 pub struct PostgresTestItemRepository {
     pool: PgPool,
@@ -14,19 +22,29 @@ impl PostgresTestItemRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    /// Append the tenant and trash conditions
+    fn push_scope(builder: &mut QueryBuilder<'_, Postgres>, tenant_id: &str, trashed: Trashed) {
+        builder.push(" WHERE tenant_id = ").push_bind(tenant_id.to_string());
+
+        if let Some(condition) = trashed.condition() {
+            builder.push(" AND ").push(condition);
+        }
+    }
 }
 
 #[async_trait]
 impl TestItemRepository for PostgresTestItemRepository {
     async fn create(&self, item: &TestItem) -> Result<TestItem, ApiError> {
         let query = r#"
-            INSERT INTO test_items (id, subject, optional_field, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO test_items (id, tenant_id, subject, optional_field, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
             RETURNING *
         "#;
 
         let created_item = sqlx::query_as::<_, TestItem>(query)
             .bind(&item.id)
+            .bind(&item.tenant_id)
             .bind(&item.subject)
             .bind(&item.optional_field)
             .bind(item.created_at)
@@ -38,10 +56,31 @@ impl TestItemRepository for PostgresTestItemRepository {
         Ok(created_item)
     }
 
-    async fn get_by_id(&self, id: &str) -> Result<Option<TestItem>, ApiError> {
-        let query = "SELECT * FROM test_items WHERE id = $1";
+    async fn create_many(&self, items: &[TestItem]) -> Result<Vec<TestItem>, ApiError> {
+        // One row per array element: a single statement, so the batch is inserted all or nothing
+        let query = r#"
+            INSERT INTO test_items (id, tenant_id, subject, optional_field, created_at, updated_at)
+            SELECT * FROM UNNEST($1::text[], $2::text[], $3::text[], $4::text[], $5::timestamptz[], $6::timestamptz[])
+            RETURNING *
+        "#;
+
+        sqlx::query_as::<_, TestItem>(query)
+            .bind(items.iter().map(|i| i.id.clone()).collect::<Vec<_>>())
+            .bind(items.iter().map(|i| i.tenant_id.clone()).collect::<Vec<_>>())
+            .bind(items.iter().map(|i| i.subject.clone()).collect::<Vec<_>>())
+            .bind(items.iter().map(|i| i.optional_field.clone()).collect::<Vec<_>>())
+            .bind(items.iter().map(|i| i.created_at).collect::<Vec<_>>())
+            .bind(items.iter().map(|i| i.updated_at).collect::<Vec<_>>())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))
+    }
+
+    async fn get_by_id(&self, tenant_id: &str, id: &str) -> Result<Option<TestItem>, ApiError> {
+        let query = "SELECT * FROM test_items WHERE tenant_id = $1 AND id = $2 AND deleted_at IS NULL";
 
         let item = sqlx::query_as::<_, TestItem>(query)
+            .bind(tenant_id)
             .bind(id)
             .fetch_optional(&self.pool)
             .await
@@ -50,61 +89,157 @@ impl TestItemRepository for PostgresTestItemRepository {
         Ok(item)
     }
 
-    async fn get_all(&self) -> Result<Vec<TestItem>, ApiError> {
-        let query = "SELECT * FROM test_items ORDER BY created_at DESC";
+    async fn get_by_id_with_trashed(&self, tenant_id: &str, id: &str) -> Result<Option<TestItem>, ApiError> {
+        let query = "SELECT * FROM test_items WHERE tenant_id = $1 AND id = $2";
 
-        let items = sqlx::query_as::<_, TestItem>(query)
-            .fetch_all(&self.pool)
+        let item = sqlx::query_as::<_, TestItem>(query)
+            .bind(tenant_id)
+            .bind(id)
+            .fetch_optional(&self.pool)
             .await
             .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
 
-        Ok(items)
+        Ok(item)
     }
 
-    async fn get_paginated(&self, page: i32, per_page: i32) -> Result<(Vec<TestItem>, i32), ApiError> {
-        let offset = (page - 1) * per_page;
+    async fn get_many(&self, tenant_id: &str, ids: &[String]) -> Result<Vec<TestItem>, ApiError> {
+        let query = "SELECT * FROM test_items WHERE tenant_id = $1 AND id = ANY($2) AND deleted_at IS NULL";
 
-        let query = r#"
-            SELECT * FROM test_items
-            ORDER BY created_at DESC
-            LIMIT $1 OFFSET $2
-        "#;
+        sqlx::query_as::<_, TestItem>(query)
+            .bind(tenant_id)
+            .bind(ids)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))
+    }
+
+    async fn get_all(&self, tenant_id: &str) -> Result<Vec<TestItem>, ApiError> {
+        let query = "SELECT * FROM test_items WHERE tenant_id = $1 AND deleted_at IS NULL ORDER BY created_at DESC";
 
         let items = sqlx::query_as::<_, TestItem>(query)
-            .bind(per_page)
-            .bind(offset)
+            .bind(tenant_id)
             .fetch_all(&self.pool)
             .await
             .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
 
-        let total = self.count().await?;
+        Ok(items)
+    }
+
+    fn stream_all(&self, tenant_id: &str, trashed: Trashed) -> BoxStream<'static, Result<TestItem, ApiError>> {
+        let mut query = "SELECT * FROM test_items WHERE tenant_id = $1".to_string();
+        if let Some(condition) = trashed.condition() {
+            query.push_str(" AND ");
+            query.push_str(condition);
+        }
+        query.push_str(" ORDER BY created_at, id");
+
+        stream_rows(self.pool.clone(), query, tenant_id.to_string())
+    }
+
+    async fn list(&self, tenant_id: &str, trashed: Trashed, spec: &QuerySpec) -> Result<Paged<TestItem>, ApiError> {
+        fetch_page(&self.pool, "test_items", |builder| Self::push_scope(builder, tenant_id, trashed), spec).await
+    }
 
-        Ok((items, total))
+    async fn search_text(
+        &self,
+        tenant_id: &str,
+        trashed: Trashed,
+        text: &TextQuery,
+        spec: &QuerySpec,
+    ) -> Result<Paged<Ranked<TestItem>>, ApiError> {
+        fetch_ranked(
+            &self.pool,
+            "test_items",
+            &TEXT_INDEX,
+            |builder| Self::push_scope(builder, tenant_id, trashed),
+            text,
+            spec,
+        )
+        .await
     }
 
-    async fn update(&self, item: &TestItem) -> Result<(), ApiError> {
+    async fn update(&self, item: &TestItem) -> Result<TestItem, ApiError> {
         let query = r#"
             UPDATE test_items
-            SET subject = $1, optional_field = $2, updated_at = $3
-            WHERE id = $4
+            SET subject = $1, optional_field = $2, updated_at = $3, version = version + 1
+            WHERE id = $4 AND tenant_id = $5 AND version = $6 AND deleted_at IS NULL
+            RETURNING *
         "#;
 
-        sqlx::query(query)
+        sqlx::query_as::<_, TestItem>(query)
             .bind(&item.subject)
             .bind(&item.optional_field)
-            .bind(Utc::now())
+            .bind(item.updated_at)
             .bind(&item.id)
+            .bind(&item.tenant_id)
+            .bind(item.version)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| ApiError::Conflict("Test item was modified or deleted by another request".to_string()))
+    }
+
+    async fn update_many(&self, items: &[TestItem]) -> Result<Vec<TestItem>, ApiError> {
+        let query = r#"
+            UPDATE test_items AS t
+            SET subject = u.subject, optional_field = u.optional_field, updated_at = u.updated_at, version = t.version + 1
+            FROM UNNEST($1::text[], $2::text[], $3::text[], $4::text[], $5::timestamptz[], $6::integer[])
+                AS u(id, tenant_id, subject, optional_field, updated_at, version)
+            WHERE t.id = u.id AND t.tenant_id = u.tenant_id AND t.version = u.version AND t.deleted_at IS NULL
+            RETURNING t.*
+        "#;
+
+        sqlx::query_as::<_, TestItem>(query)
+            .bind(items.iter().map(|i| i.id.clone()).collect::<Vec<_>>())
+            .bind(items.iter().map(|i| i.tenant_id.clone()).collect::<Vec<_>>())
+            .bind(items.iter().map(|i| i.subject.clone()).collect::<Vec<_>>())
+            .bind(items.iter().map(|i| i.optional_field.clone()).collect::<Vec<_>>())
+            .bind(items.iter().map(|i| i.updated_at).collect::<Vec<_>>())
+            .bind(items.iter().map(|i| i.version).collect::<Vec<_>>())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))
+    }
+
+    async fn delete(&self, tenant_id: &str, id: &str) -> Result<bool, ApiError> {
+        let query = r#"
+            UPDATE test_items SET deleted_at = NOW()
+            WHERE tenant_id = $1 AND id = $2 AND deleted_at IS NULL
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(tenant_id)
+            .bind(id)
             .execute(&self.pool)
             .await
             .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
 
-        Ok(())
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_many(&self, tenant_id: &str, ids: &[String]) -> Result<Vec<String>, ApiError> {
+        let query = r#"
+            UPDATE test_items SET deleted_at = NOW()
+            WHERE tenant_id = $1 AND id = ANY($2) AND deleted_at IS NULL
+            RETURNING id
+        "#;
+
+        sqlx::query_scalar(query)
+            .bind(tenant_id)
+            .bind(ids)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))
     }
 
-    async fn delete(&self, id: &str) -> Result<bool, ApiError> {
-        let query = "DELETE FROM test_items WHERE id = $1";
+    async fn restore(&self, tenant_id: &str, id: &str) -> Result<bool, ApiError> {
+        let query = r#"
+            UPDATE test_items SET deleted_at = NULL, updated_at = NOW(), version = version + 1
+            WHERE tenant_id = $1 AND id = $2 AND deleted_at IS NOT NULL
+        "#;
 
         let result = sqlx::query(query)
+            .bind(tenant_id)
             .bind(id)
             .execute(&self.pool)
             .await
@@ -113,14 +248,290 @@ impl TestItemRepository for PostgresTestItemRepository {
         Ok(result.rows_affected() > 0)
     }
 
-    async fn count(&self) -> Result<i32, ApiError> {
-        let query = "SELECT COUNT(*) as count FROM test_items";
+    async fn purge_trashed_before(&self, cutoff: DateTime<Utc>) -> Result<u64, ApiError> {
+        let result = sqlx::query("DELETE FROM test_items WHERE deleted_at < $1")
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn count(&self, tenant_id: &str) -> Result<i32, ApiError> {
+        let query = "SELECT COUNT(*) as count FROM test_items WHERE tenant_id = $1 AND deleted_at IS NULL";
 
         let row: (i64,) = sqlx::query_as(query)
+            .bind(tenant_id)
             .fetch_one(&self.pool)
             .await
             .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
 
         Ok(row.0 as i32)
     }
-}
\ No newline at end of file
+}
+
+impl Keyset for TestItem {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn sort_value(&self, column: &str) -> Option<Value> {
+        match column {
+            "subject" => Some(Value::Text(self.subject.clone())),
+            "created_at" => Some(Value::Timestamp(self.created_at)),
+            "updated_at" => Some(Value::Timestamp(self.updated_at)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::interfaces::repositories::TEST_ITEM_FIELDS;
+    use futures::StreamExt;
+
+    fn spec(params: &[(&str, &str)]) -> QuerySpec {
+        let params: HashMap<String, String> = params.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        QuerySpec::parse(&params, TEST_ITEM_FIELDS, "-created_at").unwrap()
+    }
+
+    /// Connects to `TEST_DATABASE_URL` and registers two throwaway tenants
+    async fn setup() -> (PostgresTestItemRepository, String, String) {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let mut tenants = Vec::new();
+        for _ in 0..2 {
+            let id = uuid::Uuid::new_v4().to_string();
+            sqlx::query("INSERT INTO tenants (id, slug, name) VALUES ($1, $1, $1)")
+                .bind(&id)
+                .execute(&pool)
+                .await
+                .unwrap();
+            tenants.push(id);
+        }
+
+        let tenant_b = tenants.pop().unwrap();
+        let tenant_a = tenants.pop().unwrap();
+        (PostgresTestItemRepository::new(pool), tenant_a, tenant_b)
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+    async fn test_items_are_invisible_to_other_tenants() {
+        let (repo, tenant_a, tenant_b) = setup().await;
+        let item = repo
+            .create(&TestItem::new(tenant_a.clone(), "secret".to_string(), None))
+            .await
+            .unwrap();
+
+        assert!(repo.get_by_id(&tenant_a, &item.id).await.unwrap().is_some());
+        assert!(repo.get_by_id(&tenant_b, &item.id).await.unwrap().is_none());
+        assert!(repo.get_all(&tenant_b).await.unwrap().is_empty());
+        assert_eq!(repo.list(&tenant_b, Trashed::Include, &spec(&[])).await.unwrap().total, Some(0));
+        assert_eq!(repo.count(&tenant_a).await.unwrap(), 1);
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+    async fn test_items_cannot_be_changed_from_other_tenants() {
+        let (repo, tenant_a, tenant_b) = setup().await;
+        let item = repo
+            .create(&TestItem::new(tenant_a.clone(), "original".to_string(), None))
+            .await
+            .unwrap();
+
+        let mut forged = item.clone();
+        forged.tenant_id = tenant_b.clone();
+        forged.update_subject("tampered".to_string());
+        assert!(repo.update(&forged).await.is_err());
+        assert!(!repo.delete(&tenant_b, &item.id).await.unwrap());
+
+        let stored = repo.get_by_id(&tenant_a, &item.id).await.unwrap().unwrap();
+        assert_eq!(stored.subject, "original");
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+    async fn test_deleted_items_go_to_the_trash_until_restored() {
+        let (repo, tenant, _) = setup().await;
+        let item = repo
+            .create(&TestItem::new(tenant.clone(), "trashed".to_string(), None))
+            .await
+            .unwrap();
+
+        assert!(repo.delete(&tenant, &item.id).await.unwrap());
+        assert!(!repo.delete(&tenant, &item.id).await.unwrap());
+        assert!(repo.get_by_id(&tenant, &item.id).await.unwrap().is_none());
+        assert_eq!(repo.count(&tenant).await.unwrap(), 0);
+        assert_eq!(repo.list(&tenant, Trashed::Only, &spec(&[])).await.unwrap().total, Some(1));
+        assert!(repo.get_by_id_with_trashed(&tenant, &item.id).await.unwrap().unwrap().is_trashed());
+
+        assert!(repo.restore(&tenant, &item.id).await.unwrap());
+        assert!(!repo.restore(&tenant, &item.id).await.unwrap());
+        assert!(repo.get_by_id(&tenant, &item.id).await.unwrap().is_some());
+        assert_eq!(repo.list(&tenant, Trashed::Only, &spec(&[])).await.unwrap().total, Some(0));
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+    async fn test_purge_only_removes_items_trashed_before_the_cutoff() {
+        let (repo, tenant, _) = setup().await;
+        let live = repo.create(&TestItem::new(tenant.clone(), "live".to_string(), None)).await.unwrap();
+        let trashed = repo.create(&TestItem::new(tenant.clone(), "trashed".to_string(), None)).await.unwrap();
+        repo.delete(&tenant, &trashed.id).await.unwrap();
+
+        repo.purge_trashed_before(Utc::now() - chrono::Duration::days(1)).await.unwrap();
+        assert!(repo.get_by_id_with_trashed(&tenant, &trashed.id).await.unwrap().is_some());
+
+        repo.purge_trashed_before(Utc::now() + chrono::Duration::seconds(1)).await.unwrap();
+        assert!(repo.get_by_id_with_trashed(&tenant, &trashed.id).await.unwrap().is_none());
+        assert!(repo.get_by_id(&tenant, &live.id).await.unwrap().is_some());
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+    async fn test_stale_updates_are_rejected() {
+        let (repo, tenant, _) = setup().await;
+        let item = repo
+            .create(&TestItem::new(tenant.clone(), "original".to_string(), None))
+            .await
+            .unwrap();
+
+        let mut first = item.clone();
+        first.update_subject("first".to_string());
+        let stored = repo.update(&first).await.unwrap();
+        assert_eq!(stored.version, item.version + 1);
+        assert_eq!(stored.updated_at.timestamp_micros(), first.updated_at.timestamp_micros());
+
+        let mut second = item.clone();
+        second.update_subject("second".to_string());
+        assert!(matches!(repo.update(&second).await, Err(ApiError::Conflict(_))));
+
+        let current = repo.get_by_id(&tenant, &item.id).await.unwrap().unwrap();
+        assert_eq!(current.subject, "first");
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+    async fn test_cursor_pages_cover_every_row_once() {
+        let (repo, tenant, _) = setup().await;
+        // Two subjects so the sort has ties that only the id breaks
+        for i in 0..7 {
+            let subject = if i % 2 == 0 { "even" } else { "odd" };
+            repo.create(&TestItem::new(tenant.clone(), subject.to_string(), None)).await.unwrap();
+        }
+
+        let mut seen = Vec::new();
+        let mut cursor = String::new();
+        loop {
+            let page = repo
+                .list(&tenant, Trashed::Exclude, &spec(&[("sort", "-subject"), ("per_page", "3"), ("cursor", &cursor)]))
+                .await
+                .unwrap();
+            assert!(page.total.is_none());
+            seen.extend(page.items.iter().map(|i| (i.subject.clone(), i.id.clone())));
+
+            match page.next_cursor {
+                Some(next) => cursor = next.encode(),
+                None => break,
+            }
+        }
+
+        let mut expected = seen.clone();
+        expected.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        assert_eq!(seen.len(), 7);
+        assert_eq!(seen, expected);
+
+        let filtered = repo.list(&tenant, Trashed::Exclude, &spec(&[("subject", "odd")])).await.unwrap();
+        assert_eq!(filtered.total, Some(3));
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+    async fn test_text_search_ranks_subject_matches_first() {
+        let (repo, tenant, other) = setup().await;
+        let in_note = repo
+            .create(&TestItem::new(tenant.clone(), "Quarterly report".to_string(), Some("Invoices attached".to_string())))
+            .await
+            .unwrap();
+        let in_subject = repo
+            .create(&TestItem::new(tenant.clone(), "Unpaid invoice".to_string(), None))
+            .await
+            .unwrap();
+        repo.create(&TestItem::new(tenant.clone(), "Holiday plan".to_string(), None)).await.unwrap();
+        repo.create(&TestItem::new(other, "Invoice".to_string(), None)).await.unwrap();
+
+        let text = TextQuery::parse("invoic").unwrap();
+        let hits = repo.search_text(&tenant, Trashed::Exclude, &text, &spec(&[])).await.unwrap();
+
+        assert_eq!(hits.total, Some(2));
+        let ids: Vec<_> = hits.items.iter().map(|hit| hit.item.id.clone()).collect();
+        assert_eq!(ids, vec![in_subject.id, in_note.id]);
+        assert!(hits.items[0].headline.contains("<mark>invoice</mark>"));
+
+        assert!(repo.search_text(&tenant, Trashed::Exclude, &text, &spec(&[("cursor", "")])).await.is_err());
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+    async fn test_bulk_writes_skip_stale_and_foreign_rows() {
+        let (repo, tenant, other) = setup().await;
+        let items: Vec<TestItem> = (0..3)
+            .map(|i| TestItem::new(tenant.clone(), format!("bulk {}", i), None))
+            .collect();
+        let created = repo.create_many(&items).await.unwrap();
+        assert_eq!(created.len(), 3);
+
+        let ids: Vec<String> = items.iter().map(|i| i.id.clone()).collect();
+        let mut loaded = repo.get_many(&tenant, &ids).await.unwrap();
+        assert_eq!(loaded.len(), 3);
+        assert!(repo.get_many(&other, &ids).await.unwrap().is_empty());
+
+        loaded.sort_by(|a, b| a.subject.cmp(&b.subject));
+        for item in loaded.iter_mut() {
+            item.update_subject(format!("{} updated", item.subject));
+        }
+        loaded[1].version = 7;
+
+        let updated = repo.update_many(&loaded).await.unwrap();
+        let mut subjects: Vec<_> = updated.iter().map(|i| (i.subject.clone(), i.version)).collect();
+        subjects.sort();
+        assert_eq!(subjects, vec![("bulk 0 updated".to_string(), 2), ("bulk 2 updated".to_string(), 2)]);
+
+        assert!(repo.delete_many(&other, &ids).await.unwrap().is_empty());
+        let mut deleted = repo.delete_many(&tenant, &ids).await.unwrap();
+        deleted.sort();
+        let mut expected = ids.clone();
+        expected.sort();
+        assert_eq!(deleted, expected);
+        assert!(repo.delete_many(&tenant, &ids).await.unwrap().is_empty());
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+    async fn test_stream_all_reads_the_tenant_oldest_first() {
+        let (repo, tenant, other) = setup().await;
+        let mut ids = Vec::new();
+        for i in 0..3 {
+            ids.push(repo.create(&TestItem::new(tenant.clone(), format!("export {}", i), None)).await.unwrap().id);
+        }
+        repo.create(&TestItem::new(other.clone(), "other".to_string(), None)).await.unwrap();
+        repo.delete(&tenant, &ids[1]).await.unwrap();
+
+        let live: Vec<String> = repo.stream_all(&tenant, Trashed::Exclude)
+            .map(|item| item.unwrap().id)
+            .collect()
+            .await;
+        assert_eq!(live, vec![ids[0].clone(), ids[2].clone()]);
+
+        let all: Vec<String> = repo.stream_all(&tenant, Trashed::Include)
+            .map(|item| item.unwrap().id)
+            .collect()
+            .await;
+        assert_eq!(all, ids);
+    }
+}
@@ -0,0 +1,123 @@
+use sqlx::PgPool;
+use async_trait::async_trait;
+
+use crate::domain::entities::{OAuthState, UserIdentity};
+use crate::errors::ApiError;
+use crate::interfaces::repositories::UserIdentityRepository;
+
+pub struct PostgresUserIdentityRepository {
+    pool: PgPool,
+}
+
+impl PostgresUserIdentityRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UserIdentityRepository for PostgresUserIdentityRepository {
+    async fn get_by_subject(&self, tenant_id: &str, provider: &str, subject: &str) -> Result<Option<UserIdentity>, ApiError> {
+        let query = "SELECT * FROM user_identities WHERE tenant_id = $1 AND provider = $2 AND subject = $3";
+
+        let identity = sqlx::query_as::<_, UserIdentity>(query)
+            .bind(tenant_id)
+            .bind(provider)
+            .bind(subject)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(identity)
+    }
+
+    async fn get_by_user(&self, tenant_id: &str, user_id: &str) -> Result<Vec<UserIdentity>, ApiError> {
+        let query = "SELECT * FROM user_identities WHERE tenant_id = $1 AND user_id = $2 ORDER BY created_at";
+
+        let identities = sqlx::query_as::<_, UserIdentity>(query)
+            .bind(tenant_id)
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(identities)
+    }
+
+    async fn create(&self, identity: &UserIdentity) -> Result<UserIdentity, ApiError> {
+        let query = r#"
+            INSERT INTO user_identities (id, tenant_id, user_id, provider, subject, email, created_at, last_login_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+        "#;
+
+        let created = sqlx::query_as::<_, UserIdentity>(query)
+            .bind(&identity.id)
+            .bind(&identity.tenant_id)
+            .bind(&identity.user_id)
+            .bind(&identity.provider)
+            .bind(&identity.subject)
+            .bind(&identity.email)
+            .bind(identity.created_at)
+            .bind(identity.last_login_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(created)
+    }
+
+    async fn touch_last_login(&self, id: &str) -> Result<(), ApiError> {
+        let query = "UPDATE user_identities SET last_login_at = NOW() WHERE id = $1";
+
+        sqlx::query(query)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn save_state(&self, state: &OAuthState) -> Result<(), ApiError> {
+        let query = r#"
+            INSERT INTO oauth_states (state, provider, tenant_id, code_verifier, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+        "#;
+
+        sqlx::query(query)
+            .bind(&state.state)
+            .bind(&state.provider)
+            .bind(&state.tenant_id)
+            .bind(&state.code_verifier)
+            .bind(state.expires_at)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn take_state(&self, state: &str) -> Result<Option<OAuthState>, ApiError> {
+        let query = "DELETE FROM oauth_states WHERE state = $1 RETURNING *";
+
+        let state = sqlx::query_as::<_, OAuthState>(query)
+            .bind(state)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(state)
+    }
+
+    async fn purge_expired_states(&self) -> Result<u64, ApiError> {
+        let query = "DELETE FROM oauth_states WHERE expires_at < NOW()";
+
+        let result = sqlx::query(query)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -1,10 +1,19 @@
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, QueryBuilder};
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
 
 use crate::domain::entities::User;
+use crate::domain::value_objects::Role;
 use crate::errors::ApiError;
-use crate::interfaces::repositories::UserRepository;
+use crate::interfaces::repositories::{Paged, QuerySpec, Ranked, TextQuery, Trashed, UserFilter, UserRepository, Value};
+use super::query_spec::{fetch_page, fetch_ranked, Keyset, TextIndex};
+use super::stream::stream_rows;
+
+const TEXT_INDEX: TextIndex = TextIndex {
+    config: "simple",
+    headline: "concat_ws(' ', username, email)",
+};
 
 pub struct PostgresUserRepository {
     pool: PgPool,
@@ -14,14 +23,35 @@ impl PostgresUserRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    /// Append the WHERE clause for a user list or search (values are always bound)
+    fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, tenant_id: &str, filter: &UserFilter) {
+        builder.push(" WHERE tenant_id = ").push_bind(tenant_id.to_string());
+
+        if let Some(condition) = filter.trashed.condition() {
+            builder.push(" AND ").push(condition);
+        }
+
+        if let Some(search) = filter.search.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+            let pattern = format!("%{}%", search.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+            builder
+                .push(" AND (email ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" OR username ILIKE ")
+                .push_bind(pattern)
+                .push(")");
+        }
+    }
 }
 
 #[async_trait]
 impl UserRepository for PostgresUserRepository {
     async fn create(&self, user: &User) -> Result<User, ApiError> {
         let query = r#"
-            INSERT INTO users (id, email, username, password_hash, role, is_active, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO users (id, email, username, password_hash, role, is_active,
+                two_factor_secret, two_factor_enabled, two_factor_confirmed_at, two_factor_recovery_codes,
+                created_at, updated_at, password_reset_required, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
             RETURNING *
         "#;
         
@@ -32,8 +62,14 @@ impl UserRepository for PostgresUserRepository {
             .bind(&user.password_hash)
             .bind(user.role.as_str())
             .bind(user.is_active)
+            .bind(&user.two_factor_secret)
+            .bind(user.two_factor_enabled)
+            .bind(user.two_factor_confirmed_at)
+            .bind(&user.two_factor_recovery_codes)
             .bind(user.created_at)
             .bind(user.updated_at)
+            .bind(user.password_reset_required)
+            .bind(&user.tenant_id)
             .fetch_one(&self.pool)
             .await
             .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
@@ -41,11 +77,25 @@ impl UserRepository for PostgresUserRepository {
         Ok(created_user)
     }
 
-    async fn get_by_id(&self, id: &str) -> Result<Option<User>, ApiError> {
-        let query = "SELECT * FROM users WHERE id = $1";
+    async fn get_by_id(&self, tenant_id: &str, id: &str) -> Result<Option<User>, ApiError> {
+        let query = "SELECT * FROM users WHERE tenant_id = $1 AND id = $2 AND deleted_at IS NULL";
 
         // ✅ Usar query_as
         let user = sqlx::query_as::<_, User>(query)
+            .bind(tenant_id)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(user)
+    }
+
+    async fn get_by_id_with_trashed(&self, tenant_id: &str, id: &str) -> Result<Option<User>, ApiError> {
+        let query = "SELECT * FROM users WHERE tenant_id = $1 AND id = $2";
+
+        let user = sqlx::query_as::<_, User>(query)
+            .bind(tenant_id)
             .bind(id)
             .fetch_optional(&self.pool)
             .await
@@ -54,11 +104,12 @@ impl UserRepository for PostgresUserRepository {
         Ok(user)
     }
 
-    async fn get_by_email(&self, email: &str) -> Result<Option<User>, ApiError> {
-        let query = "SELECT * FROM users WHERE email = $1";
+    async fn get_by_email(&self, tenant_id: &str, email: &str) -> Result<Option<User>, ApiError> {
+        let query = "SELECT * FROM users WHERE tenant_id = $1 AND email = $2 AND deleted_at IS NULL";
 
         // ✅ Usar query_as
         let user = sqlx::query_as::<_, User>(query)
+            .bind(tenant_id)
             .bind(email)
             .fetch_optional(&self.pool)
             .await
@@ -67,11 +118,12 @@ impl UserRepository for PostgresUserRepository {
         Ok(user)
     }
 
-    async fn get_all(&self) -> Result<Vec<User>, ApiError> {
-        let query = "SELECT * FROM users ORDER BY created_at DESC";
+    async fn get_all(&self, tenant_id: &str) -> Result<Vec<User>, ApiError> {
+        let query = "SELECT * FROM users WHERE tenant_id = $1 AND deleted_at IS NULL ORDER BY created_at DESC";
 
         // ✅ Usar query_as
         let users = sqlx::query_as::<_, User>(query)
+            .bind(tenant_id)
             .fetch_all(&self.pool)
             .await
             .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
@@ -79,44 +131,102 @@ impl UserRepository for PostgresUserRepository {
         Ok(users)
     }
 
-    async fn get_paginated(&self, page: i32, per_page: i32) -> Result<(Vec<User>, i32), ApiError> {
-        let offset = (page - 1) * per_page;
+    fn stream_all(&self, tenant_id: &str, trashed: Trashed) -> BoxStream<'static, Result<User, ApiError>> {
+        let mut query = "SELECT * FROM users WHERE tenant_id = $1".to_string();
+        if let Some(condition) = trashed.condition() {
+            query.push_str(" AND ");
+            query.push_str(condition);
+        }
+        query.push_str(" ORDER BY created_at, id");
 
+        stream_rows(self.pool.clone(), query, tenant_id.to_string())
+    }
+
+    async fn list(&self, tenant_id: &str, spec: &QuerySpec) -> Result<Paged<User>, ApiError> {
+        let filter = UserFilter::default();
+        fetch_page(&self.pool, "users", |builder| Self::push_filters(builder, tenant_id, &filter), spec).await
+    }
+
+    async fn search(&self, tenant_id: &str, filter: &UserFilter, spec: &QuerySpec) -> Result<Paged<User>, ApiError> {
+        fetch_page(&self.pool, "users", |builder| Self::push_filters(builder, tenant_id, filter), spec).await
+    }
+
+    async fn search_text(
+        &self,
+        tenant_id: &str,
+        filter: &UserFilter,
+        text: &TextQuery,
+        spec: &QuerySpec,
+    ) -> Result<Paged<Ranked<User>>, ApiError> {
+        fetch_ranked(
+            &self.pool,
+            "users",
+            &TEXT_INDEX,
+            |builder| Self::push_filters(builder, tenant_id, filter),
+            text,
+            spec,
+        )
+        .await
+    }
+
+    async fn update(&self, user: &User) -> Result<User, ApiError> {
+        write_user(&self.pool, user).await
+    }
+
+    async fn update_keeping_an_admin(&self, user: &User) -> Result<User, ApiError> {
+        let mut tx = self.pool.begin().await.map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        if !(user.is_admin() && user.is_active()) {
+            ensure_another_admin(&mut tx, &user.tenant_id, &user.id).await?;
+        }
+        let updated = write_user(&mut *tx, user).await?;
+
+        tx.commit().await.map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+        Ok(updated)
+    }
+
+    async fn accept_two_factor_step(&self, tenant_id: &str, id: &str, step: i64) -> Result<bool, ApiError> {
+        // A single conditional write, so two requests racing with the same code cannot both pass
         let query = r#"
-            SELECT * FROM users
-            ORDER BY created_at DESC
-            LIMIT $1 OFFSET $2
+            UPDATE users
+            SET two_factor_last_step = $3, two_factor_failed_attempts = 0, two_factor_locked_until = NULL
+            WHERE tenant_id = $1 AND id = $2 AND deleted_at IS NULL
+              AND (two_factor_last_step IS NULL OR two_factor_last_step < $3)
         "#;
 
-        // ✅ Usar query_as
-        let users = sqlx::query_as::<_, User>(query)
-            .bind(per_page)
-            .bind(offset)
-            .fetch_all(&self.pool)
+        let result = sqlx::query(query)
+            .bind(tenant_id)
+            .bind(id)
+            .bind(step)
+            .execute(&self.pool)
             .await
             .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
 
-        let total = self.count().await?;
-
-        Ok((users, total))
+        Ok(result.rows_affected() > 0)
     }
 
-    async fn update(&self, user: &User) -> Result<(), ApiError> {
+    async fn record_two_factor_failure(
+        &self,
+        tenant_id: &str,
+        id: &str,
+        max_attempts: i32,
+        locked_until: DateTime<Utc>,
+    ) -> Result<(), ApiError> {
+        // Every expression reads the counter before this update
         let query = r#"
             UPDATE users
-            SET email = $1, username = $2, password_hash = $3, role = $4, 
-                is_active = $5, updated_at = $6
-            WHERE id = $7
+            SET two_factor_locked_until = CASE WHEN two_factor_failed_attempts + 1 >= $3
+                    THEN $4 ELSE two_factor_locked_until END,
+                two_factor_failed_attempts = CASE WHEN two_factor_failed_attempts + 1 >= $3
+                    THEN 0 ELSE two_factor_failed_attempts + 1 END
+            WHERE tenant_id = $1 AND id = $2 AND deleted_at IS NULL
         "#;
 
         sqlx::query(query)
-            .bind(&user.email.as_str())
-            .bind(&user.username.as_str())
-            .bind(&user.password_hash)
-            .bind(user.role.as_str())
-            .bind(user.is_active)
-            .bind(Utc::now())
-            .bind(&user.id)
+            .bind(tenant_id)
+            .bind(id)
+            .bind(max_attempts)
+            .bind(locked_until)
             .execute(&self.pool)
             .await
             .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
@@ -124,10 +234,61 @@ impl UserRepository for PostgresUserRepository {
         Ok(())
     }
 
-    async fn delete(&self, id: &str) -> Result<bool, ApiError> {
-        let query = "DELETE FROM users WHERE id = $1";
+    async fn clear_two_factor_failures(&self, tenant_id: &str, id: &str) -> Result<(), ApiError> {
+        let query = r#"
+            UPDATE users SET two_factor_failed_attempts = 0
+            WHERE tenant_id = $1 AND id = $2 AND deleted_at IS NULL
+        "#;
+
+        sqlx::query(query)
+            .bind(tenant_id)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, tenant_id: &str, id: &str) -> Result<bool, ApiError> {
+        let query = r#"
+            UPDATE users SET deleted_at = NOW()
+            WHERE tenant_id = $1 AND id = $2 AND deleted_at IS NULL
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(tenant_id)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_keeping_an_admin(&self, tenant_id: &str, id: &str) -> Result<bool, ApiError> {
+        let mut tx = self.pool.begin().await.map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        ensure_another_admin(&mut tx, tenant_id, id).await?;
+        let result = sqlx::query("UPDATE users SET deleted_at = NOW() WHERE tenant_id = $1 AND id = $2 AND deleted_at IS NULL")
+            .bind(tenant_id)
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn restore(&self, tenant_id: &str, id: &str) -> Result<bool, ApiError> {
+        let query = r#"
+            UPDATE users SET deleted_at = NULL, updated_at = NOW(), version = version + 1
+            WHERE tenant_id = $1 AND id = $2 AND deleted_at IS NOT NULL
+        "#;
 
         let result = sqlx::query(query)
+            .bind(tenant_id)
             .bind(id)
             .execute(&self.pool)
             .await
@@ -136,10 +297,22 @@ impl UserRepository for PostgresUserRepository {
         Ok(result.rows_affected() > 0)
     }
 
-    async fn count(&self) -> Result<i32, ApiError> {
-        let query = "SELECT COUNT(*) as count FROM users";
+    async fn purge_trashed_before(&self, cutoff: DateTime<Utc>) -> Result<u64, ApiError> {
+        // API keys and linked identities go with the user (ON DELETE CASCADE)
+        let result = sqlx::query("DELETE FROM users WHERE deleted_at < $1")
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn count(&self, tenant_id: &str) -> Result<i32, ApiError> {
+        let query = "SELECT COUNT(*) as count FROM users WHERE tenant_id = $1 AND deleted_at IS NULL";
 
         let row: (i64,) = sqlx::query_as(query)
+            .bind(tenant_id)
             .fetch_one(&self.pool)
             .await
             .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
@@ -147,10 +320,11 @@ impl UserRepository for PostgresUserRepository {
         Ok(row.0 as i32)
     }
 
-    async fn exists_by_email(&self, email: &str) -> Result<bool, ApiError> {
-        let query = "SELECT EXISTS(SELECT 1 FROM users WHERE email = $1)";
+    async fn exists_by_email(&self, tenant_id: &str, email: &str) -> Result<bool, ApiError> {
+        let query = "SELECT EXISTS(SELECT 1 FROM users WHERE tenant_id = $1 AND email = $2)";
 
         let row: (bool,) = sqlx::query_as(query)
+            .bind(tenant_id)
             .bind(email)
             .fetch_one(&self.pool)
             .await
@@ -158,4 +332,169 @@ impl UserRepository for PostgresUserRepository {
 
         Ok(row.0)
     }
-}
\ No newline at end of file
+}
+
+impl Keyset for User {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn sort_value(&self, column: &str) -> Option<Value> {
+        match column {
+            "email" => Some(Value::Text(self.email.as_str().to_string())),
+            "username" => Some(Value::Text(self.username.as_str().to_string())),
+            "role" => Some(Value::Text(self.role.as_str().to_string())),
+            "created_at" => Some(Value::Timestamp(self.created_at)),
+            "updated_at" => Some(Value::Timestamp(self.updated_at)),
+            _ => None,
+        }
+    }
+}
+
+/// Write every mutable column of `user` if it is still at `user.version`
+async fn write_user<'e, E>(executor: E, user: &User) -> Result<User, ApiError>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+{
+    let query = r#"
+        UPDATE users
+        SET email = $1, username = $2, password_hash = $3, role = $4, 
+            is_active = $5, two_factor_secret = $6, two_factor_enabled = $7,
+            two_factor_confirmed_at = $8, two_factor_recovery_codes = $9, updated_at = $10,
+            password_reset_required = $11, token_version = $15, version = version + 1
+        WHERE id = $12 AND tenant_id = $13 AND version = $14 AND deleted_at IS NULL
+        RETURNING *
+    "#;
+
+    sqlx::query_as::<_, User>(query)
+        .bind(&user.email.as_str())
+        .bind(&user.username.as_str())
+        .bind(&user.password_hash)
+        .bind(user.role.as_str())
+        .bind(user.is_active)
+        .bind(&user.two_factor_secret)
+        .bind(user.two_factor_enabled)
+        .bind(user.two_factor_confirmed_at)
+        .bind(&user.two_factor_recovery_codes)
+        .bind(user.updated_at)
+        .bind(user.password_reset_required)
+        .bind(&user.id)
+        .bind(&user.tenant_id)
+        .bind(user.version)
+        .bind(user.token_version)
+        .fetch_optional(executor)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| ApiError::Conflict("User was modified or deleted by another request".to_string()))
+}
+
+/// Lock the tenant's active admins until the transaction ends, then refuse if `id`
+/// is the only one. Waiting on the lock sees the outcome of a concurrent demotion.
+async fn ensure_another_admin(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    tenant_id: &str,
+    id: &str,
+) -> Result<(), ApiError> {
+    let query = r#"
+        SELECT id FROM users
+        WHERE tenant_id = $1 AND role = $2 AND is_active = TRUE AND deleted_at IS NULL
+        ORDER BY id
+        FOR UPDATE
+    "#;
+
+    let admins: Vec<(String,)> = sqlx::query_as(query)
+        .bind(tenant_id)
+        .bind(Role::Admin.as_str())
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    if admins.len() <= 1 && admins.iter().any(|(admin,)| admin == id) {
+        return Err(ApiError::Conflict("Cannot remove the last active admin".to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::factories::UserFactory;
+
+    /// Connects to `TEST_DATABASE_URL` and registers a throwaway tenant
+    async fn setup() -> (PostgresUserRepository, String) {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+
+        let tenant = uuid::Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO tenants (id, slug, name) VALUES ($1, $1, $1)")
+            .bind(&tenant)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        (PostgresUserRepository::new(pool), tenant)
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+    async fn test_two_factor_steps_are_accepted_once() {
+        let (repo, tenant) = setup().await;
+        let user = UserFactory::new(&tenant).create(&repo).await.unwrap();
+
+        assert!(repo.accept_two_factor_step(&tenant, &user.id, 100).await.unwrap());
+        assert!(!repo.accept_two_factor_step(&tenant, &user.id, 100).await.unwrap());
+        assert!(!repo.accept_two_factor_step(&tenant, &user.id, 99).await.unwrap());
+        assert!(repo.accept_two_factor_step(&tenant, &user.id, 101).await.unwrap());
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+    async fn test_two_factor_failures_lock_after_the_limit() {
+        let (repo, tenant) = setup().await;
+        let user = UserFactory::new(&tenant).create(&repo).await.unwrap();
+        let until = Utc::now() + chrono::Duration::minutes(15);
+
+        for _ in 0..2 {
+            repo.record_two_factor_failure(&tenant, &user.id, 3, until).await.unwrap();
+        }
+        let stored = repo.get_by_id(&tenant, &user.id).await.unwrap().unwrap();
+        assert_eq!(stored.two_factor_failed_attempts, 2);
+        assert!(!stored.is_two_factor_locked(Utc::now()));
+
+        repo.record_two_factor_failure(&tenant, &user.id, 3, until).await.unwrap();
+        let stored = repo.get_by_id(&tenant, &user.id).await.unwrap().unwrap();
+        assert_eq!(stored.two_factor_failed_attempts, 0);
+        assert!(stored.is_two_factor_locked(Utc::now()));
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+    async fn test_concurrent_demotions_keep_one_admin() {
+        let (repo, tenant) = setup().await;
+        let mut first = UserFactory::new(&tenant).role(Role::Admin).create(&repo).await.unwrap();
+        let mut second = UserFactory::new(&tenant).role(Role::Admin).create(&repo).await.unwrap();
+
+        first.change_role(Role::User);
+        second.deactivate();
+        let (a, b) = futures::join!(repo.update_keeping_an_admin(&first), repo.update_keeping_an_admin(&second));
+        assert!(a.is_ok() != b.is_ok(), "exactly one demotion must win");
+
+        let remaining = if a.is_ok() { &second.id } else { &first.id };
+        assert!(matches!(
+            repo.delete_keeping_an_admin(&tenant, remaining).await,
+            Err(ApiError::Conflict(_))
+        ));
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+    async fn test_role_change_revokes_tokens() {
+        let (repo, tenant) = setup().await;
+        let mut user = UserFactory::new(&tenant).create(&repo).await.unwrap();
+        assert_eq!(user.token_version, 0);
+
+        user.change_role(Role::Moderator);
+        let stored = repo.update(&user).await.unwrap();
+        assert_eq!(stored.token_version, 1);
+    }
+}
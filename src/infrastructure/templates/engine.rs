@@ -0,0 +1,129 @@
+//! HTML templates, rendered with minijinja.
+//!
+//! Templates are read from the template directory (`TEMPLATE_DIRECTORY`), falling back to
+//! the framework templates built into the binary. Names are relative paths that cannot leave
+//! the directory. `.html` templates escape every value unless it is marked `|safe`, and can
+//! use `{% extends "layouts/base.html" %}` and `{% include "partials/..." %}`.
+
+use minijinja::{Environment, Error, ErrorKind};
+use serde::Serialize;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{PoisonError, RwLock};
+
+use crate::config::TemplateConfig;
+use crate::errors::ApiError;
+
+/// Framework templates, used when the template directory lacks them
+const EMBEDDED: &[(&str, &str)] = &[
+    ("layouts/base.html", include_str!("../../../templates/layouts/base.html")),
+    ("partials/footer.html", include_str!("../../../templates/partials/footer.html")),
+    ("render/down/default.html", include_str!("../../../templates/render/down/default.html")),
+    ("framework/dashboard.html", include_str!("../../../templates/framework/dashboard.html")),
+    ("framework/swagger.html", include_str!("../../../templates/framework/swagger.html")),
+];
+
+/// Renders templates; loaded templates are cached unless `reload` is set
+pub struct TemplateEngine {
+    env: RwLock<Environment<'static>>,
+    reload: bool,
+}
+
+impl TemplateEngine {
+    pub fn new(config: &TemplateConfig) -> Self {
+        let root = PathBuf::from(&config.directory);
+        let mut env = Environment::new();
+        env.set_loader(move |name| load(&root, name));
+        env.add_global("app_name", "Rust Ironclad Framework");
+        env.add_global("version", env!("CARGO_PKG_VERSION"));
+
+        Self {
+            env: RwLock::new(env),
+            reload: config.reload,
+        }
+    }
+
+    /// Render a template with the values of `context`; failures are logged with their cause
+    pub fn render<S: Serialize>(&self, name: &str, context: S) -> Result<String, ApiError> {
+        if self.reload {
+            self.env.write().unwrap_or_else(PoisonError::into_inner).clear_templates();
+        }
+        let env = self.env.read().unwrap_or_else(PoisonError::into_inner);
+        env.get_template(name)
+            .and_then(|template| template.render(context))
+            .map_err(|e| {
+                tracing::warn!("Cannot render template {}: {:#}", name, e);
+                ApiError::InternalServerError(format!("Cannot render template {}", name))
+            })
+    }
+
+    /// Whether a template exists (in the directory or built in)
+    pub fn exists(&self, name: &str) -> bool {
+        let env = self.env.read().unwrap_or_else(PoisonError::into_inner);
+        env.get_template(name).is_ok()
+    }
+}
+
+/// Source of `name` under `root`, else its built-in copy; names leaving `root` are refused
+fn load(root: &Path, name: &str) -> Result<Option<String>, Error> {
+    let relative = Path::new(name);
+    let sandboxed = !name.contains('\\')
+        && relative.components().all(|component| matches!(component, Component::Normal(_)));
+    if !sandboxed {
+        return Err(Error::new(
+            ErrorKind::InvalidOperation,
+            format!("template name leaves the template directory: {}", name),
+        ));
+    }
+
+    match std::fs::read_to_string(root.join(relative)) {
+        Ok(source) => Ok(Some(source)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(EMBEDDED
+            .iter()
+            .find(|(embedded, _)| *embedded == name)
+            .map(|(_, source)| source.to_string())),
+        Err(e) => Err(Error::new(ErrorKind::InvalidOperation, "cannot read template").with_source(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use minijinja::context;
+
+    fn engine(directory: &Path, reload: bool) -> TemplateEngine {
+        TemplateEngine::new(&TemplateConfig {
+            directory: directory.to_string_lossy().into_owned(),
+            reload,
+        })
+    }
+
+    #[test]
+    fn test_templates_are_escaped_sandboxed_and_reloaded() {
+        let dir = std::env::temp_dir().join(format!("ironclad-templates-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("pages")).unwrap();
+        std::fs::write(dir.join("secret.txt"), "outside").unwrap();
+        std::fs::write(
+            dir.join("pages/hello.html"),
+            r#"{% extends "layouts/base.html" %}{% block body %}<p>{{ name }}</p>{% endblock %}"#,
+        )
+        .unwrap();
+
+        let templates = engine(&dir.join("pages").join(".."), true);
+        let html = templates.render("pages/hello.html", context! { name => "<script>" }).unwrap();
+        assert!(html.contains("<p>&lt;script&gt;</p>"));
+        assert!(html.contains("<title>Rust Ironclad Framework</title>"), "built-in layout");
+
+        std::fs::write(dir.join("pages/hello.html"), "Bye {{ name }}").unwrap();
+        assert_eq!(templates.render("pages/hello.html", context! { name => "you" }).unwrap(), "Bye you");
+
+        let sandboxed = engine(&dir.join("pages"), false);
+        for name in ["../secret.txt", "/etc/passwd", "..\\secret.txt", "hello.html/../../secret.txt"] {
+            assert!(sandboxed.render(name, ()).is_err(), "{}", name);
+        }
+        assert!(sandboxed.exists("hello.html"));
+        assert!(!sandboxed.exists("missing.html"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,34 @@
+use crate::config::OAuthProviderConfig;
+use crate::errors::ApiError;
+use async_trait::async_trait;
+
+/// User information returned by an identity provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalIdentity {
+    /// Stable subject identifier (`sub` claim or provider user ID)
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    /// Preferred username or display name, used for new accounts
+    pub name: Option<String>,
+}
+
+/// Identity Provider Client - Back-channel calls of the authorization-code flow
+#[async_trait]
+pub trait IdentityProviderClient: Send + Sync {
+    /// Exchange an authorization code (with its PKCE verifier) for an access token
+    async fn exchange_code(
+        &self,
+        provider: &OAuthProviderConfig,
+        code: &str,
+        code_verifier: &str,
+        redirect_uri: &str,
+    ) -> Result<String, ApiError>;
+
+    /// Fetch the authenticated user's identity
+    async fn fetch_identity(
+        &self,
+        provider: &OAuthProviderConfig,
+        access_token: &str,
+    ) -> Result<ExternalIdentity, ApiError>;
+}
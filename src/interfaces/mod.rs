@@ -1,3 +1,5 @@
 pub mod repositories;
+pub mod identity_provider;
 
-pub use repositories::{UserRepository, TestItemRepository};
+pub use repositories::{UserRepository, TestItemRepository, PermissionRepository, ApiKeyRepository, UserIdentityRepository, AuditLogRepository, TenantRepository, DataImportRepository, MaintenanceStore};
+pub use identity_provider::{ExternalIdentity, IdentityProviderClient};
@@ -0,0 +1,22 @@
+use crate::domain::entities::ApiKey;
+use crate::errors::ApiError;
+use async_trait::async_trait;
+
+/// API Key Repository - Data access contract
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    /// Store a new key
+    async fn create(&self, api_key: &ApiKey) -> Result<ApiKey, ApiError>;
+
+    /// Find a key by its public prefix (prefixes are unique across tenants; the key carries its tenant)
+    async fn get_by_prefix(&self, prefix: &str) -> Result<Option<ApiKey>, ApiError>;
+
+    /// List a user's keys (newest first)
+    async fn get_by_user(&self, tenant_id: &str, user_id: &str) -> Result<Vec<ApiKey>, ApiError>;
+
+    /// Mark a user's key as revoked. Returns false if no active key matched.
+    async fn revoke(&self, tenant_id: &str, id: &str, user_id: &str) -> Result<bool, ApiError>;
+
+    /// Record a successful use
+    async fn touch_last_used(&self, id: &str) -> Result<(), ApiError>;
+}
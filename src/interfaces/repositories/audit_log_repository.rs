@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use crate::domain::entities::AuditEntry;
+use crate::errors::ApiError;
+use async_trait::async_trait;
+use super::{Paged, Pagination};
+
+/// Criteria for querying the audit log (all optional, combined with AND)
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    /// Always set for HTTP queries so admins only see their own tenant
+    pub tenant_id: Option<String>,
+    pub actor_id: Option<String>,
+    /// Exact action, or a prefix when it ends with `.*` (e.g. `user.*`)
+    pub action: Option<String>,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub ip_address: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Audit Log Repository - Data access contract (append-only)
+#[async_trait]
+pub trait AuditLogRepository: Send + Sync {
+    /// Append an entry
+    async fn append(&self, entry: &AuditEntry) -> Result<(), ApiError>;
+
+    /// Filtered entries, newest first (with the total match count for offset pages)
+    async fn search(&self, filter: &AuditFilter, pagination: &Pagination) -> Result<Paged<AuditEntry>, ApiError>;
+
+    /// Delete entries older than the cutoff (retention). Returns the number removed.
+    async fn purge_before(&self, cutoff: DateTime<Utc>) -> Result<u64, ApiError>;
+}
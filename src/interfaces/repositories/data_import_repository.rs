@@ -0,0 +1,26 @@
+use crate::domain::entities::{DataImport, ImportStatus};
+use crate::errors::ApiError;
+use async_trait::async_trait;
+
+/// Data Import Repository - progress of uploaded import files
+#[async_trait]
+pub trait DataImportRepository: Send + Sync {
+    /// Store a new (pending) import
+    async fn create(&self, import: &DataImport) -> Result<DataImport, ApiError>;
+
+    /// Get an import of `entity` within a tenant
+    async fn get_by_id(&self, tenant_id: &str, entity: &str, id: &str) -> Result<Option<DataImport>, ApiError>;
+
+    /// Move a pending import to `running` and return it (for the worker, which knows the
+    /// tenant from the job). `None` if it doesn't exist or another run already claimed it.
+    async fn claim(&self, id: &str) -> Result<Option<DataImport>, ApiError>;
+
+    /// Set the status (and the error message of a failed import)
+    async fn set_status(&self, id: &str, status: ImportStatus, error_message: Option<&str>) -> Result<(), ApiError>;
+
+    /// Record how many rows were read and rejected so far
+    async fn update_progress(&self, id: &str, processed_rows: i32, failed_rows: i32) -> Result<(), ApiError>;
+
+    /// Mark the import completed, with its error report if some rows were rejected
+    async fn complete(&self, id: &str, error_report_path: Option<&str>) -> Result<(), ApiError>;
+}
@@ -0,0 +1,20 @@
+use crate::domain::entities::MaintenanceState;
+use crate::errors::ApiError;
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+
+/// Maintenance Store - where `ironclad down` and the admin endpoint record maintenance mode
+#[async_trait]
+pub trait MaintenanceStore: Send + Sync {
+    /// Current state, `None` while the application is up
+    async fn get(&self) -> Result<Option<MaintenanceState>, ApiError>;
+
+    /// Put the application down, replacing any previous state
+    async fn put(&self, state: &MaintenanceState) -> Result<(), ApiError>;
+
+    /// Bring the application up; `false` when it was not down
+    async fn clear(&self) -> Result<bool, ApiError>;
+
+    /// Yields whenever the state may have changed, on any instance; ends when the backend is lost
+    async fn changes(&self) -> Result<BoxStream<'static, ()>, ApiError>;
+}
@@ -1,5 +1,25 @@
 pub mod user_repository;
 pub mod test_item_repository;
+pub mod permission_repository;
+pub mod api_key_repository;
+pub mod user_identity_repository;
+pub mod audit_log_repository;
+pub mod tenant_repository;
+pub mod data_import_repository;
+pub mod maintenance_store;
+pub mod soft_delete;
+pub mod query_spec;
+pub mod text_search;
 
-pub use user_repository::UserRepository;
-pub use test_item_repository::TestItemRepository;
+pub use user_repository::{UserFilter, UserRepository, USER_FIELDS};
+pub use test_item_repository::{TestItemRepository, TEST_ITEM_FIELDS};
+pub use permission_repository::PermissionRepository;
+pub use api_key_repository::ApiKeyRepository;
+pub use user_identity_repository::UserIdentityRepository;
+pub use audit_log_repository::{AuditFilter, AuditLogRepository};
+pub use tenant_repository::TenantRepository;
+pub use data_import_repository::DataImportRepository;
+pub use maintenance_store::MaintenanceStore;
+pub use soft_delete::Trashed;
+pub use query_spec::{Cursor, Field, FieldKind, FilterOp, Paged, Pagination, QuerySpec, Sort, Value};
+pub use text_search::{Ranked, TextQuery};
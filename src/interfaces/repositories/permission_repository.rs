@@ -0,0 +1,29 @@
+use crate::domain::entities::{PermissionDefinition, RolePermission};
+use crate::domain::value_objects::Permission;
+use crate::errors::ApiError;
+use async_trait::async_trait;
+
+/// Permission Repository - Role/permission storage contract
+#[async_trait]
+pub trait PermissionRepository: Send + Sync {
+    /// Get the permissions granted to a role
+    async fn get_for_role(&self, role: &str) -> Result<Vec<Permission>, ApiError>;
+
+    /// Get every role grant
+    async fn get_all_grants(&self) -> Result<Vec<RolePermission>, ApiError>;
+
+    /// Get the permission catalogue
+    async fn get_all_permissions(&self) -> Result<Vec<PermissionDefinition>, ApiError>;
+
+    /// Add a permission to the catalogue
+    async fn create_permission(&self, permission: &PermissionDefinition) -> Result<PermissionDefinition, ApiError>;
+
+    /// Check if a permission exists in the catalogue
+    async fn permission_exists(&self, name: &str) -> Result<bool, ApiError>;
+
+    /// Grant a permission to a role (idempotent)
+    async fn grant(&self, role: &str, permission: &Permission) -> Result<(), ApiError>;
+
+    /// Revoke a permission from a role
+    async fn revoke(&self, role: &str, permission: &str) -> Result<bool, ApiError>;
+}
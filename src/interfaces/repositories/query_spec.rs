@@ -0,0 +1,367 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use data_encoding::BASE64URL_NOPAD;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::ApiError;
+
+/// Query parameters with a fixed meaning on every list endpoint
+const RESERVED_PARAMS: [&str; 4] = ["sort", "page", "per_page", "cursor"];
+/// Most sort keys a client may combine
+const MAX_SORT_FIELDS: usize = 3;
+
+/// Type of a filterable column (decides how values are parsed and bound)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    Text,
+    Bool,
+    Integer,
+    Timestamp,
+}
+
+/// A column clients may filter on, and sort on when `sortable`.
+///
+/// Sortable columns must be NOT NULL: keyset pagination compares their values.
+#[derive(Debug, Clone, Copy)]
+pub struct Field {
+    /// Name in the query string
+    pub name: &'static str,
+    /// Column name (whitelisted, safe to interpolate)
+    pub column: &'static str,
+    pub kind: FieldKind,
+    pub sortable: bool,
+}
+
+impl Field {
+    pub const fn new(name: &'static str, column: &'static str, kind: FieldKind, sortable: bool) -> Self {
+        Self { name, column, kind, sortable }
+    }
+}
+
+/// Comparison of a filter (`field[op]=value`, plain `field=value` means `eq`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    /// Case-insensitive substring match (text fields only)
+    Contains,
+}
+
+impl FilterOp {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "eq" => Some(FilterOp::Eq),
+            "ne" => Some(FilterOp::Ne),
+            "lt" => Some(FilterOp::Lt),
+            "lte" => Some(FilterOp::Lte),
+            "gt" => Some(FilterOp::Gt),
+            "gte" => Some(FilterOp::Gte),
+            "contains" => Some(FilterOp::Contains),
+            _ => None,
+        }
+    }
+
+    fn allowed_for(&self, kind: FieldKind) -> bool {
+        match self {
+            FilterOp::Eq | FilterOp::Ne => true,
+            FilterOp::Contains => kind == FieldKind::Text,
+            _ => kind != FieldKind::Bool,
+        }
+    }
+}
+
+/// A typed filter or cursor value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Value {
+    Text(String),
+    Bool(bool),
+    Integer(i64),
+    Timestamp(DateTime<Utc>),
+}
+
+impl Value {
+    /// Parse a query string value for a field of the given kind
+    pub fn parse(kind: FieldKind, raw: &str) -> Option<Self> {
+        match kind {
+            FieldKind::Text => Some(Value::Text(raw.to_string())),
+            FieldKind::Bool => raw.parse().ok().map(Value::Bool),
+            FieldKind::Integer => raw.parse().ok().map(Value::Integer),
+            FieldKind::Timestamp => DateTime::parse_from_rfc3339(raw)
+                .ok()
+                .map(|t| Value::Timestamp(t.with_timezone(&Utc))),
+        }
+    }
+
+    fn is_kind(&self, kind: FieldKind) -> bool {
+        matches!(
+            (self, kind),
+            (Value::Text(_), FieldKind::Text)
+                | (Value::Bool(_), FieldKind::Bool)
+                | (Value::Integer(_), FieldKind::Integer)
+                | (Value::Timestamp(_), FieldKind::Timestamp)
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    pub column: &'static str,
+    pub op: FilterOp,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sort {
+    pub column: &'static str,
+    pub kind: FieldKind,
+    pub descending: bool,
+}
+
+/// Position right after the last row of a page: its sort values, then its id (tie-breaker)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor {
+    pub values: Vec<Value>,
+    pub id: String,
+}
+
+impl Cursor {
+    /// Opaque, URL-safe form handed to clients
+    pub fn encode(&self) -> String {
+        BASE64URL_NOPAD.encode(&serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    pub fn decode(s: &str) -> Option<Self> {
+        let bytes = BASE64URL_NOPAD.decode(s.as_bytes()).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// Whether the cursor was issued for this sort order
+    pub fn fits(&self, sort: &[Sort]) -> bool {
+        self.values.len() == sort.len()
+            && self.values.iter().zip(sort).all(|(value, s)| value.is_kind(s.kind))
+    }
+}
+
+/// Page number (with a total count) or keyset cursor (no count, stable on large tables)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pagination {
+    Offset { page: i32, per_page: i32 },
+    /// `after` is None for the first page
+    Cursor { after: Option<Cursor>, per_page: i32 },
+}
+
+impl Pagination {
+    /// Validate the paging parameters. Any `cursor` (even empty, for the first page) selects keyset pagination.
+    pub fn new(
+        page: Option<i32>,
+        per_page: Option<i32>,
+        cursor: Option<&str>,
+        default_per_page: i32,
+        max_per_page: i32,
+    ) -> Result<Self, ApiError> {
+        let per_page = per_page.unwrap_or(default_per_page);
+        if !(1..=max_per_page).contains(&per_page) {
+            return Err(ApiError::ValidationError(format!(
+                "Per page must be between 1 and {}",
+                max_per_page
+            )));
+        }
+
+        match (cursor, page) {
+            (Some(_), Some(_)) => Err(ApiError::ValidationError(
+                "Use either page or cursor, not both".to_string(),
+            )),
+            (Some(""), None) => Ok(Pagination::Cursor { after: None, per_page }),
+            (Some(cursor), None) => Cursor::decode(cursor)
+                .map(|after| Pagination::Cursor { after: Some(after), per_page })
+                .ok_or_else(|| ApiError::ValidationError("Invalid cursor".to_string())),
+            (None, page) => {
+                let page = page.unwrap_or(1);
+                if page < 1 {
+                    return Err(ApiError::ValidationError("Page must be greater than 0".to_string()));
+                }
+                Ok(Pagination::Offset { page, per_page })
+            }
+        }
+    }
+
+    pub fn per_page(&self) -> i32 {
+        match self {
+            Pagination::Offset { per_page, .. } | Pagination::Cursor { per_page, .. } => *per_page,
+        }
+    }
+}
+
+/// Filters, sort order and pagination of a list query, checked against an allow-list of fields
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuerySpec {
+    pub filters: Vec<Filter>,
+    /// Never empty; rows are additionally ordered by `id`
+    pub sort: Vec<Sort>,
+    pub pagination: Pagination,
+}
+
+impl QuerySpec {
+    /// Build from query parameters:
+    /// `?subject[contains]=foo&created_at[gte]=2024-01-01T00:00:00Z&sort=-created_at,subject&page=2&per_page=20`
+    /// or `...&cursor=<next_cursor>`. Unknown parameters are rejected.
+    pub fn parse(
+        params: &HashMap<String, String>,
+        fields: &[Field],
+        default_sort: &str,
+    ) -> Result<Self, ApiError> {
+        let mut filters = Vec::new();
+
+        for (key, raw) in params {
+            if RESERVED_PARAMS.contains(&key.as_str()) {
+                continue;
+            }
+
+            let (name, op) = match key.strip_suffix(']').and_then(|k| k.split_once('[')) {
+                Some((name, op)) => (
+                    name,
+                    FilterOp::parse(op)
+                        .ok_or_else(|| ApiError::ValidationError(format!("Invalid filter operator: {}", op)))?,
+                ),
+                None => (key.as_str(), FilterOp::Eq),
+            };
+
+            let field = fields
+                .iter()
+                .find(|f| f.name == name)
+                .ok_or_else(|| ApiError::ValidationError(format!("Unknown filter: {}", name)))?;
+
+            if !op.allowed_for(field.kind) {
+                return Err(ApiError::ValidationError(format!("Operator not supported for {}", name)));
+            }
+
+            let value = Value::parse(field.kind, raw)
+                .ok_or_else(|| ApiError::ValidationError(format!("Invalid value for {}: {}", name, raw)))?;
+
+            filters.push(Filter { column: field.column, op, value });
+        }
+
+        // HashMap order is random; keep the generated SQL stable
+        filters.sort_by_key(|f| f.column);
+
+        let sort = Self::parse_sort(params.get("sort").map(String::as_str).unwrap_or(default_sort), fields)?;
+
+        let page = Self::parse_int(params, "page")?;
+        let per_page = Self::parse_int(params, "per_page")?;
+        let pagination = Pagination::new(page, per_page, params.get("cursor").map(String::as_str), 20, 100)?;
+
+        if let Pagination::Cursor { after: Some(cursor), .. } = &pagination {
+            if !cursor.fits(&sort) {
+                return Err(ApiError::ValidationError("Cursor does not match the sort order".to_string()));
+            }
+        }
+
+        Ok(Self { filters, sort, pagination })
+    }
+
+    /// `-created_at,subject`: comma separated, `-` for descending
+    fn parse_sort(value: &str, fields: &[Field]) -> Result<Vec<Sort>, ApiError> {
+        let mut sort: Vec<Sort> = Vec::new();
+
+        for key in value.split(',').map(str::trim).filter(|k| !k.is_empty()) {
+            let (name, descending) = match key.strip_prefix('-') {
+                Some(name) => (name, true),
+                None => (key, false),
+            };
+
+            let field = fields
+                .iter()
+                .find(|f| f.name == name && f.sortable)
+                .ok_or_else(|| ApiError::ValidationError(format!("Invalid sort field: {}", name)))?;
+
+            if sort.iter().all(|s| s.column != field.column) {
+                sort.push(Sort { column: field.column, kind: field.kind, descending });
+            }
+        }
+
+        if sort.is_empty() || sort.len() > MAX_SORT_FIELDS {
+            return Err(ApiError::ValidationError(format!(
+                "Sort by 1 to {} fields",
+                MAX_SORT_FIELDS
+            )));
+        }
+
+        Ok(sort)
+    }
+
+    fn parse_int(params: &HashMap<String, String>, key: &str) -> Result<Option<i32>, ApiError> {
+        params
+            .get(key)
+            .map(|v| v.parse().map_err(|_| ApiError::ValidationError(format!("Invalid {}: {}", key, v))))
+            .transpose()
+    }
+}
+
+/// One page of a list query
+#[derive(Debug, Clone)]
+pub struct Paged<T> {
+    pub items: Vec<T>,
+    /// Matching rows (offset pagination only)
+    pub total: Option<i64>,
+    /// Where the next page starts (cursor pagination only, None on the last page)
+    pub next_cursor: Option<Cursor>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIELDS: &[Field] = &[
+        Field::new("subject", "subject", FieldKind::Text, true),
+        Field::new("note", "optional_field", FieldKind::Text, false),
+        Field::new("created_at", "created_at", FieldKind::Timestamp, true),
+    ];
+
+    fn params(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_parse_maps_names_to_columns_and_operators() {
+        let spec = QuerySpec::parse(
+            &params(&[("note[contains]", "abc"), ("created_at[gte]", "2024-01-01T00:00:00Z"), ("sort", "-created_at,subject")]),
+            FIELDS,
+            "-created_at",
+        )
+        .unwrap();
+
+        assert_eq!(spec.filters[0].column, "created_at");
+        assert_eq!(spec.filters[0].op, FilterOp::Gte);
+        assert_eq!(spec.filters[1], Filter { column: "optional_field", op: FilterOp::Contains, value: Value::Text("abc".into()) });
+        assert_eq!(spec.sort.len(), 2);
+        assert!(spec.sort[0].descending && !spec.sort[1].descending);
+        assert_eq!(spec.pagination, Pagination::Offset { page: 1, per_page: 20 });
+    }
+
+    #[test]
+    fn test_parse_rejects_fields_outside_the_allow_list() {
+        assert!(QuerySpec::parse(&params(&[("password_hash", "x")]), FIELDS, "subject").is_err());
+        assert!(QuerySpec::parse(&params(&[("sort", "note")]), FIELDS, "subject").is_err());
+        assert!(QuerySpec::parse(&params(&[("created_at[contains]", "x")]), FIELDS, "subject").is_err());
+        assert!(QuerySpec::parse(&params(&[("created_at", "yesterday")]), FIELDS, "subject").is_err());
+    }
+
+    #[test]
+    fn test_cursor_round_trips_and_must_match_the_sort() {
+        let cursor = Cursor { values: vec![Value::Text("b".into())], id: "42".into() };
+        let encoded = cursor.encode();
+        assert_eq!(Cursor::decode(&encoded), Some(cursor.clone()));
+
+        let spec = QuerySpec::parse(&params(&[("cursor", &encoded), ("sort", "subject")]), FIELDS, "subject").unwrap();
+        assert_eq!(spec.pagination, Pagination::Cursor { after: Some(cursor), per_page: 20 });
+
+        assert!(QuerySpec::parse(&params(&[("cursor", &encoded), ("sort", "-created_at")]), FIELDS, "subject").is_err());
+        assert!(QuerySpec::parse(&params(&[("cursor", "garbage")]), FIELDS, "subject").is_err());
+        assert!(QuerySpec::parse(&params(&[("cursor", ""), ("page", "2")]), FIELDS, "subject").is_err());
+    }
+}
@@ -0,0 +1,32 @@
+/// Which rows a query sees with respect to soft deletes (`deleted_at`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Trashed {
+    /// Live rows only - what every query returns unless asked otherwise
+    #[default]
+    Exclude,
+    /// Live and trashed rows ("with trashed")
+    Include,
+    /// Trashed rows only ("only trashed")
+    Only,
+}
+
+impl Trashed {
+    /// Parse the `trashed` query parameter (`without`, `with`, `only`) - None for unknown values
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "without" => Some(Trashed::Exclude),
+            "with" => Some(Trashed::Include),
+            "only" => Some(Trashed::Only),
+            _ => None,
+        }
+    }
+
+    /// Condition on `deleted_at` (constant, safe to interpolate); None when every row matches
+    pub fn condition(&self) -> Option<&'static str> {
+        match self {
+            Trashed::Exclude => Some("deleted_at IS NULL"),
+            Trashed::Include => None,
+            Trashed::Only => Some("deleted_at IS NOT NULL"),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use crate::domain::entities::Tenant;
+use crate::errors::ApiError;
+use async_trait::async_trait;
+
+/// Tenant Repository - Data access contract
+#[async_trait]
+pub trait TenantRepository: Send + Sync {
+    /// Get tenant by ID
+    async fn get_by_id(&self, id: &str) -> Result<Option<Tenant>, ApiError>;
+
+    /// Get tenant by slug (subdomain / header value)
+    async fn get_by_slug(&self, slug: &str) -> Result<Option<Tenant>, ApiError>;
+}
@@ -1,28 +1,77 @@
 use crate::domain::entities::TestItem;
 use crate::errors::ApiError;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use super::{Field, FieldKind, Paged, QuerySpec, Ranked, TextQuery, Trashed};
 
-/// TestItem Repository trait
+/// Fields of `GET /api/test-items` that can be filtered (and sorted)
+pub const TEST_ITEM_FIELDS: &[Field] = &[
+    Field::new("subject", "subject", FieldKind::Text, true),
+    Field::new("optional_field", "optional_field", FieldKind::Text, false),
+    Field::new("created_at", "created_at", FieldKind::Timestamp, true),
+    Field::new("updated_at", "updated_at", FieldKind::Timestamp, true),
+    Field::new("version", "version", FieldKind::Integer, false),
+];
+
+/// TestItem Repository trait (reads and deletes are scoped to a tenant).
+///
+/// `delete` only moves an item to the trash; reads skip trashed items unless asked.
 #[async_trait]
 pub trait TestItemRepository: Send + Sync {
     /// Create new test item
     async fn create(&self, item: &TestItem) -> Result<TestItem, ApiError>;
 
+    /// Insert all items in a single statement (all or nothing)
+    async fn create_many(&self, items: &[TestItem]) -> Result<Vec<TestItem>, ApiError>;
+
     /// Get test item by ID
-    async fn get_by_id(&self, id: &str) -> Result<Option<TestItem>, ApiError>;
+    async fn get_by_id(&self, tenant_id: &str, id: &str) -> Result<Option<TestItem>, ApiError>;
+
+    /// Get test item by ID, even if it is in the trash
+    async fn get_by_id_with_trashed(&self, tenant_id: &str, id: &str) -> Result<Option<TestItem>, ApiError>;
+
+    /// Get the live items among `ids` (missing ones are skipped)
+    async fn get_many(&self, tenant_id: &str, ids: &[String]) -> Result<Vec<TestItem>, ApiError>;
 
     /// Get all test items
-    async fn get_all(&self) -> Result<Vec<TestItem>, ApiError>;
+    async fn get_all(&self, tenant_id: &str) -> Result<Vec<TestItem>, ApiError>;
+
+    /// Every item of the tenant, oldest first, read lazily (for exports)
+    fn stream_all(&self, tenant_id: &str, trashed: Trashed) -> BoxStream<'static, Result<TestItem, ApiError>>;
+
+    /// Filtered, sorted page of test items (`trashed` selects live, trashed or both)
+    async fn list(&self, tenant_id: &str, trashed: Trashed, spec: &QuerySpec) -> Result<Paged<TestItem>, ApiError>;
+
+    /// `list` ranked by full-text relevance on subject and optional field (offset pages only)
+    async fn search_text(
+        &self,
+        tenant_id: &str,
+        trashed: Trashed,
+        text: &TextQuery,
+        spec: &QuerySpec,
+    ) -> Result<Paged<Ranked<TestItem>>, ApiError>;
+
+    /// Update test item (only within `item.tenant_id`) if it is still at `item.version`.
+    /// Returns the stored item with its new version, or `ApiError::Conflict` if it changed meanwhile.
+    async fn update(&self, item: &TestItem) -> Result<TestItem, ApiError>;
+
+    /// `update` for many items in a single statement. Returns the updated items;
+    /// the missing ones changed (or were deleted) meanwhile.
+    async fn update_many(&self, items: &[TestItem]) -> Result<Vec<TestItem>, ApiError>;
+
+    /// Move test item to the trash. Returns false if it is missing or already trashed.
+    async fn delete(&self, tenant_id: &str, id: &str) -> Result<bool, ApiError>;
 
-    /// Get test items with pagination
-    async fn get_paginated(&self, page: i32, per_page: i32) -> Result<(Vec<TestItem>, i32), ApiError>;
+    /// Move the given items to the trash. Returns the ids actually trashed.
+    async fn delete_many(&self, tenant_id: &str, ids: &[String]) -> Result<Vec<String>, ApiError>;
 
-    /// Update test item
-    async fn update(&self, item: &TestItem) -> Result<(), ApiError>;
+    /// Take test item out of the trash. Returns false if it is missing or not trashed.
+    async fn restore(&self, tenant_id: &str, id: &str) -> Result<bool, ApiError>;
 
-    /// Delete test item by ID
-    async fn delete(&self, id: &str) -> Result<bool, ApiError>;
+    /// Permanently delete items trashed before the cutoff, in every tenant. Returns the number removed.
+    async fn purge_trashed_before(&self, cutoff: DateTime<Utc>) -> Result<u64, ApiError>;
 
-    /// Count total test items
-    async fn count(&self) -> Result<i32, ApiError>;
+    /// Count live test items
+    async fn count(&self, tenant_id: &str) -> Result<i32, ApiError>;
 }
\ No newline at end of file
@@ -0,0 +1,69 @@
+use crate::errors::ApiError;
+
+/// Most terms a search query may have
+const MAX_TERMS: usize = 8;
+
+/// A `?q=` full-text query, reduced to the words it contains.
+///
+/// Every word is matched as a prefix and all of them must match, so `jo doe` finds
+/// `john.doe@example.com`. Punctuation only separates words, which keeps the generated
+/// `tsquery` valid whatever the user typed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextQuery {
+    terms: Vec<String>,
+}
+
+impl TextQuery {
+    pub fn parse(q: &str) -> Result<Self, ApiError> {
+        let terms: Vec<String> = q
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|term| !term.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+
+        if terms.is_empty() {
+            return Err(ApiError::ValidationError(
+                "Search query must contain at least one letter or digit".to_string(),
+            ));
+        }
+        if terms.len() > MAX_TERMS {
+            return Err(ApiError::ValidationError(format!(
+                "Search query cannot have more than {} words",
+                MAX_TERMS
+            )));
+        }
+
+        Ok(Self { terms })
+    }
+
+    /// `to_tsquery` input: `jo:* & doe:*`
+    pub fn to_tsquery(&self) -> String {
+        self.terms
+            .iter()
+            .map(|term| format!("{}:*", term))
+            .collect::<Vec<_>>()
+            .join(" & ")
+    }
+}
+
+/// A search result with its relevance and the matched text, `<mark>`ed
+#[derive(Debug, Clone)]
+pub struct Ranked<T> {
+    pub item: T,
+    pub rank: f32,
+    pub headline: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_keeps_only_words() {
+        let q = TextQuery::parse(" John.Doe@ex  'x' & !").unwrap();
+        assert_eq!(q.to_tsquery(), "john:* & doe:* & ex:* & x:*");
+
+        assert!(TextQuery::parse("&|!():*").is_err());
+        assert!(TextQuery::parse("a b c d e f g h i").is_err());
+    }
+}
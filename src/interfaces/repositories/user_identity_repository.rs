@@ -0,0 +1,28 @@
+use crate::domain::entities::{OAuthState, UserIdentity};
+use crate::errors::ApiError;
+use async_trait::async_trait;
+
+/// User Identity Repository - Linked OAuth identities and pending authorization requests
+#[async_trait]
+pub trait UserIdentityRepository: Send + Sync {
+    /// Find the identity for a provider subject within a tenant
+    async fn get_by_subject(&self, tenant_id: &str, provider: &str, subject: &str) -> Result<Option<UserIdentity>, ApiError>;
+
+    /// List the identities linked to a user
+    async fn get_by_user(&self, tenant_id: &str, user_id: &str) -> Result<Vec<UserIdentity>, ApiError>;
+
+    /// Link a new identity
+    async fn create(&self, identity: &UserIdentity) -> Result<UserIdentity, ApiError>;
+
+    /// Record a login through this identity
+    async fn touch_last_login(&self, id: &str) -> Result<(), ApiError>;
+
+    /// Store a pending authorization request
+    async fn save_state(&self, state: &OAuthState) -> Result<(), ApiError>;
+
+    /// Remove and return a pending authorization request (single use)
+    async fn take_state(&self, state: &str) -> Result<Option<OAuthState>, ApiError>;
+
+    /// Delete expired authorization requests
+    async fn purge_expired_states(&self) -> Result<u64, ApiError>;
+}
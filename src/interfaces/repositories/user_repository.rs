@@ -1,34 +1,109 @@
 use crate::domain::entities::User;
 use crate::errors::ApiError;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use super::{Field, FieldKind, Paged, QuerySpec, Ranked, TextQuery, Trashed};
+
+/// Fields of the user lists that can be filtered (and sorted)
+pub const USER_FIELDS: &[Field] = &[
+    Field::new("email", "email", FieldKind::Text, true),
+    Field::new("username", "username", FieldKind::Text, true),
+    Field::new("role", "role", FieldKind::Text, true),
+    Field::new("is_active", "is_active", FieldKind::Bool, false),
+    Field::new("created_at", "created_at", FieldKind::Timestamp, true),
+    Field::new("updated_at", "updated_at", FieldKind::Timestamp, true),
+    Field::new("version", "version", FieldKind::Integer, false),
+];
+
+/// Admin search criteria on top of the `QuerySpec` filters
+#[derive(Debug, Clone, Default)]
+pub struct UserFilter {
+    /// Case-insensitive match on email or username
+    pub search: Option<String>,
+    /// Live users unless asked otherwise
+    pub trashed: Trashed,
+}
 
 /// User Repository - Data access contract
+///
+/// Every read is scoped to a tenant; writes use the tenant carried by the entity.
+/// `delete` only moves a user to the trash, and reads skip trashed users unless asked.
+/// A trashed user keeps their email and username until purged.
 #[async_trait]
 pub trait UserRepository: Send + Sync {
     /// Create new user
     async fn create(&self, user: &User) -> Result<User, ApiError>;
 
     /// Get user by ID
-    async fn get_by_id(&self, id: &str) -> Result<Option<User>, ApiError>;
+    async fn get_by_id(&self, tenant_id: &str, id: &str) -> Result<Option<User>, ApiError>;
+
+    /// Get user by ID, even if they are in the trash
+    async fn get_by_id_with_trashed(&self, tenant_id: &str, id: &str) -> Result<Option<User>, ApiError>;
 
     /// Get user by email (String, no Value Object)
-    async fn get_by_email(&self, email: &str) -> Result<Option<User>, ApiError>;
+    async fn get_by_email(&self, tenant_id: &str, email: &str) -> Result<Option<User>, ApiError>;
 
     /// Get all users
-    async fn get_all(&self) -> Result<Vec<User>, ApiError>;
+    async fn get_all(&self, tenant_id: &str) -> Result<Vec<User>, ApiError>;
+
+    /// Every user of the tenant, oldest first, read lazily (for exports)
+    fn stream_all(&self, tenant_id: &str, trashed: Trashed) -> BoxStream<'static, Result<User, ApiError>>;
+
+    /// Filtered, sorted page of live users
+    async fn list(&self, tenant_id: &str, spec: &QuerySpec) -> Result<Paged<User>, ApiError>;
+
+    /// `list` plus free-text search and access to trashed users
+    async fn search(&self, tenant_id: &str, filter: &UserFilter, spec: &QuerySpec) -> Result<Paged<User>, ApiError>;
+
+    /// `search` ranked by full-text relevance on email and username (offset pages only)
+    async fn search_text(
+        &self,
+        tenant_id: &str,
+        filter: &UserFilter,
+        text: &TextQuery,
+        spec: &QuerySpec,
+    ) -> Result<Paged<Ranked<User>>, ApiError>;
+
+    /// Update user (only within `user.tenant_id`) if they are still at `user.version`.
+    /// Returns the stored user with their new version, or `ApiError::Conflict` if they changed meanwhile.
+    async fn update(&self, user: &User) -> Result<User, ApiError>;
+
+    /// Like `update`, but fails with `ApiError::Conflict` if the change would leave the tenant
+    /// without an active admin. Concurrent calls are serialized, so two admins cannot demote each other.
+    async fn update_keeping_an_admin(&self, user: &User) -> Result<User, ApiError>;
+
+    /// Record `step` as the last accepted TOTP step and clear the failed attempts.
+    /// Returns false if that step or a later one was already accepted (a replayed code).
+    async fn accept_two_factor_step(&self, tenant_id: &str, id: &str, step: i64) -> Result<bool, ApiError>;
+
+    /// Count a wrong second-factor code; the `max_attempts`-th one locks codes until `locked_until`
+    async fn record_two_factor_failure(
+        &self,
+        tenant_id: &str,
+        id: &str,
+        max_attempts: i32,
+        locked_until: DateTime<Utc>,
+    ) -> Result<(), ApiError>;
+
+    /// Clear the failed attempts after a successful recovery code
+    async fn clear_two_factor_failures(&self, tenant_id: &str, id: &str) -> Result<(), ApiError>;
+
+    /// Move user to the trash. Returns false if they are missing or already trashed.
+    async fn delete(&self, tenant_id: &str, id: &str) -> Result<bool, ApiError>;
 
-    /// Get users with pagination
-    async fn get_paginated(&self, page: i32, per_page: i32) -> Result<(Vec<User>, i32), ApiError>;
+    /// Like `delete`, but fails with `ApiError::Conflict` when trashing the last active admin
+    async fn delete_keeping_an_admin(&self, tenant_id: &str, id: &str) -> Result<bool, ApiError>;
 
-    /// Update user
-    async fn update(&self, user: &User) -> Result<(), ApiError>;
+    /// Take user out of the trash. Returns false if they are missing or not trashed.
+    async fn restore(&self, tenant_id: &str, id: &str) -> Result<bool, ApiError>;
 
-    /// Delete user by ID
-    async fn delete(&self, id: &str) -> Result<bool, ApiError>;
+    /// Permanently delete users trashed before the cutoff, in every tenant. Returns the number removed.
+    async fn purge_trashed_before(&self, cutoff: DateTime<Utc>) -> Result<u64, ApiError>;
 
-    /// Count total users
-    async fn count(&self) -> Result<i32, ApiError>;
+    /// Count live users
+    async fn count(&self, tenant_id: &str) -> Result<i32, ApiError>;
 
-    /// Check if user exists with this email (String)
-    async fn exists_by_email(&self, email: &str) -> Result<bool, ApiError>;
-}
\ No newline at end of file
+    /// Check if user exists with this email (String), trashed users included
+    async fn exists_by_email(&self, tenant_id: &str, email: &str) -> Result<bool, ApiError>;
+}
@@ -0,0 +1,19 @@
+//! Rust Ironclad Framework
+//!
+//! Shared by the API server (`src/main.rs`), the `ironclad` CLI (`src/cli/main.rs`)
+//! and integration tests.
+
+pub mod domain;
+pub mod application;
+pub mod infrastructure;
+pub mod interfaces;
+pub mod shared;
+pub mod config;
+pub mod errors;
+pub mod utils;
+pub mod db;
+pub mod middleware;
+pub mod routes;
+pub mod bootstrap;
+pub mod queue;
+pub mod monitoring;
@@ -1,37 +1,30 @@
-mod domain;
-mod application;
-mod infrastructure;
-mod interfaces;
-mod shared;
-mod config;
-mod errors;
-mod utils;
-mod cli;
-mod db;
-mod middleware;
-mod routes;
-mod bootstrap;
-mod queue;
-mod monitoring;
-
-use middleware::MaintenanceMode;
 use actix_web::{web, App, HttpServer};
-use actix_cors::Cors;
 use tracing_subscriber;
 use tracing_actix_web::TracingLogger;
 use std::sync::Arc;
-use crate::queue::Worker;
 
-use config::{AppConfig, validate_security_config};  
-use bootstrap::AppState;
-use infrastructure::http::handle_not_found;  
+use ironclad::config::{AppConfig, validate_security_config};
+use ironclad::bootstrap::AppState;
+use ironclad::db;
+use ironclad::infrastructure::http::handle_not_found;
+use ironclad::middleware::{cors, MaintenanceMode};
+use ironclad::queue::Worker;
+use ironclad::register_services;
+use ironclad::routes;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     // ============================================
     // Initial Configuration
     // ============================================
-    let app_config = AppConfig::from_env().expect("Failed to load config");
+    // Every invalid setting is listed before exiting
+    let app_config = match AppConfig::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+    };
 
     // Configure logging
     tracing_subscriber::fmt()
@@ -72,6 +65,19 @@ async fn main() -> std::io::Result<()> {
         .expect("Failed to initialize PostgreSQL pool");
     tracing::info!("✅ PostgreSQL connected");
 
+    // Pending or edited migrations: refuse to start if DB_REQUIRE_MIGRATIONS, warn otherwise
+    let migrations = async {
+        db::migrator::Migrator::new(pg_pool.clone(), db::migrator::MIGRATIONS_DIR)?.check().await
+    };
+    match migrations.await {
+        Ok(()) => tracing::info!("✅ Migrations up to date"),
+        Err(e) if app_config.db_postgres.require_migrations => {
+            tracing::error!("❌ {}", e);
+            std::process::exit(1);
+        }
+        Err(e) => tracing::warn!("⚠️  {}", e),
+    }
+
     // MySQL (optional)
     if let Some(mysql_config) = &app_config.db_mysql {
         match db::mysql::init_pool(mysql_config).await {
@@ -132,16 +138,30 @@ async fn main() -> std::io::Result<()> {
     //  Start Background Workers
     // ============================================
     let worker = Arc::new(Worker::new(
-        app_state.pool.clone(),
+        app_state.queue_manager.clone(),
         app_state.test_item_service.clone(),
+        app_state.user_service.clone(),
+        app_state.data_transfer_service.clone(),
         // TODO add more services.
         // app_state.email_service.clone(),
         // app_state.payment_service.clone(),
     ));
     
-    // Start worker pool (10 workers)
-    worker.start(10);
-    tracing::info!("✅ Background workers started (10 workers)");
+    // Start worker pool (QUEUE_WORKERS)
+    worker.start(app_config.queue.workers);
+    tracing::info!("✅ Background workers started ({} workers)", app_config.queue.workers);
+
+    // Maintenance mode: load it before serving, then follow changes from the CLI and other instances
+    if let Err(e) = app_state.maintenance_service.refresh().await {
+        tracing::warn!("Cannot read maintenance mode: {}", e);
+    }
+    app_state.maintenance_service.clone().start_watching();
+
+    // Daily purge of audit entries past AUDIT_RETENTION_DAYS
+    app_state.audit_logger.clone().start_retention();
+
+    // Daily job removing records trashed more than SOFT_DELETE_RETENTION_DAYS ago
+    app_state.queue_manager.clone().start_trash_purge(app_config.soft_delete.retention_days);
 
     let address = format!("{}:{}", app_config.server.host, app_config.server.port);
 
@@ -164,20 +184,27 @@ async fn main() -> std::io::Result<()> {
             pool,
             queue_manager,
             auth_service,
+            two_factor_service,
+            permission_service,
+            api_key_service,
+            oauth_service,
+            audit_logger,
+            tenant_service,
             user_service,
-            test_item_service
+            test_item_service,
+            data_transfer_service,
+            maintenance_service,
+            templates
         );
         
-        app.wrap(MaintenanceMode)
-            .wrap(
-                Cors::default()
-                    .allow_any_origin()
-                    .allow_any_method()
-                    .allow_any_header()
-                    .max_age(3600),
-            )
+        app.wrap(MaintenanceMode::new(
+            app_state.maintenance_service.clone(),
+            app_state.config.maintenance.trusted_proxies.clone(),
+            app_state.templates.clone(),
+        ))
+            .wrap(cors(&app_state.config.cors))
             .wrap(TracingLogger::default())
-            .configure(routes::configure)
+            .configure(|cfg| routes::configure(cfg, &app_state.config.rate_limits))
             .default_service(web::route().to(handle_not_found))
     })
     .bind(&address)?
@@ -0,0 +1,36 @@
+use actix_cors::Cors;
+
+use crate::config::CorsConfig;
+
+/// Builds the CORS middleware from `CorsConfig`.
+///
+/// `*` in the allowed origins accepts any origin; empty method and header
+/// lists accept any method and header. The values are checked beforehand by
+/// `validate_all_config`, as invalid ones would fail the app factory.
+pub fn cors(config: &CorsConfig) -> Cors {
+    let mut cors = Cors::default().max_age(config.max_age);
+
+    cors = if config.allows_any_origin() {
+        cors.allow_any_origin()
+    } else {
+        config.allowed_origins.iter().fold(cors, |cors, origin| cors.allowed_origin(origin))
+    };
+
+    cors = if config.allowed_methods.is_empty() {
+        cors.allow_any_method()
+    } else {
+        cors.allowed_methods(config.allowed_methods.iter().map(String::as_str))
+    };
+
+    cors = if config.allowed_headers.is_empty() {
+        cors.allow_any_header()
+    } else {
+        cors.allowed_headers(config.allowed_headers.iter().map(String::as_str))
+    };
+
+    if config.allow_credentials {
+        cors = cors.supports_credentials();
+    }
+
+    cors
+}
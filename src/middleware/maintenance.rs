@@ -1,20 +1,48 @@
 use actix_web::{
     body::{BoxBody, EitherBody},
-    cookie::Cookie, // Required import for the bypass cookie
+    cookie::{time::Duration as CookieDuration, Cookie, SameSite},
     dev::{Service, ServiceRequest, ServiceResponse, Transform},
     http::header,
-    Error, HttpResponse,
+    Error, FromRequest, HttpResponse,
 };
+use chrono::Utc;
 use futures::future::{ok, Ready};
 use futures::Future;
-use std::fs;
-use std::path::Path;
+use minijinja::context;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
-const MAINTENANCE_FILE: &str = "storage/framework/maintenance.json";
+use crate::application::services::MaintenanceService;
+use crate::domain::entities::maintenance::path_matches;
+use crate::domain::entities::{MaintenancePhase, MaintenanceState, RequestOrigin, DEFAULT_TEMPLATE};
+use crate::domain::value_objects::IpRange;
+use crate::infrastructure::http::request_origin::client_ip;
+use crate::infrastructure::templates::TemplateEngine;
+use crate::utils::maintenance_bypass::COOKIE_NAME;
 
-pub struct MaintenanceMode;
+/// Admin endpoints toggling maintenance mode and its bypasses, reachable while the application is down
+pub const MAINTENANCE_ENDPOINT: &str = "/api/administration/maintenance";
+/// Public window status, reachable while the application is down
+pub const MAINTENANCE_STATUS_ENDPOINT: &str = "/api/maintenance";
+
+/// Announce a scheduled window on the responses it will affect
+const STARTS_AT_HEADER: &str = "x-maintenance-starts-at";
+const ENDS_AT_HEADER: &str = "x-maintenance-ends-at";
+
+/// Answers 503 while the cluster is in maintenance mode (state cached by `MaintenanceService`)
+pub struct MaintenanceMode {
+    service: Arc<MaintenanceService>,
+    trusted_proxies: Arc<Vec<IpRange>>,
+    templates: Arc<TemplateEngine>,
+}
+
+impl MaintenanceMode {
+    /// `trusted_proxies` may name the client in X-Forwarded-For (for allow-lists)
+    pub fn new(service: Arc<MaintenanceService>, trusted_proxies: Vec<IpRange>, templates: Arc<TemplateEngine>) -> Self {
+        Self { service, trusted_proxies: Arc::new(trusted_proxies), templates }
+    }
+}
 
 impl<S, B> Transform<S, ServiceRequest> for MaintenanceMode
 where
@@ -29,12 +57,20 @@ where
     type Future = Ready<Result<Self::Transform, Self::InitError>>;
 
     fn new_transform(&self, service: S) -> Self::Future {
-        ok(MaintenanceModeMiddleware { service })
+        ok(MaintenanceModeMiddleware {
+            service,
+            maintenance: self.service.clone(),
+            trusted_proxies: self.trusted_proxies.clone(),
+            templates: self.templates.clone(),
+        })
     }
 }
 
 pub struct MaintenanceModeMiddleware<S> {
     service: S,
+    maintenance: Arc<MaintenanceService>,
+    trusted_proxies: Arc<Vec<IpRange>>,
+    templates: Arc<TemplateEngine>,
 }
 
 impl<S, B> Service<ServiceRequest> for MaintenanceModeMiddleware<S>
@@ -52,22 +88,41 @@ where
     }
 
     fn call(&self, req: ServiceRequest) -> Self::Future {
-        if !Path::new(MAINTENANCE_FILE).exists() {
-            // No maintenance mode, proceed normally
-            let fut = self.service.call(req);
-            return Box::pin(async move {
-                let res = fut.await?;
-                Ok(res.map_into_left_body())
-            });
-        }
+        let exempt = path_matches(MAINTENANCE_ENDPOINT, req.path()) || req.path() == MAINTENANCE_STATUS_ENDPOINT;
+        let data = match self.maintenance.current() {
+            Some(data) if !exempt && data.covers(req.path()) => data,
+            _ => {
+                // No maintenance mode (or not for this path), proceed normally
+                let fut = self.service.call(req);
+                return Box::pin(async move {
+                    let res = fut.await?;
+                    Ok(res.map_into_left_body())
+                });
+            }
+        };
 
-        // Read maintenance data
-        let maintenance_data = fs::read_to_string(MAINTENANCE_FILE)
-            .ok()
-            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok());
+        match data.phase(Utc::now()) {
+            MaintenancePhase::Active => {}
+            MaintenancePhase::Ended => {
+                let fut = self.service.call(req);
+                return Box::pin(async move {
+                    let res = fut.await?;
+                    Ok(res.map_into_left_body())
+                });
+            }
+            MaintenancePhase::Scheduled => {
+                // Not started yet: serve the request and announce the window
+                let fut = self.service.call(req);
+                return Box::pin(async move {
+                    let mut res = fut.await?;
+                    announce(&mut res, &data);
+                    Ok(res.map_into_left_body())
+                });
+            }
+        }
 
-        if maintenance_data.is_none() {
-            // Invalid maintenance file, proceed normally
+        // Allow-listed clients (office, health checkers) are served as usual
+        if data.allows(client_ip(req.request(), &self.trusted_proxies)) {
             let fut = self.service.call(req);
             return Box::pin(async move {
                 let res = fut.await?;
@@ -75,107 +130,88 @@ where
             });
         }
 
-        let data = maintenance_data.unwrap();
-        let secret_opt = data.get("secret").and_then(|s| s.as_str());
-
-        // ========================================
-        // FIX 1: Secret bypass (Cookie Validation)
-        // ========================================
-        if let Some(secret) = secret_opt {
-            // Check if the user already has a valid bypass cookie
-            if let Some(cookie) = req.cookie("maintenance_bypass") {
-                if cookie.value() == secret {
-                    // Valid cookie, bypass maintenance mode completely
-                    let fut = self.service.call(req);
-                    return Box::pin(async move {
-                        let res = fut.await?;
-                        Ok(res.map_into_left_body())
-                    });
-                }
-            }
-
-            // Check if the user is trying to authenticate via URL suffix
-            let path = req.path().to_string();
-            let secret_suffix = format!("/{}", secret);
-            
-            if path.ends_with(&secret_suffix) {
-                // Remove secret from path to determine where to redirect
-                let new_path = path.trim_end_matches(&secret_suffix);
-                let redirect_to = if new_path.is_empty() { "/" } else { new_path }.to_string();
-                
-                // Create a session cookie for the bypass
-                let mut cookie = Cookie::new("maintenance_bypass", secret);
-                cookie.set_path("/");
-                cookie.set_http_only(true);
-
-                let (http_req, _) = req.into_parts();
-                
-                // Redirect to the clean URL, setting the cookie
-                let response = HttpResponse::TemporaryRedirect()
-                    .insert_header((header::LOCATION, redirect_to.as_str()))
-                    .cookie(cookie)
-                    .finish();
-
+        // Signed, unexpired cookie granted by a bypass secret
+        if let Some(cookie) = req.cookie(COOKIE_NAME) {
+            if self.maintenance.bypass_allowed(&data, cookie.value()) {
+                let fut = self.service.call(req);
                 return Box::pin(async move {
-                    Ok(ServiceResponse::new(http_req, response).map_into_right_body())
+                    let res = fut.await?;
+                    Ok(res.map_into_left_body())
                 });
             }
         }
 
-        // ========================================
-        // FIX 2: Redirect (avoid infinite loop)
-        // ========================================
-        if let Some(redirect) = data.get("redirect").and_then(|r| r.as_str()) {
-            let current_path = req.path();
-            
-            // Avoid redirect loop - don't redirect if already on target path
-            if current_path != redirect {
-                let (http_req, _) = req.into_parts();
-                let response = HttpResponse::TemporaryRedirect()
-                    .insert_header(("Location", redirect))
-                    .finish();
-                
-                return Box::pin(async move {
-                    Ok(ServiceResponse::new(http_req, response).map_into_right_body())
-                });
-            } else {
-                // Already on target path, show maintenance page
-                let (http_req, _) = req.into_parts();
-                let response = render_json_response(&data);
-                return Box::pin(async move {
-                    Ok(ServiceResponse::new(http_req, response).map_into_right_body())
-                });
-            }
-        }
-
-        // ========================================
-        // Response type determination
-        // ========================================
         let is_browser_request = is_browser(&req);
-        let norender = data.get("norender").and_then(|n| n.as_bool()).unwrap_or(false);
-        let has_render = data.get("render").is_some();
-
-        let response = if norender {
-            // Force JSON even for browser requests
-            render_json_response(&data)
-        } else if is_browser_request && has_render {
-            // Render custom HTML template
-            render_custom_html(&data)
-        } else if is_browser_request {
-            // Default HTML template
-            render_default_html(&data)
-        } else {
-            // JSON response for API requests
-            render_json_response(&data)
-        };
-
+        let path = req.path().to_string();
+        let origin = RequestOrigin::extract(req.request()).into_inner().unwrap_or_default();
+        let maintenance = self.maintenance.clone();
+        let templates = self.templates.clone();
         let (http_req, _) = req.into_parts();
+
         Box::pin(async move {
+            // `/{secret}` (a whole, single segment) trades a bypass secret for the cookie
+            let secret = path.strip_prefix('/').filter(|s| !s.is_empty() && !s.contains('/'));
+            if let Some(secret) = secret.filter(|_| !data.bypass.is_empty()) {
+                if let Some(value) = maintenance.grant_bypass(&data, secret, &origin).await {
+                    let response = HttpResponse::TemporaryRedirect()
+                        .insert_header((header::LOCATION, "/"))
+                        .cookie(bypass_cookie(value, maintenance.bypass_ttl()))
+                        .finish();
+                    return Ok(ServiceResponse::new(http_req, response).map_into_right_body());
+                }
+            }
+
+            let response = maintenance_response(&templates, &data, &path, is_browser_request);
             Ok(ServiceResponse::new(http_req, response).map_into_right_body())
         })
     }
 }
 
+// Helper: Bypass cookie, sent over HTTPS only and not to cross-site subrequests
+fn bypass_cookie(value: String, ttl: u64) -> Cookie<'static> {
+    Cookie::build(COOKIE_NAME, value)
+        .path("/")
+        .http_only(true)
+        .secure(true)
+        .same_site(SameSite::Lax)
+        .max_age(CookieDuration::seconds(ttl as i64))
+        .finish()
+}
+
+// Helper: Redirect, or answer 503 as HTML or JSON
+fn maintenance_response(templates: &TemplateEngine, data: &MaintenanceState, path: &str, is_browser_request: bool) -> HttpResponse {
+    if let Some(redirect) = data.redirect.as_deref() {
+        // Avoid a redirect loop: on the target path, show the maintenance response
+        if path != redirect {
+            return HttpResponse::TemporaryRedirect()
+                .insert_header((header::LOCATION, redirect))
+                .finish();
+        }
+        return render_json_response(data);
+    }
+
+    if is_browser_request && !data.norender {
+        render_html(templates, data)
+    } else {
+        // API clients, or browsers too with norender
+        render_json_response(data)
+    }
+}
+
+// Helper: Announce an upcoming window
+fn announce<B>(res: &mut ServiceResponse<B>, data: &MaintenanceState) {
+    let headers = res.headers_mut();
+    let starts_at = data.starts_at.unwrap_or(data.created_at).to_rfc3339();
+    if let Ok(value) = header::HeaderValue::from_str(&starts_at) {
+        headers.insert(header::HeaderName::from_static(STARTS_AT_HEADER), value);
+    }
+    if let Some(ends_at) = data.ends_at {
+        if let Ok(value) = header::HeaderValue::from_str(&ends_at.to_rfc3339()) {
+            headers.insert(header::HeaderName::from_static(ENDS_AT_HEADER), value);
+        }
+    }
+}
+
 // Helper: Check if request is from a browser
 fn is_browser(req: &ServiceRequest) -> bool {
     if let Some(accept) = req.headers().get("Accept") {
@@ -187,73 +223,42 @@ fn is_browser(req: &ServiceRequest) -> bool {
 }
 
 // Helper: Render JSON response
-fn render_json_response(data: &serde_json::Value) -> HttpResponse {
-    let retry = data["retry"].as_u64().unwrap_or(60);
+fn render_json_response(data: &MaintenanceState) -> HttpResponse {
+    let retry = data.retry_after(Utc::now());
     HttpResponse::ServiceUnavailable()
         .insert_header(("Retry-After", retry.to_string()))
         .json(serde_json::json!({
             "error": "Service Unavailable",
-            "message": data["message"].as_str().unwrap_or("Application is down for maintenance"),
+            "message": data.message,
             "status": 503,
             "retry_after": retry,
         }))
 }
 
-// Helper: Render default HTML template
-fn render_default_html(data: &serde_json::Value) -> HttpResponse {
-    let template = include_str!("../../templates/render/down/default.html");
-    
-    let message = data["message"].as_str().unwrap_or("Application is down for maintenance");
-    let retry = data["retry"].as_u64().unwrap_or(60);
-    
-    let html = template
-        .replace("{{MESSAGE}}", message)
-        .replace("{{RETRY}}", &retry.to_string());
-    
-    HttpResponse::ServiceUnavailable()
-        .insert_header(("Content-Type", "text/html; charset=utf-8"))
-        .insert_header(("Retry-After", retry.to_string()))
-        .body(html)
-}
-
-// Helper: Render custom HTML template
-fn render_custom_html(data: &serde_json::Value) -> HttpResponse {
-    let template_name = data["render"].as_str().unwrap_or("");
-    
-    // Parse template path
-    let template_path = if template_name.is_empty() {
-        "templates/render/down/default.html".to_string()
-    } else if template_name.contains("::") {
-        let parts: Vec<&str> = template_name.split("::").collect();
-        if parts.len() == 2 {
-            format!("templates/render/down/{}/{}.html", parts[0], parts[1])
-        } else {
-            "templates/render/down/default.html".to_string()
-        }
-    } else {
-        format!("templates/render/down/{}/default.html", template_name)
-    };
-    
-    // Try to read custom template
-    let template = if let Ok(content) = fs::read_to_string(&template_path) {
-        content
-    } else {
-        // Fallback to default if template not found
-        tracing::warn!("Template not found: {}, using default", template_path);
-        include_str!("../../templates/render/down/default.html").to_string()
+// Helper: Render the maintenance page (`render`, else the default page)
+fn render_html(templates: &TemplateEngine, data: &MaintenanceState) -> HttpResponse {
+    let now = Utc::now();
+    let retry = data.retry_after(now);
+    let context = context! {
+        message => data.message,
+        retry,
+        starts_at => data.starts_at.unwrap_or(data.created_at).to_rfc3339(),
+        ends_at => data.ends_at.map(|t| t.to_rfc3339()),
+        // Placeholders of templates written before the template engine
+        MESSAGE => data.message,
+        RETRY => retry,
+        TIMESTAMP => data.created_at.to_rfc3339(),
     };
-    
-    let message = data["message"].as_str().unwrap_or("Application is down for maintenance");
-    let retry = data["retry"].as_u64().unwrap_or(60);
-    let timestamp = data["created_at"].as_str().unwrap_or("N/A");
-    
-    let html = template
-        .replace("{{MESSAGE}}", message)
-        .replace("{{RETRY}}", &retry.to_string())
-        .replace("{{TIMESTAMP}}", timestamp);
-    
-    HttpResponse::ServiceUnavailable()
-        .insert_header(("Content-Type", "text/html; charset=utf-8"))
-        .insert_header(("Retry-After", retry.to_string()))
-        .body(html)
-}
\ No newline at end of file
+
+    // A missing or broken template falls back to the default page, then to JSON
+    let html = templates
+        .render(&data.template(), &context)
+        .or_else(|_| templates.render(DEFAULT_TEMPLATE, &context));
+    match html {
+        Ok(html) => HttpResponse::ServiceUnavailable()
+            .insert_header(("Content-Type", "text/html; charset=utf-8"))
+            .insert_header(("Retry-After", retry.to_string()))
+            .body(html),
+        Err(_) => render_json_response(data),
+    }
+}
@@ -1,7 +1,9 @@
 // pub mod auth;
+pub mod cors;
 pub mod maintenance;  
 pub mod rate_limit;
 
 // pub use auth::AuthUser;
+pub use cors::cors;
 pub use rate_limit::api_rate_limiter;
 pub use maintenance::MaintenanceMode;  
\ No newline at end of file
@@ -49,7 +49,7 @@ use crate::errors::ApiError;
 /// # Returns
 ///
 /// Returns a `GovernorConfig` ready to be applied as actix-web middleware:
-/// ```rust
+/// ```text
 /// App::new().wrap(Governor::new(&api_rate_limiter(2, 10)))
 /// ```
 /// 
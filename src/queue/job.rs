@@ -1,11 +1,17 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use crate::application::dtos::{BulkUpdateTestItem, CreateTestItemRequest};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 
 // This enum defines the different types of jobs that can be processed by the worker. Each variant can have its own associated data as needed. For this example, we only implement a single job type (DeleteTestItem) to demonstrate the structure
 pub enum JobType {
     DeleteTestItem,
+    PurgeTrashed,
+    BulkCreateTestItems,
+    BulkUpdateTestItems,
+    BulkDeleteTestItems,
+    RunImport,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,11 +20,35 @@ pub enum JobType {
 // Here would be the payload for different job types. For simplicity, we only implement DeleteTestItem in this example.
 pub enum JobPayload {
     DeleteTestItem { item_id: String },
+    /// Hard-delete users and test items trashed more than `retention_days` ago (all tenants)
+    PurgeTrashed { retention_days: u32 },
+    /// Bulk test item requests above `config.bulk.sync_limit`, run in the tenant that queued them
+    BulkCreateTestItems { items: Vec<CreateTestItemRequest> },
+    BulkUpdateTestItems { items: Vec<BulkUpdateTestItem> },
+    BulkDeleteTestItems { ids: Vec<String> },
+    /// Apply an uploaded CSV/NDJSON import (see `DataTransferService::run_import`)
+    RunImport { import_id: String },
+}
+
+impl JobPayload {
+    /// Name stored in `job_queue.job_type`
+    pub fn job_type(&self) -> &'static str {
+        match self {
+            JobPayload::DeleteTestItem { .. } => "DeleteTestItem",
+            JobPayload::PurgeTrashed { .. } => "PurgeTrashed",
+            JobPayload::BulkCreateTestItems { .. } => "BulkCreateTestItems",
+            JobPayload::BulkUpdateTestItems { .. } => "BulkUpdateTestItems",
+            JobPayload::BulkDeleteTestItems { .. } => "BulkDeleteTestItems",
+            JobPayload::RunImport { .. } => "RunImport",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Job {
     pub id: String,
+    /// Tenant that enqueued the job; the worker acts within it
+    pub tenant_id: String,
     pub job_type: String,
     pub payload: serde_json::Value,
     pub status: String,
@@ -12,38 +12,47 @@ use crate::monitoring::alerts::{Alert, AlertLevel};
 // Alert service in /services
 use crate::application::services::AlertService;
 
-use std::env;
+use crate::config::QueueConfig;
+use crate::domain::entities::DEFAULT_TENANT_ID;
+
+use std::sync::Arc;
+
+/// How often the trash purge job is enqueued
+const TRASH_PURGE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
 
 pub struct QueueManager {
     pool: PgPool,
+    config: QueueConfig,
 }
 
 impl QueueManager {
-    pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: PgPool, config: QueueConfig) -> Self {
+        Self { pool, config }
     }
     pub fn pool(&self) -> &PgPool {
         &self.pool
     }
+    pub fn config(&self) -> &QueueConfig {
+        &self.config
+    }
 
     /// Enqueue a job to be executed immediately
     /// TODO: modify to accept priority and queue name
-    pub async fn enqueue(&self, payload: JobPayload) -> Result<String, ApiError> {
-        self.schedule(payload, Utc::now(), 3, 0, "default").await
+    pub async fn enqueue(&self, tenant_id: &str, payload: JobPayload) -> Result<String, ApiError> {
+        self.schedule(tenant_id, payload, Utc::now(), self.config.max_attempts, 0, "default").await
     }
 
     /// Schedule a job to be executed at a specific time
     pub async fn schedule(
         &self,
+        tenant_id: &str,
         payload: JobPayload,
         scheduled_at: DateTime<Utc>,
         max_attempts: i32,
         priority: i32,
         queue_name: &str,
     ) -> Result<String, ApiError> {
-        let job_type = match &payload {
-            JobPayload::DeleteTestItem { .. } => "DeleteTestItem",
-        };
+        let job_type = payload.job_type();
 
         let payload_json = serde_json::to_value(&payload)
             .map_err(|e| ApiError::InternalServerError(format!("Failed to serialize payload: {}", e)))?;
@@ -53,8 +62,8 @@ impl QueueManager {
         let job: Job = sqlx::query_as(
             r#"
             INSERT INTO job_queue 
-            (id, job_type, payload, scheduled_at, max_attempts, priority, queue_name)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            (id, job_type, payload, scheduled_at, max_attempts, priority, queue_name, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             RETURNING *
             "#
         )
@@ -65,12 +74,14 @@ impl QueueManager {
         .bind(max_attempts)
         .bind(priority)
         .bind(queue_name)
+        .bind(tenant_id)
         .fetch_one(&self.pool)
         .await
         .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
 
         tracing::info!(
             job_id = %job.id,
+            tenant_id = tenant_id,
             queue = queue_name,
             priority = priority,
             "Job scheduled"
@@ -82,13 +93,36 @@ impl QueueManager {
     /// Schedule a job to run after X seconds
     pub async fn enqueue_in(
         &self,
+        tenant_id: &str,
         payload: JobPayload,
         delay_seconds: i64,
         priority: i32,
         queue_name: &str,
     ) -> Result<String, ApiError> {
         let scheduled_at = Utc::now() + Duration::seconds(delay_seconds);
-        self.schedule(payload, scheduled_at, 3, priority, queue_name).await
+        self.schedule(tenant_id, payload, scheduled_at, self.config.max_attempts, priority, queue_name).await
+    }
+
+    /// Enqueue a `PurgeTrashed` job once a day (no-op when `retention_days` is 0).
+    /// The job covers every tenant, so it is filed under the default one.
+    pub fn start_trash_purge(self: Arc<Self>, retention_days: u32) {
+        if retention_days == 0 {
+            tracing::info!("Trash purge disabled (SOFT_DELETE_RETENTION_DAYS=0)");
+            return;
+        }
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(TRASH_PURGE_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self
+                    .enqueue_with_queue(DEFAULT_TENANT_ID, JobPayload::PurgeTrashed { retention_days }, "low")
+                    .await
+                {
+                    tracing::warn!("Failed to enqueue trash purge: {}", e);
+                }
+            }
+        });
     }
 
     /// Claim multiple jobs atomically (Batch Processing)
@@ -130,6 +164,35 @@ impl QueueManager {
         Ok(jobs)
     }
 
+    /// Keep a long-running job claimed: the 30 second lock taken by `claim_next_jobs`
+    /// is renewed every 10 seconds so `recover_stuck_jobs` does not hand the job to
+    /// another worker. Abort the returned task once the job is done.
+    pub fn keep_locked(self: Arc<Self>, job_id: String) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(10));
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+                let renewed = sqlx::query(
+                    r#"
+                    UPDATE job_queue
+                    SET lock_expires_at = NOW() + interval '30 seconds',
+                        updated_at = NOW()
+                    WHERE id = $1 AND status = 'running'
+                    "#
+                )
+                .bind(&job_id)
+                .execute(&self.pool)
+                .await;
+
+                if let Err(e) = renewed {
+                    tracing::warn!(job_id = %job_id, "Failed to renew job lock: {:?}", e);
+                }
+            }
+        })
+    }
+
     /// Mark job as completed
     pub async fn mark_completed(&self, job_id: &str) -> Result<(), ApiError> {
         sqlx::query(
@@ -182,9 +245,10 @@ impl QueueManager {
                     payload,
                     error_message,
                     attempts,
-                    max_attempts
+                    max_attempts,
+                    tenant_id
                 )
-                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
                 "#
             )
             .bind(Uuid::new_v4().to_string())
@@ -194,6 +258,7 @@ impl QueueManager {
             .bind(&job.error_message)
             .bind(job.attempts)
             .bind(job.max_attempts)
+            .bind(&job.tenant_id)
             .execute(&mut *tx)
             .await
             .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
@@ -216,7 +281,7 @@ impl QueueManager {
             );
 
         } else {
-            let delay = calculate_backoff(job.attempts);
+            let delay = calculate_backoff(&self.config, job.attempts);
             let retry_at = Utc::now() + Duration::seconds(delay);
 
             sqlx::query(
@@ -252,13 +317,12 @@ impl QueueManager {
 
     pub async fn enqueue_with_queue(
         &self,
+        tenant_id: &str,
         payload: JobPayload,
         queue_name: &str,
     ) -> Result<String, ApiError> {
 
-        let job_type = match &payload {
-            JobPayload::DeleteTestItem { .. } => "DeleteTestItem",
-        };
+        let job_type = payload.job_type();
 
         let payload_json = serde_json::to_value(&payload)
             .map_err(|e| ApiError::InternalServerError(format!("Failed to serialize payload: {}", e)))?;
@@ -267,21 +331,24 @@ impl QueueManager {
 
         sqlx::query(
             r#"
-            INSERT INTO job_queue (id, job_type, payload, queue_name, scheduled_at, max_attempts)
-            VALUES ($1, $2, $3, $4, NOW(), 3)
+            INSERT INTO job_queue (id, job_type, payload, queue_name, scheduled_at, max_attempts, tenant_id)
+            VALUES ($1, $2, $3, $4, NOW(), $5, $6)
             "#
         )
         .bind(&job_id)
         .bind(job_type)
         .bind(payload_json)
         .bind(queue_name)
+        .bind(self.config.max_attempts)
+        .bind(tenant_id)
         .execute(&self.pool)
         .await?;
 
         Ok(job_id)
     }
 
-    pub async fn requeue_from_dlq(&self, dlq_id: &str) -> Result<String, ApiError> {
+    /// Move a dead job of the tenant back into the queue (same tenant)
+    pub async fn requeue_from_dlq(&self, tenant_id: &str, dlq_id: &str) -> Result<String, ApiError> {
 
         let mut tx = self.pool.begin().await
             .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
@@ -290,10 +357,11 @@ impl QueueManager {
             r#"
             SELECT original_job_id, job_type, payload
             FROM dead_letter_queue
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = $2
             "#
         )
         .bind(dlq_id)
+        .bind(tenant_id)
         .fetch_optional(&mut *tx)
         .await
         .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
@@ -313,13 +381,15 @@ impl QueueManager {
 
         sqlx::query(
             r#"
-            INSERT INTO job_queue (id, job_type, payload, scheduled_at, max_attempts)
-            VALUES ($1, $2, $3, NOW(), 3)
+            INSERT INTO job_queue (id, job_type, payload, scheduled_at, max_attempts, tenant_id)
+            VALUES ($1, $2, $3, NOW(), $4, $5)
             "#
         )
         .bind(&new_id)
         .bind(&job_type)
         .bind(&payload)
+        .bind(self.config.max_attempts)
+        .bind(tenant_id)
         .execute(&mut *tx)
         .await
         .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
@@ -343,17 +413,19 @@ impl QueueManager {
         Ok(new_id)
     }
 
-    /// Get queue statistics
-    pub async fn get_statistics(&self) -> Result<serde_json::Value, ApiError> {
+    /// Get queue statistics of one tenant
+    pub async fn get_statistics(&self, tenant_id: &str) -> Result<serde_json::Value, ApiError> {
         let stats = sqlx::query(
             r#"
             SELECT 
                 status,
                 COUNT(*) as count
             FROM job_queue
+            WHERE tenant_id = $1
             GROUP BY status
             "#
         )
+        .bind(tenant_id)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
@@ -384,8 +456,8 @@ impl QueueManager {
         Ok(result)
     }
 
-    /// Retry a failed job
-    pub async fn retry_job(&self, job_id: &str) -> Result<(), ApiError> {
+    /// Retry a failed job of the tenant
+    pub async fn retry_job(&self, tenant_id: &str, job_id: &str) -> Result<(), ApiError> {
         let result = sqlx::query(
             r#"
             UPDATE job_queue
@@ -396,10 +468,12 @@ impl QueueManager {
                 completed_at = NULL,
                 updated_at = NOW()
             WHERE id = $1
+              AND tenant_id = $2
               AND status IN ('failed', 'cancelled')
             "#
         )
         .bind(job_id)
+        .bind(tenant_id)
         .execute(&self.pool)
         .await
         .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
@@ -412,18 +486,20 @@ impl QueueManager {
         Ok(())
     }
 
-    /// Cancel a pending job
-    pub async fn cancel_job(&self, job_id: &str) -> Result<(), ApiError> {
+    /// Cancel a pending job of the tenant
+    pub async fn cancel_job(&self, tenant_id: &str, job_id: &str) -> Result<(), ApiError> {
         let result = sqlx::query(
             r#"
             UPDATE job_queue
             SET status = 'cancelled',
                 updated_at = NOW()
             WHERE id = $1
+              AND tenant_id = $2
               AND status = 'pending'
             "#
         )
         .bind(job_id)
+        .bind(tenant_id)
         .execute(&self.pool)
         .await
         .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
@@ -462,17 +538,11 @@ impl QueueManager {
 
 }
 
-fn calculate_backoff(attempts: i32) -> i64 {
+fn calculate_backoff(config: &QueueConfig, attempts: i32) -> i64 {
     use rand::Rng;
 
-    let base_delay = env::var("QUEUE_BACKOFF_BASE")
-        .unwrap_or_else(|_| "2".to_string())
-        .parse::<i64>()
-        .unwrap_or(2);
-    let max_delay = env::var("QUEUE_BACKOFF_MAX")
-        .unwrap_or_else(|_| "300".to_string())
-        .parse::<i64>()
-        .unwrap_or(300);
+    let base_delay = config.backoff_base;
+    let max_delay = config.backoff_max;
 
     // Exponential growth
     let exp_delay = base_delay * 2_i64.pow(attempts as u32);
@@ -16,16 +16,20 @@ use std::sync::Arc;
 use std::time::Duration;
 use rand::Rng;
 use tokio::time;
-use sqlx::PgPool;
 
-use crate::application::TestItemService;
+use crate::application::{DataTransferService, TestItemService, UserService};
+use crate::application::dtos::BulkResponse;
 use crate::queue::{QueueManager, JobPayload};
 use crate::monitoring::queue_monitor::QueueMonitor;
 
 use std::sync::atomic::{AtomicUsize, Ordering};
 use tokio::sync::Semaphore;
 
-use std::env;
+/// Time a job may run before it is failed with `Timeout`
+const JOB_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Time an import job may run; its lock is renewed meanwhile
+const IMPORT_TIMEOUT: Duration = Duration::from_secs(60 * 60);
 
 /// Worker pool for processing asynchronous jobs.
 ///
@@ -49,6 +53,10 @@ pub struct Worker {
     scheduler_index: AtomicUsize,
     /// Service for processing test item jobs
     test_item_service: Arc<TestItemService>,
+    /// Service for user maintenance jobs (trash purge)
+    user_service: Arc<UserService>,
+    /// Service applying uploaded imports
+    data_transfer_service: Arc<DataTransferService>,
 }
 
 impl Worker {
@@ -56,8 +64,10 @@ impl Worker {
     ///
     /// # Arguments
     ///
-    /// * `pool` - PostgreSQL connection pool for queue persistence
+    /// * `queue` - Queue manager, whose `QueueConfig` sets batch size and polling
     /// * `test_item_service` - Service instance for executing test item jobs
+    /// * `user_service` - Service instance for user maintenance jobs
+    /// * `data_transfer_service` - Service instance for import jobs
     ///
     /// # Returns
     ///
@@ -65,17 +75,21 @@ impl Worker {
     ///
     /// # Example
     ///
-    /// ```ignore
-    /// let worker = Worker::new(pg_pool, test_item_service);
+    /// ```text
+    /// let worker = Worker::new(queue_manager, test_item_service, user_service, data_transfer_service);
     /// ```
     pub fn new(
-        pool: PgPool,
+        queue: Arc<QueueManager>,
         test_item_service: Arc<TestItemService>,
+        user_service: Arc<UserService>,
+        data_transfer_service: Arc<DataTransferService>,
     ) -> Self {
         Self {
-            queue: Arc::new(QueueManager::new(pool)),
+            queue,
             scheduler_index: AtomicUsize::new(0),
             test_item_service,
+            user_service,
+            data_transfer_service,
         }
     }
 
@@ -97,9 +111,9 @@ impl Worker {
     ///
     /// # Example
     ///
-    /// ```ignore
-    /// let worker = Arc::new(Worker::new(pool, service));
-    /// worker.start(4); // Start 4 concurrent workers
+    /// ```text
+    /// let worker = Arc::new(Worker::new(queue_manager, service));
+    /// worker.start(config.queue.workers);
     /// ```
     
     pub fn start(self: Arc<Self>, num_workers: usize) {
@@ -134,13 +148,11 @@ impl Worker {
     /// - **Success**: Job is marked as completed
     /// - **Processing Error**: Job is marked as failed with error details; retry logic is handled internally
     /// - **Claim Error**: Worker sleeps 5 seconds before retrying (DB connection issues)
-    /// - **No Jobs**: Worker sleeps 1 second before polling again (normal idle state)
+    /// - **No Jobs**: Worker sleeps `QUEUE_POLL_INTERVAL` seconds before polling again (normal idle state)
     async fn run(self: Arc<Self>, worker_id: usize) {
         let worker_id_str = format!("worker-{}", worker_id);
-        let batch_size = env::var("QUEUE_BATCH_SIZE")
-            .unwrap_or_else(|_| "5".to_string())
-            .parse::<i64>()
-            .unwrap_or(5);
+        let batch_size = self.queue.config().batch_size;
+        let poll_interval = Duration::from_secs(self.queue.config().poll_interval);
 
         let semaphore = Arc::new(Semaphore::new(11));
         let monitor = QueueMonitor::new(self.queue.pool().clone());
@@ -191,11 +203,19 @@ impl Worker {
                         tokio::spawn(async move {
                             let permit = semaphore.acquire_owned().await.unwrap();
 
+                            let timeout = Self::timeout_for(&job.job_type);
+                            let heartbeat = (timeout > JOB_TIMEOUT)
+                                .then(|| queue.clone().keep_locked(job.id.clone()));
+
                             let result = tokio::time::timeout(
-                                Duration::from_secs(30),
+                                timeout,
                                 worker.process_job(&job)
                             ).await;
 
+                            if let Some(heartbeat) = heartbeat {
+                                heartbeat.abort();
+                            }
+
                             match result {
                                 Ok(Ok(_)) => {
                                     let _ = queue.mark_completed(&job.id).await;
@@ -213,7 +233,7 @@ impl Worker {
                     }
                 }
                 Ok(_) => {
-                    time::sleep(Duration::from_secs(1)).await;
+                    time::sleep(poll_interval).await;
                 }
                 Err(e) => {
                     tracing::error!("Worker error: {:?}", e);
@@ -223,6 +243,14 @@ impl Worker {
         }
     }
 
+    /// Time a job may run: imports read whole files, everything else gets `JOB_TIMEOUT`
+    fn timeout_for(job_type: &str) -> Duration {
+        match job_type {
+            "RunImport" => IMPORT_TIMEOUT,
+            _ => JOB_TIMEOUT,
+        }
+    }
+
     /// Processes a single job based on its type and payload.
     ///
     /// This method dispatches job execution to type-specific handlers. Each job type
@@ -241,6 +269,10 @@ impl Worker {
     /// # Supported Job Types
     ///
     /// - `DeleteTestItem`: Delete a test item from the database
+    /// - `PurgeTrashed`: Permanently remove users and test items past the trash retention
+    /// - `BulkCreateTestItems` / `BulkUpdateTestItems` / `BulkDeleteTestItems`: Large bulk requests;
+    ///   per-item failures are logged, only a database error fails the job
+    /// - `RunImport`: Apply an uploaded import file; its progress and errors are kept on the import
     ///
     /// # Error Handling
     ///
@@ -254,14 +286,68 @@ impl Worker {
         // Dispatch to the appropriate handler based on job type.
         match payload {
             JobPayload::DeleteTestItem { item_id } => {
-                tracing::info!("Processing DeleteTestItem job for item: {}", item_id);
+                tracing::info!(tenant_id = %job.tenant_id, "Processing DeleteTestItem job for item: {}", item_id);
                 
-                // Delete the test item from the database.
-                self.test_item_service.delete(&item_id).await?;
+                // Delete the test item from the database, within the tenant that scheduled it.
+                self.test_item_service.delete(&job.tenant_id, &item_id).await?;
                 tracing::info!("✅ Test item {} deleted successfully", item_id);
                 Ok(())
             }
+
+            JobPayload::PurgeTrashed { retention_days } => {
+                let cutoff = chrono::Utc::now() - chrono::Duration::days(retention_days as i64);
+
+                let items = self.test_item_service.purge_trashed(cutoff).await?;
+                let users = self.user_service.purge_trashed(cutoff).await?;
+                tracing::info!(items, users, "🗑️ Trashed records purged");
+                Ok(())
+            }
+
+            JobPayload::BulkCreateTestItems { items } => {
+                let response = self.test_item_service.bulk_create(&job.tenant_id, items).await?;
+                Self::log_bulk(job, &response);
+                Ok(())
+            }
+
+            JobPayload::BulkUpdateTestItems { items } => {
+                let response = self.test_item_service.bulk_update(&job.tenant_id, items).await?;
+                Self::log_bulk(job, &response);
+                Ok(())
+            }
+
+            JobPayload::BulkDeleteTestItems { ids } => {
+                let response = self.test_item_service.bulk_delete(&job.tenant_id, ids).await?;
+                Self::log_bulk(job, &response);
+                Ok(())
+            }
+
+            JobPayload::RunImport { import_id } => {
+                self.data_transfer_service.run_import(&import_id).await
+            }
             
         }
     }
+
+    /// Summary of a queued bulk request, with the elements that failed
+    fn log_bulk(job: &crate::queue::Job, response: &BulkResponse) {
+        tracing::info!(
+            job_id = %job.id,
+            tenant_id = %job.tenant_id,
+            succeeded = response.succeeded,
+            failed = response.failed,
+            "📦 {} finished",
+            job.job_type
+        );
+
+        for result in response.results.iter().filter(|r| !r.is_success()) {
+            tracing::warn!(
+                job_id = %job.id,
+                index = result.index,
+                status = result.status,
+                id = result.id.as_deref().unwrap_or("-"),
+                "{}",
+                result.error.as_deref().unwrap_or_default()
+            );
+        }
+    }
 }
\ No newline at end of file
@@ -1,68 +1,146 @@
 use actix_web::web;
-use actix_files::Files; 
-use actix_governor::Governor;
-use crate::infrastructure::http::{AuthController, UserController, TestItemController, HealthController, QueueController,};
-use crate::middleware::rate_limit::api_rate_limiter;
+use actix_files::Files;
+use crate::infrastructure::http::{AuthController, UserController, TestItemController, HealthController, QueueController, TwoFactorController, PermissionController, AdminUserController, ApiKeyController, OAuthController, AuditController, DataTransferController, RouteController, DocsController, MaintenanceController};
+use crate::application::dtos::{ApiKeyResponse, AuditEntryResponse, AuthResponse, BulkResponse, BypassSecretResponse, CreatedApiKeyResponse, DataImportResponse, ImpersonationResponse, LoginResponse, MaintenanceResponse, MaintenanceWindowResponse, OAuthProviderResponse, PaginatedResponse, RecoveryCodesResponse, RolePermissionsResponse, TestItemResponse, TwoFactorSetupResponse, UserIdentityResponse, UserResponse};
+use crate::infrastructure::http::controllers::test_item_controller::BULK_BODY_LIMIT;
+use super::openapi::OpenApiDocument;
+use super::registry::{RouteInfo, RouteTable, Routes};
+use crate::config::RateLimitConfig;
 
-pub fn configure(cfg: &mut web::ServiceConfig) {
-    // 1. Configure the static files route (CSS, JS, Images)
-    // The first argument "/static" is the URL in the browser.
-    // The second argument "./static" is the physical folder on your disk.
-    cfg.service(Files::new("/static", "./static"));
+/// Static files route (CSS, JS, Images): the URL in the browser and the physical folder on disk
+const STATIC_PATH: &str = "/static";
+const STATIC_DIR: &str = "./static";
 
-    // 2. API routes configuration 
-    cfg.service(
-        web::scope("/api")
-            .service(
-                web::scope("/docs")
-                    .route("", web::get().to(|| async {
-                        actix_web::HttpResponse::Ok().json(serde_json::json!({
-                            "message": "API Documentation is not implemented yet. Please check back later.",
-                            "timestamp": chrono::Utc::now().to_rfc3339(),
-                            "hint": "In the future, this endpoint will provide interactive API documentation using Swagger or similar tools."
-                        }))
-                    }))
-            )
-            .service(
-                web::scope("/auth")
-                    .route("/register", web::post().to(AuthController::register))
-                    .route("/login", web::post().to(AuthController::login))
-                    .route("/verify-admin", web::get().to(AuthController::verify_admin))
-            )
-            .service(
-                web::scope("/user")
-                    .route("/profile", web::get().to(UserController::get_profile))
-                    .route("/all", web::get().to(UserController::get_all_users))
-                    .route("/{id}", web::get().to(UserController::get_user))
-            )
-            .service(
-                web::scope("/noauth")
-                    .route("/users", web::get().to(UserController::get_all_users_no_session))
-            )
-            .service(
-                web::scope("/test-items")
-                    .route("", web::post().to(TestItemController::create))
-                    .route("", web::get().to(TestItemController::get_all))
-                    .route("/{id}", web::get().to(TestItemController::get_by_id))
-                    .route("/{id}", web::put().to(TestItemController::update))
-                    .route("/{id}", web::delete().to(TestItemController::delete))
-                    .route("/queue/{id}", web::delete().to(TestItemController::schedule_delete)) 
-            )
-            .service(
-                web::scope("/administration")
-                    .route("/health", web::get().to(HealthController::health_check))
-                    .route("/uptime", web::get().to(HealthController::uptime))
-                    .route("/system", web::get().to(HealthController::system_dashboard)) 
-                    .service(
-                        web::resource("/system-json")
-                            .wrap(Governor::new(&api_rate_limiter(1, 4)))
-                            .route(web::get().to(HealthController::system_info_json))
-                    )
-                    .route("/jobs/info", web::get().to(QueueController::jobs_info))
-                    .route("/queue/stats", web::get().to(QueueController::get_stats))
-                    .route("/queue/{job_id}/retry", web::post().to(QueueController::retry_job))
-                    .route("/queue/{job_id}/cancel", web::delete().to(QueueController::cancel_job))
-            )
-        , // Web scope "api" ends here
-    );
-}
\ No newline at end of file
+pub fn configure(cfg: &mut web::ServiceConfig, rate_limits: &RateLimitConfig) {
+    let (api, table) = routes(rate_limits);
+
+    // 1. Configure the static files route
+    cfg.service(Files::new(STATIC_PATH, STATIC_DIR));
+
+    // 2. API routes configuration, listed at /api/administration/routes and documented at /api/docs
+    cfg.service(api);
+    cfg.app_data(web::Data::new(OpenApiDocument::build(&table)));
+    cfg.app_data(web::Data::new(table));
+}
+
+/// Every route of the application, without starting the server (`ironclad route:list`)
+pub fn table(rate_limits: &RateLimitConfig) -> RouteTable {
+    routes(rate_limits).1
+}
+
+fn routes(rate_limits: &RateLimitConfig) -> (actix_web::Scope, RouteTable) {
+    let mut api = Routes::new("/api");
+
+    api.scope("/docs", |docs| {
+        docs.get("", DocsController::ui)
+            .get("/openapi.json", DocsController::json)
+            .get("/openapi.yaml", DocsController::yaml);
+    });
+
+    api.get("/maintenance", MaintenanceController::window).returns::<MaintenanceWindowResponse>(200);
+
+    api.scope("/auth", |auth| {
+        auth.post("/register", AuthController::register).returns::<AuthResponse>(201)
+            .post("/login", AuthController::login).returns::<LoginResponse>(200)
+            .post("/login/two-factor", AuthController::login_two_factor).returns::<AuthResponse>(200)
+            .post("/change-password", AuthController::change_password).returns::<AuthResponse>(200)
+            .get("/verify-admin", AuthController::verify_admin)
+            .scope("/two-factor", |two_factor| {
+                two_factor.post("/setup", TwoFactorController::setup).returns::<TwoFactorSetupResponse>(200)
+                    .post("/confirm", TwoFactorController::confirm)
+                    .post("/disable", TwoFactorController::disable)
+                    .post("/recovery-codes", TwoFactorController::regenerate_recovery_codes).returns::<RecoveryCodesResponse>(200);
+            })
+            .scope("/oauth", |oauth| {
+                oauth.get("/providers", OAuthController::providers).returns::<Vec<OAuthProviderResponse>>(200)
+                    .get("/identities", OAuthController::identities).returns::<Vec<UserIdentityResponse>>(200)
+                    .get("/{provider}/authorize", OAuthController::authorize)
+                    .get("/{provider}/callback", OAuthController::callback);
+            })
+            .scope("/api-keys", |api_keys| {
+                api_keys.get("", ApiKeyController::list).returns::<Vec<ApiKeyResponse>>(200)
+                    .post("", ApiKeyController::create).returns::<CreatedApiKeyResponse>(201)
+                    .delete("/{id}", ApiKeyController::revoke);
+            });
+    });
+
+    api.scope("/user", |user| {
+        user.get("/profile", UserController::get_profile).returns::<UserResponse>(200)
+            .get("/all", UserController::get_all_users).returns::<PaginatedResponse<UserResponse>>(200)
+            .get("/{id}", UserController::get_user).returns::<UserResponse>(200)
+            .put("/{id}", UserController::update_profile).returns::<UserResponse>(200)
+            .delete("/{id}", UserController::delete_user);
+    });
+
+    api.scope("/noauth", |noauth| {
+        noauth.get("/users", UserController::get_all_users_no_session);
+    });
+
+    api.scope("/test-items", |items| {
+        items.post("", TestItemController::create).returns::<TestItemResponse>(201)
+            .get("", TestItemController::get_all).returns::<PaginatedResponse<TestItemResponse>>(200)
+            .resource("/bulk", |bulk| {
+                bulk.json_limit(BULK_BODY_LIMIT)
+                    .post(TestItemController::bulk_create).returns::<BulkResponse>(200)
+                    .patch(TestItemController::bulk_update).returns::<BulkResponse>(200)
+                    .delete(TestItemController::bulk_delete);
+            })
+            .get("/export", DataTransferController::export_test_items)
+            .post("/import", DataTransferController::import_test_items)
+            .get("/imports/{id}", DataTransferController::test_item_import).returns::<DataImportResponse>(200)
+            .get("/imports/{id}/errors", DataTransferController::test_item_import_errors)
+            .get("/{id}", TestItemController::get_by_id).returns::<TestItemResponse>(200)
+            .put("/{id}", TestItemController::update).returns::<TestItemResponse>(200)
+            .delete("/{id}", TestItemController::delete)
+            .post("/{id}/restore", TestItemController::restore).returns::<TestItemResponse>(200)
+            .delete("/queue/{id}", TestItemController::schedule_delete);
+    });
+
+    api.scope("/administration", |admin| {
+        admin.get("/health", HealthController::health_check)
+            .get("/uptime", HealthController::uptime)
+            .get("/system", HealthController::system_dashboard)
+            .resource("/system-json", |system| {
+                if let Some(limit) = rate_limits.get(rate_limits.system) {
+                    system.rate_limit(limit.seconds, limit.burst);
+                }
+                system.get(HealthController::system_info_json);
+            })
+            .get("/routes", RouteController::list)
+            .resource("/maintenance", |maintenance| {
+                maintenance.get(MaintenanceController::status).returns::<MaintenanceResponse>(200)
+                    .post(MaintenanceController::down).returns::<MaintenanceResponse>(200)
+                    .delete(MaintenanceController::up).returns::<MaintenanceResponse>(200);
+            })
+            .post("/maintenance/bypass", MaintenanceController::add_bypass).returns::<BypassSecretResponse>(201)
+            .delete("/maintenance/bypass/{name}", MaintenanceController::revoke_bypass)
+            .get("/jobs/info", QueueController::jobs_info)
+            .get("/queue/stats", QueueController::get_stats)
+            .post("/queue/{job_id}/retry", QueueController::retry_job)
+            .delete("/queue/{job_id}/cancel", QueueController::cancel_job)
+            .get("/roles", PermissionController::list_roles).returns::<Vec<RolePermissionsResponse>>(200)
+            .post("/roles/{role}/permissions", PermissionController::grant)
+            .delete("/roles/{role}/permissions/{permission}", PermissionController::revoke)
+            .get("/permissions", PermissionController::list_permissions)
+            .post("/permissions", PermissionController::create_permission)
+            .get("/audit-log", AuditController::search).returns::<PaginatedResponse<AuditEntryResponse>>(200)
+            .scope("/users", |users| {
+                users.get("", AdminUserController::search).returns::<PaginatedResponse<UserResponse>>(200)
+                    .get("/export", DataTransferController::export_users)
+                    .post("/import", DataTransferController::import_users)
+                    .get("/imports/{id}", DataTransferController::user_import).returns::<DataImportResponse>(200)
+                    .get("/imports/{id}/errors", DataTransferController::user_import_errors)
+                    .put("/{id}/role", AdminUserController::update_role).returns::<UserResponse>(200)
+                    .post("/{id}/activate", AdminUserController::activate)
+                    .post("/{id}/deactivate", AdminUserController::deactivate)
+                    .post("/{id}/restore", AdminUserController::restore).returns::<UserResponse>(200)
+                    .post("/{id}/force-password-reset", AdminUserController::force_password_reset)
+                    .post("/{id}/impersonate", AdminUserController::impersonate).returns::<ImpersonationResponse>(200);
+            });
+    });
+
+    let (scope, routes) = api.finish();
+    let mut table = vec![RouteInfo::files(STATIC_PATH, STATIC_DIR)];
+    table.extend(routes);
+    (scope, RouteTable(table))
+}
@@ -1,7 +1,18 @@
 /// Route configuration modules
 pub mod api;
+pub mod openapi;
+pub mod registry;
 use actix_web::web;
 
-pub fn configure(cfg: &mut web::ServiceConfig) {
-    api::configure(cfg);
-}
\ No newline at end of file
+use crate::config::RateLimitConfig;
+
+pub use registry::{RouteInfo, RouteTable};
+
+pub fn configure(cfg: &mut web::ServiceConfig, rate_limits: &RateLimitConfig) {
+    api::configure(cfg, rate_limits);
+}
+
+/// Every registered route
+pub fn table(rate_limits: &RateLimitConfig) -> RouteTable {
+    api::table(rate_limits)
+}
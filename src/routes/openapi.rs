@@ -0,0 +1,277 @@
+//! OpenAPI 3.1 document of the API, built from the route registry: paths, security
+//! and error responses come from each route's guards and middleware, schemas from
+//! the DTOs (`JsonSchema`, which also picks up their `validator` constraints).
+
+use schemars::generate::SchemaSettings;
+use schemars::SchemaGenerator;
+use serde_json::{json, Map, Value};
+
+use crate::errors::ErrorResponse;
+use super::registry::{RouteInfo, RouteTable};
+
+/// Routes under this prefix serve the document itself and are left out of it
+const DOCS_PATH: &str = "/api/docs";
+
+/// Error responses shared by the operations
+const ERROR_RESPONSES: &[(&str, &str, &str)] = &[
+    ("400", "BadRequest", "Invalid JSON, query string or validation error"),
+    ("401", "Unauthorized", "Missing, invalid or expired credentials"),
+    ("403", "Forbidden", "The credentials lack the role or permission required"),
+    ("404", "NotFound", "Resource not found"),
+    ("429", "TooManyRequests", "Rate limit exceeded, see the x-ratelimit-after header"),
+];
+
+/// Generated OpenAPI document
+#[derive(Debug, Clone)]
+pub struct OpenApiDocument(Value);
+
+impl OpenApiDocument {
+    pub fn build(table: &RouteTable) -> Self {
+        let mut generator = SchemaSettings::draft2020_12()
+            .with(|settings| settings.definitions_path = "/components/schemas".into())
+            .into_generator();
+        let error = generator.subschema_for::<ErrorResponse>().to_value();
+
+        let mut paths = Map::new();
+        for route in &table.0 {
+            if !route.path.starts_with("/api/") || route.path.starts_with(DOCS_PATH) {
+                continue;
+            }
+            let operation = operation(route, &mut generator);
+            if let Value::Object(item) = paths.entry(route.path.clone()).or_insert_with(|| json!({})) {
+                item.insert(route.method.to_lowercase(), operation);
+            }
+        }
+
+        let responses: Map<String, Value> = ERROR_RESPONSES
+            .iter()
+            .map(|(_, name, description)| {
+                let response = json!({
+                    "description": description,
+                    "content": { "application/json": { "schema": error } },
+                });
+                (name.to_string(), response)
+            })
+            .collect();
+
+        Self(json!({
+            "openapi": "3.1.0",
+            "info": {
+                "title": "Rust Ironclad API",
+                "version": env!("CARGO_PKG_VERSION"),
+                "description": "Generated from the registered routes and their DTOs.",
+            },
+            "paths": paths,
+            "components": {
+                "schemas": generator.take_definitions(true),
+                "responses": responses,
+                "securitySchemes": {
+                    "bearerAuth": {
+                        "type": "http",
+                        "scheme": "bearer",
+                        "bearerFormat": "JWT",
+                        "description": "Access token from /api/auth/login; API keys are accepted here too",
+                    },
+                    "apiKey": { "type": "apiKey", "in": "header", "name": "X-Api-Key" },
+                },
+            },
+        }))
+    }
+
+    pub fn value(&self) -> &Value {
+        &self.0
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.0).expect("OpenAPI document serializes to JSON")
+    }
+
+    pub fn to_yaml(&self) -> String {
+        serde_yaml::to_string(&self.0).expect("OpenAPI document serializes to YAML")
+    }
+}
+
+fn operation(route: &RouteInfo, generator: &mut SchemaGenerator) -> Value {
+    let (controller, method) = route.handler.split_once("::").unwrap_or(("", route.handler.as_str()));
+    let mut operation = Map::new();
+    operation.insert("operationId".into(), json!(format!("{}_{}", controller, method)));
+    operation.insert("summary".into(), json!(summary(method)));
+    if !controller.is_empty() {
+        operation.insert("tags".into(), json!([controller.trim_end_matches("Controller")]));
+    }
+    if !route.guards.is_empty() {
+        operation.insert("description".into(), json!(format!("Guards: {}", route.guards.join(", "))));
+    }
+
+    let parameters = parameters(route, generator);
+    if !parameters.is_empty() {
+        operation.insert("parameters".into(), Value::Array(parameters));
+    }
+
+    let schemas = &route.schemas;
+    if let Some(body) = schemas.body {
+        let schema = body(generator).to_value();
+        operation.insert(
+            "requestBody".into(),
+            json!({ "required": true, "content": { "application/json": { "schema": schema } } }),
+        );
+    } else if schemas.upload {
+        let file = json!({ "schema": { "type": "string" } });
+        operation.insert(
+            "requestBody".into(),
+            json!({ "required": true, "content": { "text/csv": file, "application/x-ndjson": file } }),
+        );
+    }
+
+    let mut responses = Map::new();
+    match schemas.response {
+        Some((status, schema)) => {
+            let schema = schema(generator).to_value();
+            responses.insert(
+                status.to_string(),
+                json!({ "description": "Successful response", "content": { "application/json": { "schema": schema } } }),
+            );
+        }
+        None => {
+            responses.insert("200".into(), json!({ "description": "Successful response" }));
+        }
+    }
+    for (status, name, _) in ERROR_RESPONSES {
+        if error_applies(route, status) {
+            responses.insert(status.to_string(), json!({ "$ref": format!("#/components/responses/{}", name) }));
+        }
+    }
+    operation.insert("responses".into(), Value::Object(responses));
+
+    if let Some(security) = security(&route.guards) {
+        operation.insert("security".into(), security);
+    }
+    Value::Object(operation)
+}
+
+/// Path parameters from the `{name}` segments, the query DTO's fields and the tenant header
+fn parameters(route: &RouteInfo, generator: &mut SchemaGenerator) -> Vec<Value> {
+    let mut parameters: Vec<Value> = path_parameters(&route.path)
+        .into_iter()
+        .map(|name| json!({ "name": name, "in": "path", "required": true, "schema": { "type": "string" } }))
+        .collect();
+
+    if let Some(query) = route.schemas.query {
+        let schema = query(generator).to_value();
+        let required: Vec<&str> = schema["required"]
+            .as_array()
+            .map(|names| names.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        // Free-form queries (filters, sort and pagination parsed by `QuerySpec`)
+        if schema.get("properties").is_none() && schema.get("additionalProperties").is_some() {
+            parameters.push(json!({
+                "name": "query",
+                "in": "query",
+                "style": "form",
+                "explode": true,
+                "description": "Filters, sort (`sort=-field`) and pagination (`page`, `per_page`, `cursor`)",
+                "schema": schema,
+            }));
+        }
+        if let Some(properties) = schema["properties"].as_object() {
+            for (name, property) in properties {
+                let mut parameter = json!({
+                    "name": name,
+                    "in": "query",
+                    "required": required.contains(&name.as_str()),
+                    "schema": property,
+                });
+                if let Some(description) = property.get("description") {
+                    parameter["description"] = description.clone();
+                }
+                parameters.push(parameter);
+            }
+        }
+    }
+
+    if route.guards.iter().any(|guard| guard == "tenant") {
+        parameters.push(json!({
+            "name": crate::infrastructure::http::tenant::TENANT_HEADER,
+            "in": "header",
+            "required": false,
+            "description": "Tenant slug, when neither a credential nor the subdomain names it",
+            "schema": { "type": "string" },
+        }));
+    }
+    parameters
+}
+
+fn path_parameters(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+        .map(|name| name.split(':').next().unwrap_or(name))
+        .collect()
+}
+
+fn error_applies(route: &RouteInfo, status: &str) -> bool {
+    let authenticated = route.guards.iter().any(|guard| guard != "tenant");
+    // role:, permission:, auth:session (no API keys) and auth:policy can all refuse a valid credential
+    let restricted = route.guards.iter().any(|guard| guard.contains(':'));
+    let schemas = &route.schemas;
+
+    match status {
+        "400" => schemas.body.is_some() || schemas.query.is_some() || schemas.upload,
+        "401" => authenticated,
+        "403" => restricted,
+        "404" => route.path.contains('{'),
+        "429" => route.middleware.iter().any(|middleware| middleware.starts_with("rate_limit")),
+        _ => false,
+    }
+}
+
+/// JWT-only routes (`auth:session`) refuse API keys
+fn security(guards: &[String]) -> Option<Value> {
+    if guards.iter().any(|guard| guard == "auth:session") {
+        return Some(json!([{ "bearerAuth": [] }]));
+    }
+    guards
+        .iter()
+        .any(|guard| guard != "tenant")
+        .then(|| json!([{ "bearerAuth": [] }, { "apiKey": [] }]))
+}
+
+/// `get_all_users` -> `Get all users`
+fn summary(method: &str) -> String {
+    let words = method.replace('_', " ");
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => words,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_documents_routes_with_schemas_security_and_errors() {
+        let document = OpenApiDocument::build(&crate::routes::table(&Default::default()));
+        let doc = document.value();
+
+        let register = &doc["paths"]["/api/auth/register"]["post"];
+        assert_eq!(register["requestBody"]["content"]["application/json"]["schema"]["$ref"], "#/components/schemas/RegisterUserRequest");
+        assert!(register.get("security").is_none());
+
+        // validator constraints end up in the schema
+        let username = &doc["components"]["schemas"]["RegisterUserRequest"]["properties"]["username"];
+        assert_eq!(username["minLength"], 3);
+        assert_eq!(username["maxLength"], 50);
+
+        let item = &doc["paths"]["/api/test-items/{id}"]["get"];
+        assert_eq!(item["parameters"][0]["name"], "id");
+        assert!(item["responses"]["404"].is_object());
+
+        let audit = &doc["paths"]["/api/administration/audit-log"]["get"];
+        assert_eq!(audit["security"][1]["apiKey"], json!([]));
+        assert!(audit["responses"]["403"].is_object());
+        assert!(doc["components"]["schemas"]["ErrorResponse"].is_object());
+        assert!(doc["paths"].get("/api/docs").is_none());
+    }
+}
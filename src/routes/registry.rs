@@ -0,0 +1,472 @@
+//! Route declarations that are recorded as they are registered, so the running
+//! application and the CLI (`ironclad route:list`) can list every route with its
+//! handler, guards and middleware, and `openapi` can document it.
+
+use actix_governor::Governor;
+use actix_web::{http::Method, web, FromRequest, Handler, HttpRequest, Resource, Responder, Scope};
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+use serde::Serialize;
+use std::mem;
+
+use crate::application::policies::Actor;
+use crate::domain::entities::RequestOrigin;
+use crate::infrastructure::http::authentication::{
+    AdminUser, AuthUser, ModeratorUser, PremiumUser, RoleUser, SessionUser,
+};
+use crate::infrastructure::http::authorization::{Operator, PermissionName, Require};
+use crate::infrastructure::http::etag::IfMatch;
+use crate::infrastructure::http::tenant::CurrentTenant;
+use crate::middleware::rate_limit::api_rate_limiter;
+use crate::shared::extractors::ValidatedJson;
+
+/// A registered route
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteInfo {
+    pub method: String,
+    pub path: String,
+    /// `Controller::method` of the handler
+    pub handler: String,
+    /// Checks made by the handler's extractors (`auth`, `role:admin`, `permission:users.view`...)
+    pub guards: Vec<String>,
+    /// Middleware wrapping this route; app-wide middleware (maintenance mode, CORS, logger) is not listed
+    pub middleware: Vec<String>,
+    #[serde(skip)]
+    pub schemas: RouteSchemas,
+}
+
+/// Schema of a DTO, registered in the generator's components
+pub type SchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+/// Request and response bodies of a route, for the OpenAPI document
+#[derive(Debug, Clone, Default)]
+pub struct RouteSchemas {
+    /// JSON request body
+    pub body: Option<SchemaFn>,
+    /// Query string, one parameter per property
+    pub query: Option<SchemaFn>,
+    /// Raw request body (file uploads)
+    pub upload: bool,
+    /// Success status and JSON body, see `Routes::returns`
+    pub response: Option<(u16, SchemaFn)>,
+}
+
+fn subschema<T: JsonSchema>(generator: &mut SchemaGenerator) -> Schema {
+    generator.subschema_for::<T>()
+}
+
+impl RouteInfo {
+    /// Static files served from `dir` under `path`
+    pub fn files(path: &str, dir: &str) -> Self {
+        Self {
+            method: Method::GET.to_string(),
+            path: format!("{}/{{file}}", path),
+            handler: format!("static files ({})", dir),
+            guards: Vec::new(),
+            middleware: Vec::new(),
+            schemas: RouteSchemas::default(),
+        }
+    }
+}
+
+/// Every route of the application in registration order, shared as app data
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(transparent)]
+pub struct RouteTable(pub Vec<RouteInfo>);
+
+impl RouteTable {
+    /// Routes with the given method (case-insensitive) whose path contains `path`
+    pub fn filter(&self, method: Option<&str>, path: Option<&str>) -> Vec<&RouteInfo> {
+        self.0
+            .iter()
+            .filter(|route| method.is_none_or(|method| route.method.eq_ignore_ascii_case(method)))
+            .filter(|route| path.is_none_or(|path| route.path.contains(path)))
+            .collect()
+    }
+}
+
+/// Builds an actix `Scope` while recording its routes
+pub struct Routes {
+    prefix: String,
+    scope: Scope,
+    routes: Vec<RouteInfo>,
+}
+
+impl Routes {
+    pub fn new(path: &str) -> Self {
+        Self::nested(path.to_string(), path)
+    }
+
+    fn nested(prefix: String, path: &str) -> Self {
+        Self { prefix, scope: web::scope(path), routes: Vec::new() }
+    }
+
+    pub fn get<F, Args>(&mut self, path: &str, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + Extractors + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.route(Method::GET, path, handler)
+    }
+
+    pub fn post<F, Args>(&mut self, path: &str, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + Extractors + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.route(Method::POST, path, handler)
+    }
+
+    pub fn put<F, Args>(&mut self, path: &str, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + Extractors + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.route(Method::PUT, path, handler)
+    }
+
+    pub fn patch<F, Args>(&mut self, path: &str, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + Extractors + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.route(Method::PATCH, path, handler)
+    }
+
+    pub fn delete<F, Args>(&mut self, path: &str, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + Extractors + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.route(Method::DELETE, path, handler)
+    }
+
+    fn route<F, Args>(&mut self, method: Method, path: &str, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + Extractors + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.routes.push(RouteInfo {
+            method: method.to_string(),
+            path: format!("{}{}", self.prefix, path),
+            handler: handler_name::<F>(),
+            guards: Args::guards(),
+            middleware: Vec::new(),
+            schemas: Args::schemas(),
+        });
+        self.map(|scope| scope.route(path, web::method(method).to(handler)))
+    }
+
+    /// Success response of the route declared just before
+    pub fn returns<T: JsonSchema>(&mut self, status: u16) -> &mut Self {
+        returns::<T>(&mut self.routes, status);
+        self
+    }
+
+    /// Nested scope under `path`
+    pub fn scope(&mut self, path: &str, build: impl FnOnce(&mut Routes)) -> &mut Self {
+        let mut child = Routes::nested(format!("{}{}", self.prefix, path), path);
+        build(&mut child);
+
+        self.routes.append(&mut child.routes);
+        self.map(|scope| scope.service(child.scope))
+    }
+
+    /// Several methods on one path sharing the same settings
+    pub fn resource(&mut self, path: &str, build: impl FnOnce(&mut ResourceRoutes)) -> &mut Self {
+        let mut resource = ResourceRoutes {
+            path: format!("{}{}", self.prefix, path),
+            resource: web::resource(path),
+            routes: Vec::new(),
+            rate_limit: None,
+        };
+        build(&mut resource);
+
+        let ResourceRoutes { resource, mut routes, rate_limit, .. } = resource;
+        match rate_limit {
+            Some((seconds, burst)) => {
+                for route in &mut routes {
+                    route.middleware.push(format!("rate_limit({}s, burst {})", seconds, burst));
+                }
+                self.routes.append(&mut routes);
+                self.map(|scope| scope.service(resource.wrap(Governor::new(&api_rate_limiter(seconds, burst)))))
+            }
+            None => {
+                self.routes.append(&mut routes);
+                self.map(|scope| scope.service(resource))
+            }
+        }
+    }
+
+    /// The scope to register and the routes it holds
+    pub fn finish(self) -> (Scope, Vec<RouteInfo>) {
+        (self.scope, self.routes)
+    }
+
+    fn map(&mut self, f: impl FnOnce(Scope) -> Scope) -> &mut Self {
+        let scope = mem::replace(&mut self.scope, web::scope(""));
+        self.scope = f(scope);
+        self
+    }
+}
+
+/// Routes of a single resource, see `Routes::resource`
+pub struct ResourceRoutes {
+    path: String,
+    resource: Resource,
+    routes: Vec<RouteInfo>,
+    rate_limit: Option<(u64, u32)>,
+}
+
+impl ResourceRoutes {
+    pub fn get<F, Args>(&mut self, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + Extractors + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.route(Method::GET, handler)
+    }
+
+    pub fn post<F, Args>(&mut self, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + Extractors + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.route(Method::POST, handler)
+    }
+
+    pub fn patch<F, Args>(&mut self, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + Extractors + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.route(Method::PATCH, handler)
+    }
+
+    pub fn delete<F, Args>(&mut self, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + Extractors + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.route(Method::DELETE, handler)
+    }
+
+    /// Maximum JSON body size, in bytes
+    pub fn json_limit(&mut self, bytes: usize) -> &mut Self {
+        self.map(|resource| resource.app_data(web::JsonConfig::default().limit(bytes)))
+    }
+
+    /// Success response of the method declared just before
+    pub fn returns<T: JsonSchema>(&mut self, status: u16) -> &mut Self {
+        returns::<T>(&mut self.routes, status);
+        self
+    }
+
+    /// Per-IP rate limit, see `api_rate_limiter`
+    pub fn rate_limit(&mut self, seconds: u64, burst: u32) -> &mut Self {
+        self.rate_limit = Some((seconds, burst));
+        self
+    }
+
+    fn route<F, Args>(&mut self, method: Method, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + Extractors + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.routes.push(RouteInfo {
+            method: method.to_string(),
+            path: self.path.clone(),
+            handler: handler_name::<F>(),
+            guards: Args::guards(),
+            middleware: Vec::new(),
+            schemas: Args::schemas(),
+        });
+        self.map(|resource| resource.route(web::method(method).to(handler)))
+    }
+
+    fn map(&mut self, f: impl FnOnce(Resource) -> Resource) -> &mut Self {
+        let resource = mem::replace(&mut self.resource, web::resource(""));
+        self.resource = f(resource);
+        self
+    }
+}
+
+fn returns<T: JsonSchema>(routes: &mut [RouteInfo], status: u16) {
+    let route = routes.last_mut().expect("returns() follows a route");
+    route.schemas.response = Some((status, subschema::<T>));
+}
+
+/// `Controller::method` from the handler's type name
+fn handler_name<F>() -> String {
+    let name = std::any::type_name::<F>();
+    if name.contains("{{closure}}") {
+        return "closure".to_string();
+    }
+    let mut segments = name.rsplit("::");
+    match (segments.next(), segments.next()) {
+        (Some(method), Some(owner)) => format!("{}::{}", owner, method),
+        _ => name.to_string(),
+    }
+}
+
+// ============================================
+// EXTRACTORS: What each handler argument checks and reads
+// ============================================
+/// Implemented by every extractor a handler can take. Guard extractors name the
+/// check they make and body/query extractors their DTO; a handler argument without
+/// an impl does not compile, so neither the route list nor the API docs can miss one.
+pub trait RouteExtractor {
+    fn guard() -> Option<String> {
+        None
+    }
+
+    fn describe(_schemas: &mut RouteSchemas) {}
+}
+
+/// Guards and schemas of a handler's arguments
+pub trait Extractors {
+    /// In order and without duplicates
+    fn guards() -> Vec<String>;
+    fn schemas() -> RouteSchemas;
+}
+
+macro_rules! tuple_extractors {
+    ($($arg:ident),*) => {
+        impl<$($arg: RouteExtractor),*> Extractors for ($($arg,)*) {
+            fn guards() -> Vec<String> {
+                let labels: Vec<Option<String>> = vec![$($arg::guard()),*];
+                let mut guards = Vec::new();
+                for label in labels.into_iter().flatten() {
+                    if !guards.contains(&label) {
+                        guards.push(label);
+                    }
+                }
+                guards
+            }
+
+            #[allow(unused_mut)]
+            fn schemas() -> RouteSchemas {
+                let mut schemas = RouteSchemas::default();
+                $($arg::describe(&mut schemas);)*
+                schemas
+            }
+        }
+    };
+}
+
+tuple_extractors!();
+tuple_extractors!(A);
+tuple_extractors!(A, B);
+tuple_extractors!(A, B, C);
+tuple_extractors!(A, B, C, D);
+tuple_extractors!(A, B, C, D, E);
+tuple_extractors!(A, B, C, D, E, F);
+tuple_extractors!(A, B, C, D, E, F, G);
+tuple_extractors!(A, B, C, D, E, F, G, H);
+tuple_extractors!(A, B, C, D, E, F, G, H, I);
+tuple_extractors!(A, B, C, D, E, F, G, H, I, J);
+
+macro_rules! guard {
+    ($extractor:ty => $label:expr) => {
+        impl RouteExtractor for $extractor {
+            fn guard() -> Option<String> {
+                Some($label.to_string())
+            }
+        }
+    };
+}
+
+guard!(AuthUser => "auth");
+guard!(SessionUser => "auth:session");
+guard!(RoleUser => "auth");
+guard!(AdminUser => "role:admin");
+guard!(ModeratorUser => "role:moderator");
+guard!(PremiumUser => "role:premium");
+guard!(Actor => "auth:policy");
+guard!(CurrentTenant => "tenant");
+
+impl<P: PermissionName> RouteExtractor for Require<P> {
+    fn guard() -> Option<String> {
+        Some(format!("permission:{}", P::NAME))
+    }
+}
+
+impl<P: PermissionName> RouteExtractor for Operator<P> {
+    fn guard() -> Option<String> {
+        Some(format!("operator:{}", P::NAME))
+    }
+}
+
+impl<T: JsonSchema> RouteExtractor for ValidatedJson<T> {
+    fn describe(schemas: &mut RouteSchemas) {
+        schemas.body = Some(subschema::<T>);
+    }
+}
+
+impl<T: JsonSchema> RouteExtractor for web::Json<T> {
+    fn describe(schemas: &mut RouteSchemas) {
+        schemas.body = Some(subschema::<T>);
+    }
+}
+
+impl<T: JsonSchema> RouteExtractor for web::Query<T> {
+    fn describe(schemas: &mut RouteSchemas) {
+        schemas.query = Some(T::json_schema);
+    }
+}
+
+impl RouteExtractor for web::Payload {
+    fn describe(schemas: &mut RouteSchemas) {
+        schemas.upload = true;
+    }
+}
+
+// Plain extractors; path parameters are documented from the route path
+impl RouteExtractor for HttpRequest {}
+impl RouteExtractor for RequestOrigin {}
+impl RouteExtractor for IfMatch {}
+impl<T: ?Sized> RouteExtractor for web::Data<T> {}
+impl<T> RouteExtractor for web::Path<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::http::authorization::permissions::ViewUsers;
+
+    async fn handler(_: web::Data<String>, _: AuthUser, _: Require<ViewUsers>, _: AuthUser) -> &'static str {
+        "ok"
+    }
+
+    #[test]
+    fn test_records_full_paths_handlers_and_guards() {
+        let mut routes = Routes::new("/api");
+        routes.scope("/items", |items| {
+            items.get("", handler).resource("/bulk", |bulk| {
+                bulk.rate_limit(1, 4).post(handler);
+            });
+        });
+        let (_, routes) = routes.finish();
+
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].method, "GET");
+        assert_eq!(routes[0].path, "/api/items");
+        assert_eq!(routes[0].handler, "tests::handler");
+        assert_eq!(routes[0].guards, vec!["auth", "permission:users.view"]);
+        assert_eq!(routes[1].path, "/api/items/bulk");
+        assert_eq!(routes[1].middleware, vec!["rate_limit(1s, burst 4)"]);
+
+        let table = RouteTable(routes);
+        assert_eq!(table.filter(Some("post"), None).len(), 1);
+        assert_eq!(table.filter(None, Some("/bulk")).len(), 1);
+    }
+}
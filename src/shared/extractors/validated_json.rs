@@ -4,6 +4,7 @@ use serde::de::DeserializeOwned;
 use validator::Validate;
 
 use crate::errors::ApiError;
+use crate::shared::validator::validation_message;
 
 /// Extractor that automatically validates DTOs
 pub struct ValidatedJson<T>(pub T);
@@ -24,23 +25,7 @@ where
                     // Validar el objeto
                     match json.0.validate() {
                         Ok(_) => Ok(ValidatedJson(json.0)),
-                        Err(errors) => {
-                            // Format validation errors
-                            let error_messages = errors
-                                .field_errors()
-                                .iter()
-                                .map(|(field, errors)| {
-                                    let messages: Vec<String> = errors
-                                        .iter()
-                                        .filter_map(|e| e.message.as_ref().map(|m| m.to_string()))
-                                        .collect();
-                                    format!("{}: {}", field, messages.join(", "))
-                                })
-                                .collect::<Vec<_>>()
-                                .join("; ");
-
-                            Err(ApiError::ValidationError(error_messages))
-                        }
+                        Err(errors) => Err(ApiError::ValidationError(validation_message(&errors))),
                     }
                 }
                 Err(e) => Err(ApiError::ValidationError(format!("Invalid JSON: {}", e))),
@@ -2,4 +2,4 @@ pub mod validator;
 pub mod extractors;
 
 pub use extractors::ValidatedJson;
-pub use validator::{validate_strong_password, validate_username};
\ No newline at end of file
+pub use validator::{validate_strong_password, validate_username, validation_message};
\ No newline at end of file
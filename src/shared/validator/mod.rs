@@ -1,4 +1,20 @@
-use validator::ValidationError;
+use validator::{ValidationError, ValidationErrors};
+
+/// Flatten validation errors into `field: message, message; field: message`
+pub fn validation_message(errors: &ValidationErrors) -> String {
+    errors
+        .field_errors()
+        .iter()
+        .map(|(field, errors)| {
+            let messages: Vec<String> = errors
+                .iter()
+                .filter_map(|e| e.message.as_ref().map(|m| m.to_string()))
+                .collect();
+            format!("{}: {}", field, messages.join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
 
 /// Validador personalizado para passwords fuertes
 pub fn validate_strong_password(password: &str) -> Result<(), ValidationError> {
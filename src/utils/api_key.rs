@@ -0,0 +1,83 @@
+//! API key generation and hashing.
+//!
+//! Keys look like `ick_<prefix>_<secret>`. The prefix is stored in clear text so
+//! keys can be identified in listings and looked up; only the SHA-256 of the
+//! whole key is persisted.
+
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+/// Marker that distinguishes API keys from JWTs in the `Authorization` header
+pub const API_KEY_MARKER: &str = "ick_";
+
+const PREFIX_LENGTH: usize = 8;
+const SECRET_LENGTH: usize = 32;
+const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// A freshly generated key. `key` is shown to the user once and never stored.
+pub struct GeneratedApiKey {
+    pub prefix: String,
+    pub key: String,
+}
+
+/// Generate a new random key
+pub fn generate() -> GeneratedApiKey {
+    let prefix = random_string(PREFIX_LENGTH);
+    let key = format!("{}{}_{}", API_KEY_MARKER, prefix, random_string(SECRET_LENGTH));
+    GeneratedApiKey { prefix, key }
+}
+
+/// Whether a bearer credential is an API key rather than a JWT
+pub fn is_api_key(value: &str) -> bool {
+    value.starts_with(API_KEY_MARKER)
+}
+
+/// Extract the lookup prefix from a full key
+pub fn parse_prefix(key: &str) -> Option<&str> {
+    let (prefix, secret) = key.strip_prefix(API_KEY_MARKER)?.split_once('_')?;
+    (prefix.len() == PREFIX_LENGTH && secret.len() == SECRET_LENGTH).then_some(prefix)
+}
+
+/// Hash a key for storage (keys are random, so a fast digest is enough)
+pub fn hash(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// Constant-time comparison of a presented key against the stored hash
+pub fn verify(key: &str, key_hash: &str) -> bool {
+    let computed = hash(key);
+    computed.len() == key_hash.len()
+        && computed
+            .bytes()
+            .zip(key_hash.bytes())
+            .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+fn random_string(length: usize) -> String {
+    let mut rng = rand::thread_rng();
+    (0..length)
+        .map(|_| ALPHABET[rng.gen_range(0..ALPHABET.len())] as char)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_key_round_trip() {
+        let generated = generate();
+
+        assert!(is_api_key(&generated.key));
+        assert_eq!(parse_prefix(&generated.key), Some(generated.prefix.as_str()));
+        assert!(verify(&generated.key, &hash(&generated.key)));
+        assert!(!verify(&generate().key, &hash(&generated.key)));
+    }
+
+    #[test]
+    fn test_parse_prefix_rejects_malformed_keys() {
+        assert_eq!(parse_prefix("ick_short_secret"), None);
+        assert_eq!(parse_prefix("eyJhbGciOiJIUzI1NiJ9.e30.sig"), None);
+    }
+}
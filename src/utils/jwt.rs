@@ -1,25 +1,52 @@
 use chrono::Utc;
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use crate::errors::ApiError;
-use crate::domain::entities::user::Claims;
+use crate::domain::entities::user::{Claims, TokenType, User};
 use crate::config::AppConfig;
 
-pub fn create_token(user_id: &str, email: &str, role: &str, config: &AppConfig) -> Result<String, ApiError> {
-    let now = Utc::now();
-    let iat = now.timestamp();
-    let exp = (now.timestamp()) + config.jwt.expiration;
+pub fn create_token(user: &User, config: &AppConfig) -> Result<String, ApiError> {
+    sign(&base_claims(user, config.jwt.expiration), config)
+}
+
+/// Access token for a session that completed the TOTP step
+pub fn create_mfa_verified_token(user: &User, config: &AppConfig) -> Result<String, ApiError> {
+    let mut claims = base_claims(user, config.jwt.expiration);
+    claims.mfa = true;
+    sign(&claims, config)
+}
+
+/// Short-lived token proving the password step succeeded; only valid for `/auth/login/two-factor`
+pub fn create_mfa_pending_token(user: &User, config: &AppConfig) -> Result<String, ApiError> {
+    let mut claims = base_claims(user, config.two_factor.pending_token_ttl);
+    claims.token_type = TokenType::MfaPending;
+    sign(&claims, config)
+}
+
+/// Short-lived access token for `user` issued to the admin `impersonator_id`
+pub fn create_impersonation_token(user: &User, impersonator_id: &str, config: &AppConfig) -> Result<String, ApiError> {
+    let mut claims = base_claims(user, config.authorization.impersonation_ttl);
+    claims.impersonator = Some(impersonator_id.to_string());
+    sign(&claims, config)
+}
 
-    let claims = Claims {
-        sub: user_id.to_string(),
-        email: email.to_string(),
-        role: role.to_string(),
-        exp,
-        iat,
-    };
+fn base_claims(user: &User, ttl_seconds: i64) -> Claims {
+    let now = Utc::now().timestamp();
+    let mut claims = Claims::new(
+        user.id.clone(),
+        user.email.as_str().to_string(),
+        user.role.to_string(),
+        user.tenant_id.clone(),
+        now + ttl_seconds,
+    );
+    claims.iat = now;
+    claims.ver = user.token_version;
+    claims
+}
 
+fn sign(claims: &Claims, config: &AppConfig) -> Result<String, ApiError> {
     encode(
         &Header::default(),
-        &claims,
+        claims,
         &EncodingKey::from_secret(config.jwt.secret.as_bytes()),
     )
     .map_err(|e| ApiError::JwtError(e.to_string()))
@@ -0,0 +1,111 @@
+//! Maintenance bypass secrets and the signed cookies they grant.
+//!
+//! Only the SHA-256 of a secret is stored. Visiting `/{secret}` sets a cookie
+//! `<name>.<expires>.<signature>`, signed with HMAC-SHA256 over the name, the
+//! expiry and the secret hash: revoking or replacing the secret voids its cookies.
+
+use data_encoding::BASE64URL_NOPAD;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use super::oauth::random_token;
+
+pub const COOKIE_NAME: &str = "maintenance_bypass";
+
+/// Label mixing the JWT secret into a key used for nothing else
+const KEY_CONTEXT: &[u8] = b"ironclad maintenance bypass";
+
+/// Random URL-safe secret (32 characters)
+pub fn generate_secret() -> String {
+    random_token(24)
+}
+
+/// 8 to 128 unreserved URL characters, so `/{secret}` is a single path segment
+pub fn is_valid_secret(secret: &str) -> bool {
+    (8..=128).contains(&secret.len())
+        && secret.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '~'))
+}
+
+/// Hash a secret for storage (secrets are random or admin-chosen, a fast digest is enough)
+pub fn hash(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+/// Constant-time comparison of a presented secret against a stored hash
+pub fn verify_secret(secret: &str, secret_hash: &str) -> bool {
+    let computed = hash(secret);
+    computed.len() == secret_hash.len()
+        && computed
+            .bytes()
+            .zip(secret_hash.bytes())
+            .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+/// Cookie signing key derived from the JWT secret, shared by every instance
+pub fn signing_key(jwt_secret: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(jwt_secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(KEY_CONTEXT);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Cookie value for the bypass `name`, valid until `expires` (Unix seconds)
+pub fn sign_cookie(key: &[u8], name: &str, secret_hash: &str, expires: i64) -> String {
+    let signature = mac(key, name, secret_hash, expires).finalize().into_bytes();
+    format!("{}.{}.{}", name, expires, BASE64URL_NOPAD.encode(&signature))
+}
+
+/// Bypass name and expiry of a cookie value, before its signature is checked
+pub fn parse_cookie(value: &str) -> Option<(&str, i64)> {
+    let mut parts = value.rsplitn(3, '.');
+    let _signature = parts.next()?;
+    let expires = parts.next()?.parse().ok()?;
+    Some((parts.next()?, expires))
+}
+
+/// Whether a cookie value was signed for `secret_hash` and has not expired
+pub fn verify_cookie(key: &[u8], value: &str, secret_hash: &str, now: i64) -> bool {
+    let Some((name, expires)) = parse_cookie(value) else {
+        return false;
+    };
+    let Some(signature) = value.rsplit('.').next().and_then(|s| BASE64URL_NOPAD.decode(s.as_bytes()).ok()) else {
+        return false;
+    };
+    now < expires && mac(key, name, secret_hash, expires).verify_slice(&signature).is_ok()
+}
+
+fn mac(key: &[u8], name: &str, secret_hash: &str, expires: i64) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(format!("{}.{}.{}", name, expires, secret_hash).as_bytes());
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_cookie_round_trip_expiry_and_revocation() {
+        let key = signing_key("jwt-secret");
+        let secret = generate_secret();
+        let secret_hash = hash(&secret);
+        assert_eq!(secret.len(), 32);
+        assert!(is_valid_secret(&secret));
+        assert!(!is_valid_secret("short"));
+        assert!(!is_valid_secret("with/slash"));
+        assert!(verify_secret(&secret, &secret_hash));
+        assert!(!verify_secret("guess", &secret_hash));
+
+        let cookie = sign_cookie(&key, "office", &secret_hash, 2_000);
+        assert_eq!(parse_cookie(&cookie), Some(("office", 2_000)));
+        assert!(verify_cookie(&key, &cookie, &secret_hash, 1_000));
+
+        // expired, secret replaced, other key, tampered name or expiry
+        assert!(!verify_cookie(&key, &cookie, &secret_hash, 2_000));
+        assert!(!verify_cookie(&key, &cookie, &hash("rotated"), 1_000));
+        assert!(!verify_cookie(&signing_key("other"), &cookie, &secret_hash, 1_000));
+        assert!(!verify_cookie(&key, &cookie.replacen("office", "vendor", 1), &secret_hash, 1_000));
+        assert!(!verify_cookie(&key, &cookie.replacen("2000", "9000", 1), &secret_hash, 1_000));
+        assert!(!verify_cookie(&key, "office", &secret_hash, 1_000));
+    }
+}
@@ -1,2 +1,7 @@
 pub mod auth;
+pub mod password;
 pub mod jwt;
+pub mod totp;
+pub mod api_key;
+pub mod oauth;
+pub mod maintenance_bypass;
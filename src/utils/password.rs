@@ -0,0 +1,234 @@
+//! Password hashing with Argon2id and bcrypt.
+//!
+//! The algorithm of a stored hash is detected from its PHC / modular crypt
+//! prefix (`$argon2id$…`, `$2b$…`), so both kinds verify side by side while
+//! new hashes use the configured algorithm. Hashing is CPU-bound by design and
+//! runs on tokio's blocking pool to keep the async workers free.
+
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use crate::config::{AppConfig, PasswordAlgorithm};
+use crate::errors::ApiError;
+
+/// Outcome of checking a password against a stored hash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordVerification {
+    Mismatch,
+    /// `needs_rehash` is set when the hash was made with another algorithm or older parameters
+    Match { needs_rehash: bool },
+}
+
+impl PasswordVerification {
+    pub fn is_match(&self) -> bool {
+        matches!(self, PasswordVerification::Match { .. })
+    }
+}
+
+/// Hashes new passwords with the configured algorithm and verifies any supported one
+#[derive(Debug, Clone)]
+pub struct PasswordHasher {
+    algorithm: PasswordAlgorithm,
+    bcrypt_cost: u32,
+    argon2_memory_kib: u32,
+    argon2_iterations: u32,
+    argon2_parallelism: u32,
+}
+
+impl PasswordHasher {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            algorithm: config.password.algorithm,
+            bcrypt_cost: config.bcrypt.cost,
+            argon2_memory_kib: config.password.argon2_memory_kib,
+            argon2_iterations: config.password.argon2_iterations,
+            argon2_parallelism: config.password.argon2_parallelism,
+        }
+    }
+
+    /// Hash a password on the blocking pool
+    pub async fn hash(&self, password: &str) -> Result<String, ApiError> {
+        let hasher = self.clone();
+        let password = password.to_string();
+        run_blocking(move || hasher.hash_blocking(&password)).await
+    }
+
+    /// Verify a password on the blocking pool
+    pub async fn verify(&self, password: &str, hash: &str) -> Result<PasswordVerification, ApiError> {
+        let hasher = self.clone();
+        let password = password.to_string();
+        let hash = hash.to_string();
+        run_blocking(move || hasher.verify_blocking(&password, &hash)).await
+    }
+
+    /// Hash a password on the current thread
+    pub fn hash_blocking(&self, password: &str) -> Result<String, ApiError> {
+        match self.algorithm {
+            PasswordAlgorithm::Argon2id => {
+                let salt = SaltString::generate(&mut OsRng);
+                self.argon2()?
+                    .hash_password(password.as_bytes(), &salt)
+                    .map(|hash| hash.to_string())
+                    .map_err(|e| ApiError::InternalServerError(format!("Error hashing password: {}", e)))
+            }
+            PasswordAlgorithm::Bcrypt => bcrypt::hash(password, self.bcrypt_cost)
+                .map_err(|e| ApiError::InternalServerError(format!("Error hashing password: {}", e))),
+        }
+    }
+
+    /// Verify a password on the current thread
+    pub fn verify_blocking(&self, password: &str, hash: &str) -> Result<PasswordVerification, ApiError> {
+        let matches = match HashKind::detect(hash) {
+            Some(HashKind::Argon2) => {
+                let parsed = PasswordHash::new(hash)
+                    .map_err(|e| ApiError::InternalServerError(format!("Invalid password hash: {}", e)))?;
+                // Parameters are read from the hash itself, not from our settings
+                Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok()
+            }
+            Some(HashKind::Bcrypt) => bcrypt::verify(password, hash)
+                .map_err(|e| ApiError::InternalServerError(format!("Error verifying password: {}", e)))?,
+            None => {
+                return Err(ApiError::InternalServerError(
+                    "Unsupported password hash format".to_string(),
+                ))
+            }
+        };
+
+        Ok(if matches {
+            PasswordVerification::Match {
+                needs_rehash: self.needs_rehash(hash),
+            }
+        } else {
+            PasswordVerification::Mismatch
+        })
+    }
+
+    /// Whether a stored hash differs from what `hash` would produce today
+    pub fn needs_rehash(&self, hash: &str) -> bool {
+        match (self.algorithm, HashKind::detect(hash)) {
+            (PasswordAlgorithm::Argon2id, Some(HashKind::Argon2)) => {
+                let Ok(parsed) = PasswordHash::new(hash) else {
+                    return true;
+                };
+                let current = parsed.algorithm == Algorithm::Argon2id.ident()
+                    && parsed.version == Some(Version::V0x13.into());
+
+                !current
+                    || Params::try_from(&parsed).map_or(true, |params| {
+                        params.m_cost() != self.argon2_memory_kib
+                            || params.t_cost() != self.argon2_iterations
+                            || params.p_cost() != self.argon2_parallelism
+                    })
+            }
+            (PasswordAlgorithm::Bcrypt, Some(HashKind::Bcrypt)) => {
+                bcrypt_cost(hash) != Some(self.bcrypt_cost)
+            }
+            _ => true,
+        }
+    }
+
+    fn argon2(&self) -> Result<Argon2<'static>, ApiError> {
+        let params = Params::new(
+            self.argon2_memory_kib,
+            self.argon2_iterations,
+            self.argon2_parallelism,
+            None,
+        )
+        .map_err(|e| ApiError::InternalServerError(format!("Invalid Argon2 parameters: {}", e)))?;
+
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+}
+
+/// Hash families we can verify
+enum HashKind {
+    Argon2,
+    Bcrypt,
+}
+
+impl HashKind {
+    fn detect(hash: &str) -> Option<Self> {
+        if hash.starts_with("$argon2") {
+            Some(HashKind::Argon2)
+        } else if ["$2a$", "$2b$", "$2x$", "$2y$"].iter().any(|p| hash.starts_with(p)) {
+            Some(HashKind::Bcrypt)
+        } else {
+            None
+        }
+    }
+}
+
+/// Cost of a `$2b$<cost>$…` hash
+fn bcrypt_cost(hash: &str) -> Option<u32> {
+    hash.split('$').nth(2)?.parse().ok()
+}
+
+async fn run_blocking<T, F>(f: F) -> Result<T, ApiError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, ApiError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| ApiError::InternalServerError(format!("Password hashing task failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hasher(algorithm: PasswordAlgorithm) -> PasswordHasher {
+        PasswordHasher {
+            algorithm,
+            bcrypt_cost: 4,
+            argon2_memory_kib: 1024,
+            argon2_iterations: 1,
+            argon2_parallelism: 1,
+        }
+    }
+
+    #[test]
+    fn test_argon2id_round_trip() {
+        let hasher = hasher(PasswordAlgorithm::Argon2id);
+        let hash = hasher.hash_blocking("correct horse battery staple").unwrap();
+
+        assert!(hash.starts_with("$argon2id$v=19$m=1024,t=1,p=1$"));
+        assert_eq!(
+            hasher.verify_blocking("correct horse battery staple", &hash).unwrap(),
+            PasswordVerification::Match { needs_rehash: false }
+        );
+        assert_eq!(
+            hasher.verify_blocking("wrong", &hash).unwrap(),
+            PasswordVerification::Mismatch
+        );
+    }
+
+    #[test]
+    fn test_bcrypt_hash_is_verified_and_flagged_for_upgrade() {
+        let legacy = hasher(PasswordAlgorithm::Bcrypt).hash_blocking("Secret123!").unwrap();
+        let hasher = hasher(PasswordAlgorithm::Argon2id);
+
+        assert_eq!(
+            hasher.verify_blocking("Secret123!", &legacy).unwrap(),
+            PasswordVerification::Match { needs_rehash: true }
+        );
+    }
+
+    #[test]
+    fn test_changed_parameters_need_rehash() {
+        let hash = hasher(PasswordAlgorithm::Argon2id).hash_blocking("Secret123!").unwrap();
+        let stronger = PasswordHasher {
+            argon2_iterations: 2,
+            ..hasher(PasswordAlgorithm::Argon2id)
+        };
+        assert!(stronger.needs_rehash(&hash));
+
+        let bcrypt_hash = hasher(PasswordAlgorithm::Bcrypt).hash_blocking("Secret123!").unwrap();
+        let higher_cost = PasswordHasher {
+            bcrypt_cost: 5,
+            ..hasher(PasswordAlgorithm::Bcrypt)
+        };
+        assert!(!hasher(PasswordAlgorithm::Bcrypt).needs_rehash(&bcrypt_hash));
+        assert!(higher_cost.needs_rehash(&bcrypt_hash));
+    }
+}